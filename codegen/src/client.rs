@@ -0,0 +1,727 @@
+use std::{collections::HashSet, fmt::Write};
+
+use async_graphql::{
+    parser::{
+        parse_query,
+        types::{
+            BaseType, Directive, ExecutableDocument, Field, OperationDefinition, OperationType,
+            Selection, Type,
+        },
+    },
+    registry::{MetaType, Registry},
+    Name, Positioned,
+};
+use inflector::Inflector;
+
+use crate::{
+    generator::{field_ident, type_ident, variant_ident, write_description},
+    ClientCodegen, Error, Result,
+};
+
+/// The selections of a selection set, once the fragments which always apply
+/// are inlined.
+#[derive(Default)]
+struct Collected<'a> {
+    /// The fields by response key, and whether they are conditional.
+    fields: Vec<(&'a str, Vec<(&'a Positioned<Field>, bool)>)>,
+    /// The named fragments which always apply, and whether they are
+    /// conditional.
+    fragments: Vec<(&'a Name, bool)>,
+    /// The selections which only apply to an object type.
+    branches: Vec<(&'a str, Vec<(&'a Positioned<Selection>, bool)>)>,
+}
+
+/// The items of a module, and the names of its types.
+struct Scope {
+    prefix: &'static str,
+    names: HashSet<String>,
+    items: Vec<String>,
+}
+
+struct Generator<'a> {
+    codegen: &'a ClientCodegen,
+    doc: &'a ExecutableDocument,
+    /// The enums and input objects used by the document.
+    shared: Vec<&'a str>,
+    /// The generated fragments, and whether they select `__typename`.
+    fragments: Vec<(&'a Name, bool)>,
+    /// The top-level scope is the first one, followed by a scope for each
+    /// operation.
+    scopes: Vec<Scope>,
+}
+
+pub(crate) fn generate(codegen: &ClientCodegen, query: &str) -> Result<String> {
+    let doc = parse_query(query)?;
+    codegen
+        .registry
+        .validate(&doc, None)
+        .map_err(Error::Validation)?;
+
+    let mut generator = Generator {
+        codegen,
+        doc: &doc,
+        shared: Vec::new(),
+        fragments: Vec::new(),
+        scopes: vec![Scope {
+            prefix: "",
+            names: HashSet::new(),
+            items: Vec::new(),
+        }],
+    };
+
+    let mut fragments = doc.fragments.iter().collect::<Vec<_>>();
+    fragments.sort_by_key(|(_, fragment)| fragment.pos);
+    for (name, _) in fragments {
+        generator.fragment(name)?;
+    }
+
+    let mut operations = doc.operations.iter().collect::<Vec<_>>();
+    operations.sort_by_key(|(_, operation)| operation.pos);
+    let mut modules = HashSet::new();
+    let mut out = String::new();
+    for (name, operation) in operations {
+        let name = name.ok_or(Error::UnnamedOperation)?;
+        let (module, _) = field_ident(name)?;
+        if !modules.insert(module.clone()) {
+            return Err(Error::NameConflict(module));
+        }
+        out.push('\n');
+        out.push_str(&generator.write_operation(name, &module, &operation.node)?);
+    }
+    generator.write_shared()?;
+
+    let mut header = String::new();
+    header.push_str("// Code generated by async-graphql-codegen, do not edit.\n\n");
+    header.push_str("/// The executable document containing all the operations.\n");
+    let hashes = "#".repeat(
+        query
+            .split('"')
+            .skip(1)
+            .map(|part| part.len() - part.trim_start_matches('#').len() + 1)
+            .max()
+            .unwrap_or(0),
+    );
+    writeln!(
+        header,
+        "pub const DOCUMENT: &str = r{}\"{}\"{};",
+        hashes, query, hashes
+    )
+    .ok();
+    for item in &generator.scopes[0].items {
+        header.push('\n');
+        header.push_str(item);
+    }
+    header.push_str(&out);
+    Ok(header)
+}
+
+fn is_conditional(directives: &[Positioned<Directive>]) -> bool {
+    directives
+        .iter()
+        .any(|directive| matches!(directive.node.name.node.as_str(), "skip" | "include"))
+}
+
+fn base_name(ty: &Type) -> &str {
+    match &ty.base {
+        BaseType::Named(name) => name.as_str(),
+        BaseType::List(ty) => base_name(ty),
+    }
+}
+
+fn wrap_type(ty: &Type, inner: &str) -> String {
+    let rust_type = match &ty.base {
+        BaseType::Named(_) => inner.to_string(),
+        BaseType::List(ty) => format!("Vec<{}>", wrap_type(ty, inner)),
+    };
+    if ty.nullable {
+        format!("Option<{}>", rust_type)
+    } else {
+        rust_type
+    }
+}
+
+fn parse_type(ty: &str) -> Type {
+    Type::new(ty).expect("The registry contains an invalid type")
+}
+
+fn write_serde_attr(out: &mut String, indent: &str, items: &[String]) {
+    if !items.is_empty() {
+        writeln!(out, "{}#[serde({})]", indent, items.join(", ")).ok();
+    }
+}
+
+impl<'a> Generator<'a> {
+    fn registry(&self) -> &'a Registry {
+        &self.codegen.registry
+    }
+
+    /// Returns the name of a type, borrowed from the registry.
+    fn type_name(&self, name: &str) -> &'a str {
+        self.registry()
+            .types
+            .get_key_value(name)
+            .map(|(name, _)| name.as_str())
+            .expect("The registry contains an unknown type")
+    }
+
+    fn possible_types(&self, name: &'a str) -> Vec<&'a str> {
+        match self.registry().types.get(name) {
+            Some(MetaType::Interface { possible_types, .. })
+            | Some(MetaType::Union { possible_types, .. }) => {
+                possible_types.iter().map(String::as_str).collect()
+            }
+            _ => vec![name],
+        }
+    }
+
+    /// Returns whether a fragment on the type `on` applies to all the values
+    /// of the type `ty`.
+    fn applies_always(&self, ty: &'a str, on: &'a str) -> bool {
+        let possible_types = self.possible_types(on);
+        on == ty
+            || self
+                .possible_types(ty)
+                .iter()
+                .all(|ty| possible_types.contains(ty))
+    }
+
+    fn collect(
+        &self,
+        ty: &'a str,
+        items: &'a [Positioned<Selection>],
+        conditional: bool,
+        out: &mut Collected<'a>,
+    ) -> Result<()> {
+        for item in items {
+            self.collect_selection(ty, item, conditional, out)?;
+        }
+        Ok(())
+    }
+
+    fn collect_selection(
+        &self,
+        ty: &'a str,
+        item: &'a Positioned<Selection>,
+        conditional: bool,
+        out: &mut Collected<'a>,
+    ) -> Result<()> {
+        match &item.node {
+            Selection::Field(field) => {
+                let conditional = conditional || is_conditional(&field.node.directives);
+                let key = field.node.response_key().node.as_str();
+                match out.fields.iter_mut().find(|(name, _)| *name == key) {
+                    Some((_, fields)) => fields.push((field, conditional)),
+                    None => out.fields.push((key, vec![(field, conditional)])),
+                }
+            }
+            Selection::FragmentSpread(spread) => {
+                let conditional = conditional || is_conditional(&spread.node.directives);
+                let name = &spread.node.fragment_name.node;
+                let fragment = &self.doc.fragments[name];
+                let on = fragment.node.type_condition.node.on.node.as_str();
+                if self.applies_always(ty, on) {
+                    match out
+                        .fragments
+                        .iter_mut()
+                        .find(|(fragment, _)| *fragment == name)
+                    {
+                        Some((_, fragment_conditional)) => {
+                            *fragment_conditional = *fragment_conditional && conditional
+                        }
+                        None => out.fragments.push((name, conditional)),
+                    }
+                } else {
+                    self.collect_branch(ty, on, item, conditional, out)?;
+                }
+            }
+            Selection::InlineFragment(fragment) => {
+                let conditional = conditional || is_conditional(&fragment.node.directives);
+                let items = &fragment.node.selection_set.node.items;
+                match &fragment.node.type_condition {
+                    Some(on) if !self.applies_always(ty, &on.node.on.node) => {
+                        for item in items {
+                            self.collect_branch(ty, &on.node.on.node, item, conditional, out)?;
+                        }
+                    }
+                    _ => self.collect(ty, items, conditional, out)?,
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn collect_branch(
+        &self,
+        ty: &'a str,
+        on: &'a str,
+        item: &'a Positioned<Selection>,
+        conditional: bool,
+        out: &mut Collected<'a>,
+    ) -> Result<()> {
+        if !matches!(self.registry().types.get(on), Some(MetaType::Object { .. })) {
+            return Err(Error::UnsupportedSelection {
+                ty: ty.to_string(),
+                on: on.to_string(),
+            });
+        }
+        match out.branches.iter_mut().find(|(name, _)| *name == on) {
+            Some((_, items)) => items.push((item, conditional)),
+            None => out.branches.push((on, vec![(item, conditional)])),
+        }
+        Ok(())
+    }
+
+    /// Returns the Rust type of a scalar, an enum or an input object.
+    fn named_type(&mut self, name: &'a str, prefix: &str) -> Result<String> {
+        if let Some(rust_type) = self.codegen.scalars.get(name) {
+            return Ok(rust_type.clone());
+        }
+        Ok(match name {
+            "Int" => "i32".to_string(),
+            "Float" => "f64".to_string(),
+            "String" | "ID" => "String".to_string(),
+            "Boolean" => "bool".to_string(),
+            _ => match self.registry().types.get(name) {
+                Some(MetaType::Enum { .. }) | Some(MetaType::InputObject { .. }) => {
+                    type_ident(name)?;
+                    if !self.shared.contains(&name) {
+                        self.shared.push(name);
+                    }
+                    format!("{}{}", prefix, name)
+                }
+                _ => "::serde_json::Value".to_string(),
+            },
+        })
+    }
+
+    /// Generates the struct of a fragment, and returns whether it selects
+    /// `__typename`.
+    fn fragment(&mut self, name: &'a Name) -> Result<bool> {
+        if let Some((_, typename)) = self
+            .fragments
+            .iter()
+            .find(|(fragment, _)| *fragment == name)
+        {
+            return Ok(*typename);
+        }
+        let fragment = &self.doc.fragments[name];
+        let on = fragment.node.type_condition.node.on.node.as_str();
+        let mut collected = Collected::default();
+        self.collect(
+            on,
+            &fragment.node.selection_set.node.items,
+            false,
+            &mut collected,
+        )?;
+        let struct_name = name.to_pascal_case();
+        let typename = self.write_struct(0, on, &struct_name, &struct_name, collected)?;
+        self.fragments.push((name, typename));
+        Ok(typename)
+    }
+
+    fn add_name(&mut self, scope: usize, name: &str) -> Result<()> {
+        if !self.scopes[scope].names.insert(name.to_string()) {
+            return Err(Error::NameConflict(name.to_string()));
+        }
+        Ok(())
+    }
+
+    /// Generates the struct of a selection set on the type `ty`, and returns
+    /// whether it selects `__typename`.
+    ///
+    /// The structs of the nested selection sets are named after the `path` of
+    /// the struct.
+    fn write_struct(
+        &mut self,
+        scope: usize,
+        ty: &'a str,
+        name: &str,
+        path: &str,
+        collected: Collected<'a>,
+    ) -> Result<bool> {
+        self.add_name(scope, name)?;
+        let index = self.scopes[scope].items.len();
+        self.scopes[scope].items.push(String::new());
+        let prefix = self.scopes[scope].prefix;
+
+        let mut idents = HashSet::new();
+        let mut fields = String::new();
+        let mut values = String::new();
+        let mut typename = false;
+
+        for (key, refs) in &collected.fields {
+            let (field, _) = refs[0];
+            let conditional = refs.iter().all(|(_, conditional)| *conditional);
+            let field_name = field.node.name.node.as_str();
+            let (rust_type, nullable, description) = if field_name == "__typename" {
+                typename |= !conditional;
+                ("String".to_string(), false, None)
+            } else {
+                let meta_field = self
+                    .registry()
+                    .types
+                    .get(ty)
+                    .and_then(|meta_type| meta_type.field_by_name(field_name))
+                    .expect("The document has been validated");
+                let field_type = parse_type(&meta_field.ty);
+                let base = base_name(&field_type);
+                let inner = match self.registry().types.get(base) {
+                    Some(meta_type) if meta_type.is_composite() => {
+                        let base = meta_type.name();
+                        let mut nested = Collected::default();
+                        for (field, _) in refs {
+                            self.collect(
+                                base,
+                                &field.node.selection_set.node.items,
+                                false,
+                                &mut nested,
+                            )?;
+                        }
+                        let nested_name = format!("{}{}", path, key.to_pascal_case());
+                        self.write_struct(scope, base, &nested_name, &nested_name, nested)?;
+                        nested_name
+                    }
+                    Some(meta_type) => self.named_type(meta_type.name(), prefix)?,
+                    None => unreachable!("The document has been validated"),
+                };
+                (
+                    wrap_type(&field_type, &inner),
+                    field_type.nullable,
+                    meta_field.description,
+                )
+            };
+            let rust_type = if conditional && !nullable {
+                format!("Option<{}>", rust_type)
+            } else {
+                rust_type
+            };
+
+            let ident = if field_name == "__typename" {
+                "typename".to_string()
+            } else {
+                field_ident(key)?.0
+            };
+            if !idents.insert(ident.clone()) {
+                return Err(Error::NameConflict(format!("{}.{}", name, ident)));
+            }
+            write_description(&mut fields, "    ", description);
+            if ident != *key {
+                writeln!(fields, "    #[serde(rename = \"{}\")]", key).ok();
+            }
+            writeln!(fields, "    pub {}: {},", ident, rust_type).ok();
+            writeln!(
+                values,
+                "            {}: ::serde_json::from_value(value.get(\"{}\").cloned().unwrap_or_default()).map_err(D::Error::custom)?,",
+                ident, key
+            )
+            .ok();
+        }
+
+        for (fragment, conditional) in &collected.fragments {
+            typename |= self.fragment(fragment)? && !conditional;
+            let (ident, _) = field_ident(fragment)?;
+            if !idents.insert(ident.clone()) {
+                return Err(Error::NameConflict(format!("{}.{}", name, ident)));
+            }
+            let rust_type = format!("{}{}", prefix, fragment.to_pascal_case());
+            if *conditional {
+                writeln!(fields, "    pub {}: Option<{}>,", ident, rust_type).ok();
+                writeln!(
+                    values,
+                    "            {}: ::serde_json::from_value(value.clone()).ok(),",
+                    ident
+                )
+                .ok();
+            } else {
+                writeln!(fields, "    pub {}: {},", ident, rust_type).ok();
+                writeln!(
+                    values,
+                    "            {}: ::serde_json::from_value(value.clone()).map_err(D::Error::custom)?,",
+                    ident
+                )
+                .ok();
+            }
+        }
+
+        let mut enum_out = String::new();
+        if !collected.branches.is_empty() {
+            if !typename {
+                return Err(Error::MissingTypename(name.to_string()));
+            }
+            if !idents.insert("on".to_string()) {
+                return Err(Error::NameConflict(format!("{}.on", name)));
+            }
+            let enum_name = format!("{}On", name);
+            writeln!(fields, "    pub on: {},", enum_name).ok();
+            writeln!(
+                values,
+                "            on: match value.get(\"__typename\").and_then(::serde_json::Value::as_str) {{"
+            )
+            .ok();
+            writeln!(
+                enum_out,
+                "/// The fields selected depending on the type of `{}`.",
+                name
+            )
+            .ok();
+            enum_out.push_str("#[derive(Debug, Clone, PartialEq)]\n");
+            writeln!(enum_out, "pub enum {} {{", enum_name).ok();
+            self.add_name(scope, &enum_name)?;
+
+            for (on, items) in collected.branches {
+                let (variant, _) = variant_ident(on)?;
+                if variant == "Other" {
+                    return Err(Error::NameConflict(format!("{}::Other", enum_name)));
+                }
+                let mut branch = Collected::default();
+                for (item, conditional) in items {
+                    self.collect_selection(on, item, conditional, &mut branch)?;
+                }
+                let branch_name = format!("{}{}", enum_name, variant);
+                self.write_struct(scope, on, &branch_name, &branch_name, branch)?;
+                writeln!(enum_out, "    {}({}),", variant, branch_name).ok();
+                writeln!(
+                    values,
+                    "                Some(\"{}\") => {}::{}(::serde_json::from_value(value.clone()).map_err(D::Error::custom)?),",
+                    on, enum_name, variant
+                )
+                .ok();
+            }
+
+            enum_out.push_str("    /// Any other type.\n");
+            enum_out.push_str("    Other,\n");
+            enum_out.push_str("}\n");
+            writeln!(values, "                _ => {}::Other,", enum_name).ok();
+            values.push_str("            },\n");
+        }
+
+        let mut out = String::new();
+        if path.is_empty() {
+            out.push_str("/// The data of the response.\n");
+        } else {
+            writeln!(out, "/// The fields selected on the `{}` type.", ty).ok();
+        }
+        if collected.fragments.is_empty() && enum_out.is_empty() {
+            out.push_str("#[derive(Debug, Clone, PartialEq, ::serde::Deserialize)]\n");
+            writeln!(out, "pub struct {} {{", name).ok();
+            out.push_str(&fields);
+            out.push_str("}\n");
+        } else {
+            let fields = fields
+                .lines()
+                .filter(|line| !line.trim_start().starts_with("#[serde("))
+                .map(|line| format!("{}\n", line))
+                .collect::<String>();
+            out.push_str("#[derive(Debug, Clone, PartialEq)]\n");
+            writeln!(out, "pub struct {} {{", name).ok();
+            out.push_str(&fields);
+            out.push_str("}\n\n");
+            writeln!(out, "impl<'de> ::serde::Deserialize<'de> for {} {{", name).ok();
+            out.push_str("    fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> ::std::result::Result<Self, D::Error> {\n");
+            out.push_str("        use ::serde::de::Error;\n\n");
+            out.push_str("        let value = ::serde_json::Value::deserialize(deserializer)?;\n");
+            out.push_str("        Ok(Self {\n");
+            out.push_str(&values);
+            out.push_str("        })\n");
+            out.push_str("    }\n");
+            out.push_str("}\n");
+        }
+        if !enum_out.is_empty() {
+            out.push('\n');
+            out.push_str(&enum_out);
+        }
+
+        self.scopes[scope].items[index] = out;
+        Ok(typename)
+    }
+
+    fn write_operation(
+        &mut self,
+        name: &'a str,
+        module: &str,
+        operation: &'a OperationDefinition,
+    ) -> Result<String> {
+        let root = match operation.ty {
+            OperationType::Query => Some(&self.registry().query_type),
+            OperationType::Mutation => self.registry().mutation_type.as_ref(),
+            OperationType::Subscription => self.registry().subscription_type.as_ref(),
+        }
+        .expect("The document has been validated");
+
+        let scope = self.scopes.len();
+        self.scopes.push(Scope {
+            prefix: "super::",
+            names: HashSet::new(),
+            items: Vec::new(),
+        });
+
+        self.add_name(scope, "Variables")?;
+        let mut variables = String::new();
+        variables.push_str("/// The variables of the operation.\n");
+        variables.push_str("#[derive(Debug, Clone, PartialEq, ::serde::Serialize)]\n");
+        if operation.variable_definitions.is_empty() {
+            variables.push_str("pub struct Variables {}\n");
+        } else {
+            variables.push_str("pub struct Variables {\n");
+            for variable in &operation.variable_definitions {
+                let variable = &variable.node;
+                let key = variable.name.node.as_str();
+                let (ident, _) = field_ident(key)?;
+                let ty = &variable.var_type.node;
+                let inner = self.named_type(base_name(ty), "super::")?;
+                let mut rust_type = wrap_type(ty, &inner);
+                if !ty.nullable && variable.default_value.is_some() {
+                    rust_type = format!("Option<{}>", rust_type);
+                }
+                let mut attrs = Vec::new();
+                if ident != key {
+                    attrs.push(format!("rename = \"{}\"", key));
+                }
+                if rust_type.starts_with("Option<") {
+                    attrs.push("skip_serializing_if = \"Option::is_none\"".to_string());
+                }
+                write_serde_attr(&mut variables, "    ", &attrs);
+                writeln!(variables, "    pub {}: {},", ident, rust_type).ok();
+            }
+            variables.push_str("}\n");
+        }
+        self.scopes[scope].items.push(variables);
+
+        let mut collected = Collected::default();
+        self.collect(
+            root,
+            &operation.selection_set.node.items,
+            false,
+            &mut collected,
+        )?;
+        self.write_struct(scope, root, "ResponseData", "", collected)?;
+
+        let mut out = String::new();
+        writeln!(out, "/// The `{}` operation.", name).ok();
+        writeln!(out, "pub mod {} {{", module).ok();
+        out.push_str("    /// The name of the operation.\n");
+        writeln!(out, "    pub const OPERATION_NAME: &str = \"{}\";", name).ok();
+        out.push_str("    /// The document containing the operation.\n");
+        out.push_str("    pub const QUERY: &str = super::DOCUMENT;\n");
+        let scope = self.scopes.pop().unwrap();
+        for item in scope.items {
+            out.push('\n');
+            for line in item.lines() {
+                if line.is_empty() {
+                    out.push('\n');
+                } else {
+                    writeln!(out, "    {}", line).ok();
+                }
+            }
+        }
+        out.push('\n');
+        out.push_str("    /// Returns the body of a request executing the operation.\n");
+        out.push_str("    pub fn request(variables: Variables) -> ::serde_json::Value {\n");
+        out.push_str("        ::serde_json::json!({\n");
+        out.push_str("            \"query\": QUERY,\n");
+        out.push_str("            \"operationName\": OPERATION_NAME,\n");
+        out.push_str("            \"variables\": variables,\n");
+        out.push_str("        })\n");
+        out.push_str("    }\n");
+        out.push_str("}\n");
+        Ok(out)
+    }
+
+    /// Generates the enums and the input objects used by the document.
+    fn write_shared(&mut self) -> Result<()> {
+        let mut index = 0;
+        while let Some(name) = self.shared.get(index).copied() {
+            index += 1;
+            self.add_name(0, name)?;
+            let mut out = String::new();
+            match &self.registry().types[name] {
+                MetaType::Enum {
+                    description,
+                    enum_values,
+                    ..
+                } => {
+                    write_description(&mut out, "", *description);
+                    out.push_str("#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, ::serde::Serialize, ::serde::Deserialize)]\n");
+                    writeln!(out, "pub enum {} {{", name).ok();
+                    for value in enum_values.values() {
+                        let (ident, _) = variant_ident(value.name)?;
+                        if ident == "Other" {
+                            return Err(Error::NameConflict(format!("{}::Other", name)));
+                        }
+                        write_description(&mut out, "    ", value.description);
+                        if ident != value.name {
+                            writeln!(out, "    #[serde(rename = \"{}\")]", value.name).ok();
+                        }
+                        writeln!(out, "    {},", ident).ok();
+                    }
+                    out.push_str(
+                        "    /// A value added to the schema after the code was generated.\n",
+                    );
+                    out.push_str("    #[serde(other)]\n");
+                    out.push_str("    Other,\n");
+                    out.push_str("}\n");
+                }
+                MetaType::InputObject {
+                    description,
+                    input_fields,
+                    ..
+                } => {
+                    write_description(&mut out, "", *description);
+                    out.push_str("#[derive(Debug, Clone, PartialEq, ::serde::Serialize)]\n");
+                    writeln!(out, "pub struct {} {{", name).ok();
+                    for field in input_fields.values() {
+                        let (ident, _) = field_ident(field.name)?;
+                        let ty = parse_type(&field.ty);
+                        let mut inner = self.named_type(self.type_name(base_name(&ty)), "")?;
+                        if matches!(ty.base, BaseType::Named(_)) && self.is_recursive(name, &ty) {
+                            inner = format!("Box<{}>", inner);
+                        }
+                        let mut rust_type = wrap_type(&ty, &inner);
+                        if !ty.nullable && field.default_value.is_some() {
+                            rust_type = format!("Option<{}>", rust_type);
+                        }
+                        let mut attrs = Vec::new();
+                        if ident != field.name {
+                            attrs.push(format!("rename = \"{}\"", field.name));
+                        }
+                        if rust_type.starts_with("Option<") {
+                            attrs.push("skip_serializing_if = \"Option::is_none\"".to_string());
+                        }
+                        write_description(&mut out, "    ", field.description);
+                        write_serde_attr(&mut out, "    ", &attrs);
+                        writeln!(out, "    pub {}: {},", ident, rust_type).ok();
+                    }
+                    out.push_str("}\n");
+                }
+                _ => unreachable!(),
+            }
+            self.scopes[0].items.push(out);
+        }
+        Ok(())
+    }
+
+    /// Returns whether an input object field of type `ty` can contain the
+    /// input object `name`, without a list between them.
+    fn is_recursive(&self, name: &str, ty: &Type) -> bool {
+        let mut visited = HashSet::new();
+        let mut pending = vec![base_name(ty)];
+        while let Some(ty) = pending.pop() {
+            if ty == name {
+                return true;
+            }
+            if !visited.insert(ty) {
+                continue;
+            }
+            if let Some(MetaType::InputObject { input_fields, .. }) = self.registry().types.get(ty)
+            {
+                for field in input_fields.values() {
+                    let ty = parse_type(&field.ty);
+                    if matches!(ty.base, BaseType::Named(_)) {
+                        pending.push(self.type_name(base_name(&ty)));
+                    }
+                }
+            }
+        }
+        false
+    }
+}
@@ -0,0 +1,1050 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Write,
+};
+
+use async_graphql_parser::types::{
+    BaseType, ConstDirective, EnumValueDefinition, FieldDefinition, InputValueDefinition,
+    ServiceDocument, Type, TypeKind, TypeSystemDefinition,
+};
+use async_graphql_value::ConstValue;
+use inflector::Inflector;
+
+use crate::{Codegen, Error, Result};
+
+const CONTEXT: &str = "&::async_graphql::Context<'_>";
+
+const KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "crate",
+    "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "if", "impl", "in",
+    "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+    "return", "self", "Self", "static", "struct", "super", "trait", "true", "try", "type",
+    "typeof", "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+];
+
+enum Kind<'a> {
+    Scalar {
+        specified_by_url: Option<String>,
+    },
+    Object {
+        implements: Vec<&'a str>,
+        fields: Vec<&'a FieldDefinition>,
+    },
+    Interface {
+        fields: Vec<&'a FieldDefinition>,
+    },
+    Union {
+        members: Vec<&'a str>,
+    },
+    Enum {
+        values: Vec<&'a EnumValueDefinition>,
+    },
+    InputObject {
+        fields: Vec<&'a InputValueDefinition>,
+    },
+}
+
+struct Definition<'a> {
+    name: &'a str,
+    description: Option<&'a str>,
+    kind: Kind<'a>,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum Root {
+    Query,
+    Mutation,
+    Subscription,
+}
+
+struct Generator<'a> {
+    codegen: &'a Codegen,
+    definitions: Vec<Definition<'a>>,
+    index: HashMap<&'a str, usize>,
+    roots: HashMap<&'a str, Root>,
+    implementors: HashMap<&'a str, Vec<&'a str>>,
+    recursive: HashSet<&'a str>,
+    out: String,
+}
+
+pub(crate) fn generate(codegen: &Codegen, doc: &ServiceDocument) -> Result<String> {
+    let mut generator = Generator::new(codegen, doc)?;
+    generator.check_types()?;
+    generator.find_recursive_types();
+
+    generator
+        .out
+        .push_str("// Code generated by async-graphql-codegen, do not edit.\n");
+    for definition in &generator.definitions {
+        let mut out = String::new();
+        match &definition.kind {
+            Kind::Scalar { specified_by_url } => {
+                if is_builtin_scalar(definition.name)
+                    || generator.codegen.scalars.contains_key(definition.name)
+                {
+                    continue;
+                }
+                generator.write_scalar(&mut out, definition, specified_by_url.as_deref())
+            }
+            Kind::Enum { values } => generator.write_enum(&mut out, definition, values)?,
+            Kind::InputObject { fields } => {
+                generator.write_input_object(&mut out, definition, fields)?
+            }
+            Kind::Interface { fields } => {
+                generator.write_interface(&mut out, definition, fields)?
+            }
+            Kind::Union { members } => generator.write_union(&mut out, definition, members),
+            Kind::Object { implements, fields } => {
+                generator.write_object(&mut out, definition, implements, fields)?
+            }
+        }
+        generator.out.push('\n');
+        generator.out.push_str(&out);
+    }
+    generator.write_register_types();
+    Ok(generator.out)
+}
+
+fn is_builtin_scalar(name: &str) -> bool {
+    matches!(name, "Int" | "Float" | "String" | "Boolean" | "ID")
+}
+
+fn directive<'a>(
+    directives: &'a [async_graphql_parser::Positioned<ConstDirective>],
+    name: &str,
+) -> Option<&'a ConstDirective> {
+    directives
+        .iter()
+        .map(|directive| &directive.node)
+        .find(|directive| directive.name.node == name)
+}
+
+fn deprecation(directives: &[async_graphql_parser::Positioned<ConstDirective>]) -> Option<String> {
+    directive(directives, "deprecated").map(|directive| {
+        match directive.get_argument("reason").map(|reason| &reason.node) {
+            Some(ConstValue::String(reason)) => format!("deprecation = {:?}", reason),
+            _ => "deprecation".to_string(),
+        }
+    })
+}
+
+fn base_name(ty: &Type) -> &str {
+    match &ty.base {
+        BaseType::Named(name) => name.as_str(),
+        BaseType::List(ty) => base_name(ty),
+    }
+}
+
+/// Returns the Rust identifier of a field or an argument, and whether its
+/// GraphQL name must be specified.
+pub(crate) fn field_ident(name: &str) -> Result<(String, bool)> {
+    let mut ident = name.to_snake_case();
+    if ident.is_empty() || ident.starts_with(|c: char| c.is_ascii_digit()) {
+        return Err(Error::UnsupportedName(name.to_string()));
+    }
+    if KEYWORDS.contains(&ident.as_str()) || ident == "ctx" {
+        ident.push('_');
+    }
+    let rename = ident.to_camel_case() != name;
+    Ok((ident, rename))
+}
+
+/// Returns the Rust identifier of an enum value, and whether its GraphQL name
+/// must be specified.
+pub(crate) fn variant_ident(name: &str) -> Result<(String, bool)> {
+    let ident = name.to_pascal_case();
+    if ident.is_empty()
+        || ident.starts_with(|c: char| c.is_ascii_digit())
+        || KEYWORDS.contains(&ident.as_str())
+    {
+        return Err(Error::UnsupportedName(name.to_string()));
+    }
+    let rename = ident.to_screaming_snake_case() != name;
+    Ok((ident, rename))
+}
+
+pub(crate) fn type_ident(name: &str) -> Result<&str> {
+    if KEYWORDS.contains(&name) {
+        return Err(Error::UnsupportedName(name.to_string()));
+    }
+    Ok(name)
+}
+
+pub(crate) fn write_description(out: &mut String, indent: &str, description: Option<&str>) {
+    if let Some(description) = description {
+        for line in description.lines() {
+            let line = line.trim_end();
+            if line.is_empty() {
+                writeln!(out, "{}///", indent).ok();
+            } else {
+                writeln!(out, "{}/// {}", indent, line).ok();
+            }
+        }
+    }
+}
+
+fn write_graphql_attr(out: &mut String, indent: &str, items: &[String]) {
+    if !items.is_empty() {
+        writeln!(out, "{}#[graphql({})]", indent, items.join(", ")).ok();
+    }
+}
+
+impl<'a> Generator<'a> {
+    fn new(codegen: &'a Codegen, doc: &'a ServiceDocument) -> Result<Self> {
+        let mut generator = Generator {
+            codegen,
+            definitions: Vec::new(),
+            index: HashMap::new(),
+            roots: HashMap::new(),
+            implementors: HashMap::new(),
+            recursive: HashSet::new(),
+            out: String::new(),
+        };
+        let mut schema_roots = Vec::new();
+
+        for definition in &doc.definitions {
+            let definition = match definition {
+                TypeSystemDefinition::Schema(schema) => {
+                    let schema = &schema.node;
+                    for (name, root) in [
+                        (&schema.query, Root::Query),
+                        (&schema.mutation, Root::Mutation),
+                        (&schema.subscription, Root::Subscription),
+                    ] {
+                        if let Some(name) = name {
+                            schema_roots.push((name.node.as_str(), root));
+                        }
+                    }
+                    continue;
+                }
+                TypeSystemDefinition::Type(definition) => &definition.node,
+                TypeSystemDefinition::Directive(_) => continue,
+            };
+            let name = definition.name.node.as_str();
+            if name.starts_with("__") {
+                continue;
+            }
+
+            let kind = match &definition.kind {
+                TypeKind::Scalar => Kind::Scalar {
+                    specified_by_url: directive(&definition.directives, "specifiedBy")
+                        .and_then(|directive| directive.get_argument("url"))
+                        .and_then(|url| match &url.node {
+                            ConstValue::String(url) => Some(url.clone()),
+                            _ => None,
+                        }),
+                },
+                TypeKind::Object(ty) => Kind::Object {
+                    implements: ty
+                        .implements
+                        .iter()
+                        .map(|name| name.node.as_str())
+                        .collect(),
+                    fields: ty.fields.iter().map(|field| &field.node).collect(),
+                },
+                TypeKind::Interface(ty) => Kind::Interface {
+                    fields: ty.fields.iter().map(|field| &field.node).collect(),
+                },
+                TypeKind::Union(ty) => Kind::Union {
+                    members: ty.members.iter().map(|name| name.node.as_str()).collect(),
+                },
+                TypeKind::Enum(ty) => Kind::Enum {
+                    values: ty.values.iter().map(|value| &value.node).collect(),
+                },
+                TypeKind::InputObject(ty) => Kind::InputObject {
+                    fields: ty.fields.iter().map(|field| &field.node).collect(),
+                },
+            };
+
+            match generator.index.get(name) {
+                Some(idx) if definition.extend => {
+                    let existing = &mut generator.definitions[*idx].kind;
+                    match (existing, kind) {
+                        (
+                            Kind::Object { implements, fields },
+                            Kind::Object {
+                                implements: extra_implements,
+                                fields: extra_fields,
+                            },
+                        ) => {
+                            implements.extend(extra_implements);
+                            fields.extend(extra_fields);
+                        }
+                        (
+                            Kind::Interface { fields },
+                            Kind::Interface {
+                                fields: extra_fields,
+                            },
+                        ) => fields.extend(extra_fields),
+                        (
+                            Kind::Union { members },
+                            Kind::Union {
+                                members: extra_members,
+                            },
+                        ) => members.extend(extra_members),
+                        (
+                            Kind::Enum { values },
+                            Kind::Enum {
+                                values: extra_values,
+                            },
+                        ) => values.extend(extra_values),
+                        (
+                            Kind::InputObject { fields },
+                            Kind::InputObject {
+                                fields: extra_fields,
+                            },
+                        ) => fields.extend(extra_fields),
+                        _ => {}
+                    }
+                }
+                _ => {
+                    type_ident(name)?;
+                    generator.index.insert(name, generator.definitions.len());
+                    generator.definitions.push(Definition {
+                        name,
+                        description: definition
+                            .description
+                            .as_ref()
+                            .map(|description| description.node.as_str()),
+                        kind,
+                    });
+                }
+            }
+        }
+
+        if schema_roots.is_empty() {
+            for (name, root) in [
+                ("Query", Root::Query),
+                ("Mutation", Root::Mutation),
+                ("Subscription", Root::Subscription),
+            ] {
+                if generator.index.contains_key(name) {
+                    schema_roots.push((name, root));
+                }
+            }
+        }
+        generator.roots.extend(schema_roots);
+
+        for definition in &generator.definitions {
+            if let Kind::Object { implements, .. } = &definition.kind {
+                for interface in implements {
+                    generator
+                        .implementors
+                        .entry(*interface)
+                        .or_default()
+                        .push(definition.name);
+                }
+            }
+        }
+
+        Ok(generator)
+    }
+
+    fn definition(&self, name: &str) -> Option<&Definition<'a>> {
+        self.index.get(name).map(|idx| &self.definitions[*idx])
+    }
+
+    fn check_type(&self, name: &str) -> Result<()> {
+        if is_builtin_scalar(name)
+            || self.codegen.scalars.contains_key(name)
+            || self.index.contains_key(name)
+        {
+            Ok(())
+        } else {
+            Err(Error::UnknownType(name.to_string()))
+        }
+    }
+
+    fn check_types(&self) -> Result<()> {
+        let check_fields = |fields: &[&FieldDefinition]| {
+            fields.iter().try_for_each(|field| {
+                self.check_type(base_name(&field.ty.node))?;
+                field
+                    .arguments
+                    .iter()
+                    .try_for_each(|arg| self.check_type(base_name(&arg.node.ty.node)))
+            })
+        };
+
+        for definition in &self.definitions {
+            match &definition.kind {
+                Kind::Scalar { .. } => {}
+                Kind::Object { implements, fields } => {
+                    implements
+                        .iter()
+                        .try_for_each(|name| self.check_type(name))?;
+                    check_fields(fields)?;
+                }
+                Kind::Interface { fields } => {
+                    if !self.implementors.contains_key(definition.name) {
+                        return Err(Error::NoImplementors(definition.name.to_string()));
+                    }
+                    check_fields(fields)?;
+                }
+                Kind::Union { members } => {
+                    members.iter().try_for_each(|name| self.check_type(name))?
+                }
+                Kind::Enum { .. } => {}
+                Kind::InputObject { fields } => fields
+                    .iter()
+                    .try_for_each(|field| self.check_type(base_name(&field.ty.node)))?,
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the types directly embedded by a generated type, which must be
+    /// boxed when they are recursive.
+    fn embedded_types(&self, definition: &Definition<'a>) -> Vec<&'a str> {
+        let direct = |ty: &'a Type| match &ty.base {
+            BaseType::Named(name) => Some(name.as_str()),
+            BaseType::List(_) => None,
+        };
+        match &definition.kind {
+            Kind::Object { fields, .. } if !self.roots.contains_key(definition.name) => fields
+                .iter()
+                .filter(|field| field.arguments.is_empty())
+                .filter_map(|field| direct(&field.ty.node))
+                .collect(),
+            Kind::Interface { .. } => self
+                .implementors
+                .get(definition.name)
+                .cloned()
+                .unwrap_or_default(),
+            Kind::Union { members } => members.clone(),
+            Kind::InputObject { fields } => fields
+                .iter()
+                .filter_map(|field| direct(&field.ty.node))
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn find_recursive_types(&mut self) {
+        let edges: HashMap<&'a str, Vec<&'a str>> = self
+            .definitions
+            .iter()
+            .map(|definition| (definition.name, self.embedded_types(definition)))
+            .collect();
+
+        for definition in &self.definitions {
+            let mut visited = HashSet::new();
+            let mut stack = edges[definition.name].clone();
+            while let Some(name) = stack.pop() {
+                if name == definition.name {
+                    self.recursive.insert(definition.name);
+                    break;
+                }
+                if visited.insert(name) {
+                    if let Some(next) = edges.get(name) {
+                        stack.extend(next);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Returns the Rust type of a GraphQL type. Recursive types are boxed when
+    /// they are `stored` in a field of a generated type.
+    fn rust_type(&self, ty: &Type, stored: bool) -> String {
+        let base = match &ty.base {
+            BaseType::Named(name) => {
+                let name = name.as_str();
+                let rust_type = match name {
+                    "Int" => "i32",
+                    "Float" => "f64",
+                    "String" => "String",
+                    "Boolean" => "bool",
+                    "ID" => "::async_graphql::ID",
+                    _ => self
+                        .codegen
+                        .scalars
+                        .get(name)
+                        .map(String::as_str)
+                        .unwrap_or(name),
+                };
+                if stored && self.recursive.contains(name) {
+                    format!("Box<{}>", rust_type)
+                } else {
+                    rust_type.to_string()
+                }
+            }
+            BaseType::List(ty) => format!("Vec<{}>", self.rust_type(ty, false)),
+        };
+        if ty.nullable {
+            format!("Option<{}>", base)
+        } else {
+            base
+        }
+    }
+
+    fn default_value(
+        &self,
+        target: &str,
+        ty: &Type,
+        value: &ConstValue,
+        stored: bool,
+    ) -> Result<String> {
+        if let ConstValue::Null = value {
+            return if ty.nullable {
+                Ok("None".to_string())
+            } else {
+                Err(Error::UnsupportedDefaultValue(target.to_string()))
+            };
+        }
+
+        let expr = match &ty.base {
+            BaseType::List(ty) => match value {
+                ConstValue::List(items) => format!(
+                    "vec![{}]",
+                    items
+                        .iter()
+                        .map(|item| self.default_value(target, ty, item, false))
+                        .collect::<Result<Vec<_>>>()?
+                        .join(", ")
+                ),
+                _ => format!("vec![{}]", self.default_value(target, ty, value, false)?),
+            },
+            BaseType::Named(name) => {
+                let expr = self.named_default_value(target, name.as_str(), value)?;
+                if stored && self.recursive.contains(name.as_str()) {
+                    format!("Box::new({})", expr)
+                } else {
+                    expr
+                }
+            }
+        };
+        Ok(if ty.nullable {
+            format!("Some({})", expr)
+        } else {
+            expr
+        })
+    }
+
+    fn named_default_value(&self, target: &str, name: &str, value: &ConstValue) -> Result<String> {
+        let unsupported = || Error::UnsupportedDefaultValue(target.to_string());
+        Ok(match (name, value) {
+            ("Int", ConstValue::Number(n)) if n.is_i64() => n.to_string(),
+            ("Float", ConstValue::Number(n)) => {
+                format!("{:?}", n.as_f64().ok_or_else(unsupported)?)
+            }
+            ("String", ConstValue::String(s)) => format!("::std::string::String::from({:?})", s),
+            ("ID", ConstValue::String(s)) => format!("::async_graphql::ID::from({:?})", s),
+            ("ID", ConstValue::Number(n)) => {
+                format!("::async_graphql::ID::from({:?})", n.to_string())
+            }
+            ("Boolean", ConstValue::Boolean(b)) => b.to_string(),
+            _ => match self.definition(name).map(|definition| &definition.kind) {
+                Some(Kind::Enum { values }) => match value {
+                    ConstValue::Enum(value)
+                        if values.iter().any(|item| item.value.node == *value) =>
+                    {
+                        format!("{}::{}", name, variant_ident(value)?.0)
+                    }
+                    _ => return Err(unsupported()),
+                },
+                Some(Kind::InputObject { fields }) => match value {
+                    ConstValue::Object(object) => {
+                        if object
+                            .keys()
+                            .any(|key| !fields.iter().any(|field| field.name.node == *key))
+                        {
+                            return Err(unsupported());
+                        }
+                        let mut exprs = Vec::new();
+                        for field in fields {
+                            let ty = &field.ty.node;
+                            let value = object
+                                .get(&field.name.node)
+                                .or_else(|| field.default_value.as_ref().map(|value| &value.node));
+                            let expr = match value {
+                                Some(value) => self.default_value(target, ty, value, true)?,
+                                None if ty.nullable => "None".to_string(),
+                                None => return Err(unsupported()),
+                            };
+                            exprs.push(format!("{}: {}", field_ident(&field.name.node)?.0, expr));
+                        }
+                        format!("{} {{ {} }}", name, exprs.join(", "))
+                    }
+                    _ => return Err(unsupported()),
+                },
+                Some(Kind::Scalar { .. }) if !self.codegen.scalars.contains_key(name) => {
+                    format!("{}(::async_graphql::Any({}))", name, value_expr(value))
+                }
+                _ => return Err(unsupported()),
+            },
+        })
+    }
+
+    fn default_attr(
+        &self,
+        target: &str,
+        arg: &InputValueDefinition,
+        stored: bool,
+    ) -> Result<Option<String>> {
+        arg.default_value
+            .as_ref()
+            .map(|value| {
+                self.default_value(target, &arg.ty.node, &value.node, stored)
+                    .map(|expr| format!("default_with = {:?}", expr))
+            })
+            .transpose()
+    }
+
+    /// Writes a function registering all the types of the schema, because
+    /// the types which are not referenced by a field, such as the interfaces
+    /// only used in fragments, are not registered by default.
+    fn write_register_types(&mut self) {
+        let mut out = String::new();
+        writeln!(out).ok();
+        writeln!(
+            out,
+            "/// Registers all the types of the schema, including the types which are not"
+        )
+        .ok();
+        writeln!(out, "/// returned by any field.").ok();
+        writeln!(
+            out,
+            "pub fn register_types<Q, M, S>(builder: ::async_graphql::SchemaBuilder<Q, M, S>) -> ::async_graphql::SchemaBuilder<Q, M, S> {{"
+        )
+        .ok();
+        writeln!(out, "    builder").ok();
+        for definition in &self.definitions {
+            let register = match &definition.kind {
+                Kind::Scalar { .. }
+                    if is_builtin_scalar(definition.name)
+                        || self.codegen.scalars.contains_key(definition.name) =>
+                {
+                    continue
+                }
+                Kind::Object { .. } if self.roots.contains_key(definition.name) => continue,
+                Kind::InputObject { .. } => "register_input_type",
+                _ => "register_output_type",
+            };
+            writeln!(out, "        .{}::<{}>()", register, definition.name).ok();
+        }
+        writeln!(out, "}}").ok();
+        self.out.push_str(&out);
+    }
+
+    fn write_scalar(
+        &self,
+        out: &mut String,
+        definition: &Definition<'a>,
+        specified_by_url: Option<&str>,
+    ) {
+        write_description(out, "", definition.description);
+        writeln!(
+            out,
+            "#[derive(::async_graphql::NewType, Clone, Debug, PartialEq)]"
+        )
+        .ok();
+        let mut attrs = vec!["name".to_string()];
+        if let Some(url) = specified_by_url {
+            attrs.push(format!("specified_by_url = {:?}", url));
+        }
+        write_graphql_attr(out, "", &attrs);
+        writeln!(
+            out,
+            "pub struct {}(pub ::async_graphql::Any);",
+            definition.name
+        )
+        .ok();
+    }
+
+    fn write_enum(
+        &self,
+        out: &mut String,
+        definition: &Definition<'a>,
+        values: &[&EnumValueDefinition],
+    ) -> Result<()> {
+        write_description(out, "", definition.description);
+        writeln!(
+            out,
+            "#[derive(::async_graphql::Enum, Copy, Clone, Debug, Eq, PartialEq)]"
+        )
+        .ok();
+        writeln!(out, "pub enum {} {{", definition.name).ok();
+        for value in values {
+            let name = value.value.node.as_str();
+            let (ident, rename) = variant_ident(name)?;
+            write_description(
+                out,
+                "    ",
+                value.description.as_ref().map(|desc| desc.node.as_str()),
+            );
+            let mut attrs = Vec::new();
+            if rename {
+                attrs.push(format!("name = {:?}", name));
+            }
+            attrs.extend(deprecation(&value.directives));
+            write_graphql_attr(out, "    ", &attrs);
+            writeln!(out, "    {},", ident).ok();
+        }
+        writeln!(out, "}}").ok();
+        Ok(())
+    }
+
+    fn write_input_object(
+        &self,
+        out: &mut String,
+        definition: &Definition<'a>,
+        fields: &[&InputValueDefinition],
+    ) -> Result<()> {
+        write_description(out, "", definition.description);
+        writeln!(out, "#[derive(::async_graphql::InputObject, Clone)]").ok();
+        writeln!(out, "pub struct {} {{", definition.name).ok();
+        for field in fields {
+            let name = field.name.node.as_str();
+            let (ident, rename) = field_ident(name)?;
+            write_description(
+                out,
+                "    ",
+                field.description.as_ref().map(|desc| desc.node.as_str()),
+            );
+            let mut attrs = Vec::new();
+            if rename {
+                attrs.push(format!("name = {:?}", name));
+            }
+            attrs.extend(self.default_attr(
+                &format!("{}.{}", definition.name, name),
+                field,
+                true,
+            )?);
+            write_graphql_attr(out, "    ", &attrs);
+            writeln!(
+                out,
+                "    pub {}: {},",
+                ident,
+                self.rust_type(&field.ty.node, true)
+            )
+            .ok();
+        }
+        writeln!(out, "}}").ok();
+        Ok(())
+    }
+
+    fn write_interface(
+        &self,
+        out: &mut String,
+        definition: &Definition<'a>,
+        fields: &[&FieldDefinition],
+    ) -> Result<()> {
+        // The arguments of the interface fields are declared by their Rust
+        // identifiers, which can only be renamed all at once.
+        let mut origin_args = false;
+        for field in fields {
+            for arg in &field.arguments {
+                let name = arg.node.name.node.as_str();
+                if field_ident(name)?.1 {
+                    if KEYWORDS.contains(&name) {
+                        return Err(Error::UnsupportedName(name.to_string()));
+                    }
+                    origin_args = true;
+                }
+            }
+        }
+
+        write_description(out, "", definition.description);
+        writeln!(out, "#[derive(::async_graphql::Interface, Clone)]").ok();
+        writeln!(out, "#[graphql(").ok();
+        if origin_args {
+            writeln!(out, "    rename_args = \"Origin\",").ok();
+        }
+        for field in fields {
+            let name = field.name.node.as_str();
+            let target = format!("{}.{}", definition.name, name);
+            let mut items = vec![
+                format!("name = {:?}", name),
+                format!("method = {:?}", field_ident(name)?.0),
+                format!(
+                    "type = {:?}",
+                    self.rust_type(&field.ty.node, field.arguments.is_empty())
+                ),
+            ];
+            if let Some(description) = &field.description {
+                items.push(format!("desc = {:?}", description.node));
+            }
+            items.extend(deprecation(&field.directives));
+            for arg in &field.arguments {
+                let arg = &arg.node;
+                let arg_name = arg.name.node.as_str();
+                let mut arg_items = vec![
+                    format!(
+                        "name = {:?}",
+                        if origin_args {
+                            arg_name.to_string()
+                        } else {
+                            field_ident(arg_name)?.0
+                        }
+                    ),
+                    format!("type = {:?}", self.rust_type(&arg.ty.node, false)),
+                ];
+                if let Some(description) = &arg.description {
+                    arg_items.push(format!("desc = {:?}", description.node));
+                }
+                arg_items.extend(self.default_attr(
+                    &format!("{}.{}", target, arg_name),
+                    arg,
+                    false,
+                )?);
+                items.push(format!("arg({})", arg_items.join(", ")));
+            }
+            writeln!(out, "    field({}),", items.join(", ")).ok();
+        }
+        writeln!(out, ")]").ok();
+        writeln!(out, "pub enum {} {{", definition.name).ok();
+        for implementor in &self.implementors[definition.name] {
+            writeln!(out, "    {}({}),", implementor, implementor).ok();
+        }
+        writeln!(out, "}}").ok();
+        Ok(())
+    }
+
+    fn write_union(&self, out: &mut String, definition: &Definition<'a>, members: &[&str]) {
+        write_description(out, "", definition.description);
+        writeln!(out, "#[derive(::async_graphql::Union, Clone)]").ok();
+        writeln!(out, "pub enum {} {{", definition.name).ok();
+        for member in members {
+            writeln!(out, "    {}({}),", member, member).ok();
+        }
+        writeln!(out, "}}").ok();
+    }
+
+    fn write_object(
+        &self,
+        out: &mut String,
+        definition: &Definition<'a>,
+        implements: &[&str],
+        fields: &[&FieldDefinition],
+    ) -> Result<()> {
+        let name = definition.name;
+        let root = self.roots.get(name).copied();
+        let (plain_fields, resolver_fields): (Vec<&FieldDefinition>, Vec<&FieldDefinition>) =
+            fields
+                .iter()
+                .copied()
+                .partition(|field| root.is_none() && field.arguments.is_empty());
+
+        let interface_fields: HashSet<&str> = implements
+            .iter()
+            .filter_map(
+                |interface| match self.definition(interface).map(|d| &d.kind) {
+                    Some(Kind::Interface { fields }) => Some(fields),
+                    _ => None,
+                },
+            )
+            .flatten()
+            .map(|field| field.name.node.as_str())
+            .collect();
+
+        write_description(out, "", definition.description);
+        if plain_fields.is_empty() {
+            writeln!(out, "#[derive(Clone, Copy, Debug, Default)]").ok();
+            writeln!(out, "pub struct {};", name).ok();
+        } else {
+            writeln!(out, "#[derive(::async_graphql::SimpleObject, Clone)]").ok();
+            if !resolver_fields.is_empty() {
+                writeln!(out, "#[graphql(complex)]").ok();
+            }
+            writeln!(out, "pub struct {} {{", name).ok();
+            for field in &plain_fields {
+                let field_name = field.name.node.as_str();
+                let (ident, rename) = field_ident(field_name)?;
+                write_description(
+                    out,
+                    "    ",
+                    field.description.as_ref().map(|desc| desc.node.as_str()),
+                );
+                let mut attrs = Vec::new();
+                if rename {
+                    attrs.push(format!("name = {:?}", field_name));
+                }
+                if interface_fields.contains(field_name) {
+                    attrs.push("owned".to_string());
+                }
+                attrs.extend(deprecation(&field.directives));
+                write_graphql_attr(out, "    ", &attrs);
+                writeln!(
+                    out,
+                    "    pub {}: {},",
+                    ident,
+                    self.rust_type(&field.ty.node, true)
+                )
+                .ok();
+            }
+            writeln!(out, "}}").ok();
+        }
+
+        if resolver_fields.is_empty() {
+            return Ok(());
+        }
+
+        let return_type = |field: &FieldDefinition| {
+            let ty = self.rust_type(&field.ty.node, false);
+            if root == Some(Root::Subscription) {
+                format!(
+                    "::async_graphql::Result<::async_graphql::futures_util::stream::BoxStream<'static, {}>>",
+                    ty
+                )
+            } else {
+                format!("::async_graphql::Result<{}>", ty)
+            }
+        };
+
+        // The resolver trait
+        writeln!(out).ok();
+        writeln!(out, "/// The resolvers of the `{}` type.", name).ok();
+        writeln!(out, "#[::async_graphql::async_trait::async_trait]").ok();
+        writeln!(out, "pub trait {}Resolver: Send + Sync {{", name).ok();
+        for (idx, field) in resolver_fields.iter().enumerate() {
+            if idx > 0 {
+                writeln!(out).ok();
+            }
+            write_description(
+                out,
+                "    ",
+                field.description.as_ref().map(|desc| desc.node.as_str()),
+            );
+            let mut params = vec!["&self".to_string(), format!("ctx: {}", CONTEXT)];
+            for arg in &field.arguments {
+                let arg = &arg.node;
+                params.push(format!(
+                    "{}: {}",
+                    field_ident(&arg.name.node)?.0,
+                    self.rust_type(&arg.ty.node, false)
+                ));
+            }
+            writeln!(
+                out,
+                "    async fn {}({}) -> {};",
+                field_ident(&field.name.node)?.0,
+                params.join(", "),
+                return_type(field)
+            )
+            .ok();
+        }
+        writeln!(out, "}}").ok();
+
+        // The resolvers, delegating to the trait
+        writeln!(out).ok();
+        let attr = match (root, plain_fields.is_empty()) {
+            (Some(Root::Subscription), _) => "::async_graphql::Subscription",
+            (_, true) => "::async_graphql::Object",
+            (_, false) => "::async_graphql::ComplexObject",
+        };
+        if plain_fields.is_empty() {
+            write_description(out, "", definition.description);
+        }
+        writeln!(out, "#[{}]", attr).ok();
+        writeln!(out, "impl {} {{", name).ok();
+        for (idx, field) in resolver_fields.iter().enumerate() {
+            if idx > 0 {
+                writeln!(out).ok();
+            }
+            let field_name = field.name.node.as_str();
+            let (ident, rename) = field_ident(field_name)?;
+            write_description(
+                out,
+                "    ",
+                field.description.as_ref().map(|desc| desc.node.as_str()),
+            );
+            let mut attrs = Vec::new();
+            if rename {
+                attrs.push(format!("name = {:?}", field_name));
+            }
+            attrs.extend(deprecation(&field.directives));
+            write_graphql_attr(out, "    ", &attrs);
+
+            let mut params = vec!["&self".to_string(), format!("ctx: {}", CONTEXT)];
+            let mut args = vec!["self".to_string(), "ctx".to_string()];
+            for arg in &field.arguments {
+                let arg = &arg.node;
+                let arg_name = arg.name.node.as_str();
+                let (arg_ident, rename) = field_ident(arg_name)?;
+                let mut attrs = Vec::new();
+                if rename {
+                    attrs.push(format!("name = {:?}", arg_name));
+                }
+                if let Some(description) = &arg.description {
+                    attrs.push(format!("desc = {:?}", description.node));
+                }
+                attrs.extend(self.default_attr(
+                    &format!("{}.{}.{}", name, field_name, arg_name),
+                    arg,
+                    false,
+                )?);
+                let attr = if attrs.is_empty() {
+                    String::new()
+                } else {
+                    format!("#[graphql({})] ", attrs.join(", "))
+                };
+                params.push(format!(
+                    "{}{}: {}",
+                    attr,
+                    arg_ident,
+                    self.rust_type(&arg.ty.node, false)
+                ));
+                args.push(arg_ident);
+            }
+            writeln!(
+                out,
+                "    async fn {}({}) -> {} {{",
+                ident,
+                params.join(", "),
+                return_type(field)
+            )
+            .ok();
+            writeln!(
+                out,
+                "        <Self as {}Resolver>::{}({}).await",
+                name,
+                ident,
+                args.join(", ")
+            )
+            .ok();
+            writeln!(out, "    }}").ok();
+        }
+        writeln!(out, "}}").ok();
+        Ok(())
+    }
+}
+
+fn value_expr(value: &ConstValue) -> String {
+    match value {
+        ConstValue::Null => "::async_graphql::Value::Null".to_string(),
+        ConstValue::Number(n) => match n.as_i64() {
+            Some(n) => format!("::async_graphql::Value::from({}i64)", n),
+            None => format!(
+                "::async_graphql::Value::from({:?}f64)",
+                n.as_f64().unwrap_or_default()
+            ),
+        },
+        ConstValue::String(s) => format!("::async_graphql::Value::from({:?})", s),
+        ConstValue::Boolean(b) => format!("::async_graphql::Value::from({})", b),
+        ConstValue::Binary(bytes) => format!(
+            "::async_graphql::Value::Binary(::async_graphql::Bytes::from_static(&{:?}))",
+            bytes.as_ref()
+        ),
+        ConstValue::Enum(name) => format!(
+            "::async_graphql::Value::Enum(::async_graphql::Name::new({:?}))",
+            name.as_str()
+        ),
+        ConstValue::List(items) => format!(
+            "::async_graphql::Value::List(vec![{}])",
+            items.iter().map(value_expr).collect::<Vec<_>>().join(", ")
+        ),
+        ConstValue::Object(object) => format!(
+            "::async_graphql::Value::Object(::std::iter::FromIterator::from_iter(vec![{}]))",
+            object
+                .iter()
+                .map(|(name, value)| format!(
+                    "(::async_graphql::Name::new({:?}), {})",
+                    name.as_str(),
+                    value_expr(value)
+                ))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
@@ -0,0 +1,290 @@
+//! Schema-first code generator for async-graphql.
+//!
+//! This crate reads a GraphQL schema written in SDL, and generates the Rust
+//! types implementing it with the async-graphql derive macros:
+//!
+//! - `scalar` types are new types wrapping an [`Any`] value, unless they are
+//!   mapped to an existing Rust type with [`Codegen::scalar`].
+//! - `enum` types derive [`Enum`].
+//! - `input` types derive [`InputObject`].
+//! - `interface` types derive [`Interface`], with a variant for each object
+//!   implementing them.
+//! - `union` types derive [`Union`].
+//! - `type` types derive [`SimpleObject`], with a field for each field without
+//!   arguments. The fields with arguments are resolved by a `{Name}Resolver`
+//!   trait, which must be implemented for the generated type.
+//! - The query, mutation and subscription root types are unit structs, all
+//!   their fields are resolved by their resolver trait.
+//!
+//! Recursive types are boxed when they are stored in a field, and a
+//! `register_types` function registers all the types in a `SchemaBuilder`, so
+//! that the types which are not returned by any field are part of the schema.
+//!
+//! The resolver traits use [`async_trait`], so their implementations must be
+//! annotated with `#[async_graphql::async_trait::async_trait]`.
+//!
+//! # Build script
+//!
+//! Add `async-graphql-codegen` to the `build-dependencies`, and generate the
+//! code in `build.rs`:
+//!
+//! ```no_run
+//! async_graphql_codegen::Codegen::new()
+//!     .scalar("DateTime", "chrono::DateTime<chrono::Utc>")
+//!     .compile("schema.graphql", "schema.rs")
+//!     .unwrap();
+//! ```
+//!
+//! Then include the generated code, and implement the resolvers:
+//!
+//! ```ignore
+//! mod schema {
+//!     include!(concat!(env!("OUT_DIR"), "/schema.rs"));
+//! }
+//!
+//! use async_graphql::{async_trait::async_trait, Context, Result};
+//! use schema::*;
+//!
+//! #[async_trait]
+//! impl QueryResolver for Query {
+//!     async fn hello(&self, ctx: &Context<'_>, name: String) -> Result<String> {
+//!         Ok(format!("Hello, {}!", name))
+//!     }
+//! }
+//! ```
+//!
+//! # Client
+//!
+//! [`ClientCodegen`] generates typed request and response types from the
+//! operations of an executable document, after validating it against the
+//! schema:
+//!
+//! ```no_run
+//! let sdl = std::fs::read_to_string("schema.graphql").unwrap();
+//! async_graphql_codegen::ClientCodegen::from_sdl(&sdl)
+//!     .unwrap()
+//!     .scalar("DateTime", "String")
+//!     .compile("queries.graphql", "queries.rs")
+//!     .unwrap();
+//! ```
+//!
+//! Each operation is a module, whose `request` function returns the body of
+//! the request, and whose `ResponseData` type deserializes the `data` of the
+//! response:
+//!
+//! ```ignore
+//! let body = queries::get_todo::request(queries::get_todo::Variables { id: "1".into() });
+//! let data: queries::get_todo::ResponseData = serde_json::from_value(response["data"].take())?;
+//! ```
+//!
+//! [`Any`]: https://docs.rs/async-graphql/latest/async_graphql/struct.Any.html
+//! [`Enum`]: https://docs.rs/async-graphql/latest/async_graphql/derive.Enum.html
+//! [`InputObject`]: https://docs.rs/async-graphql/latest/async_graphql/derive.InputObject.html
+//! [`Interface`]: https://docs.rs/async-graphql/latest/async_graphql/derive.Interface.html
+//! [`Union`]: https://docs.rs/async-graphql/latest/async_graphql/derive.Union.html
+//! [`SimpleObject`]: https://docs.rs/async-graphql/latest/async_graphql/derive.SimpleObject.html
+//! [`async_trait`]: https://docs.rs/async-trait
+
+#![warn(missing_docs)]
+#![forbid(unsafe_code)]
+
+mod client;
+mod generator;
+
+use std::{collections::HashMap, path::Path};
+
+use async_graphql::{dynamic::SchemaError, registry::Registry, ServerError};
+use thiserror::Error;
+
+/// Code generation error.
+#[derive(Debug, Error)]
+pub enum Error {
+    /// Failed to read the schema or to write the generated code.
+    #[error("{0}")]
+    Io(#[from] std::io::Error),
+
+    /// Failed to parse the schema.
+    #[error("Failed to parse the schema: {0}")]
+    Parse(#[from] async_graphql_parser::Error),
+
+    /// The schema references a type which is not defined.
+    #[error("Unknown type \"{0}\"")]
+    UnknownType(String),
+
+    /// An interface is not implemented by any object, so it cannot be
+    /// represented by an enum.
+    #[error("The interface \"{0}\" is not implemented by any object")]
+    NoImplementors(String),
+
+    /// A default value cannot be converted to a Rust expression.
+    #[error("Unsupported default value for \"{0}\"")]
+    UnsupportedDefaultValue(String),
+
+    /// A name cannot be converted to a Rust identifier.
+    #[error("Unsupported name \"{0}\"")]
+    UnsupportedName(String),
+
+    /// The schema used to generate a client is invalid.
+    #[error("Invalid schema: {0}")]
+    Schema(#[from] SchemaError),
+
+    /// The executable document is not valid against the schema.
+    #[error("Invalid document: {}", messages(.0))]
+    Validation(Vec<ServerError>),
+
+    /// An operation has no name, so it cannot be given a module.
+    #[error("The operations must be named")]
+    UnnamedOperation,
+
+    /// A fragment on an abstract type is spread in a selection on another
+    /// abstract type which it does not always apply to.
+    #[error("Unsupported fragment on \"{on}\" in a selection on \"{ty}\"")]
+    UnsupportedSelection {
+        /// The type of the selection.
+        ty: String,
+        /// The type condition of the fragment.
+        on: String,
+    },
+
+    /// The fields depending on the type of a value are selected without
+    /// `__typename`.
+    #[error("\"{0}\" has fragments on object types, but does not select `__typename`")]
+    MissingTypename(String),
+
+    /// Two generated items have the same name.
+    #[error("Conflicting name \"{0}\"")]
+    NameConflict(String),
+}
+
+fn messages(errors: &[ServerError]) -> String {
+    errors
+        .iter()
+        .map(|err| err.message.as_str())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Code generation result.
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// Schema-first code generator.
+#[derive(Debug, Clone, Default)]
+pub struct Codegen {
+    scalars: HashMap<String, String>,
+}
+
+impl Codegen {
+    /// Create a code generator.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Map a custom scalar to a Rust type implementing
+    /// `async_graphql::ScalarType`, instead of generating a new type for it.
+    #[must_use]
+    pub fn scalar(mut self, name: impl Into<String>, rust_type: impl Into<String>) -> Self {
+        self.scalars.insert(name.into(), rust_type.into());
+        self
+    }
+
+    /// Generate the Rust code of a schema.
+    pub fn generate(&self, sdl: &str) -> Result<String> {
+        let doc = async_graphql_parser::parse_schema(sdl)?;
+        generator::generate(self, &doc)
+    }
+
+    /// Generate the Rust code of the `input` schema file into the `output` file
+    /// of the `OUT_DIR` directory, from a build script.
+    ///
+    /// The build script is run again when the schema file changes.
+    pub fn compile(&self, input: impl AsRef<Path>, output: impl AsRef<Path>) -> Result<()> {
+        compile(input.as_ref(), output.as_ref(), |input| {
+            self.generate(input)
+        })
+    }
+}
+
+/// Typed client code generator.
+///
+/// It generates, for each named operation of an executable document, a module
+/// with:
+///
+/// - `OPERATION_NAME` and `QUERY` constants.
+/// - A `Variables` struct implementing `serde::Serialize`.
+/// - A `ResponseData` struct implementing `serde::Deserialize`, with a nested
+///   struct for each selection set.
+/// - A `request` function returning the JSON body of a request.
+///
+/// The enums, input objects and fragments are shared by the operations. A
+/// fragment spread is a field named after the fragment, and the fragments on
+/// object types in a selection on an interface or a union are an `on` enum,
+/// chosen by `__typename`.
+///
+/// The document is validated against the schema before the code is
+/// generated, and the generated code depends on the `serde` and `serde_json`
+/// crates.
+pub struct ClientCodegen {
+    registry: Registry,
+    scalars: HashMap<String, String>,
+}
+
+impl ClientCodegen {
+    /// Create a client code generator for a schema.
+    pub fn new(registry: Registry) -> Self {
+        Self {
+            registry,
+            scalars: Default::default(),
+        }
+    }
+
+    /// Create a client code generator for a schema written in SDL.
+    pub fn from_sdl(sdl: &str) -> Result<Self> {
+        let doc = async_graphql_parser::parse_schema(sdl)?;
+        Ok(Self::new(Registry::from_service_document(&doc)?))
+    }
+
+    /// Map a custom scalar to a Rust type implementing `serde::Serialize` and
+    /// `serde::Deserialize`, instead of `serde_json::Value`.
+    #[must_use]
+    pub fn scalar(mut self, name: impl Into<String>, rust_type: impl Into<String>) -> Self {
+        self.scalars.insert(name.into(), rust_type.into());
+        self
+    }
+
+    /// Generate the Rust code of the operations of an executable document.
+    pub fn generate(&self, query: &str) -> Result<String> {
+        client::generate(self, query)
+    }
+
+    /// Generate the Rust code of the `input` executable document file into
+    /// the `output` file of the `OUT_DIR` directory, from a build script.
+    ///
+    /// The build script is run again when the document file changes.
+    pub fn compile(&self, input: impl AsRef<Path>, output: impl AsRef<Path>) -> Result<()> {
+        compile(input.as_ref(), output.as_ref(), |input| {
+            self.generate(input)
+        })
+    }
+}
+
+fn compile(
+    input: &Path,
+    output: &Path,
+    generate: impl FnOnce(&str) -> Result<String>,
+) -> Result<()> {
+    println!("cargo:rerun-if-changed={}", input.display());
+    let out_dir = std::env::var_os("OUT_DIR").ok_or_else(|| {
+        std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            "The OUT_DIR environment variable is not set, `compile` must be called from a build script.",
+        )
+    })?;
+    let code = generate(&std::fs::read_to_string(input)?)?;
+    std::fs::write(Path::new(&out_dir).join(output), code)?;
+    Ok(())
+}
+
+/// Generate the Rust code of a schema with the default options.
+pub fn generate(sdl: &str) -> Result<String> {
+    Codegen::new().generate(sdl)
+}
@@ -0,0 +1,182 @@
+use std::fs;
+
+use async_graphql_codegen::{ClientCodegen, Error};
+use serde_json::json;
+
+mod common;
+
+#[rustfmt::skip]
+#[allow(dead_code)]
+#[path = "generated/client.rs"]
+mod client;
+
+use client::*;
+
+fn codegen() -> ClientCodegen {
+    let sdl = fs::read_to_string("./tests/schema.graphql").unwrap();
+    ClientCodegen::from_sdl(&sdl)
+        .unwrap()
+        .scalar("DateTime", "String")
+}
+
+#[test]
+fn generated_code_is_fresh() {
+    let query = fs::read_to_string("./tests/client.graphql").unwrap();
+    let code = codegen().generate(&query).unwrap();
+    common::check_generated("./tests/generated/client.rs", &code);
+}
+
+#[test]
+fn generate_errors() {
+    assert!(matches!(
+        ClientCodegen::from_sdl("type Query { a: Unknown }"),
+        Err(Error::Schema(_))
+    ));
+    assert!(matches!(
+        codegen().generate("{ todo(id: 1) { id } }"),
+        Err(Error::UnnamedOperation)
+    ));
+    assert!(matches!(
+        codegen().generate("query A { todo { id name } }"),
+        Err(Error::Validation(errors)) if errors.len() == 2
+    ));
+    assert!(matches!(
+        codegen().generate(r#"query A { search(text: "a") { ... on Todo { title } } }"#),
+        Err(Error::MissingTypename(name)) if name == "Search"
+    ));
+    assert!(matches!(
+        codegen().generate("query A { nodes { ... on Named { name } } }"),
+        Err(Error::UnsupportedSelection { ty, on }) if ty == "Node" && on == "Named"
+    ));
+    assert!(matches!(
+        codegen().generate("query A { todos { id } } query a { todos { id } }"),
+        Err(Error::NameConflict(name)) if name == "a"
+    ));
+}
+
+#[test]
+fn test_request() {
+    assert_eq!(
+        get_todo::request(get_todo::Variables {
+            id: "1".to_string(),
+            with_assignee: None,
+        }),
+        json!({
+            "query": DOCUMENT,
+            "operationName": "GetTodo",
+            "variables": { "id": "1" },
+        })
+    );
+
+    assert_eq!(
+        list_todos::request(list_todos::Variables {
+            filter: Some(TodoFilter {
+                status: Some(Status::Done),
+                title_contains: None,
+                and: None,
+                not: Some(Box::new(TodoFilter {
+                    status: None,
+                    title_contains: Some("a".to_string()),
+                    and: None,
+                    not: None,
+                })),
+            }),
+        })["variables"],
+        json!({ "filter": { "status": "DONE", "not": { "titleContains": "a" } } })
+    );
+
+    assert_eq!(
+        todo_added::request(todo_added::Variables {})["variables"],
+        json!({})
+    );
+}
+
+#[test]
+fn test_response() {
+    let todo_fields = TodoFields {
+        id: "1".to_string(),
+        title: "a".to_string(),
+        status: Status::Open,
+        created_at: "2022-01-01T00:00:00Z".to_string(),
+        metadata: Some(json!({ "priority": 1 })),
+    };
+
+    let data: get_todo::ResponseData = serde_json::from_value(json!({
+        "todo": {
+            "id": "1",
+            "title": "a",
+            "status": "OPEN",
+            "createdAt": "2022-01-01T00:00:00Z",
+            "metadata": { "priority": 1 },
+            "parent": null,
+        },
+    }))
+    .unwrap();
+    assert_eq!(
+        data,
+        get_todo::ResponseData {
+            todo: Some(get_todo::Todo {
+                parent: None,
+                assignee: None,
+                todo_fields: todo_fields.clone(),
+            }),
+        }
+    );
+
+    let data: list_todos::ResponseData = serde_json::from_value(json!({
+        "todos": [{ "id": "1", "state": "DONE" }, { "id": "2", "state": "ARCHIVED" }],
+    }))
+    .unwrap();
+    assert_eq!(
+        data.todos
+            .into_iter()
+            .map(|todo| todo.state)
+            .collect::<Vec<_>>(),
+        vec![Status::Done, Status::Other]
+    );
+
+    let data: search::ResponseData = serde_json::from_value(json!({
+        "search": [
+            { "__typename": "User", "id": "1", "name": "USER1" },
+            { "__typename": "Todo", "title": "a" },
+            { "__typename": "Project" },
+        ],
+        "nodes": [{ "id": "1" }],
+    }))
+    .unwrap();
+    assert_eq!(
+        data.search
+            .into_iter()
+            .map(|result| result.on)
+            .collect::<Vec<_>>(),
+        vec![
+            search::SearchOn::User(search::SearchOnUser {
+                user_fields: UserFields {
+                    id: "1".to_string(),
+                    name: "USER1".to_string(),
+                },
+            }),
+            search::SearchOn::Todo(search::SearchOnTodo {
+                title: "a".to_string(),
+            }),
+            search::SearchOn::Other,
+        ]
+    );
+
+    let data: add_todo::ResponseData = serde_json::from_value(json!({
+        "addTodo": {
+            "id": "1",
+            "title": "a",
+            "status": "OPEN",
+            "createdAt": "2022-01-01T00:00:00Z",
+            "metadata": { "priority": 1 },
+        },
+    }))
+    .unwrap();
+    assert_eq!(data.add_todo.todo_fields, todo_fields);
+
+    assert!(serde_json::from_value::<add_todo::ResponseData>(json!({
+        "addTodo": { "id": "1" },
+    }))
+    .is_err());
+}
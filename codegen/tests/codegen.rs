@@ -0,0 +1,225 @@
+use std::fs;
+
+use async_graphql::{
+    async_trait::async_trait,
+    futures_util::{stream::BoxStream, StreamExt},
+    value, Context, Result, Schema, ID,
+};
+use async_graphql_codegen::{Codegen, Error};
+
+mod common;
+
+#[rustfmt::skip]
+#[allow(dead_code)]
+#[path = "generated/schema.rs"]
+mod schema;
+
+use schema::*;
+
+fn codegen() -> Codegen {
+    Codegen::new().scalar("DateTime", "String")
+}
+
+#[test]
+fn generated_code_is_fresh() {
+    let sdl = fs::read_to_string("./tests/schema.graphql").unwrap();
+    let code = codegen().generate(&sdl).unwrap();
+    common::check_generated("./tests/generated/schema.rs", &code);
+}
+
+#[test]
+fn generate_errors() {
+    assert!(matches!(
+        codegen().generate("type Query { a: Unknown }"),
+        Err(Error::UnknownType(name)) if name == "Unknown"
+    ));
+    assert!(matches!(
+        codegen().generate("interface Node { id: ID! } type Query { node: Node }"),
+        Err(Error::NoImplementors(name)) if name == "Node"
+    ));
+    assert!(matches!(
+        codegen().generate("type Query { a(at: DateTime = \"now\"): Int }"),
+        Err(Error::UnsupportedDefaultValue(name)) if name == "Query.a.at"
+    ));
+    assert!(matches!(
+        codegen().generate("type Query { a: Int"),
+        Err(Error::Parse(_))
+    ));
+}
+
+fn todo(id: &str, title: &str) -> Todo {
+    Todo {
+        id: ID::from(id),
+        title: title.to_string(),
+        status: Status::Open,
+        created_at: "2022-01-01T00:00:00Z".to_string(),
+        metadata: None,
+        assignee: None,
+        parent: None,
+    }
+}
+
+#[async_trait]
+impl UserResolver for User {
+    async fn name(&self, _ctx: &Context<'_>, uppercase: Option<bool>) -> Result<String> {
+        let name = format!("user{}", self.id.as_str());
+        Ok(match uppercase {
+            Some(true) => name.to_uppercase(),
+            _ => name,
+        })
+    }
+
+    async fn todos(
+        &self,
+        _ctx: &Context<'_>,
+        _status: Option<Status>,
+        first: Option<i32>,
+    ) -> Result<Vec<Todo>> {
+        Ok((0..first.unwrap_or_default())
+            .map(|idx| todo(&idx.to_string(), "todo"))
+            .collect())
+    }
+}
+
+#[async_trait]
+impl QueryResolver for Query {
+    async fn todo(&self, _ctx: &Context<'_>, id: ID) -> Result<Option<Todo>> {
+        let mut child = todo(&id, "child");
+        child.parent = Some(Box::new(todo("0", "parent")));
+        child.assignee = Some(User { id: ID::from("1") });
+        Ok(Some(child))
+    }
+
+    async fn todos(&self, _ctx: &Context<'_>, filter: Option<TodoFilter>) -> Result<Vec<Todo>> {
+        let filter = filter.unwrap();
+        Ok(vec![todo(
+            "1",
+            &format!("{:?} {:?}", filter.status, filter.title_contains),
+        )])
+    }
+
+    async fn search(&self, _ctx: &Context<'_>, text: String) -> Result<Vec<SearchResult>> {
+        Ok(vec![
+            SearchResult::User(User { id: ID::from("1") }),
+            SearchResult::Todo(todo("2", &text)),
+        ])
+    }
+
+    async fn nodes(&self, _ctx: &Context<'_>) -> Result<Vec<Node>> {
+        Ok(vec![
+            Node::User(User { id: ID::from("1") }),
+            Node::Todo(todo("2", "todo")),
+        ])
+    }
+
+    async fn type_(&self, _ctx: &Context<'_>) -> Result<String> {
+        Ok("type".to_string())
+    }
+}
+
+#[async_trait]
+impl MutationResolver for Mutation {
+    async fn add_todo(&self, _ctx: &Context<'_>, input: NewTodo) -> Result<Todo> {
+        let mut todo = todo("1", &input.title);
+        todo.metadata = input.metadata;
+        Ok(todo)
+    }
+}
+
+#[async_trait]
+impl SubscriptionResolver for Subscription {
+    async fn todo_added(&self, _ctx: &Context<'_>) -> Result<BoxStream<'static, Todo>> {
+        Ok(async_graphql::futures_util::stream::iter(vec![todo("1", "a"), todo("2", "b")]).boxed())
+    }
+}
+
+#[tokio::test]
+async fn test_generated_schema() {
+    let schema = register_types(Schema::build(Query, Mutation, Subscription)).finish();
+
+    assert_eq!(
+        schema
+            .execute(
+                r#"{
+                    todo(id: "3") {
+                        id title status createdAt
+                        parent { title }
+                        assignee { name todos { id } upper: name(uppercase: true) }
+                    }
+                    todos { title }
+                    search(text: "abc") {
+                        __typename
+                        ... on Todo { title }
+                        ... on User { id }
+                    }
+                    nodes { id ... on Named { name } }
+                    type
+                }"#
+            )
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({
+            "todo": {
+                "id": "3",
+                "title": "child",
+                "status": "OPEN",
+                "createdAt": "2022-01-01T00:00:00Z",
+                "parent": { "title": "parent" },
+                "assignee": {
+                    "name": "user1",
+                    "todos": (0..10).map(|idx| value!({ "id": idx.to_string() })).collect::<Vec<_>>(),
+                    "upper": "USER1",
+                },
+            },
+            "todos": [{ "title": "Some(Done) None" }],
+            "search": [
+                { "__typename": "User", "id": "1" },
+                { "__typename": "Todo", "title": "abc" },
+            ],
+            "nodes": [{ "id": "1", "name": "user1" }, { "id": "2" }],
+            "type": "type",
+        })
+    );
+
+    assert_eq!(
+        schema
+            .execute(r#"mutation { addTodo(input: { title: "new" }) { title metadata } }"#)
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "addTodo": { "title": "new", "metadata": { "priority": 1 } } })
+    );
+
+    let mut stream = schema.execute_stream("subscription { todoAdded { title } }");
+    assert_eq!(
+        stream.next().await.unwrap().into_result().unwrap().data,
+        value!({ "todoAdded": { "title": "a" } })
+    );
+    assert_eq!(
+        stream.next().await.unwrap().into_result().unwrap().data,
+        value!({ "todoAdded": { "title": "b" } })
+    );
+    assert!(stream.next().await.is_none());
+}
+
+#[tokio::test]
+async fn test_generated_sdl() {
+    let sdl = register_types(Schema::build(Query, Mutation, Subscription))
+        .finish()
+        .sdl();
+    for expected in [
+        "scalar Json",
+        "interface Named",
+        r#"CLOSED @deprecated(reason: "Use `DONE`")"#,
+        "todos(status: Status, first: Int = 10): [Todo!]!",
+        "todos(filter: TodoFilter = {status: DONE,titleContains: null,and: null,not: null}): [Todo!]!",
+        "metadata: Json = {priority: 1}",
+        "type: String! @deprecated",
+        "union SearchResult = User | Todo",
+    ] {
+        assert!(sdl.contains(expected), "{}", expected);
+    }
+}
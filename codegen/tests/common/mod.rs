@@ -0,0 +1,42 @@
+use std::fs;
+
+/// Checks that the generated file at `path` is up to date with `code`.
+///
+/// The file is only rewritten when the `UPDATE_GENERATED` environment variable
+/// is set, otherwise the test fails with the changed lines.
+pub fn check_generated(path: &str, code: &str) {
+    let current = fs::read_to_string(path).unwrap_or_default();
+    if current == code {
+        return;
+    }
+    if std::env::var_os("UPDATE_GENERATED").is_some() {
+        fs::write(path, code).unwrap();
+        return;
+    }
+
+    let current = current.lines().collect::<Vec<_>>();
+    let code = code.lines().collect::<Vec<_>>();
+    let prefix = current
+        .iter()
+        .zip(&code)
+        .take_while(|(a, b)| a == b)
+        .count();
+    let suffix = current[prefix..]
+        .iter()
+        .rev()
+        .zip(code[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let mut diff = format!("@@ line {} @@\n", prefix + 1);
+    for line in &current[prefix..current.len() - suffix] {
+        diff += &format!("-{}\n", line);
+    }
+    for line in &code[prefix..code.len() - suffix] {
+        diff += &format!("+{}\n", line);
+    }
+    panic!(
+        "{} is out of date, run the tests with UPDATE_GENERATED=1 to regenerate it:\n{}",
+        path, diff
+    );
+}
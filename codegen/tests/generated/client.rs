@@ -0,0 +1,402 @@
+// Code generated by async-graphql-codegen, do not edit.
+
+/// The executable document containing all the operations.
+pub const DOCUMENT: &str = r"query GetTodo($id: ID!, $withAssignee: Boolean = false) {
+  todo(id: $id) {
+    ...TodoFields
+    parent {
+      id
+    }
+    assignee @include(if: $withAssignee) {
+      ...UserFields
+    }
+  }
+}
+
+query ListTodos($filter: TodoFilter) {
+  todos(filter: $filter) {
+    id
+    state: status
+  }
+}
+
+query Search($text: String!) {
+  search(text: $text) {
+    __typename
+    ... on User {
+      ...UserFields
+    }
+    ... on Todo {
+      title
+    }
+  }
+  nodes {
+    id
+  }
+}
+
+mutation AddTodo($input: NewTodo!) {
+  addTodo(input: $input) {
+    ...TodoFields
+  }
+}
+
+subscription TodoAdded {
+  todoAdded {
+    id
+    title
+  }
+}
+
+fragment TodoFields on Todo {
+  id
+  title
+  status
+  createdAt
+  metadata
+}
+
+fragment UserFields on User {
+  id
+  name(uppercase: true)
+}
+";
+
+/// The fields selected on the `Todo` type.
+#[derive(Debug, Clone, PartialEq, ::serde::Deserialize)]
+pub struct TodoFields {
+    pub id: String,
+    pub title: String,
+    pub status: Status,
+    #[serde(rename = "createdAt")]
+    pub created_at: String,
+    pub metadata: Option<::serde_json::Value>,
+}
+
+/// The fields selected on the `User` type.
+#[derive(Debug, Clone, PartialEq, ::serde::Deserialize)]
+pub struct UserFields {
+    pub id: String,
+    pub name: String,
+}
+
+/// The status of a todo.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, ::serde::Serialize, ::serde::Deserialize)]
+pub enum Status {
+    #[serde(rename = "OPEN")]
+    Open,
+    #[serde(rename = "DONE")]
+    Done,
+    /// Use `DONE` instead.
+    #[serde(rename = "CLOSED")]
+    Closed,
+    /// A value added to the schema after the code was generated.
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Debug, Clone, PartialEq, ::serde::Serialize)]
+pub struct TodoFilter {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<Status>,
+    #[serde(rename = "titleContains", skip_serializing_if = "Option::is_none")]
+    pub title_contains: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub and: Option<Vec<TodoFilter>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub not: Option<Box<TodoFilter>>,
+}
+
+#[derive(Debug, Clone, PartialEq, ::serde::Serialize)]
+pub struct NewTodo {
+    pub title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<::serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filter: Option<TodoFilter>,
+}
+
+/// The `GetTodo` operation.
+pub mod get_todo {
+    /// The name of the operation.
+    pub const OPERATION_NAME: &str = "GetTodo";
+    /// The document containing the operation.
+    pub const QUERY: &str = super::DOCUMENT;
+
+    /// The variables of the operation.
+    #[derive(Debug, Clone, PartialEq, ::serde::Serialize)]
+    pub struct Variables {
+        pub id: String,
+        #[serde(rename = "withAssignee", skip_serializing_if = "Option::is_none")]
+        pub with_assignee: Option<bool>,
+    }
+
+    /// The data of the response.
+    #[derive(Debug, Clone, PartialEq, ::serde::Deserialize)]
+    pub struct ResponseData {
+        pub todo: Option<Todo>,
+    }
+
+    /// The fields selected on the `Todo` type.
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct Todo {
+        pub parent: Option<TodoParent>,
+        pub assignee: Option<TodoAssignee>,
+        pub todo_fields: super::TodoFields,
+    }
+
+    impl<'de> ::serde::Deserialize<'de> for Todo {
+        fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> ::std::result::Result<Self, D::Error> {
+            use ::serde::de::Error;
+
+            let value = ::serde_json::Value::deserialize(deserializer)?;
+            Ok(Self {
+                parent: ::serde_json::from_value(value.get("parent").cloned().unwrap_or_default()).map_err(D::Error::custom)?,
+                assignee: ::serde_json::from_value(value.get("assignee").cloned().unwrap_or_default()).map_err(D::Error::custom)?,
+                todo_fields: ::serde_json::from_value(value.clone()).map_err(D::Error::custom)?,
+            })
+        }
+    }
+
+    /// The fields selected on the `Todo` type.
+    #[derive(Debug, Clone, PartialEq, ::serde::Deserialize)]
+    pub struct TodoParent {
+        pub id: String,
+    }
+
+    /// The fields selected on the `User` type.
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct TodoAssignee {
+        pub user_fields: super::UserFields,
+    }
+
+    impl<'de> ::serde::Deserialize<'de> for TodoAssignee {
+        fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> ::std::result::Result<Self, D::Error> {
+            use ::serde::de::Error;
+
+            let value = ::serde_json::Value::deserialize(deserializer)?;
+            Ok(Self {
+                user_fields: ::serde_json::from_value(value.clone()).map_err(D::Error::custom)?,
+            })
+        }
+    }
+
+    /// Returns the body of a request executing the operation.
+    pub fn request(variables: Variables) -> ::serde_json::Value {
+        ::serde_json::json!({
+            "query": QUERY,
+            "operationName": OPERATION_NAME,
+            "variables": variables,
+        })
+    }
+}
+
+/// The `ListTodos` operation.
+pub mod list_todos {
+    /// The name of the operation.
+    pub const OPERATION_NAME: &str = "ListTodos";
+    /// The document containing the operation.
+    pub const QUERY: &str = super::DOCUMENT;
+
+    /// The variables of the operation.
+    #[derive(Debug, Clone, PartialEq, ::serde::Serialize)]
+    pub struct Variables {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub filter: Option<super::TodoFilter>,
+    }
+
+    /// The data of the response.
+    #[derive(Debug, Clone, PartialEq, ::serde::Deserialize)]
+    pub struct ResponseData {
+        pub todos: Vec<Todos>,
+    }
+
+    /// The fields selected on the `Todo` type.
+    #[derive(Debug, Clone, PartialEq, ::serde::Deserialize)]
+    pub struct Todos {
+        pub id: String,
+        pub state: super::Status,
+    }
+
+    /// Returns the body of a request executing the operation.
+    pub fn request(variables: Variables) -> ::serde_json::Value {
+        ::serde_json::json!({
+            "query": QUERY,
+            "operationName": OPERATION_NAME,
+            "variables": variables,
+        })
+    }
+}
+
+/// The `Search` operation.
+pub mod search {
+    /// The name of the operation.
+    pub const OPERATION_NAME: &str = "Search";
+    /// The document containing the operation.
+    pub const QUERY: &str = super::DOCUMENT;
+
+    /// The variables of the operation.
+    #[derive(Debug, Clone, PartialEq, ::serde::Serialize)]
+    pub struct Variables {
+        pub text: String,
+    }
+
+    /// The data of the response.
+    #[derive(Debug, Clone, PartialEq, ::serde::Deserialize)]
+    pub struct ResponseData {
+        pub search: Vec<Search>,
+        pub nodes: Vec<Nodes>,
+    }
+
+    /// The fields selected on the `SearchResult` type.
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct Search {
+        pub typename: String,
+        pub on: SearchOn,
+    }
+
+    impl<'de> ::serde::Deserialize<'de> for Search {
+        fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> ::std::result::Result<Self, D::Error> {
+            use ::serde::de::Error;
+
+            let value = ::serde_json::Value::deserialize(deserializer)?;
+            Ok(Self {
+                typename: ::serde_json::from_value(value.get("__typename").cloned().unwrap_or_default()).map_err(D::Error::custom)?,
+                on: match value.get("__typename").and_then(::serde_json::Value::as_str) {
+                    Some("User") => SearchOn::User(::serde_json::from_value(value.clone()).map_err(D::Error::custom)?),
+                    Some("Todo") => SearchOn::Todo(::serde_json::from_value(value.clone()).map_err(D::Error::custom)?),
+                    _ => SearchOn::Other,
+                },
+            })
+        }
+    }
+
+    /// The fields selected depending on the type of `Search`.
+    #[derive(Debug, Clone, PartialEq)]
+    pub enum SearchOn {
+        User(SearchOnUser),
+        Todo(SearchOnTodo),
+        /// Any other type.
+        Other,
+    }
+
+    /// The fields selected on the `User` type.
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct SearchOnUser {
+        pub user_fields: super::UserFields,
+    }
+
+    impl<'de> ::serde::Deserialize<'de> for SearchOnUser {
+        fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> ::std::result::Result<Self, D::Error> {
+            use ::serde::de::Error;
+
+            let value = ::serde_json::Value::deserialize(deserializer)?;
+            Ok(Self {
+                user_fields: ::serde_json::from_value(value.clone()).map_err(D::Error::custom)?,
+            })
+        }
+    }
+
+    /// The fields selected on the `Todo` type.
+    #[derive(Debug, Clone, PartialEq, ::serde::Deserialize)]
+    pub struct SearchOnTodo {
+        pub title: String,
+    }
+
+    /// The fields selected on the `Node` type.
+    #[derive(Debug, Clone, PartialEq, ::serde::Deserialize)]
+    pub struct Nodes {
+        pub id: String,
+    }
+
+    /// Returns the body of a request executing the operation.
+    pub fn request(variables: Variables) -> ::serde_json::Value {
+        ::serde_json::json!({
+            "query": QUERY,
+            "operationName": OPERATION_NAME,
+            "variables": variables,
+        })
+    }
+}
+
+/// The `AddTodo` operation.
+pub mod add_todo {
+    /// The name of the operation.
+    pub const OPERATION_NAME: &str = "AddTodo";
+    /// The document containing the operation.
+    pub const QUERY: &str = super::DOCUMENT;
+
+    /// The variables of the operation.
+    #[derive(Debug, Clone, PartialEq, ::serde::Serialize)]
+    pub struct Variables {
+        pub input: super::NewTodo,
+    }
+
+    /// The data of the response.
+    #[derive(Debug, Clone, PartialEq, ::serde::Deserialize)]
+    pub struct ResponseData {
+        #[serde(rename = "addTodo")]
+        pub add_todo: AddTodo,
+    }
+
+    /// The fields selected on the `Todo` type.
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct AddTodo {
+        pub todo_fields: super::TodoFields,
+    }
+
+    impl<'de> ::serde::Deserialize<'de> for AddTodo {
+        fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> ::std::result::Result<Self, D::Error> {
+            use ::serde::de::Error;
+
+            let value = ::serde_json::Value::deserialize(deserializer)?;
+            Ok(Self {
+                todo_fields: ::serde_json::from_value(value.clone()).map_err(D::Error::custom)?,
+            })
+        }
+    }
+
+    /// Returns the body of a request executing the operation.
+    pub fn request(variables: Variables) -> ::serde_json::Value {
+        ::serde_json::json!({
+            "query": QUERY,
+            "operationName": OPERATION_NAME,
+            "variables": variables,
+        })
+    }
+}
+
+/// The `TodoAdded` operation.
+pub mod todo_added {
+    /// The name of the operation.
+    pub const OPERATION_NAME: &str = "TodoAdded";
+    /// The document containing the operation.
+    pub const QUERY: &str = super::DOCUMENT;
+
+    /// The variables of the operation.
+    #[derive(Debug, Clone, PartialEq, ::serde::Serialize)]
+    pub struct Variables {}
+
+    /// The data of the response.
+    #[derive(Debug, Clone, PartialEq, ::serde::Deserialize)]
+    pub struct ResponseData {
+        #[serde(rename = "todoAdded")]
+        pub todo_added: TodoAdded,
+    }
+
+    /// The fields selected on the `Todo` type.
+    #[derive(Debug, Clone, PartialEq, ::serde::Deserialize)]
+    pub struct TodoAdded {
+        pub id: String,
+        pub title: String,
+    }
+
+    /// Returns the body of a request executing the operation.
+    pub fn request(variables: Variables) -> ::serde_json::Value {
+        ::serde_json::json!({
+            "query": QUERY,
+            "operationName": OPERATION_NAME,
+            "variables": variables,
+        })
+    }
+}
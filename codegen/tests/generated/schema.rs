@@ -0,0 +1,185 @@
+// Code generated by async-graphql-codegen, do not edit.
+
+#[derive(::async_graphql::NewType, Clone, Debug, PartialEq)]
+#[graphql(name)]
+pub struct Json(pub ::async_graphql::Any);
+
+/// The status of a todo.
+#[derive(::async_graphql::Enum, Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Status {
+    Open,
+    Done,
+    /// Use `DONE` instead.
+    #[graphql(deprecation = "Use `DONE`")]
+    Closed,
+}
+
+/// A node with a global id.
+#[derive(::async_graphql::Interface, Clone)]
+#[graphql(
+    field(name = "id", method = "id", type = "::async_graphql::ID"),
+)]
+pub enum Node {
+    User(User),
+    Todo(Todo),
+}
+
+#[derive(::async_graphql::Interface, Clone)]
+#[graphql(
+    field(name = "name", method = "name", type = "String", arg(name = "uppercase", type = "Option<bool>", default_with = "Some(false)")),
+)]
+pub enum Named {
+    User(User),
+}
+
+#[derive(::async_graphql::SimpleObject, Clone)]
+#[graphql(complex)]
+pub struct User {
+    #[graphql(owned)]
+    pub id: ::async_graphql::ID,
+}
+
+/// The resolvers of the `User` type.
+#[::async_graphql::async_trait::async_trait]
+pub trait UserResolver: Send + Sync {
+    async fn name(&self, ctx: &::async_graphql::Context<'_>, uppercase: Option<bool>) -> ::async_graphql::Result<String>;
+
+    /// The todos assigned to the user.
+    async fn todos(&self, ctx: &::async_graphql::Context<'_>, status: Option<Status>, first: Option<i32>) -> ::async_graphql::Result<Vec<Todo>>;
+}
+
+#[::async_graphql::ComplexObject]
+impl User {
+    async fn name(&self, ctx: &::async_graphql::Context<'_>, #[graphql(default_with = "Some(false)")] uppercase: Option<bool>) -> ::async_graphql::Result<String> {
+        <Self as UserResolver>::name(self, ctx, uppercase).await
+    }
+
+    /// The todos assigned to the user.
+    async fn todos(&self, ctx: &::async_graphql::Context<'_>, status: Option<Status>, #[graphql(default_with = "Some(10)")] first: Option<i32>) -> ::async_graphql::Result<Vec<Todo>> {
+        <Self as UserResolver>::todos(self, ctx, status, first).await
+    }
+}
+
+#[derive(::async_graphql::SimpleObject, Clone)]
+pub struct Todo {
+    #[graphql(owned)]
+    pub id: ::async_graphql::ID,
+    pub title: String,
+    pub status: Status,
+    pub created_at: String,
+    pub metadata: Option<Json>,
+    pub assignee: Option<User>,
+    pub parent: Option<Box<Todo>>,
+}
+
+#[derive(::async_graphql::Union, Clone)]
+pub enum SearchResult {
+    User(User),
+    Todo(Todo),
+}
+
+#[derive(::async_graphql::InputObject, Clone)]
+pub struct TodoFilter {
+    #[graphql(default_with = "Some(Status::Open)")]
+    pub status: Option<Status>,
+    pub title_contains: Option<String>,
+    pub and: Option<Vec<TodoFilter>>,
+    pub not: Option<Box<TodoFilter>>,
+}
+
+#[derive(::async_graphql::InputObject, Clone)]
+pub struct NewTodo {
+    pub title: String,
+    #[graphql(default_with = "Some(Json(::async_graphql::Any(::async_graphql::Value::Object(::std::iter::FromIterator::from_iter(vec![(::async_graphql::Name::new(\"priority\"), ::async_graphql::Value::from(1i64))])))))")]
+    pub metadata: Option<Json>,
+    #[graphql(default_with = "Some(Box::new(TodoFilter { status: Some(Status::Open), title_contains: Some(::std::string::String::from(\"a\")), and: None, not: None }))")]
+    pub filter: Option<Box<TodoFilter>>,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Query;
+
+/// The resolvers of the `Query` type.
+#[::async_graphql::async_trait::async_trait]
+pub trait QueryResolver: Send + Sync {
+    async fn todo(&self, ctx: &::async_graphql::Context<'_>, id: ::async_graphql::ID) -> ::async_graphql::Result<Option<Todo>>;
+
+    async fn todos(&self, ctx: &::async_graphql::Context<'_>, filter: Option<TodoFilter>) -> ::async_graphql::Result<Vec<Todo>>;
+
+    async fn search(&self, ctx: &::async_graphql::Context<'_>, text: String) -> ::async_graphql::Result<Vec<SearchResult>>;
+
+    async fn nodes(&self, ctx: &::async_graphql::Context<'_>) -> ::async_graphql::Result<Vec<Node>>;
+
+    async fn type_(&self, ctx: &::async_graphql::Context<'_>) -> ::async_graphql::Result<String>;
+}
+
+#[::async_graphql::Object]
+impl Query {
+    async fn todo(&self, ctx: &::async_graphql::Context<'_>, id: ::async_graphql::ID) -> ::async_graphql::Result<Option<Todo>> {
+        <Self as QueryResolver>::todo(self, ctx, id).await
+    }
+
+    async fn todos(&self, ctx: &::async_graphql::Context<'_>, #[graphql(default_with = "Some(TodoFilter { status: Some(Status::Done), title_contains: None, and: None, not: None })")] filter: Option<TodoFilter>) -> ::async_graphql::Result<Vec<Todo>> {
+        <Self as QueryResolver>::todos(self, ctx, filter).await
+    }
+
+    async fn search(&self, ctx: &::async_graphql::Context<'_>, text: String) -> ::async_graphql::Result<Vec<SearchResult>> {
+        <Self as QueryResolver>::search(self, ctx, text).await
+    }
+
+    async fn nodes(&self, ctx: &::async_graphql::Context<'_>) -> ::async_graphql::Result<Vec<Node>> {
+        <Self as QueryResolver>::nodes(self, ctx).await
+    }
+
+    #[graphql(deprecation)]
+    async fn type_(&self, ctx: &::async_graphql::Context<'_>) -> ::async_graphql::Result<String> {
+        <Self as QueryResolver>::type_(self, ctx).await
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Mutation;
+
+/// The resolvers of the `Mutation` type.
+#[::async_graphql::async_trait::async_trait]
+pub trait MutationResolver: Send + Sync {
+    async fn add_todo(&self, ctx: &::async_graphql::Context<'_>, input: NewTodo) -> ::async_graphql::Result<Todo>;
+}
+
+#[::async_graphql::Object]
+impl Mutation {
+    async fn add_todo(&self, ctx: &::async_graphql::Context<'_>, input: NewTodo) -> ::async_graphql::Result<Todo> {
+        <Self as MutationResolver>::add_todo(self, ctx, input).await
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Subscription;
+
+/// The resolvers of the `Subscription` type.
+#[::async_graphql::async_trait::async_trait]
+pub trait SubscriptionResolver: Send + Sync {
+    async fn todo_added(&self, ctx: &::async_graphql::Context<'_>) -> ::async_graphql::Result<::async_graphql::futures_util::stream::BoxStream<'static, Todo>>;
+}
+
+#[::async_graphql::Subscription]
+impl Subscription {
+    async fn todo_added(&self, ctx: &::async_graphql::Context<'_>) -> ::async_graphql::Result<::async_graphql::futures_util::stream::BoxStream<'static, Todo>> {
+        <Self as SubscriptionResolver>::todo_added(self, ctx).await
+    }
+}
+
+/// Registers all the types of the schema, including the types which are not
+/// returned by any field.
+pub fn register_types<Q, M, S>(builder: ::async_graphql::SchemaBuilder<Q, M, S>) -> ::async_graphql::SchemaBuilder<Q, M, S> {
+    builder
+        .register_output_type::<Json>()
+        .register_output_type::<Status>()
+        .register_output_type::<Node>()
+        .register_output_type::<Named>()
+        .register_output_type::<User>()
+        .register_output_type::<Todo>()
+        .register_output_type::<SearchResult>()
+        .register_input_type::<TodoFilter>()
+        .register_input_type::<NewTodo>()
+}
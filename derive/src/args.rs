@@ -121,6 +121,23 @@ impl FromMeta for Deprecation {
     }
 }
 
+#[derive(FromMeta, Default, Clone)]
+#[darling(default)]
+pub struct ListSize {
+    pub assumed_size: Option<usize>,
+    #[darling(multiple, rename = "slicing_argument")]
+    pub slicing_arguments: Vec<String>,
+    #[darling(multiple, rename = "sized_field")]
+    pub sized_fields: Vec<String>,
+}
+
+#[derive(FromMeta, Default, Clone)]
+#[darling(default)]
+pub struct Auth {
+    #[darling(multiple)]
+    pub requires: Vec<String>,
+}
+
 #[derive(FromField)]
 #[darling(attributes(graphql), forward_attrs(doc))]
 pub struct SimpleObjectField {
@@ -151,6 +168,22 @@ pub struct SimpleObjectField {
     #[darling(default)]
     pub requires: Option<String>,
     #[darling(default)]
+    pub shareable: bool,
+    #[darling(default)]
+    pub inaccessible: bool,
+    #[darling(default, multiple, rename = "tag")]
+    pub tags: Vec<String>,
+    #[darling(default)]
+    pub override_from: Option<String>,
+    #[darling(default)]
+    pub cost: Option<usize>,
+    #[darling(default)]
+    pub list_size: Option<ListSize>,
+    #[darling(default)]
+    pub auth: Option<Auth>,
+    #[darling(default)]
+    pub redact: Option<String>,
+    #[darling(default)]
     pub guard: Option<SpannedValue<String>>,
     #[darling(default)]
     pub visible: Option<Visible>,
@@ -199,6 +232,12 @@ pub struct SimpleObject {
     pub extends: bool,
     #[darling(default)]
     pub visible: Option<Visible>,
+    #[darling(default)]
+    pub shareable: bool,
+    #[darling(default)]
+    pub inaccessible: bool,
+    #[darling(default, multiple, rename = "tag")]
+    pub tags: Vec<String>,
     #[darling(default, multiple, rename = "concrete")]
     pub concretes: Vec<ConcreteType>,
     #[darling(default)]
@@ -222,6 +261,9 @@ pub struct Argument {
     pub process_with: Option<String>,
     pub key: bool, // for entity
     pub visible: Option<Visible>,
+    pub inaccessible: bool,
+    #[darling(multiple, rename = "tag")]
+    pub tags: Vec<String>,
     pub secret: bool,
 }
 
@@ -237,6 +279,10 @@ pub struct Object {
     pub extends: bool,
     pub use_type_description: bool,
     pub visible: Option<Visible>,
+    pub shareable: bool,
+    pub inaccessible: bool,
+    #[darling(multiple, rename = "tag")]
+    pub tags: Vec<String>,
     pub serial: bool,
     #[darling(multiple, rename = "concrete")]
     pub concretes: Vec<ConcreteType>,
@@ -278,6 +324,15 @@ pub struct ObjectField {
     pub external: bool,
     pub provides: Option<String>,
     pub requires: Option<String>,
+    pub shareable: bool,
+    pub inaccessible: bool,
+    #[darling(multiple, rename = "tag")]
+    pub tags: Vec<String>,
+    pub override_from: Option<String>,
+    pub cost: Option<usize>,
+    pub list_size: Option<ListSize>,
+    pub auth: Option<Auth>,
+    pub redact: Option<String>,
     pub guard: Option<SpannedValue<String>>,
     pub visible: Option<Visible>,
     pub complexity: Option<ComplexityType>,
@@ -315,6 +370,10 @@ pub struct Enum {
     pub remote: Option<String>,
     #[darling(default)]
     pub visible: Option<Visible>,
+    #[darling(default)]
+    pub inaccessible: bool,
+    #[darling(default, multiple, rename = "tag")]
+    pub tags: Vec<String>,
 }
 
 #[derive(FromVariant)]
@@ -330,6 +389,10 @@ pub struct EnumItem {
     pub deprecation: Deprecation,
     #[darling(default)]
     pub visible: Option<Visible>,
+    #[darling(default)]
+    pub inaccessible: bool,
+    #[darling(default, multiple, rename = "tag")]
+    pub tags: Vec<String>,
 }
 
 #[derive(FromDeriveInput)]
@@ -346,6 +409,10 @@ pub struct Union {
     pub name: Option<String>,
     #[darling(default)]
     pub visible: Option<Visible>,
+    #[darling(default)]
+    pub inaccessible: bool,
+    #[darling(default, multiple, rename = "tag")]
+    pub tags: Vec<String>,
 }
 
 #[derive(FromVariant)]
@@ -388,6 +455,10 @@ pub struct InputObjectField {
     #[darling(default)]
     pub visible: Option<Visible>,
     #[darling(default)]
+    pub inaccessible: bool,
+    #[darling(default, multiple, rename = "tag")]
+    pub tags: Vec<String>,
+    #[darling(default)]
     pub secret: bool,
 }
 
@@ -409,6 +480,10 @@ pub struct InputObject {
     pub rename_fields: Option<RenameRule>,
     #[darling(default)]
     pub visible: Option<Visible>,
+    #[darling(default)]
+    pub inaccessible: bool,
+    #[darling(default, multiple, rename = "tag")]
+    pub tags: Vec<String>,
     #[darling(default, multiple, rename = "concrete")]
     pub concretes: Vec<ConcreteType>,
     // for SimpleObject
@@ -430,6 +505,10 @@ pub struct OneofObjectField {
     #[darling(default)]
     pub visible: Option<Visible>,
     #[darling(default)]
+    pub inaccessible: bool,
+    #[darling(default, multiple, rename = "tag")]
+    pub tags: Vec<String>,
+    #[darling(default)]
     pub secret: bool,
 }
 
@@ -449,6 +528,10 @@ pub struct OneofObject {
     pub rename_fields: Option<RenameRule>,
     #[darling(default)]
     pub visible: Option<Visible>,
+    #[darling(default)]
+    pub inaccessible: bool,
+    #[darling(default, multiple, rename = "tag")]
+    pub tags: Vec<String>,
     #[darling(default, multiple, rename = "concrete")]
     pub concretes: Vec<ConcreteType>,
 }
@@ -467,6 +550,10 @@ pub struct InterfaceFieldArgument {
     #[darling(default)]
     pub visible: Option<Visible>,
     #[darling(default)]
+    pub inaccessible: bool,
+    #[darling(default, multiple, rename = "tag")]
+    pub tags: Vec<String>,
+    #[darling(default)]
     pub secret: bool,
 }
 
@@ -490,6 +577,20 @@ pub struct InterfaceField {
     #[darling(default)]
     pub requires: Option<String>,
     #[darling(default)]
+    pub shareable: bool,
+    #[darling(default)]
+    pub inaccessible: bool,
+    #[darling(default, multiple, rename = "tag")]
+    pub tags: Vec<String>,
+    #[darling(default)]
+    pub override_from: Option<String>,
+    #[darling(default)]
+    pub cost: Option<usize>,
+    #[darling(default)]
+    pub list_size: Option<ListSize>,
+    #[darling(default)]
+    pub auth: Option<Auth>,
+    #[darling(default)]
     pub visible: Option<Visible>,
 }
 
@@ -521,6 +622,10 @@ pub struct Interface {
     pub extends: bool,
     #[darling(default)]
     pub visible: Option<Visible>,
+    #[darling(default)]
+    pub inaccessible: bool,
+    #[darling(default, multiple, rename = "tag")]
+    pub tags: Vec<String>,
 }
 
 #[derive(FromMeta, Default)]
@@ -530,6 +635,9 @@ pub struct Scalar {
     pub name: Option<String>,
     pub use_type_description: bool,
     pub visible: Option<Visible>,
+    pub inaccessible: bool,
+    #[darling(multiple, rename = "tag")]
+    pub tags: Vec<String>,
     pub specified_by_url: Option<String>,
 }
 
@@ -596,6 +704,12 @@ pub struct MergedObject {
     #[darling(default)]
     pub visible: Option<Visible>,
     #[darling(default)]
+    pub shareable: bool,
+    #[darling(default)]
+    pub inaccessible: bool,
+    #[darling(default, multiple, rename = "tag")]
+    pub tags: Vec<String>,
+    #[darling(default)]
     pub serial: bool,
 }
 
@@ -741,6 +855,10 @@ pub struct NewType {
     #[darling(default)]
     pub visible: Option<Visible>,
     #[darling(default)]
+    pub inaccessible: bool,
+    #[darling(default, multiple, rename = "tag")]
+    pub tags: Vec<String>,
+    #[darling(default)]
     pub specified_by_url: Option<String>,
 }
 
@@ -764,6 +882,15 @@ pub struct ComplexObjectField {
     pub external: bool,
     pub provides: Option<String>,
     pub requires: Option<String>,
+    pub shareable: bool,
+    pub inaccessible: bool,
+    #[darling(multiple, rename = "tag")]
+    pub tags: Vec<String>,
+    pub override_from: Option<String>,
+    pub cost: Option<usize>,
+    pub list_size: Option<ListSize>,
+    pub auth: Option<Auth>,
+    pub redact: Option<String>,
     pub guard: Option<SpannedValue<String>>,
     pub visible: Option<Visible>,
     pub complexity: Option<ComplexityType>,
@@ -12,9 +12,10 @@ use crate::{
     args::{self, ComplexityType, RenameRuleExt, RenameTarget},
     output_type::OutputType,
     utils::{
-        extract_input_args, gen_deprecation, generate_default, generate_guards, get_cfg_attrs,
-        get_crate_name, get_rustdoc, get_type_path_and_name, parse_complexity_expr,
-        parse_graphql_attrs, remove_graphql_attrs, visible_fn, GeneratorResult,
+        extract_input_args, gen_auth, gen_cost, gen_deprecation, gen_list_size, gen_redact,
+        gen_tags, generate_default, generate_guards, get_cfg_attrs, get_crate_name, get_rustdoc,
+        get_type_path_and_name, parse_complexity_expr, parse_graphql_attrs, remove_graphql_attrs,
+        visible_fn, GeneratorResult,
     },
 };
 
@@ -180,6 +181,17 @@ pub fn generate(
                 Some(provides) => quote! { ::std::option::Option::Some(#provides) },
                 None => quote! { ::std::option::Option::None },
             };
+            let shareable = method_args.shareable;
+            let inaccessible = method_args.inaccessible;
+            let tags = gen_tags(&method_args.tags);
+            let override_from = match &method_args.override_from {
+                Some(from) => quote! { ::std::option::Option::Some(#from) },
+                None => quote! { ::std::option::Option::None },
+            };
+            let cost = gen_cost(&method_args.cost);
+            let list_size = gen_list_size(&method_args.list_size, &crate_name);
+            let auth = gen_auth(&method_args.auth);
+            let redact = gen_redact(&method_args.redact);
             let cache_control = {
                 let public = method_args.cache_control.is_public();
                 let max_age = method_args.cache_control.max_age;
@@ -207,6 +219,8 @@ pub fn generate(
                     validator,
                     process_with,
                     visible,
+                    inaccessible,
+                    tags,
                     secret,
                     ..
                 },
@@ -234,6 +248,7 @@ pub fn generate(
                     .unwrap_or_else(|| quote! {::std::option::Option::None});
 
                 let visible = visible_fn(visible);
+                let tags = gen_tags(tags);
                 schema_args.push(quote! {
                         args.insert(::std::borrow::ToOwned::to_owned(#name), #crate_name::registry::MetaInputValue {
                             name: #name,
@@ -241,6 +256,8 @@ pub fn generate(
                             ty: <#ty as #crate_name::InputType>::create_type_info(registry),
                             default_value: #schema_default,
                             visible: #visible,
+                            inaccessible: #inaccessible,
+                            tags: #tags,
                             is_secret: #secret,
                         });
                     });
@@ -366,6 +383,14 @@ pub fn generate(
                     provides: #provides,
                     requires: #requires,
                     visible: #visible,
+                    shareable: #shareable,
+                    inaccessible: #inaccessible,
+                    tags: #tags,
+                    override_from: #override_from,
+                    cost: #cost,
+                    list_size: #list_size,
+                    auth: #auth,
+                    redact: #redact,
                     compute_complexity: #complexity,
                 }));
             });
@@ -89,6 +89,8 @@ pub fn generate(
                 ty: <#arg_ty as #crate_name::InputType>::create_type_info(registry),
                 default_value: #schema_default,
                 visible: #visible,
+                inaccessible: false,
+                tags: ::std::vec::Vec::new(),
                 is_secret: #secret,
             });
         });
@@ -5,7 +5,7 @@ use syn::{ext::IdentExt, Error};
 
 use crate::{
     args::{self, RenameRuleExt, RenameTarget},
-    utils::{gen_deprecation, get_crate_name, get_rustdoc, visible_fn, GeneratorResult},
+    utils::{gen_deprecation, gen_tags, get_crate_name, get_rustdoc, visible_fn, GeneratorResult},
 };
 
 pub fn generate(enum_args: &args::Enum) -> GeneratorResult<TokenStream> {
@@ -61,12 +61,16 @@ pub fn generate(enum_args: &args::Enum) -> GeneratorResult<TokenStream> {
         });
 
         let visible = visible_fn(&variant.visible);
+        let inaccessible = variant.inaccessible;
+        let tags = gen_tags(&variant.tags);
         schema_enum_items.push(quote! {
             enum_items.insert(#gql_item_name, #crate_name::registry::MetaEnumValue {
                 name: #gql_item_name,
                 description: #item_desc,
                 deprecation: #item_deprecation,
                 visible: #visible,
+                inaccessible: #inaccessible,
+                tags: #tags,
             });
         });
     }
@@ -116,6 +120,8 @@ pub fn generate(enum_args: &args::Enum) -> GeneratorResult<TokenStream> {
     }
 
     let visible = visible_fn(&enum_args.visible);
+    let inaccessible = enum_args.inaccessible;
+    let tags = gen_tags(&enum_args.tags);
     let expanded = quote! {
         #[allow(clippy::all, clippy::pedantic)]
         impl #crate_name::resolver_utils::EnumType for #ident {
@@ -141,6 +147,8 @@ pub fn generate(enum_args: &args::Enum) -> GeneratorResult<TokenStream> {
                             enum_items
                         },
                         visible: #visible,
+                        inaccessible: #inaccessible,
+                        tags: #tags,
                         rust_typename: ::std::any::type_name::<Self>(),
                     }
                 })
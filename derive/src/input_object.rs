@@ -5,7 +5,7 @@ use syn::{ext::IdentExt, Error};
 
 use crate::{
     args::{self, RenameRuleExt, RenameTarget},
-    utils::{generate_default, get_crate_name, get_rustdoc, visible_fn, GeneratorResult},
+    utils::{gen_tags, generate_default, get_crate_name, get_rustdoc, visible_fn, GeneratorResult},
 };
 
 pub fn generate(object_args: &args::InputObject) -> GeneratorResult<TokenStream> {
@@ -181,6 +181,8 @@ pub fn generate(object_args: &args::InputObject) -> GeneratorResult<TokenStream>
 
         fields.push(ident);
         let visible = visible_fn(&field.visible);
+        let inaccessible = field.inaccessible;
+        let tags = gen_tags(&field.tags);
         schema_fields.push(quote! {
             fields.insert(::std::borrow::ToOwned::to_owned(#name), #crate_name::registry::MetaInputValue {
                 name: #name,
@@ -188,6 +190,8 @@ pub fn generate(object_args: &args::InputObject) -> GeneratorResult<TokenStream>
                 ty: <#ty as #crate_name::InputType>::create_type_info(registry),
                 default_value: #schema_default,
                 visible: #visible,
+                inaccessible: #inaccessible,
+                tags: #tags,
                 is_secret: #secret,
             });
         })
@@ -202,6 +206,8 @@ pub fn generate(object_args: &args::InputObject) -> GeneratorResult<TokenStream>
     }
 
     let visible = visible_fn(&object_args.visible);
+    let inaccessible = object_args.inaccessible;
+    let tags = gen_tags(&object_args.tags);
 
     let get_federation_fields = {
         let fields = federation_fields.into_iter().map(|(ty, name)| {
@@ -240,6 +246,8 @@ pub fn generate(object_args: &args::InputObject) -> GeneratorResult<TokenStream>
                             fields
                         },
                         visible: #visible,
+                        inaccessible: #inaccessible,
+                        tags: #tags,
                         rust_typename: ::std::any::type_name::<Self>(),
                         oneof: false,
                     })
@@ -287,6 +295,8 @@ pub fn generate(object_args: &args::InputObject) -> GeneratorResult<TokenStream>
                             fields
                         },
                         visible: #visible,
+                        inaccessible: #inaccessible,
+                        tags: #tags,
                         rust_typename: ::std::any::type_name::<Self>(),
                         oneof: false,
                     })
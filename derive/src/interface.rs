@@ -10,8 +10,8 @@ use crate::{
     args::{self, InterfaceField, InterfaceFieldArgument, RenameRuleExt, RenameTarget},
     output_type::OutputType,
     utils::{
-        gen_deprecation, generate_default, get_crate_name, get_rustdoc, visible_fn,
-        GeneratorResult, RemoveLifetime,
+        gen_auth, gen_cost, gen_deprecation, gen_list_size, gen_tags, generate_default,
+        get_crate_name, get_rustdoc, visible_fn, GeneratorResult, RemoveLifetime,
     },
 };
 
@@ -136,6 +136,13 @@ pub fn generate(interface_args: &args::Interface) -> GeneratorResult<TokenStream
         external,
         provides,
         requires,
+        shareable,
+        inaccessible,
+        tags,
+        override_from,
+        cost,
+        list_size,
+        auth,
         visible,
     } in &interface_args.fields
     {
@@ -167,6 +174,14 @@ pub fn generate(interface_args: &args::Interface) -> GeneratorResult<TokenStream
             Some(provides) => quote! { ::std::option::Option::Some(#provides) },
             None => quote! { ::std::option::Option::None },
         };
+        let tags = gen_tags(tags);
+        let override_from = match override_from {
+            Some(from) => quote! { ::std::option::Option::Some(#from) },
+            None => quote! { ::std::option::Option::None },
+        };
+        let cost = gen_cost(cost);
+        let list_size = gen_list_size(list_size, &crate_name);
+        let auth = gen_auth(auth);
 
         decl_params.push(quote! { ctx: &'ctx #crate_name::Context<'ctx> });
         use_params.push(quote! { ctx });
@@ -178,6 +193,8 @@ pub fn generate(interface_args: &args::Interface) -> GeneratorResult<TokenStream
             default,
             default_with,
             visible,
+            inaccessible,
+            tags,
             secret,
         } in args
         {
@@ -216,6 +233,7 @@ pub fn generate(interface_args: &args::Interface) -> GeneratorResult<TokenStream
                 })
                 .unwrap_or_else(|| quote! {::std::option::Option::None});
             let visible = visible_fn(visible);
+            let tags = gen_tags(tags);
             schema_args.push(quote! {
                     args.insert(::std::borrow::ToOwned::to_owned(#name), #crate_name::registry::MetaInputValue {
                         name: #name,
@@ -223,6 +241,8 @@ pub fn generate(interface_args: &args::Interface) -> GeneratorResult<TokenStream
                         ty: <#ty as #crate_name::InputType>::create_type_info(registry),
                         default_value: #schema_default,
                         visible: #visible,
+                        inaccessible: #inaccessible,
+                        tags: #tags,
                         is_secret: #secret,
                     });
                 });
@@ -275,6 +295,14 @@ pub fn generate(interface_args: &args::Interface) -> GeneratorResult<TokenStream
                 provides: #provides,
                 requires: #requires,
                 visible: #visible,
+                shareable: #shareable,
+                inaccessible: #inaccessible,
+                tags: #tags,
+                override_from: #override_from,
+                cost: #cost,
+                list_size: #list_size,
+                auth: #auth,
+                redact: ::std::option::Option::None,
                 compute_complexity: ::std::option::Option::None,
             });
         });
@@ -305,6 +333,8 @@ pub fn generate(interface_args: &args::Interface) -> GeneratorResult<TokenStream
     };
 
     let visible = visible_fn(&interface_args.visible);
+    let inaccessible = interface_args.inaccessible;
+    let tags = gen_tags(&interface_args.tags);
     let expanded = quote! {
         #(#type_into_impls)*
 
@@ -359,6 +389,8 @@ pub fn generate(interface_args: &args::Interface) -> GeneratorResult<TokenStream
                         extends: #extends,
                         keys: ::std::option::Option::None,
                         visible: #visible,
+                        inaccessible: #inaccessible,
+                        tags: #tags,
                         rust_typename: ::std::any::type_name::<Self>(),
                     }
                 })
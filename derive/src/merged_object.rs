@@ -6,7 +6,7 @@ use syn::{Error, LitInt};
 
 use crate::{
     args::{self, RenameTarget},
-    utils::{get_crate_name, get_rustdoc, visible_fn, GeneratorResult},
+    utils::{gen_tags, get_crate_name, get_rustdoc, visible_fn, GeneratorResult},
 };
 
 pub fn generate(object_args: &args::MergedObject) -> GeneratorResult<TokenStream> {
@@ -57,6 +57,9 @@ pub fn generate(object_args: &args::MergedObject) -> GeneratorResult<TokenStream
     };
 
     let visible = visible_fn(&object_args.visible);
+    let shareable = object_args.shareable;
+    let inaccessible = object_args.inaccessible;
+    let tags = gen_tags(&object_args.tags);
     let resolve_container = if object_args.serial {
         quote! { #crate_name::resolver_utils::resolve_container_serial(ctx, self).await }
     } else {
@@ -105,6 +108,9 @@ pub fn generate(object_args: &args::MergedObject) -> GeneratorResult<TokenStream
                         extends: #extends,
                         keys: ::std::option::Option::None,
                         visible: #visible,
+                        shareable: #shareable,
+                        inaccessible: #inaccessible,
+                        tags: #tags,
                         is_subscription: false,
                         rust_typename: ::std::any::type_name::<Self>(),
                     }
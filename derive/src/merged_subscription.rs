@@ -74,6 +74,9 @@ pub fn generate(object_args: &args::MergedSubscription) -> GeneratorResult<Token
                         extends: #extends,
                         keys: ::std::option::Option::None,
                         visible: #visible,
+                        shareable: false,
+                        inaccessible: false,
+                        tags: ::std::vec::Vec::new(),
                         is_subscription: true,
                         rust_typename: ::std::any::type_name::<Self>(),
                     }
@@ -5,7 +5,7 @@ use syn::Error;
 
 use crate::{
     args::{self, NewTypeName, RenameTarget},
-    utils::{get_crate_name, get_rustdoc, visible_fn, GeneratorResult},
+    utils::{gen_tags, get_crate_name, get_rustdoc, visible_fn, GeneratorResult},
 };
 
 pub fn generate(newtype_args: &args::NewType) -> GeneratorResult<TokenStream> {
@@ -21,6 +21,8 @@ pub fn generate(newtype_args: &args::NewType) -> GeneratorResult<TokenStream> {
         .map(|s| quote! { ::std::option::Option::Some(#s) })
         .unwrap_or_else(|| quote! {::std::option::Option::None});
     let visible = visible_fn(&newtype_args.visible);
+    let inaccessible = newtype_args.inaccessible;
+    let tags = gen_tags(&newtype_args.tags);
 
     let fields = match &newtype_args.data {
         Data::Struct(e) => e,
@@ -51,6 +53,8 @@ pub fn generate(newtype_args: &args::NewType) -> GeneratorResult<TokenStream> {
                 description: #desc,
                 is_valid: |value| <#ident as #crate_name::ScalarType>::is_valid(value),
                 visible: #visible,
+                inaccessible: #inaccessible,
+                tags: #tags,
                 specified_by_url: #specified_by_url,
             })
         }
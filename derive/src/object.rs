@@ -12,9 +12,10 @@ use crate::{
     args::{self, ComplexityType, RenameRuleExt, RenameTarget},
     output_type::OutputType,
     utils::{
-        extract_input_args, gen_deprecation, generate_default, generate_guards, get_cfg_attrs,
-        get_crate_name, get_rustdoc, get_type_path_and_name, parse_complexity_expr,
-        parse_graphql_attrs, remove_graphql_attrs, visible_fn, GeneratorResult,
+        extract_input_args, gen_auth, gen_cost, gen_deprecation, gen_list_size, gen_redact,
+        gen_tags, generate_default, generate_guards, get_cfg_attrs, get_crate_name, get_rustdoc,
+        get_type_path_and_name, parse_complexity_expr, parse_graphql_attrs, remove_graphql_attrs,
+        visible_fn, GeneratorResult,
     },
 };
 
@@ -323,6 +324,17 @@ pub fn generate(
                     Some(provides) => quote! { ::std::option::Option::Some(#provides) },
                     None => quote! { ::std::option::Option::None },
                 };
+                let shareable = method_args.shareable;
+                let inaccessible = method_args.inaccessible;
+                let tags = gen_tags(&method_args.tags);
+                let override_from = match &method_args.override_from {
+                    Some(from) => quote! { ::std::option::Option::Some(#from) },
+                    None => quote! { ::std::option::Option::None },
+                };
+                let cost = gen_cost(&method_args.cost);
+                let list_size = gen_list_size(&method_args.list_size, &crate_name);
+                let auth = gen_auth(&method_args.auth);
+                let redact = gen_redact(&method_args.redact);
                 let cache_control = {
                     let public = method_args.cache_control.is_public();
                     let max_age = method_args.cache_control.max_age;
@@ -350,6 +362,8 @@ pub fn generate(
                         process_with,
                         validator,
                         visible,
+                        inaccessible,
+                        tags,
                         secret,
                         ..
                     },
@@ -377,6 +391,7 @@ pub fn generate(
                         .unwrap_or_else(|| quote! {::std::option::Option::None});
 
                     let visible = visible_fn(visible);
+                    let tags = gen_tags(tags);
                     schema_args.push(quote! {
                             args.insert(::std::borrow::ToOwned::to_owned(#name), #crate_name::registry::MetaInputValue {
                                 name: #name,
@@ -384,6 +399,8 @@ pub fn generate(
                                 ty: <#ty as #crate_name::InputType>::create_type_info(registry),
                                 default_value: #schema_default,
                                 visible: #visible,
+                                inaccessible: #inaccessible,
+                                tags: #tags,
                                 is_secret: #secret,
                             });
                         });
@@ -508,6 +525,14 @@ pub fn generate(
                         provides: #provides,
                         requires: #requires,
                         visible: #visible,
+                        shareable: #shareable,
+                        inaccessible: #inaccessible,
+                        tags: #tags,
+                        override_from: #override_from,
+                        cost: #cost,
+                        list_size: #list_size,
+                        auth: #auth,
+                        redact: #redact,
                         compute_complexity: #complexity,
                     });
                 });
@@ -586,6 +611,9 @@ pub fn generate(
     }
 
     let visible = visible_fn(&object_args.visible);
+    let shareable = object_args.shareable;
+    let inaccessible = object_args.inaccessible;
+    let tags = gen_tags(&object_args.tags);
     let resolve_container = if object_args.serial {
         quote! { #crate_name::resolver_utils::resolve_container_serial(ctx, self).await }
     } else {
@@ -642,6 +670,9 @@ pub fn generate(
                         extends: #extends,
                         keys: ::std::option::Option::None,
                         visible: #visible,
+                        shareable: #shareable,
+                        inaccessible: #inaccessible,
+                        tags: #tags,
                         is_subscription: false,
                         rust_typename: ::std::any::type_name::<Self>(),
                     });
@@ -681,6 +712,9 @@ pub fn generate(
                         extends: #extends,
                         keys: ::std::option::Option::None,
                         visible: #visible,
+                        shareable: #shareable,
+                        inaccessible: #inaccessible,
+                        tags: #tags,
                         is_subscription: false,
                         rust_typename: ::std::any::type_name::<Self>(),
                     });
@@ -6,7 +6,7 @@ use syn::{Error, Type};
 use crate::{
     args,
     args::{RenameRuleExt, RenameTarget},
-    utils::{get_crate_name, get_rustdoc, visible_fn, GeneratorResult},
+    utils::{gen_tags, get_crate_name, get_rustdoc, visible_fn, GeneratorResult},
 };
 
 pub fn generate(object_args: &args::OneofObject) -> GeneratorResult<TokenStream> {
@@ -72,6 +72,8 @@ pub fn generate(object_args: &args::OneofObject) -> GeneratorResult<TokenStream>
 
             let secret = variant.secret;
             let visible = visible_fn(&variant.visible);
+            let inaccessible = variant.inaccessible;
+            let tags = gen_tags(&variant.tags);
 
             schema_fields.push(quote! {
                 fields.insert(::std::borrow::ToOwned::to_owned(#field_name), #crate_name::registry::MetaInputValue {
@@ -80,6 +82,8 @@ pub fn generate(object_args: &args::OneofObject) -> GeneratorResult<TokenStream>
                     ty: <::std::option::Option<#ty> as #crate_name::InputType>::create_type_info(registry),
                     default_value: ::std::option::Option::None,
                     visible: #visible,
+                    inaccessible: #inaccessible,
+                    tags: #tags,
                     is_secret: #secret,
                 });
             });
@@ -114,6 +118,8 @@ pub fn generate(object_args: &args::OneofObject) -> GeneratorResult<TokenStream>
     }
 
     let visible = visible_fn(&object_args.visible);
+    let inaccessible = object_args.inaccessible;
+    let tags = gen_tags(&object_args.tags);
     let expanded = if object_args.concretes.is_empty() {
         quote! {
             impl #crate_name::InputType for #ident {
@@ -133,6 +139,8 @@ pub fn generate(object_args: &args::OneofObject) -> GeneratorResult<TokenStream>
                             fields
                         },
                         visible: #visible,
+                        inaccessible: #inaccessible,
+                        tags: #tags,
                         rust_typename: ::std::any::type_name::<Self>(),
                         oneof: true,
                     })
@@ -183,6 +191,8 @@ pub fn generate(object_args: &args::OneofObject) -> GeneratorResult<TokenStream>
                             fields
                         },
                         visible: #visible,
+                        inaccessible: #inaccessible,
+                        tags: #tags,
                         rust_typename: ::std::any::type_name::<Self>(),
                         oneof: true,
                     })
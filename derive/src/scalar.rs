@@ -4,7 +4,9 @@ use syn::ItemImpl;
 
 use crate::{
     args::{self, RenameTarget},
-    utils::{get_crate_name, get_rustdoc, get_type_path_and_name, visible_fn, GeneratorResult},
+    utils::{
+        gen_tags, get_crate_name, get_rustdoc, get_type_path_and_name, visible_fn, GeneratorResult,
+    },
 };
 
 pub fn generate(
@@ -29,6 +31,8 @@ pub fn generate(
     let self_ty = &item_impl.self_ty;
     let generic = &item_impl.generics;
     let where_clause = &item_impl.generics.where_clause;
+    let inaccessible = scalar_args.inaccessible;
+    let tags = gen_tags(&scalar_args.tags);
     let visible = visible_fn(&scalar_args.visible);
     let specified_by_url = match &scalar_args.specified_by_url {
         Some(specified_by_url) => quote! { ::std::option::Option::Some(#specified_by_url) },
@@ -52,6 +56,8 @@ pub fn generate(
                     description: #desc,
                     is_valid: |value| <#self_ty as #crate_name::ScalarType>::is_valid(value),
                     visible: #visible,
+                    inaccessible: #inaccessible,
+                    tags: #tags,
                     specified_by_url: #specified_by_url,
                 })
             }
@@ -82,6 +88,8 @@ pub fn generate(
                     description: #desc,
                     is_valid: |value| <#self_ty as #crate_name::ScalarType>::is_valid(value),
                     visible: #visible,
+                    inaccessible: #inaccessible,
+                    tags: #tags,
                     specified_by_url: #specified_by_url,
                 })
             }
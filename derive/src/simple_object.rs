@@ -8,7 +8,8 @@ use syn::{ext::IdentExt, visit::Visit, Error, Ident, LifetimeDef, Path, Type};
 use crate::{
     args::{self, RenameRuleExt, RenameTarget, SimpleObjectField},
     utils::{
-        gen_deprecation, generate_guards, get_crate_name, get_rustdoc, visible_fn, GeneratorResult,
+        gen_auth, gen_cost, gen_deprecation, gen_list_size, gen_redact, gen_tags, generate_guards,
+        get_crate_name, get_rustdoc, visible_fn, GeneratorResult,
     },
 };
 
@@ -135,6 +136,17 @@ pub fn generate(object_args: &args::SimpleObject) -> GeneratorResult<TokenStream
             Some(provides) => quote! { ::std::option::Option::Some(#provides) },
             None => quote! { ::std::option::Option::None },
         };
+        let shareable = field.shareable;
+        let inaccessible = field.inaccessible;
+        let tags = gen_tags(&field.tags);
+        let override_from = match &field.override_from {
+            Some(from) => quote! { ::std::option::Option::Some(#from) },
+            None => quote! { ::std::option::Option::None },
+        };
+        let cost = gen_cost(&field.cost);
+        let list_size = gen_list_size(&field.list_size, &crate_name);
+        let auth = gen_auth(&field.auth);
+        let redact = gen_redact(&field.redact);
         let vis = &field.vis;
 
         let ty = if let Some(derived) = derived {
@@ -175,6 +187,14 @@ pub fn generate(object_args: &args::SimpleObject) -> GeneratorResult<TokenStream
                     provides: #provides,
                     requires: #requires,
                     visible: #visible,
+                    shareable: #shareable,
+                    inaccessible: #inaccessible,
+                    tags: #tags,
+                    override_from: #override_from,
+                    cost: #cost,
+                    list_size: #list_size,
+                    auth: #auth,
+                    redact: #redact,
                     compute_complexity: ::std::option::Option::None,
                 });
             });
@@ -271,6 +291,9 @@ pub fn generate(object_args: &args::SimpleObject) -> GeneratorResult<TokenStream
     };
 
     let visible = visible_fn(&object_args.visible);
+    let shareable = object_args.shareable;
+    let inaccessible = object_args.inaccessible;
+    let tags = gen_tags(&object_args.tags);
 
     let mut concat_complex_fields = quote!();
     let mut complex_resolver = quote!();
@@ -331,6 +354,9 @@ pub fn generate(object_args: &args::SimpleObject) -> GeneratorResult<TokenStream
                         extends: #extends,
                         keys: ::std::option::Option::None,
                         visible: #visible,
+                        shareable: #shareable,
+                        inaccessible: #inaccessible,
+                        tags: #tags,
                         is_subscription: false,
                         rust_typename: ::std::any::type_name::<Self>(),
                     })
@@ -395,6 +421,9 @@ pub fn generate(object_args: &args::SimpleObject) -> GeneratorResult<TokenStream
                         extends: #extends,
                         keys: ::std::option::Option::None,
                         visible: #visible,
+                        shareable: #shareable,
+                        inaccessible: #inaccessible,
+                        tags: #tags,
                         is_subscription: false,
                         rust_typename: ::std::any::type_name::<Self>(),
                     })
@@ -119,6 +119,8 @@ pub fn generate(
                             ty: <#ty as #crate_name::InputType>::create_type_info(registry),
                             default_value: #schema_default,
                             visible: #visible,
+                            inaccessible: false,
+                            tags: ::std::vec::Vec::new(),
                             is_secret: #secret,
                         });
                     });
@@ -268,6 +270,14 @@ pub fn generate(
                     requires: ::std::option::Option::None,
                     provides: ::std::option::Option::None,
                     visible: #visible,
+                    shareable: false,
+                    inaccessible: false,
+                    tags: ::std::vec::Vec::new(),
+                    override_from: ::std::option::Option::None,
+                    cost: ::std::option::Option::None,
+                    list_size: ::std::option::Option::None,
+                    auth: ::std::option::Option::None,
+                    redact: ::std::option::Option::None,
                     compute_complexity: #complexity,
                 });
             });
@@ -408,6 +418,9 @@ pub fn generate(
                     extends: #extends,
                     keys: ::std::option::Option::None,
                     visible: #visible,
+                    shareable: false,
+                    inaccessible: false,
+                    tags: ::std::vec::Vec::new(),
                     is_subscription: true,
                     rust_typename: ::std::any::type_name::<Self>(),
                 })
@@ -7,7 +7,7 @@ use syn::{visit_mut::VisitMut, Error, Type};
 
 use crate::{
     args::{self, RenameTarget},
-    utils::{get_crate_name, get_rustdoc, visible_fn, GeneratorResult, RemoveLifetime},
+    utils::{gen_tags, get_crate_name, get_rustdoc, visible_fn, GeneratorResult, RemoveLifetime},
 };
 
 pub fn generate(union_args: &args::Union) -> GeneratorResult<TokenStream> {
@@ -149,6 +149,8 @@ pub fn generate(union_args: &args::Union) -> GeneratorResult<TokenStream> {
     }
 
     let visible = visible_fn(&union_args.visible);
+    let inaccessible = union_args.inaccessible;
+    let tags = gen_tags(&union_args.tags);
     let expanded = quote! {
         #(#type_into_impls)*
 
@@ -193,6 +195,8 @@ pub fn generate(union_args: &args::Union) -> GeneratorResult<TokenStream> {
                             possible_types
                         },
                         visible: #visible,
+                        inaccessible: #inaccessible,
+                        tags: #tags,
                         rust_typename: ::std::any::type_name::<Self>(),
                     }
                 })
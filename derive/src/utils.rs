@@ -11,7 +11,7 @@ use syn::{
 };
 use thiserror::Error;
 
-use crate::args::{self, Deprecation, Visible};
+use crate::args::{self, Auth, Deprecation, ListSize, Visible};
 
 #[derive(Error, Debug)]
 pub enum GeneratorError {
@@ -238,6 +238,61 @@ pub fn gen_deprecation(deprecation: &Deprecation, crate_name: &TokenStream) -> T
     }
 }
 
+pub fn gen_tags(tags: &[String]) -> TokenStream {
+    quote! { ::std::vec![ #(::std::string::ToString::to_string(#tags)),* ] }
+}
+
+pub fn gen_cost(cost: &Option<usize>) -> TokenStream {
+    match cost {
+        Some(cost) => quote! { ::std::option::Option::Some(#cost) },
+        None => quote! { ::std::option::Option::None },
+    }
+}
+
+pub fn gen_list_size(list_size: &Option<ListSize>, crate_name: &TokenStream) -> TokenStream {
+    match list_size {
+        Some(ListSize {
+            assumed_size,
+            slicing_arguments,
+            sized_fields,
+        }) => {
+            let assumed_size = match assumed_size {
+                Some(size) => quote! { ::std::option::Option::Some(#size) },
+                None => quote! { ::std::option::Option::None },
+            };
+            let slicing_arguments = gen_tags(slicing_arguments);
+            let sized_fields = gen_tags(sized_fields);
+            quote! {
+                ::std::option::Option::Some(#crate_name::registry::MetaListSize {
+                    assumed_size: #assumed_size,
+                    slicing_arguments: #slicing_arguments,
+                    sized_fields: #sized_fields,
+                })
+            }
+        }
+        None => quote! { ::std::option::Option::None },
+    }
+}
+
+pub fn gen_auth(auth: &Option<Auth>) -> TokenStream {
+    match auth {
+        Some(Auth { requires }) => {
+            let requires = gen_tags(requires);
+            quote! { ::std::option::Option::Some(#requires) }
+        }
+        None => quote! { ::std::option::Option::None },
+    }
+}
+
+pub fn gen_redact(redact: &Option<String>) -> TokenStream {
+    match redact {
+        Some(category) => {
+            quote! { ::std::option::Option::Some(::std::string::ToString::to_string(#category)) }
+        }
+        None => quote! { ::std::option::Option::None },
+    }
+}
+
 pub fn extract_input_args<T: FromMeta + Default>(
     crate_name: &proc_macro2::TokenStream,
     method: &mut ImplItemMethod,
@@ -12,7 +12,10 @@ use actix_web::{
     http::{Method, StatusCode},
     Error, FromRequest, HttpRequest, HttpResponse, Responder, Result,
 };
-use async_graphql::{http::MultipartOptions, ParseRequestError};
+use async_graphql::{
+    http::{MultipartOptions, ResponseContentType},
+    ParseRequestError,
+};
 use futures_util::{
     future::{self, FutureExt},
     StreamExt, TryStreamExt,
@@ -75,7 +78,15 @@ impl FromRequest for GraphQLBatchRequest {
             .unwrap_or_default();
 
         if req.method() == Method::GET {
-            let res = serde_urlencoded::from_str(req.query_string());
+            let res =
+                async_graphql::http::parse_query_string(req.query_string()).map_err(
+                    |err| match err {
+                        ParseRequestError::MutationInGetRequest => {
+                            actix_web::error::ErrorMethodNotAllowed(err)
+                        }
+                        _ => actix_web::error::ErrorBadRequest(err),
+                    },
+                );
             Box::pin(async move { Ok(Self(async_graphql::BatchRequest::Single(res?))) })
         } else if req.method() == Method::POST {
             let content_type = req
@@ -146,6 +157,10 @@ impl FromRequest for GraphQLBatchRequest {
 ///
 /// This contains a batch response, but since regular responses are a type of
 /// batch response it works for both.
+///
+/// The content type is negotiated from the `Accept` header of the request, if
+/// the client accepts `application/graphql-response+json` requests failing
+/// before execution are answered with `400 Bad Request`.
 pub struct GraphQLResponse(pub async_graphql::BatchResponse);
 
 impl From<async_graphql::Response> for GraphQLResponse {
@@ -184,38 +199,43 @@ impl Responder for GraphQLResponse {
     type Body = BoxBody;
 
     fn respond_to(self, req: &HttpRequest) -> HttpResponse {
-        let mut builder = HttpResponse::build(StatusCode::OK);
-
-        if self.0.is_ok() {
-            if let Some(cache_control) = self.0.cache_control().value() {
-                builder.append_header((http::header::CACHE_CONTROL, cache_control));
-            }
-        }
-
         let accept = req
             .headers()
             .get(http::header::ACCEPT)
             .and_then(|val| val.to_str().ok());
-        let (ct, body) = match accept {
+        let (status, ct, body) = match accept {
             // optional cbor support
             #[cfg(feature = "cbor")]
             // this avoids copy-pasting the mime type
             Some(ct @ "application/cbor") => (
+                StatusCode::OK,
                 ct,
                 match serde_cbor::to_vec(&self.0) {
                     Ok(body) => body,
                     Err(e) => return HttpResponse::from_error(cbor::Error(e)),
                 },
             ),
-            _ => (
-                "application/json",
-                match serde_json::to_vec(&self.0) {
-                    Ok(body) => body,
-                    Err(e) => return HttpResponse::from_error(JsonPayloadError::Serialize(e)),
-                },
-            ),
+            _ => {
+                let content_type = ResponseContentType::from_accept(accept);
+                (
+                    self.0.status_code(content_type),
+                    content_type.content_type(),
+                    match serde_json::to_vec(&self.0) {
+                        Ok(body) => body,
+                        Err(e) => return HttpResponse::from_error(JsonPayloadError::Serialize(e)),
+                    },
+                )
+            }
         };
 
+        let mut builder = HttpResponse::build(status);
+
+        if self.0.is_ok() {
+            if let Some(cache_control) = self.0.cache_control().value() {
+                builder.append_header((http::header::CACHE_CONTROL, cache_control));
+            }
+        }
+
         let mut resp = builder.content_type(ct).body(body);
 
         for (name, value) in self.0.http_headers_iter() {
@@ -219,6 +219,125 @@ async fn test_count() {
     );
 }
 
+#[actix_rt::test]
+async fn test_graphql_response_content_type() {
+    let srv = test::init_service(
+        App::new()
+            .app_data(Data::new(Schema::new(
+                AddQueryRoot,
+                EmptyMutation,
+                EmptySubscription,
+            )))
+            .service(
+                web::resource("/")
+                    .guard(guard::Post())
+                    .to(gql_handle_schema::<AddQueryRoot, EmptyMutation, EmptySubscription>),
+            ),
+    )
+    .await;
+
+    let response = srv
+        .call(
+            test::TestRequest::with_uri("/")
+                .method(Method::POST)
+                .insert_header((
+                    actix_http::header::ACCEPT,
+                    "application/graphql-response+json",
+                ))
+                .set_payload(r#"{"query":"{ add(a: 10, b: 20) }"}"#)
+                .to_request(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), actix_http::StatusCode::OK);
+    assert_eq!(
+        response
+            .headers()
+            .get(actix_http::header::CONTENT_TYPE)
+            .unwrap(),
+        "application/graphql-response+json"
+    );
+
+    let response = srv
+        .call(
+            test::TestRequest::with_uri("/")
+                .method(Method::POST)
+                .insert_header((
+                    actix_http::header::ACCEPT,
+                    "application/graphql-response+json",
+                ))
+                .set_payload(r#"{"query":"{ sub(a: 10, b: 20) }"}"#)
+                .to_request(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), actix_http::StatusCode::BAD_REQUEST);
+
+    let response = srv
+        .call(
+            test::TestRequest::with_uri("/")
+                .method(Method::POST)
+                .set_payload(r#"{"query":"{ sub(a: 10, b: 20) }"}"#)
+                .to_request(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), actix_http::StatusCode::OK);
+    assert_eq!(
+        response
+            .headers()
+            .get(actix_http::header::CONTENT_TYPE)
+            .unwrap(),
+        "application/json"
+    );
+}
+
+#[actix_rt::test]
+async fn test_get() {
+    let srv = test::init_service(
+        App::new()
+            .app_data(Data::new(
+                Schema::build(CountQueryRoot, CountMutation, EmptySubscription)
+                    .data(Count::default())
+                    .finish(),
+            ))
+            .service(
+                web::resource("/")
+                    .guard(guard::Get())
+                    .to(gql_handle_schema::<CountQueryRoot, CountMutation, EmptySubscription>),
+            ),
+    )
+    .await;
+
+    let response = srv
+        .call(
+            test::TestRequest::with_uri("/?query=%7B%20count%20%7D")
+                .method(Method::GET)
+                .to_request(),
+        )
+        .await
+        .unwrap();
+    assert!(response.status().is_success());
+    let body = response.into_body();
+    assert_eq!(
+        actix_web::body::to_bytes(body).await.unwrap(),
+        json!({"data": {"count": 0}}).to_string().into_bytes()
+    );
+
+    let response = srv
+        .call(
+            test::TestRequest::with_uri("/?query=mutation%7B%20addCount(count%3A%2010)%20%7D")
+                .method(Method::GET)
+                .to_request(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        response.status(),
+        actix_http::StatusCode::METHOD_NOT_ALLOWED
+    );
+}
+
 #[cfg(feature = "cbor")]
 #[actix_rt::test]
 async fn test_cbor() {
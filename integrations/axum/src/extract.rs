@@ -1,6 +1,10 @@
 use std::{io::ErrorKind, marker::PhantomData};
 
-use async_graphql::{futures_util::TryStreamExt, http::MultipartOptions, ParseRequestError};
+use async_graphql::{
+    futures_util::TryStreamExt,
+    http::{MultipartOptions, ResponseContentType},
+    ParseRequestError,
+};
 use axum::{
     extract::{BodyStream, FromRequest, RequestParts},
     http,
@@ -12,9 +16,14 @@ use bytes::Bytes;
 use tokio_util::compat::TokioAsyncReadCompatExt;
 
 /// Extractor for GraphQL request.
+///
+/// The content type of the response is negotiated from the `Accept` header
+/// of the request, it is set on a [`GraphQLResponse`](crate::GraphQLResponse)
+/// with [`GraphQLResponse::content_type`](crate::GraphQLResponse::content_type).
 pub struct GraphQLRequest<R = rejection::GraphQLRejection>(
     pub async_graphql::Request,
     PhantomData<R>,
+    ResponseContentType,
 );
 
 impl<R> GraphQLRequest<R> {
@@ -23,6 +32,12 @@ impl<R> GraphQLRequest<R> {
     pub fn into_inner(self) -> async_graphql::Request {
         self.0
     }
+
+    /// Returns the content type of the response negotiated from the `Accept`
+    /// header of the request.
+    pub fn response_content_type(&self) -> ResponseContentType {
+        self.2
+    }
 }
 
 /// Rejection response types.
@@ -45,6 +60,13 @@ pub mod rejection {
                     .status(StatusCode::PAYLOAD_TOO_LARGE)
                     .body(boxed(Body::empty()))
                     .unwrap(),
+                ParseRequestError::MutationInGetRequest => http::Response::builder()
+                    .status(StatusCode::METHOD_NOT_ALLOWED)
+                    .header(http::header::ALLOW, "POST")
+                    .body(boxed(Body::from(
+                        ParseRequestError::MutationInGetRequest.to_string(),
+                    )))
+                    .unwrap(),
                 bad_request => http::Response::builder()
                     .status(StatusCode::BAD_REQUEST)
                     .body(boxed(Body::from(format!("{:?}", bad_request))))
@@ -71,12 +93,12 @@ where
     type Rejection = R;
 
     async fn from_request(req: &mut RequestParts<B>) -> Result<Self, Self::Rejection> {
+        let batch_request = GraphQLBatchRequest::<R>::from_request(req).await?;
+        let content_type = batch_request.response_content_type();
         Ok(GraphQLRequest(
-            GraphQLBatchRequest::<R>::from_request(req)
-                .await?
-                .0
-                .into_single()?,
+            batch_request.0.into_single()?,
             PhantomData,
+            content_type,
         ))
     }
 }
@@ -85,6 +107,7 @@ where
 pub struct GraphQLBatchRequest<R = rejection::GraphQLRejection>(
     pub async_graphql::BatchRequest,
     PhantomData<R>,
+    ResponseContentType,
 );
 
 impl<R> GraphQLBatchRequest<R> {
@@ -93,6 +116,12 @@ impl<R> GraphQLBatchRequest<R> {
     pub fn into_inner(self) -> async_graphql::BatchRequest {
         self.0
     }
+
+    /// Returns the content type of the response negotiated from the `Accept`
+    /// header of the request.
+    pub fn response_content_type(&self) -> ResponseContentType {
+        self.2
+    }
 }
 
 #[async_trait::async_trait]
@@ -106,14 +135,18 @@ where
     type Rejection = R;
 
     async fn from_request(req: &mut RequestParts<B>) -> Result<Self, Self::Rejection> {
+        let response_content_type = ResponseContentType::from_accept(
+            req.headers()
+                .get(http::header::ACCEPT)
+                .and_then(|value| value.to_str().ok()),
+        );
         if let (&Method::GET, uri) = (req.method(), req.uri()) {
-            let res = serde_urlencoded::from_str(uri.query().unwrap_or_default()).map_err(|err| {
-                ParseRequestError::Io(std::io::Error::new(
-                    ErrorKind::Other,
-                    format!("failed to parse graphql request from uri query: {}", err),
-                ))
-            });
-            Ok(Self(async_graphql::BatchRequest::Single(res?), PhantomData))
+            let res = async_graphql::http::parse_query_string(uri.query().unwrap_or_default());
+            Ok(Self(
+                async_graphql::BatchRequest::Single(res?),
+                PhantomData,
+                response_content_type,
+            ))
         } else {
             let content_type = req
                 .headers()
@@ -138,6 +171,7 @@ where
                 )
                 .await?,
                 PhantomData,
+                response_content_type,
             ))
         }
     }
@@ -4,8 +4,10 @@
 
 mod extract;
 mod response;
+mod sse;
 mod subscription;
 
 pub use extract::{GraphQLBatchRequest, GraphQLRequest};
 pub use response::GraphQLResponse;
+pub use sse::GraphQLSse;
 pub use subscription::{GraphQLProtocol, GraphQLSubscription, GraphQLWebSocket};
@@ -1,3 +1,4 @@
+use async_graphql::http::ResponseContentType;
 use axum::{
     body::{boxed, Body, BoxBody},
     http,
@@ -8,28 +9,68 @@ use axum::{
 /// Responder for a GraphQL response.
 ///
 /// This contains a batch response, but since regular responses are a type of
-/// batch response it works for both.
-pub struct GraphQLResponse(pub async_graphql::BatchResponse);
+/// batch response it works for both. It is sent as `application/json`, use
+/// [`GraphQLResponse::content_type`] to send it with the content type
+/// negotiated by the [`GraphQLRequest`](crate::GraphQLRequest) extractor.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::{EmptyMutation, EmptySubscription, Object, Schema};
+/// use async_graphql_axum::{GraphQLRequest, GraphQLResponse};
+/// use axum::Extension;
+///
+/// struct Query;
+///
+/// #[Object]
+/// impl Query {
+///     async fn value(&self) -> i32 {
+///         100
+///     }
+/// }
+///
+/// type MySchema = Schema<Query, EmptyMutation, EmptySubscription>;
+///
+/// async fn graphql_handler(schema: Extension<MySchema>, req: GraphQLRequest) -> GraphQLResponse {
+///     let content_type = req.response_content_type();
+///     GraphQLResponse::from(schema.execute(req.into_inner()).await).content_type(content_type)
+/// }
+/// ```
+pub struct GraphQLResponse(pub async_graphql::BatchResponse, ResponseContentType);
 
 impl From<async_graphql::Response> for GraphQLResponse {
     fn from(resp: async_graphql::Response) -> Self {
-        Self(resp.into())
+        Self(resp.into(), ResponseContentType::Json)
     }
 }
 
 impl From<async_graphql::BatchResponse> for GraphQLResponse {
     fn from(resp: async_graphql::BatchResponse) -> Self {
-        Self(resp)
+        Self(resp, ResponseContentType::Json)
     }
 }
 
-impl IntoResponse for GraphQLResponse {
-    fn into_response(self) -> Response<BoxBody> {
+impl GraphQLResponse {
+    /// Sends the response with `content_type`, usually the one negotiated by
+    /// [`GraphQLRequest::response_content_type`](crate::GraphQLRequest::response_content_type).
+    #[must_use]
+    pub fn content_type(self, content_type: ResponseContentType) -> Self {
+        Self(self.0, content_type)
+    }
+
+    /// Converts the response to an HTTP response with the content type
+    /// negotiated with `ResponseContentType::from_accept`, setting the
+    /// status code accordingly.
+    pub fn into_response_with_content_type(
+        self,
+        content_type: ResponseContentType,
+    ) -> Response<BoxBody> {
         let body: Body = serde_json::to_string(&self.0).unwrap().into();
         let mut resp = Response::new(boxed(body));
+        *resp.status_mut() = self.0.status_code(content_type);
         resp.headers_mut().insert(
             http::header::CONTENT_TYPE,
-            HeaderValue::from_static("application/json"),
+            HeaderValue::from_static(content_type.content_type()),
         );
         if self.0.is_ok() {
             if let Some(cache_control) = self.0.cache_control().value() {
@@ -44,3 +85,10 @@ impl IntoResponse for GraphQLResponse {
         resp
     }
 }
+
+impl IntoResponse for GraphQLResponse {
+    fn into_response(self) -> Response<BoxBody> {
+        let content_type = self.1;
+        self.into_response_with_content_type(content_type)
+    }
+}
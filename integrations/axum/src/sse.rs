@@ -0,0 +1,205 @@
+use std::{collections::HashMap, convert::Infallible};
+
+use async_graphql::{
+    futures_util::task::{Context, Poll},
+    http::{
+        accepts_event_stream, create_sse_stream, SseConnections, SseError, SSE_CONTENT_TYPE,
+        SSE_TOKEN_HEADER,
+    },
+    ObjectType, Schema, SubscriptionType,
+};
+use axum::{
+    body::{boxed, Body, BoxBody, StreamBody},
+    extract::{FromRequest, RequestParts},
+    http::{self, HeaderValue, Method, Request, Response, StatusCode},
+    response::IntoResponse,
+    BoxError,
+};
+use bytes::Bytes;
+use futures_util::{future::BoxFuture, Stream, StreamExt};
+use tower_service::Service;
+
+use crate::GraphQLRequest;
+
+/// A GraphQL subscription service using Server-Sent Events.
+///
+/// It implements both the "distinct connections" and the "single connection"
+/// modes of the [graphql-sse](https://github.com/enisdenjo/graphql-sse/blob/master/PROTOCOL.md)
+/// protocol, and should be routed for the `GET`, `POST`, `PUT` and `DELETE`
+/// methods.
+///
+/// # Example
+///
+/// ```
+/// use async_graphql::{EmptyMutation, Object, Schema, Subscription};
+/// use async_graphql_axum::GraphQLSse;
+/// use axum::{routing::any_service, Router};
+/// use futures_util::{stream, Stream};
+///
+/// struct Query;
+///
+/// #[Object]
+/// impl Query {
+///     async fn value(&self) -> i32 {
+///         100
+///     }
+/// }
+///
+/// struct Subscription;
+///
+/// #[Subscription]
+/// impl Subscription {
+///     async fn values(&self) -> impl Stream<Item = i32> {
+///         stream::iter(vec![1, 2, 3, 4, 5])
+///     }
+/// }
+///
+/// let schema = Schema::new(Query, EmptyMutation, Subscription);
+/// let app: Router = Router::new().route("/sse", any_service(GraphQLSse::new(schema)));
+/// ```
+pub struct GraphQLSse<Query, Mutation, Subscription> {
+    connections: SseConnections<Query, Mutation, Subscription>,
+}
+
+impl<Query, Mutation, Subscription> Clone for GraphQLSse<Query, Mutation, Subscription> {
+    fn clone(&self) -> Self {
+        Self {
+            connections: self.connections.clone(),
+        }
+    }
+}
+
+impl<Query, Mutation, Subscription> GraphQLSse<Query, Mutation, Subscription>
+where
+    Query: ObjectType + 'static,
+    Mutation: ObjectType + 'static,
+    Subscription: SubscriptionType + 'static,
+{
+    /// Create a GraphQL Server-Sent Events service.
+    pub fn new(schema: Schema<Query, Mutation, Subscription>) -> Self {
+        Self::with_connections(SseConnections::new(schema))
+    }
+
+    /// Create a GraphQL Server-Sent Events service using the event streams of
+    /// `connections`, for example to change their limits.
+    pub fn with_connections(connections: SseConnections<Query, Mutation, Subscription>) -> Self {
+        Self { connections }
+    }
+}
+
+fn event_stream_response(stream: impl Stream<Item = Bytes> + Send + 'static) -> Response<BoxBody> {
+    let mut resp = Response::new(boxed(StreamBody::new(stream.map(Ok::<_, Infallible>))));
+    resp.headers_mut().insert(
+        http::header::CONTENT_TYPE,
+        HeaderValue::from_static(SSE_CONTENT_TYPE),
+    );
+    resp.headers_mut().insert(
+        http::header::CACHE_CONTROL,
+        HeaderValue::from_static("no-cache"),
+    );
+    resp
+}
+
+fn status_response(status: StatusCode, body: impl Into<Body>) -> Response<BoxBody> {
+    Response::builder()
+        .status(status)
+        .body(boxed(body.into()))
+        .unwrap()
+}
+
+fn sse_error_response(err: SseError) -> Response<BoxBody> {
+    status_response(err.status_code(), err.to_string())
+}
+
+impl<B, Query, Mutation, Subscription> Service<Request<B>>
+    for GraphQLSse<Query, Mutation, Subscription>
+where
+    B: http_body::Body + Unpin + Send + Sync + 'static,
+    B::Data: Into<Bytes>,
+    B::Error: Into<BoxError>,
+    Query: ObjectType + 'static,
+    Mutation: ObjectType + 'static,
+    Subscription: SubscriptionType + 'static,
+{
+    type Response = Response<BoxBody>;
+    type Error = Infallible;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: Request<B>) -> Self::Future {
+        let connections = self.connections.clone();
+
+        Box::pin(async move {
+            let mut parts = RequestParts::new(req);
+            let params = axum::extract::Query::<HashMap<String, String>>::from_request(&mut parts)
+                .await
+                .map(|params| params.0)
+                .unwrap_or_default();
+            let token = parts
+                .headers()
+                .get(SSE_TOKEN_HEADER)
+                .and_then(|value| value.to_str().ok())
+                .map(ToString::to_string)
+                .or_else(|| params.get("token").cloned());
+            let accept_event_stream = parts
+                .headers()
+                .get(http::header::ACCEPT)
+                .and_then(|value| value.to_str().ok())
+                .map(accepts_event_stream)
+                .unwrap_or_default();
+
+            let method = parts.method().clone();
+            let resp = match (method, token) {
+                (Method::PUT, _) => match connections.reserve() {
+                    Ok(token) => {
+                        let mut resp = status_response(StatusCode::CREATED, token);
+                        resp.headers_mut().insert(
+                            http::header::CONTENT_TYPE,
+                            HeaderValue::from_static("text/plain; charset=utf-8"),
+                        );
+                        resp
+                    }
+                    Err(err) => sse_error_response(err),
+                },
+                (Method::DELETE, Some(token)) => match params.get("operationId") {
+                    Some(operation_id) => match connections.stop(&token, operation_id) {
+                        Ok(()) => status_response(StatusCode::OK, Body::empty()),
+                        Err(err) => sse_error_response(err),
+                    },
+                    None => sse_error_response(SseError::MissingOperationId),
+                },
+                (Method::GET | Method::POST, Some(token)) if accept_event_stream => {
+                    match connections.connect(&token) {
+                        Ok(stream) => event_stream_response(stream),
+                        Err(err) => sse_error_response(err),
+                    }
+                }
+                (Method::POST, Some(token)) => {
+                    match <GraphQLRequest>::from_request(&mut parts).await {
+                        Ok(req) => match connections.execute(&token, req.into_inner()) {
+                            Ok(()) => status_response(StatusCode::ACCEPTED, Body::empty()),
+                            Err(err) => sse_error_response(err),
+                        },
+                        Err(err) => err.into_response(),
+                    }
+                }
+                (Method::GET | Method::POST, None) if accept_event_stream => {
+                    match <GraphQLRequest>::from_request(&mut parts).await {
+                        Ok(req) => event_stream_response(create_sse_stream(
+                            connections.schema().execute_stream(req.into_inner()),
+                        )),
+                        Err(err) => err.into_response(),
+                    }
+                }
+                (Method::GET | Method::POST, None) => {
+                    status_response(StatusCode::NOT_ACCEPTABLE, Body::empty())
+                }
+                _ => status_response(StatusCode::METHOD_NOT_ALLOWED, Body::empty()),
+            };
+            Ok(resp)
+        })
+    }
+}
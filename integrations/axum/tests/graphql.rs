@@ -0,0 +1,89 @@
+use async_graphql::*;
+use async_graphql_axum::{GraphQLRequest, GraphQLResponse};
+use axum::{
+    body::{Body, BoxBody, HttpBody},
+    http::{header, Method, Request, Response, StatusCode},
+    routing::post,
+    Extension, Router,
+};
+use tower_service::Service;
+
+struct Query;
+
+#[Object]
+impl Query {
+    async fn value(&self) -> i32 {
+        10
+    }
+}
+
+type MySchema = Schema<Query, EmptyMutation, EmptySubscription>;
+
+async fn graphql_handler(schema: Extension<MySchema>, req: GraphQLRequest) -> GraphQLResponse {
+    let content_type = req.response_content_type();
+    GraphQLResponse::from(schema.execute(req.into_inner()).await).content_type(content_type)
+}
+
+async fn call(query: &str, accept: Option<&str>) -> Response<BoxBody> {
+    let mut app = Router::new()
+        .route("/", post(graphql_handler))
+        .layer(Extension(Schema::new(
+            Query,
+            EmptyMutation,
+            EmptySubscription,
+        )));
+    let mut req = Request::builder()
+        .method(Method::POST)
+        .uri("/")
+        .header(header::CONTENT_TYPE, "application/json");
+    if let Some(accept) = accept {
+        req = req.header(header::ACCEPT, accept);
+    }
+    app.call(
+        req.body(Body::from(
+            serde_json::to_string(&serde_json::json!({ "query": query })).unwrap(),
+        ))
+        .unwrap(),
+    )
+    .await
+    .unwrap()
+}
+
+async fn body_string(mut body: BoxBody) -> String {
+    let mut bytes = Vec::new();
+    while let Some(data) = body.data().await {
+        bytes.extend_from_slice(&data.unwrap());
+    }
+    String::from_utf8(bytes).unwrap()
+}
+
+#[tokio::test]
+async fn test_response_content_type() {
+    let resp = call("{ value }", None).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(resp.headers()[header::CONTENT_TYPE], "application/json");
+    assert_eq!(
+        body_string(resp.into_body()).await,
+        r#"{"data":{"value":10}}"#
+    );
+
+    let resp = call("{ value }", Some("application/graphql-response+json")).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(
+        resp.headers()[header::CONTENT_TYPE],
+        "application/graphql-response+json"
+    );
+
+    // A request failing before execution is a bad request with this content
+    // type.
+    let resp = call("{ unknown }", Some("application/graphql-response+json")).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(
+        resp.headers()[header::CONTENT_TYPE],
+        "application/graphql-response+json"
+    );
+
+    let resp = call("{ unknown }", None).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(resp.headers()[header::CONTENT_TYPE], "application/json");
+}
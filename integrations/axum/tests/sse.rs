@@ -0,0 +1,201 @@
+use std::convert::Infallible;
+
+use async_graphql::{http::SSE_TOKEN_HEADER, *};
+use async_graphql_axum::GraphQLSse;
+use axum::{
+    body::{Body, BoxBody, HttpBody},
+    http::{header, Method, Request, Response, StatusCode},
+};
+use futures_util::{stream, Stream, StreamExt};
+use tower_service::Service;
+
+struct Query;
+
+#[Object]
+impl Query {
+    async fn value(&self) -> i32 {
+        10
+    }
+}
+
+struct Subscription;
+
+#[Subscription]
+impl Subscription {
+    async fn values(&self) -> impl Stream<Item = i32> {
+        stream::iter(vec![1, 2]).chain(stream::pending())
+    }
+}
+
+async fn call(
+    service: &mut GraphQLSse<Query, EmptyMutation, Subscription>,
+    req: Request<Body>,
+) -> Response<BoxBody> {
+    let resp: Result<_, Infallible> = service.call(req).await;
+    resp.unwrap()
+}
+
+async fn next_event(body: &mut BoxBody) -> String {
+    String::from_utf8(body.data().await.unwrap().unwrap().to_vec()).unwrap()
+}
+
+#[tokio::test]
+async fn test_sse_single_connection() {
+    let mut service = GraphQLSse::new(Schema::new(Query, EmptyMutation, Subscription));
+
+    // Reserve
+    let resp = call(
+        &mut service,
+        Request::builder()
+            .method(Method::PUT)
+            .uri("/")
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let token = String::from_utf8(body_bytes(resp.into_body()).await).unwrap();
+
+    // Connect
+    let resp = call(
+        &mut service,
+        Request::builder()
+            .method(Method::GET)
+            .uri(format!("/?token={}", token))
+            .header(header::ACCEPT, "text/event-stream")
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(resp.headers()[header::CONTENT_TYPE], "text/event-stream");
+    let mut events = resp.into_body();
+
+    // Execute
+    let resp = call(
+        &mut service,
+        Request::builder()
+            .method(Method::POST)
+            .uri("/")
+            .header(SSE_TOKEN_HEADER, &token)
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(
+                r#"{"query":"subscription { values }","extensions":{"operationId":"1"}}"#,
+            ))
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(resp.status(), StatusCode::ACCEPTED);
+    assert_eq!(
+        next_event(&mut events).await,
+        "event: next\ndata: {\"id\":\"1\",\"payload\":{\"data\":{\"values\":1}}}\n\n"
+    );
+    assert_eq!(
+        next_event(&mut events).await,
+        "event: next\ndata: {\"id\":\"1\",\"payload\":{\"data\":{\"values\":2}}}\n\n"
+    );
+
+    // Stop
+    let resp = call(
+        &mut service,
+        Request::builder()
+            .method(Method::DELETE)
+            .uri(format!("/?token={}&operationId=1", token))
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    // The operation is stopped, another one can use the same id.
+    let resp = call(
+        &mut service,
+        Request::builder()
+            .method(Method::POST)
+            .uri("/")
+            .header(SSE_TOKEN_HEADER, &token)
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(
+                r#"{"query":"{ value }","extensions":{"operationId":"1"}}"#,
+            ))
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(resp.status(), StatusCode::ACCEPTED);
+    assert_eq!(
+        next_event(&mut events).await,
+        "event: next\ndata: {\"id\":\"1\",\"payload\":{\"data\":{\"value\":10}}}\n\n"
+    );
+    assert_eq!(
+        next_event(&mut events).await,
+        "event: complete\ndata: {\"id\":\"1\"}\n\n"
+    );
+
+    // The event stream is already established.
+    let resp = call(
+        &mut service,
+        Request::builder()
+            .method(Method::GET)
+            .uri(format!("/?token={}", token))
+            .header(header::ACCEPT, "text/event-stream")
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(resp.status(), StatusCode::CONFLICT);
+
+    // The token is released with the event stream.
+    drop(events);
+    let resp = call(
+        &mut service,
+        Request::builder()
+            .method(Method::DELETE)
+            .uri(format!("/?token={}&operationId=1", token))
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_sse_distinct_connections() {
+    let mut service = GraphQLSse::new(Schema::new(Query, EmptyMutation, Subscription));
+
+    let resp = call(
+        &mut service,
+        Request::builder()
+            .method(Method::POST)
+            .uri("/")
+            .header(header::ACCEPT, "text/event-stream")
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(r#"{"query":"{ value }"}"#))
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(
+        String::from_utf8(body_bytes(resp.into_body()).await).unwrap(),
+        "event: next\ndata: {\"data\":{\"value\":10}}\n\nevent: complete\ndata:\n\n"
+    );
+
+    let resp = call(
+        &mut service,
+        Request::builder()
+            .method(Method::POST)
+            .uri("/")
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(r#"{"query":"{ value }"}"#))
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(resp.status(), StatusCode::NOT_ACCEPTABLE);
+}
+
+async fn body_bytes(mut body: BoxBody) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    while let Some(data) = body.data().await {
+        bytes.extend_from_slice(&data.unwrap());
+    }
+    bytes
+}
@@ -1,10 +1,12 @@
-use async_graphql::http::MultipartOptions;
+use async_graphql::{
+    http::{MultipartOptions, ResponseContentType},
+    ParseRequestError,
+};
 use poem::{
     async_trait,
     error::BadRequest,
-    http::{header, Method},
-    web::Query,
-    FromRequest, Request, RequestBody, Result,
+    http::{header, Method, StatusCode},
+    Error, FromRequest, Request, RequestBody, Result,
 };
 use tokio_util::compat::TokioAsyncReadCompatExt;
 
@@ -18,14 +20,8 @@ use tokio_util::compat::TokioAsyncReadCompatExt;
 ///
 /// ```
 /// use async_graphql::{EmptyMutation, EmptySubscription, Object, Schema};
-/// use async_graphql_poem::GraphQLRequest;
-/// use poem::{
-///     handler,
-///     middleware::AddData,
-///     post,
-///     web::{Data, Json},
-///     EndpointExt, Route,
-/// };
+/// use async_graphql_poem::{GraphQLRequest, GraphQLResponse};
+/// use poem::{handler, middleware::AddData, post, web::Data, EndpointExt, Route};
 ///
 /// struct Query;
 ///
@@ -39,37 +35,64 @@ use tokio_util::compat::TokioAsyncReadCompatExt;
 /// type MySchema = Schema<Query, EmptyMutation, EmptySubscription>;
 ///
 /// #[handler]
-/// async fn index(req: GraphQLRequest, schema: Data<&MySchema>) -> Json<async_graphql::Response> {
-///     Json(schema.execute(req.0).await)
+/// async fn index(req: GraphQLRequest, schema: Data<&MySchema>) -> GraphQLResponse {
+///     let content_type = req.response_content_type();
+///     GraphQLResponse::from(schema.execute(req.0).await).content_type(content_type)
 /// }
 ///
 /// let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
 /// let app = Route::new().at("/", post(index.with(AddData::new(schema))));
 /// ```
-pub struct GraphQLRequest(pub async_graphql::Request);
+pub struct GraphQLRequest(pub async_graphql::Request, ResponseContentType);
+
+impl GraphQLRequest {
+    /// Returns the content type of the response negotiated from the `Accept`
+    /// header of the request.
+    pub fn response_content_type(&self) -> ResponseContentType {
+        self.1
+    }
+}
 
 #[async_trait]
 impl<'a> FromRequest<'a> for GraphQLRequest {
     async fn from_request(req: &'a Request, body: &mut RequestBody) -> Result<Self> {
+        let batch_request = GraphQLBatchRequest::from_request(req, body).await?;
+        let content_type = batch_request.response_content_type();
         Ok(GraphQLRequest(
-            GraphQLBatchRequest::from_request(req, body)
-                .await?
-                .0
-                .into_single()
-                .map_err(BadRequest)?,
+            batch_request.0.into_single().map_err(BadRequest)?,
+            content_type,
         ))
     }
 }
 
 /// An extractor for GraphQL batch request.
-pub struct GraphQLBatchRequest(pub async_graphql::BatchRequest);
+pub struct GraphQLBatchRequest(pub async_graphql::BatchRequest, ResponseContentType);
+
+impl GraphQLBatchRequest {
+    /// Returns the content type of the response negotiated from the `Accept`
+    /// header of the request.
+    pub fn response_content_type(&self) -> ResponseContentType {
+        self.1
+    }
+}
 
 #[async_trait]
 impl<'a> FromRequest<'a> for GraphQLBatchRequest {
     async fn from_request(req: &'a Request, body: &mut RequestBody) -> Result<Self> {
+        let response_content_type = ResponseContentType::from_accept(req.header(header::ACCEPT));
         if req.method() == Method::GET {
-            let req = Query::from_request(req, body).await?.0;
-            Ok(Self(async_graphql::BatchRequest::Single(req)))
+            let req =
+                async_graphql::http::parse_query_string(req.uri().query().unwrap_or_default())
+                    .map_err(|err| match err {
+                        ParseRequestError::MutationInGetRequest => {
+                            Error::new(err, StatusCode::METHOD_NOT_ALLOWED)
+                        }
+                        _ => BadRequest(err),
+                    })?;
+            Ok(Self(
+                async_graphql::BatchRequest::Single(req),
+                response_content_type,
+            ))
         } else {
             let content_type = req
                 .headers()
@@ -84,6 +107,7 @@ impl<'a> FromRequest<'a> for GraphQLBatchRequest {
                 )
                 .await
                 .map_err(BadRequest)?,
+                response_content_type,
             ))
         }
     }
@@ -5,9 +5,11 @@
 mod extractor;
 mod query;
 mod response;
+mod sse;
 mod subscription;
 
 pub use extractor::{GraphQLBatchRequest, GraphQLRequest};
 pub use query::GraphQL;
 pub use response::{GraphQLBatchResponse, GraphQLResponse};
+pub use sse::GraphQLSse;
 pub use subscription::{GraphQLProtocol, GraphQLSubscription, GraphQLWebSocket};
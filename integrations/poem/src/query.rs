@@ -1,10 +1,13 @@
-use async_graphql::{ObjectType, Schema, SubscriptionType};
-use poem::{async_trait, Endpoint, FromRequest, Request, Result};
+use async_graphql::{http::ResponseContentType, ObjectType, Schema, SubscriptionType};
+use poem::{async_trait, http::header, Endpoint, FromRequest, Request, Response, Result};
 
 use crate::{GraphQLBatchRequest, GraphQLBatchResponse};
 
 /// A GraphQL query endpoint.
 ///
+/// The content type of the response is negotiated from the `Accept` header of
+/// the request.
+///
 /// # Example
 ///
 /// ```
@@ -44,11 +47,15 @@ where
     Mutation: ObjectType + 'static,
     Subscription: SubscriptionType + 'static,
 {
-    type Output = GraphQLBatchResponse;
+    type Output = Response;
 
     async fn call(&self, req: Request) -> Result<Self::Output> {
         let (req, mut body) = req.split();
+        let content_type = ResponseContentType::from_accept(req.header(header::ACCEPT));
         let req = GraphQLBatchRequest::from_request(&req, &mut body).await?;
-        Ok(GraphQLBatchResponse(self.schema.execute_batch(req.0).await))
+        Ok(
+            GraphQLBatchResponse::from(self.schema.execute_batch(req.0).await)
+                .into_response_with_content_type(content_type),
+        )
     }
 }
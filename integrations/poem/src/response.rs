@@ -1,32 +1,74 @@
-use poem::{web::Json, IntoResponse, Response};
+use async_graphql::http::ResponseContentType;
+use poem::{http::HeaderValue, web::Json, IntoResponse, Response};
 
 /// Response for `async_graphql::Request`.
-pub struct GraphQLResponse(pub async_graphql::Response);
+///
+/// It is sent as `application/json`, use [`GraphQLResponse::content_type`] to
+/// send it with the content type negotiated by the
+/// [`GraphQLRequest`](crate::GraphQLRequest) extractor.
+pub struct GraphQLResponse(pub async_graphql::Response, ResponseContentType);
 
 impl From<async_graphql::Response> for GraphQLResponse {
     fn from(resp: async_graphql::Response) -> Self {
-        Self(resp)
+        Self(resp, ResponseContentType::Json)
+    }
+}
+
+impl GraphQLResponse {
+    /// Sends the response with `content_type`, usually the one negotiated by
+    /// [`GraphQLRequest::response_content_type`](crate::GraphQLRequest::response_content_type).
+    #[must_use]
+    pub fn content_type(self, content_type: ResponseContentType) -> Self {
+        Self(self.0, content_type)
+    }
+
+    /// Converts the response to an HTTP response with the content type
+    /// negotiated with `ResponseContentType::from_accept`, setting the
+    /// status code accordingly.
+    pub fn into_response_with_content_type(self, content_type: ResponseContentType) -> Response {
+        GraphQLBatchResponse::from(async_graphql::BatchResponse::from(self.0))
+            .into_response_with_content_type(content_type)
     }
 }
 
 impl IntoResponse for GraphQLResponse {
     fn into_response(self) -> Response {
-        GraphQLBatchResponse(self.0.into()).into_response()
+        let content_type = self.1;
+        self.into_response_with_content_type(content_type)
     }
 }
 
 /// Response for `async_graphql::BatchRequest`.
-pub struct GraphQLBatchResponse(pub async_graphql::BatchResponse);
+///
+/// It is sent as `application/json`, use [`GraphQLBatchResponse::content_type`]
+/// to send it with the content type negotiated by the
+/// [`GraphQLBatchRequest`](crate::GraphQLBatchRequest) extractor.
+pub struct GraphQLBatchResponse(pub async_graphql::BatchResponse, ResponseContentType);
 
 impl From<async_graphql::BatchResponse> for GraphQLBatchResponse {
     fn from(resp: async_graphql::BatchResponse) -> Self {
-        Self(resp)
+        Self(resp, ResponseContentType::Json)
     }
 }
 
-impl IntoResponse for GraphQLBatchResponse {
-    fn into_response(self) -> Response {
+impl GraphQLBatchResponse {
+    /// Sends the response with `content_type`, usually the one negotiated by
+    /// [`GraphQLBatchRequest::response_content_type`](crate::GraphQLBatchRequest::response_content_type).
+    #[must_use]
+    pub fn content_type(self, content_type: ResponseContentType) -> Self {
+        Self(self.0, content_type)
+    }
+
+    /// Converts the response to an HTTP response with the content type
+    /// negotiated with `ResponseContentType::from_accept`, setting the
+    /// status code accordingly.
+    pub fn into_response_with_content_type(self, content_type: ResponseContentType) -> Response {
         let mut resp = Json(&self.0).into_response();
+        resp.set_status(self.0.status_code(content_type));
+        resp.headers_mut().insert(
+            "content-type",
+            HeaderValue::from_static(content_type.content_type()),
+        );
 
         if self.0.is_ok() {
             if let Some(cache_control) = self.0.cache_control().value() {
@@ -40,3 +82,10 @@ impl IntoResponse for GraphQLBatchResponse {
         resp
     }
 }
+
+impl IntoResponse for GraphQLBatchResponse {
+    fn into_response(self) -> Response {
+        let content_type = self.1;
+        self.into_response_with_content_type(content_type)
+    }
+}
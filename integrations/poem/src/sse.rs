@@ -0,0 +1,153 @@
+use std::collections::HashMap;
+
+use async_graphql::{
+    http::{
+        accepts_event_stream, create_sse_stream, SseConnections, SseError, SSE_CONTENT_TYPE,
+        SSE_TOKEN_HEADER,
+    },
+    ObjectType, Schema, SubscriptionType,
+};
+use bytes::Bytes;
+use futures_util::{Stream, StreamExt};
+use poem::{
+    http::{header, Method, StatusCode},
+    Body, Endpoint, FromRequest, IntoResponse, Request, Response, Result,
+};
+
+use crate::GraphQLRequest;
+
+/// A GraphQL subscription endpoint using Server-Sent Events.
+///
+/// It implements both the "distinct connections" and the "single connection"
+/// modes of the [graphql-sse](https://github.com/enisdenjo/graphql-sse/blob/master/PROTOCOL.md)
+/// protocol, and should be routed for the `GET`, `POST`, `PUT` and `DELETE`
+/// methods.
+///
+/// # Example
+///
+/// ```
+/// use async_graphql::{EmptyMutation, Object, Schema, Subscription};
+/// use async_graphql_poem::GraphQLSse;
+/// use futures_util::{stream, Stream};
+/// use poem::Route;
+///
+/// struct Query;
+///
+/// #[Object]
+/// impl Query {
+///     async fn value(&self) -> i32 {
+///         100
+///     }
+/// }
+///
+/// struct Subscription;
+///
+/// #[Subscription]
+/// impl Subscription {
+///     async fn values(&self) -> impl Stream<Item = i32> {
+///         stream::iter(vec![1, 2, 3, 4, 5])
+///     }
+/// }
+///
+/// let schema = Schema::new(Query, EmptyMutation, Subscription);
+/// let app = Route::new().at("/sse", GraphQLSse::new(schema));
+/// ```
+pub struct GraphQLSse<Query, Mutation, Subscription> {
+    connections: SseConnections<Query, Mutation, Subscription>,
+}
+
+impl<Query, Mutation, Subscription> GraphQLSse<Query, Mutation, Subscription>
+where
+    Query: ObjectType + 'static,
+    Mutation: ObjectType + 'static,
+    Subscription: SubscriptionType + 'static,
+{
+    /// Create a GraphQL Server-Sent Events endpoint.
+    pub fn new(schema: Schema<Query, Mutation, Subscription>) -> Self {
+        Self::with_connections(SseConnections::new(schema))
+    }
+
+    /// Create a GraphQL Server-Sent Events endpoint using the event streams
+    /// of `connections`, for example to change their limits.
+    pub fn with_connections(connections: SseConnections<Query, Mutation, Subscription>) -> Self {
+        Self { connections }
+    }
+}
+
+fn event_stream_response(stream: impl Stream<Item = Bytes> + Send + 'static) -> Response {
+    Response::builder()
+        .content_type(SSE_CONTENT_TYPE)
+        .header(header::CACHE_CONTROL, "no-cache")
+        .body(Body::from_bytes_stream(stream.map(Ok::<_, std::io::Error>)))
+}
+
+fn sse_error_response(err: SseError) -> Response {
+    Response::builder()
+        .status(err.status_code())
+        .body(err.to_string())
+}
+
+#[poem::async_trait]
+impl<Query, Mutation, Subscription> Endpoint for GraphQLSse<Query, Mutation, Subscription>
+where
+    Query: ObjectType + 'static,
+    Mutation: ObjectType + 'static,
+    Subscription: SubscriptionType + 'static,
+{
+    type Output = Response;
+
+    async fn call(&self, req: Request) -> Result<Self::Output> {
+        let (req, mut body) = req.split();
+        let params = poem::web::Query::<HashMap<String, String>>::from_request(&req, &mut body)
+            .await
+            .map(|params| params.0)
+            .unwrap_or_default();
+        let token = req
+            .header(SSE_TOKEN_HEADER)
+            .map(ToString::to_string)
+            .or_else(|| params.get("token").cloned());
+        let accept_event_stream = req
+            .header(header::ACCEPT)
+            .map(accepts_event_stream)
+            .unwrap_or_default();
+
+        let resp = match (req.method().clone(), token) {
+            (Method::PUT, _) => match self.connections.reserve() {
+                Ok(token) => Response::builder()
+                    .status(StatusCode::CREATED)
+                    .content_type("text/plain; charset=utf-8")
+                    .body(token),
+                Err(err) => sse_error_response(err),
+            },
+            (Method::DELETE, Some(token)) => match params.get("operationId") {
+                Some(operation_id) => match self.connections.stop(&token, operation_id) {
+                    Ok(()) => StatusCode::OK.into_response(),
+                    Err(err) => sse_error_response(err),
+                },
+                None => sse_error_response(SseError::MissingOperationId),
+            },
+            (Method::GET | Method::POST, Some(token)) if accept_event_stream => {
+                match self.connections.connect(&token) {
+                    Ok(stream) => event_stream_response(stream),
+                    Err(err) => sse_error_response(err),
+                }
+            }
+            (Method::POST, Some(token)) => {
+                let request = GraphQLRequest::from_request(&req, &mut body).await?;
+                match self.connections.execute(&token, request.0) {
+                    Ok(()) => StatusCode::ACCEPTED.into_response(),
+                    Err(err) => sse_error_response(err),
+                }
+            }
+            (Method::GET | Method::POST, None) if accept_event_stream => {
+                let request = GraphQLRequest::from_request(&req, &mut body).await?;
+                event_stream_response(create_sse_stream(
+                    self.connections.schema().execute_stream(request.0),
+                ))
+            }
+            (Method::GET | Method::POST, None) => StatusCode::NOT_ACCEPTABLE.into_response(),
+            _ => StatusCode::METHOD_NOT_ALLOWED.into_response(),
+        };
+        Ok(resp)
+    }
+}
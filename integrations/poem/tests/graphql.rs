@@ -0,0 +1,79 @@
+use async_graphql::*;
+use async_graphql_poem::{GraphQLRequest, GraphQLResponse};
+use poem::{
+    handler,
+    http::{header, Method, StatusCode},
+    middleware::AddData,
+    post,
+    web::Data,
+    Endpoint, EndpointExt, Request, Response, Route,
+};
+
+struct Query;
+
+#[Object]
+impl Query {
+    async fn value(&self) -> i32 {
+        10
+    }
+}
+
+type MySchema = Schema<Query, EmptyMutation, EmptySubscription>;
+
+#[handler]
+async fn index(req: GraphQLRequest, schema: Data<&MySchema>) -> GraphQLResponse {
+    let content_type = req.response_content_type();
+    GraphQLResponse::from(schema.execute(req.0).await).content_type(content_type)
+}
+
+async fn call(query: &str, accept: Option<&str>) -> Response {
+    let app = Route::new().at(
+        "/",
+        post(index.with(AddData::new(Schema::new(
+            Query,
+            EmptyMutation,
+            EmptySubscription,
+        )))),
+    );
+    let mut req = Request::builder()
+        .method(Method::POST)
+        .content_type("application/json");
+    if let Some(accept) = accept {
+        req = req.header(header::ACCEPT, accept);
+    }
+    app.get_response(
+        req.body(serde_json::to_string(&serde_json::json!({ "query": query })).unwrap()),
+    )
+    .await
+}
+
+#[tokio::test]
+async fn test_response_content_type() {
+    let resp = call("{ value }", None).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(resp.headers()[header::CONTENT_TYPE], "application/json");
+    assert_eq!(
+        resp.into_body().into_string().await.unwrap(),
+        r#"{"data":{"value":10}}"#
+    );
+
+    let resp = call("{ value }", Some("application/graphql-response+json")).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(
+        resp.headers()[header::CONTENT_TYPE],
+        "application/graphql-response+json"
+    );
+
+    // A request failing before execution is a bad request with this content
+    // type.
+    let resp = call("{ unknown }", Some("application/graphql-response+json")).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(
+        resp.headers()[header::CONTENT_TYPE],
+        "application/graphql-response+json"
+    );
+
+    let resp = call("{ unknown }", None).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(resp.headers()[header::CONTENT_TYPE], "application/json");
+}
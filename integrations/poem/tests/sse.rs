@@ -0,0 +1,161 @@
+use async_graphql::{http::SSE_TOKEN_HEADER, *};
+use async_graphql_poem::GraphQLSse;
+use futures_util::{stream, Stream, StreamExt};
+use poem::{
+    http::{header, Method, StatusCode},
+    Endpoint, Request, Response,
+};
+
+struct Query;
+
+#[Object]
+impl Query {
+    async fn value(&self) -> i32 {
+        10
+    }
+}
+
+struct Subscription;
+
+#[Subscription]
+impl Subscription {
+    async fn values(&self) -> impl Stream<Item = i32> {
+        stream::iter(vec![1, 2]).chain(stream::pending())
+    }
+}
+
+async fn call(endpoint: &GraphQLSse<Query, EmptyMutation, Subscription>, req: Request) -> Response {
+    endpoint.call(req).await.unwrap()
+}
+
+#[tokio::test]
+async fn test_sse_single_connection() {
+    let endpoint = GraphQLSse::new(Schema::new(Query, EmptyMutation, Subscription));
+
+    // Reserve
+    let resp = call(&endpoint, Request::builder().method(Method::PUT).finish()).await;
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let token = resp.into_body().into_string().await.unwrap();
+
+    // Connect
+    let resp = call(
+        &endpoint,
+        Request::builder()
+            .method(Method::GET)
+            .uri_str(format!("/?token={}", token))
+            .header(header::ACCEPT, "text/event-stream")
+            .finish(),
+    )
+    .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(resp.headers()[header::CONTENT_TYPE], "text/event-stream");
+    let mut events = resp
+        .into_body()
+        .into_bytes_stream()
+        .map(|data| String::from_utf8(data.unwrap().to_vec()).unwrap());
+
+    // Execute
+    let resp = call(
+        &endpoint,
+        Request::builder()
+            .method(Method::POST)
+            .header(SSE_TOKEN_HEADER, &token)
+            .content_type("application/json")
+            .body(r#"{"query":"subscription { values }","extensions":{"operationId":"1"}}"#),
+    )
+    .await;
+    assert_eq!(resp.status(), StatusCode::ACCEPTED);
+    assert_eq!(
+        events.next().await.unwrap(),
+        "event: next\ndata: {\"id\":\"1\",\"payload\":{\"data\":{\"values\":1}}}\n\n"
+    );
+    assert_eq!(
+        events.next().await.unwrap(),
+        "event: next\ndata: {\"id\":\"1\",\"payload\":{\"data\":{\"values\":2}}}\n\n"
+    );
+
+    // Stop
+    let resp = call(
+        &endpoint,
+        Request::builder()
+            .method(Method::DELETE)
+            .uri_str(format!("/?token={}&operationId=1", token))
+            .finish(),
+    )
+    .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    // The operation is stopped, another one can use the same id.
+    let resp = call(
+        &endpoint,
+        Request::builder()
+            .method(Method::POST)
+            .header(SSE_TOKEN_HEADER, &token)
+            .content_type("application/json")
+            .body(r#"{"query":"{ value }","extensions":{"operationId":"1"}}"#),
+    )
+    .await;
+    assert_eq!(resp.status(), StatusCode::ACCEPTED);
+    assert_eq!(
+        events.next().await.unwrap(),
+        "event: next\ndata: {\"id\":\"1\",\"payload\":{\"data\":{\"value\":10}}}\n\n"
+    );
+    assert_eq!(
+        events.next().await.unwrap(),
+        "event: complete\ndata: {\"id\":\"1\"}\n\n"
+    );
+
+    // The event stream is already established.
+    let resp = call(
+        &endpoint,
+        Request::builder()
+            .method(Method::GET)
+            .uri_str(format!("/?token={}", token))
+            .header(header::ACCEPT, "text/event-stream")
+            .finish(),
+    )
+    .await;
+    assert_eq!(resp.status(), StatusCode::CONFLICT);
+
+    // The token is released with the event stream.
+    drop(events);
+    let resp = call(
+        &endpoint,
+        Request::builder()
+            .method(Method::DELETE)
+            .uri_str(format!("/?token={}&operationId=1", token))
+            .finish(),
+    )
+    .await;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_sse_distinct_connections() {
+    let endpoint = GraphQLSse::new(Schema::new(Query, EmptyMutation, Subscription));
+
+    let resp = call(
+        &endpoint,
+        Request::builder()
+            .method(Method::POST)
+            .header(header::ACCEPT, "text/event-stream")
+            .content_type("application/json")
+            .body(r#"{"query":"{ value }"}"#),
+    )
+    .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(
+        resp.into_body().into_string().await.unwrap(),
+        "event: next\ndata: {\"data\":{\"value\":10}}\n\nevent: complete\ndata:\n\n"
+    );
+
+    let resp = call(
+        &endpoint,
+        Request::builder()
+            .method(Method::POST)
+            .content_type("application/json")
+            .body(r#"{"query":"{ value }"}"#),
+    )
+    .await;
+    assert_eq!(resp.status(), StatusCode::NOT_ACCEPTABLE);
+}
@@ -16,12 +16,13 @@ use core::any::Any;
 use std::io::Cursor;
 
 use async_graphql::{
-    http::MultipartOptions, ObjectType, ParseRequestError, Schema, SubscriptionType,
+    http::{MultipartOptions, ResponseContentType},
+    ObjectType, ParseRequestError, Schema, SubscriptionType,
 };
 use rocket::{
     data::{self, Data, FromData, ToByteUnit},
-    form::FromForm,
-    http::{ContentType, Header, Status},
+    http::{Header, Status},
+    request::{self, FromRequest},
     response::{self, Responder},
 };
 use tokio_util::compat::TokioAsyncReadCompatExt;
@@ -123,38 +124,43 @@ impl GraphQLRequest {
 
 impl From<GraphQLQuery> for GraphQLRequest {
     fn from(query: GraphQLQuery) -> Self {
-        let mut request = async_graphql::Request::new(query.query);
-
-        if let Some(operation_name) = query.operation_name {
-            request = request.operation_name(operation_name);
-        }
-
-        if let Some(variables) = query.variables {
-            let value = serde_json::from_str(&variables).unwrap_or_default();
-            let variables = async_graphql::Variables::from_json(value);
-            request = request.variables(variables);
-        }
-
-        GraphQLRequest(request)
+        GraphQLRequest(query.0)
     }
 }
 
 /// A GraphQL request which can be extracted from a query string.
 ///
+/// Mutations are rejected with `405 Method Not Allowed`, they must be sent in
+/// the request's body.
+///
 /// # Examples
 ///
 /// ```ignore
-/// #[rocket::get("/graphql?<query..>")]
-/// async fn graphql_query(schema: State<'_, ExampleSchema>, query: Query) -> Result<Response, Status> {
+/// #[rocket::get("/graphql")]
+/// async fn graphql_query(schema: State<'_, ExampleSchema>, query: GraphQLQuery) -> Result<Response, Status> {
 ///     query.execute(&schema).await
 /// }
 /// ```
-#[derive(FromForm, Debug)]
-pub struct GraphQLQuery {
-    query: String,
-    #[field(name = "operationName")]
-    operation_name: Option<String>,
-    variables: Option<String>,
+#[derive(Debug)]
+pub struct GraphQLQuery(pub async_graphql::Request);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for GraphQLQuery {
+    type Error = ParseRequestError;
+
+    async fn from_request(req: &'r rocket::Request<'_>) -> request::Outcome<Self, Self::Error> {
+        let query = req.uri().query().map(|query| query.as_str());
+        match async_graphql::http::parse_query_string(query.unwrap_or_default()) {
+            Ok(request) => request::Outcome::Success(Self(request)),
+            Err(e) => request::Outcome::Failure((
+                match e {
+                    ParseRequestError::MutationInGetRequest => Status::MethodNotAllowed,
+                    _ => Status::BadRequest,
+                },
+                e,
+            )),
+        }
+    }
 }
 
 impl GraphQLQuery {
@@ -191,7 +197,8 @@ impl<'r> FromData<'r> for GraphQLRequest {
 /// can be returned from a routing function in Rocket.
 ///
 /// It contains a `BatchResponse` but since a response is a type of batch
-/// response it works for both.
+/// response it works for both. The content type and status code are
+/// negotiated from the `Accept` header of the request.
 #[derive(Debug)]
 pub struct GraphQLResponse(pub async_graphql::BatchResponse);
 
@@ -207,11 +214,13 @@ impl From<async_graphql::Response> for GraphQLResponse {
 }
 
 impl<'r> Responder<'r, 'static> for GraphQLResponse {
-    fn respond_to(self, _: &'r rocket::Request<'_>) -> response::Result<'static> {
+    fn respond_to(self, req: &'r rocket::Request<'_>) -> response::Result<'static> {
         let body = serde_json::to_string(&self.0).unwrap();
+        let content_type = ResponseContentType::from_accept(req.headers().get_one("accept"));
 
         let mut response = rocket::Response::new();
-        response.set_header(ContentType::new("application", "json"));
+        response.set_status(Status::new(self.0.status_code(content_type).as_u16()));
+        response.set_header(Header::new("content-type", content_type.content_type()));
 
         if self.0.is_ok() {
             if let Some(cache_control) = self.0.cache_control().value() {
@@ -12,7 +12,8 @@
 mod subscription;
 
 use async_graphql::{
-    http::MultipartOptions, ObjectType, ParseRequestError, Schema, SubscriptionType,
+    http::{MultipartOptions, ResponseContentType},
+    ObjectType, ParseRequestError, Schema, SubscriptionType,
 };
 #[cfg(feature = "websocket")]
 pub use subscription::GraphQLSubscription;
@@ -85,7 +86,13 @@ where
     TideState: Clone + Send + Sync + 'static,
 {
     async fn call(&self, request: Request<TideState>) -> tide::Result {
-        respond(
+        let content_type = ResponseContentType::from_accept(
+            request
+                .header(headers::ACCEPT)
+                .and_then(|values| values.get(0))
+                .map(HeaderValue::as_str),
+        );
+        respond_with_content_type(
             self.schema
                 .execute_batch(if self.batch {
                     receive_batch_request_opts(request, self.opts).await
@@ -95,6 +102,7 @@ where
                         .map(Into::into)
                 }?)
                 .await,
+            content_type,
         )
     }
 }
@@ -132,7 +140,17 @@ pub async fn receive_batch_request_opts<State: Clone + Send + Sync + 'static>(
     opts: MultipartOptions,
 ) -> tide::Result<async_graphql::BatchRequest> {
     if request.method() == Method::Get {
-        request.query::<async_graphql::Request>().map(Into::into)
+        async_graphql::http::parse_query_string(request.url().query().unwrap_or_default())
+            .map(Into::into)
+            .map_err(|e| {
+                tide::Error::new(
+                    match &e {
+                        ParseRequestError::MutationInGetRequest => StatusCode::MethodNotAllowed,
+                        _ => StatusCode::BadRequest,
+                    },
+                    e,
+                )
+            })
     } else if request.method() == Method::Post {
         let body = request.take_body();
         let content_type = request
@@ -161,9 +179,18 @@ pub async fn receive_batch_request_opts<State: Clone + Send + Sync + 'static>(
 
 /// Convert a GraphQL response to a Tide response.
 pub fn respond(resp: impl Into<async_graphql::BatchResponse>) -> tide::Result {
+    respond_with_content_type(resp, ResponseContentType::Json)
+}
+
+/// Convert a GraphQL response to a Tide response with the content type
+/// negotiated from the `Accept` header of the request.
+pub fn respond_with_content_type(
+    resp: impl Into<async_graphql::BatchResponse>,
+    content_type: ResponseContentType,
+) -> tide::Result {
     let resp = resp.into();
 
-    let mut response = Response::new(StatusCode::Ok);
+    let mut response = Response::new(resp.status_code(content_type).as_u16());
     if resp.is_ok() {
         if let Some(cache_control) = resp.cache_control().value() {
             response.insert_header(headers::CACHE_CONTROL, cache_control);
@@ -177,5 +204,6 @@ pub fn respond(resp: impl Into<async_graphql::BatchResponse>) -> tide::Result {
     }
 
     response.set_body(Body::from_json(&resp)?);
+    response.insert_header(headers::CONTENT_TYPE, content_type.content_type());
     Ok(response)
 }
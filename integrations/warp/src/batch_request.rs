@@ -1,6 +1,9 @@
 use std::{io, io::ErrorKind};
 
-use async_graphql::{http::MultipartOptions, BatchRequest, ObjectType, Schema, SubscriptionType};
+use async_graphql::{
+    http::{MultipartOptions, ResponseContentType},
+    BatchRequest, ObjectType, Schema, SubscriptionType,
+};
 use futures_util::TryStreamExt;
 use warp::{reply::Response as WarpResponse, Buf, Filter, Rejection, Reply};
 
@@ -35,7 +38,15 @@ where
     Subscription: SubscriptionType + 'static,
 {
     warp::any()
-        .and(warp::get().and(warp::query()).map(BatchRequest::Single))
+        .and(
+            warp::get()
+                .and(warp::query::raw().or(warp::any().map(String::new)).unify())
+                .and_then(|query: String| async move {
+                    async_graphql::http::parse_query_string(&query)
+                        .map(BatchRequest::Single)
+                        .map_err(|e| warp::reject::custom(GraphQLBadRequest(e)))
+                }),
+        )
         .or(warp::post()
             .and(warp::header::optional::<String>("content-type"))
             .and(warp::body::stream())
@@ -58,6 +69,10 @@ where
 }
 
 /// Reply for `async_graphql::BatchRequest`.
+///
+/// It is sent as `application/json`, use
+/// [`GraphQLBatchResponse::into_response_with_content_type`] to send it with
+/// the content type negotiated from the `Accept` header of the request.
 #[derive(Debug)]
 pub struct GraphQLBatchResponse(pub async_graphql::BatchResponse);
 
@@ -67,12 +82,21 @@ impl From<async_graphql::BatchResponse> for GraphQLBatchResponse {
     }
 }
 
-impl Reply for GraphQLBatchResponse {
-    fn into_response(self) -> WarpResponse {
-        let mut resp = warp::reply::with_header(
-            warp::reply::json(&self.0),
-            "content-type",
-            "application/json",
+impl GraphQLBatchResponse {
+    /// Converts the response to a reply with the content type negotiated with
+    /// `ResponseContentType::from_accept`, setting the status code
+    /// accordingly.
+    pub fn into_response_with_content_type(
+        self,
+        content_type: ResponseContentType,
+    ) -> WarpResponse {
+        let mut resp = warp::reply::with_status(
+            warp::reply::with_header(
+                warp::reply::json(&self.0),
+                "content-type",
+                content_type.content_type(),
+            ),
+            self.0.status_code(content_type),
         )
         .into_response();
 
@@ -88,3 +112,9 @@ impl Reply for GraphQLBatchResponse {
         resp
     }
 }
+
+impl Reply for GraphQLBatchResponse {
+    fn into_response(self) -> WarpResponse {
+        self.into_response_with_content_type(ResponseContentType::Json)
+    }
+}
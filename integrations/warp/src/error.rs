@@ -25,6 +25,7 @@ impl GraphQLBadRequest {
     pub fn status(&self) -> StatusCode {
         match self.0 {
             ParseRequestError::PayloadTooLarge => StatusCode::PAYLOAD_TOO_LARGE,
+            ParseRequestError::MutationInGetRequest => StatusCode::METHOD_NOT_ALLOWED,
             _ => StatusCode::BAD_REQUEST,
         }
     }
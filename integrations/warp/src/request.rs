@@ -1,5 +1,6 @@
 use async_graphql::{
-    http::MultipartOptions, BatchRequest, ObjectType, Request, Schema, SubscriptionType,
+    http::{MultipartOptions, ResponseContentType},
+    BatchRequest, ObjectType, Request, Schema, SubscriptionType,
 };
 use warp::{reply::Response as WarpResponse, Filter, Rejection, Reply};
 
@@ -92,6 +93,18 @@ impl From<async_graphql::Response> for GraphQLResponse {
     }
 }
 
+impl GraphQLResponse {
+    /// Converts the response to a reply with the content type negotiated with
+    /// `ResponseContentType::from_accept`, setting the status code
+    /// accordingly.
+    pub fn into_response_with_content_type(
+        self,
+        content_type: ResponseContentType,
+    ) -> WarpResponse {
+        GraphQLBatchResponse(self.0.into()).into_response_with_content_type(content_type)
+    }
+}
+
 impl Reply for GraphQLResponse {
     fn into_response(self) -> WarpResponse {
         GraphQLBatchResponse(self.0.into()).into_response()
@@ -0,0 +1,404 @@
+//! Field authorization
+
+use std::collections::HashMap;
+
+use futures_util::{future::BoxFuture, FutureExt};
+use indexmap::IndexMap;
+
+use crate::{
+    context::QueryPathSegment,
+    extensions::ResolveInfo,
+    parser::types::{OperationType, Selection},
+    registry::{MetaField, MetaType, MetaTypeName},
+    Context, ContextSelectionSet, Name, Pos, QueryEnv, Result, SchemaEnv, ServerError,
+    ServerResult, Value,
+};
+
+/// Authorization policy
+///
+/// The policy checks the requirements of the fields annotated with the
+/// `@auth` directive, with `#[graphql(auth(requires = "..."))]`. The fields
+/// of an operation are checked before it is executed, and the
+/// [`AuthMode`] of the schema decides whether a denied field rejects the
+/// whole operation.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+///
+/// struct Roles(Vec<String>);
+///
+/// struct Query;
+///
+/// #[Object]
+/// impl Query {
+///     async fn name(&self) -> &str {
+///         "Alice"
+///     }
+///
+///     #[graphql(auth(requires = "admin"))]
+///     async fn email(&self) -> &str {
+///         "alice@example.com"
+///     }
+/// }
+///
+/// let policy = |ctx: &Context<'_>, request: &AuthRequest<'_>| {
+///     let roles = ctx.data::<Roles>()?;
+///     match request.requires.iter().all(|role| roles.0.contains(role)) {
+///         true => Ok(()),
+///         false => Err(format!("Forbidden field \"{}\".", request.info.name).into()),
+///     }
+/// };
+/// let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+///     .auth_policy(policy)
+///     .finish();
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async move {
+/// let request = Request::new("{ name email }").data(Roles(vec!["admin".to_string()]));
+/// assert_eq!(
+///     schema.execute(request).await.into_result().unwrap().data,
+///     value!({ "name": "Alice", "email": "alice@example.com" })
+/// );
+///
+/// let request = Request::new("{ name email }").data(Roles(vec![]));
+/// assert_eq!(
+///     schema.execute(request).await.errors[0].message,
+///     "Forbidden field \"email\"."
+/// );
+/// # });
+/// ```
+#[async_trait::async_trait]
+pub trait AuthPolicy: Send + Sync + 'static {
+    /// Check whether a field can be resolved, returns an error if it is
+    /// denied.
+    async fn check(&self, ctx: &Context<'_>, request: &AuthRequest<'_>) -> Result<()>;
+}
+
+#[async_trait::async_trait]
+impl<T> AuthPolicy for T
+where
+    T: Fn(&Context<'_>, &AuthRequest<'_>) -> Result<()> + Send + Sync + 'static,
+{
+    async fn check(&self, ctx: &Context<'_>, request: &AuthRequest<'_>) -> Result<()> {
+        self(ctx, request)
+    }
+}
+
+/// A field checked by an [`AuthPolicy`].
+pub struct AuthRequest<'a> {
+    /// The field, its parent type is the object type it is resolved on.
+    ///
+    /// The fields are checked before the execution, so the path doesn't
+    /// contain the indices of the lists.
+    pub info: ResolveInfo<'a>,
+
+    /// The requirements of the `@auth` directive of the field.
+    pub requires: &'a [String],
+
+    /// The arguments of the field in the operation, with the variables
+    /// replaced by their values.
+    pub arguments: &'a IndexMap<Name, Value>,
+
+    /// The field is checked to be listed by the introspection, with
+    /// [`SchemaBuilder::hide_denied_fields`](crate::SchemaBuilder::hide_denied_fields).
+    /// The path is then the path of the introspection field, and there are no
+    /// arguments.
+    pub introspection: bool,
+}
+
+/// What happens to an operation selecting fields denied by the
+/// [`AuthPolicy`].
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub enum AuthMode {
+    /// The operation is rejected with the errors of the denied fields,
+    /// without being executed.
+    #[default]
+    Reject,
+    /// The operation is executed, and the denied fields are resolved as
+    /// `null` with their errors.
+    ///
+    /// The subscriptions selecting denied root fields are still rejected,
+    /// their events are only nullified below the root fields.
+    Nullify,
+}
+
+/// A field denied by the authorization policy, with [`AuthMode::Nullify`].
+#[derive(Clone)]
+pub(crate) struct DeniedField {
+    error: ServerError,
+    nullable: bool,
+}
+
+/// The fields denied by the authorization policy, by the position of the
+/// field in the operation, its path without the indices of the lists, and the
+/// type it is resolved on.
+pub(crate) type DeniedFields = HashMap<(Pos, Vec<String>), HashMap<String, DeniedField>>;
+
+/// Checks the fields of an operation with the authorization policy of the
+/// schema, before it is executed.
+pub(crate) async fn authorize(
+    schema_env: &SchemaEnv,
+    query_env: &QueryEnv,
+) -> Result<(), Vec<ServerError>> {
+    let policy = match &schema_env.auth_policy {
+        Some(policy) => &**policy,
+        None => return Ok(()),
+    };
+    let registry = &schema_env.registry;
+    let root_type = match query_env.operation.node.ty {
+        OperationType::Query => Some(registry.query_type.as_str()),
+        OperationType::Mutation => registry.mutation_type.as_deref(),
+        OperationType::Subscription => registry.subscription_type.as_deref(),
+    };
+    let root_type = match root_type {
+        Some(root_type) => root_type,
+        None => return Ok(()),
+    };
+
+    let ctx = query_env.create_context(schema_env, None, &query_env.operation.node.selection_set);
+    let mut authorizer = Authorizer {
+        policy,
+        denied: Vec::new(),
+    };
+    authorizer.check_selection_set(&ctx, root_type).await;
+    if authorizer.denied.is_empty() {
+        return Ok(());
+    }
+
+    // The root fields of a subscription are not resolved as the fields of an
+    // object, they cannot be nullified.
+    let nullify = schema_env.auth_mode == AuthMode::Nullify
+        && (query_env.operation.node.ty != OperationType::Subscription
+            || authorizer
+                .denied
+                .iter()
+                .all(|(_, path, _, _)| path.len() > 1));
+    match nullify {
+        false => Err(authorizer
+            .denied
+            .into_iter()
+            .map(|(_, _, _, denied)| denied.error)
+            .collect()),
+        true => {
+            let mut denied_fields = DeniedFields::new();
+            for (pos, path, type_name, denied) in authorizer.denied {
+                denied_fields
+                    .entry((pos, path))
+                    .or_default()
+                    .insert(type_name, denied);
+            }
+            let _ = query_env.auth_denied.set(denied_fields);
+            Ok(())
+        }
+    }
+}
+
+/// Returns the error of a field denied by the authorization policy, and
+/// whether the field is nullable.
+pub(crate) fn denied_field(ctx: &Context<'_>, type_name: &str) -> Option<(ServerError, bool)> {
+    let denied = ctx
+        .query_env
+        .auth_denied
+        .get()?
+        .get(&(ctx.item.pos, field_path(ctx)))?
+        .get(type_name)?;
+    Some((ctx.set_error_path(denied.error.clone()), denied.nullable))
+}
+
+/// Returns the path of a field without the indices of the lists.
+fn field_path(ctx: &Context<'_>) -> Vec<String> {
+    let mut path = Vec::new();
+    if let Some(path_node) = &ctx.path_node {
+        path_node.for_each(|segment| {
+            if let QueryPathSegment::Name(name) = segment {
+                path.push(name.to_string());
+            }
+        });
+    }
+    path
+}
+
+/// Returns `false` if a field is hidden from the introspection, because it
+/// is denied by the authorization policy.
+pub(crate) async fn is_visible(ctx: &Context<'_>, type_name: &str, field: &MetaField) -> bool {
+    let (policy, requires) = match (&ctx.schema_env.auth_policy, &field.auth) {
+        (Some(policy), Some(requires)) if ctx.schema_env.hide_denied_fields => (policy, requires),
+        _ => return true,
+    };
+    let path_node = match &ctx.path_node {
+        Some(path_node) => path_node,
+        None => return true,
+    };
+    let request = AuthRequest {
+        info: ResolveInfo {
+            path_node,
+            parent_type: type_name,
+            return_type: &field.ty,
+            name: &field.name,
+            alias: None,
+        },
+        requires,
+        arguments: &IndexMap::new(),
+        introspection: true,
+    };
+    policy.check(ctx, &request).await.is_ok()
+}
+
+struct Authorizer<'a> {
+    policy: &'a dyn AuthPolicy,
+    denied: Vec<(Pos, Vec<String>, String, DeniedField)>,
+}
+
+impl<'a> Authorizer<'a> {
+    fn check_selection_set<'b>(
+        &'b mut self,
+        ctx: &'b ContextSelectionSet<'b>,
+        type_name: &'b str,
+    ) -> BoxFuture<'b, ()> {
+        async move {
+            let registry = &ctx.schema_env.registry;
+            let ty = match registry.types.get(type_name) {
+                Some(ty) => ty,
+                None => return,
+            };
+            // The fields of abstract types are checked on each of their
+            // object types.
+            let object_types = match ty {
+                MetaType::Object { .. } => vec![type_name],
+                _ => ty
+                    .possible_types()
+                    .map(|types| types.iter().map(String::as_str).collect())
+                    .unwrap_or_default(),
+            };
+
+            for selection in &ctx.item.node.items {
+                match &selection.node {
+                    Selection::Field(field) => {
+                        let name = field.node.name.node.as_str();
+                        if name.starts_with("__") {
+                            continue;
+                        }
+                        let meta_field = match ty.field_by_name(name) {
+                            Some(meta_field) => meta_field,
+                            None => continue,
+                        };
+                        let ctx_field = ctx.with_field(field);
+
+                        let mut allowed = object_types.is_empty();
+                        for object_type in &object_types {
+                            let object_field = registry
+                                .types
+                                .get(*object_type)
+                                .and_then(|ty| ty.field_by_name(name));
+                            let (return_type, requires) = match object_field {
+                                Some(object_field) => (
+                                    &object_field.ty,
+                                    object_field.auth.as_ref().or(meta_field.auth.as_ref()),
+                                ),
+                                None => continue,
+                            };
+                            let requires = match requires {
+                                Some(requires) => requires,
+                                None => {
+                                    allowed = true;
+                                    continue;
+                                }
+                            };
+                            // The field is denied when its arguments cannot
+                            // be resolved, rather than checked with defaults.
+                            let arguments = field
+                                .node
+                                .arguments
+                                .iter()
+                                .map(|(name, value)| {
+                                    Ok((
+                                        name.node.clone(),
+                                        ctx_field.resolve_input_value(value.clone())?,
+                                    ))
+                                })
+                                .collect::<ServerResult<IndexMap<_, _>>>();
+                            let result = match arguments {
+                                Ok(arguments) => {
+                                    let request = AuthRequest {
+                                        info: ResolveInfo {
+                                            path_node: ctx_field.path_node.as_ref().unwrap(),
+                                            parent_type: object_type,
+                                            return_type,
+                                            name,
+                                            alias: field
+                                                .node
+                                                .alias
+                                                .as_ref()
+                                                .map(|alias| alias.node.as_str()),
+                                        },
+                                        requires,
+                                        arguments: &arguments,
+                                        introspection: false,
+                                    };
+                                    self.policy
+                                        .check(&ctx_field, &request)
+                                        .await
+                                        .map_err(|err| err.into_server_error(field.pos))
+                                }
+                                Err(err) => Err(err),
+                            };
+                            match result {
+                                Ok(()) => allowed = true,
+                                Err(err) => self.denied.push((
+                                    field.pos,
+                                    field_path(&ctx_field),
+                                    object_type.to_string(),
+                                    DeniedField {
+                                        error: ctx_field.set_error_path(err),
+                                        nullable: !return_type.ends_with('!'),
+                                    },
+                                )),
+                            }
+                        }
+
+                        // The selections of a field denied on every type are
+                        // never executed.
+                        if allowed && !field.node.selection_set.node.items.is_empty() {
+                            let ctx_selection_set =
+                                ctx_field.with_selection_set(&field.node.selection_set);
+                            self.check_selection_set(
+                                &ctx_selection_set,
+                                MetaTypeName::concrete_typename(&meta_field.ty),
+                            )
+                            .await;
+                        }
+                    }
+                    Selection::FragmentSpread(spread) => {
+                        if let Some(fragment) =
+                            ctx.query_env.fragments.get(&spread.node.fragment_name.node)
+                        {
+                            let type_name =
+                                fragment_type(ty, &fragment.node.type_condition.node.on.node);
+                            let ctx_fragment = ctx.with_selection_set(&fragment.node.selection_set);
+                            self.check_selection_set(&ctx_fragment, type_name).await;
+                        }
+                    }
+                    Selection::InlineFragment(fragment) => {
+                        let type_name = match &fragment.node.type_condition {
+                            Some(type_condition) => fragment_type(ty, &type_condition.node.on.node),
+                            None => type_name,
+                        };
+                        let ctx_fragment = ctx.with_selection_set(&fragment.node.selection_set);
+                        self.check_selection_set(&ctx_fragment, type_name).await;
+                    }
+                }
+            }
+        }
+        .boxed()
+    }
+}
+
+/// The type the selections of a fragment are checked on, the object types
+/// are kept when they are narrower than the type condition.
+fn fragment_type<'a>(ty: &'a MetaType, type_condition: &'a str) -> &'a str {
+    match ty {
+        MetaType::Object { name, .. } => name,
+        _ => type_condition,
+    }
+}
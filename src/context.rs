@@ -14,13 +14,16 @@ use http::{
     header::{AsHeaderName, HeaderMap, IntoHeaderName},
     HeaderValue,
 };
+use once_cell::sync::OnceCell;
 use serde::{
     ser::{SerializeSeq, Serializer},
     Serialize,
 };
 
 use crate::{
+    auth::DeniedFields,
     extensions::Extensions,
+    incremental::Incremental,
     parser::types::{
         Directive, Field, FragmentDefinition, OperationDefinition, Selection, SelectionSet,
     },
@@ -257,6 +260,9 @@ pub struct QueryEnvInner {
     pub http_headers: Mutex<HeaderMap>,
     pub introspection_mode: IntrospectionMode,
     pub errors: Mutex<Vec<ServerError>>,
+    pub(crate) auth_denied: OnceCell<DeniedFields>,
+    pub(crate) redacted: Mutex<Vec<Value>>,
+    pub(crate) incremental: Option<Incremental>,
 }
 
 #[doc(hidden)]
@@ -560,7 +566,7 @@ impl<'a, T> ContextBase<'a, T> {
             })
     }
 
-    fn resolve_input_value(&self, value: Positioned<InputValue>) -> ServerResult<Value> {
+    pub(crate) fn resolve_input_value(&self, value: Positioned<InputValue>) -> ServerResult<Value> {
         let pos = value.pos;
         value
             .node
@@ -1,10 +1,15 @@
 use std::{
+    any::{Any, TypeId},
     borrow::Cow,
-    collections::{hash_map::RandomState, HashMap},
+    collections::{hash_map::RandomState, HashMap, VecDeque},
     hash::{BuildHasher, Hash},
     marker::PhantomData,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
 };
 
+use super::Loader;
+
 /// Factory for creating cache storage.
 pub trait CacheFactory: Send + Sync + 'static {
     /// Create a cache storage.
@@ -14,6 +19,18 @@ pub trait CacheFactory: Send + Sync + 'static {
     where
         K: Send + Sync + Clone + Eq + Hash + 'static,
         V: Send + Sync + Clone + 'static;
+
+    /// Create a cache storage for the loader whose type id is `loader`.
+    ///
+    /// [DataLoader](super::DataLoader) creates its cache storage with this
+    /// method, the default implementation calls [CacheFactory::create].
+    fn create_for_loader<K, V>(&self, _loader: TypeId) -> Box<dyn CacheStorage<Key = K, Value = V>>
+    where
+        K: Send + Sync + Clone + Eq + Hash + 'static,
+        V: Send + Sync + Clone + 'static,
+    {
+        self.create::<K, V>()
+    }
 }
 
 /// Cache storage for [DataLoader].
@@ -195,3 +212,334 @@ where
         self.0.clear();
     }
 }
+
+/// Cache expiring the values after a time to live.
+///
+/// The values are stored in the cache storage created by another factory,
+/// [HashMapCache] by default, with the time they expire at. An expired value
+/// is removed when it is read, and loaded again, the other expired values are
+/// removed when a value is inserted, so that the storage does not keep the
+/// values which are not read again.
+pub struct TtlCache<C = HashMapCache> {
+    ttl: Duration,
+    cache_factory: C,
+    clock: Clock,
+}
+
+type Clock = Arc<dyn Fn() -> Instant + Send + Sync>;
+
+impl TtlCache<HashMapCache> {
+    /// Creates a new cache expiring the values after `ttl`.
+    pub fn new(ttl: Duration) -> Self {
+        Self::with_cache(ttl, HashMapCache::default())
+    }
+}
+
+impl<C: CacheFactory> TtlCache<C> {
+    /// Creates a new cache expiring the values after `ttl`, stored in the
+    /// cache storage created by `cache_factory`.
+    pub fn with_cache(ttl: Duration, cache_factory: C) -> Self {
+        Self {
+            ttl,
+            cache_factory,
+            clock: Arc::new(Instant::now),
+        }
+    }
+
+    /// Use `clock` instead of [Instant::now] to read the current time, for
+    /// example to control the expiration in tests.
+    #[must_use]
+    pub fn with_clock(self, clock: impl Fn() -> Instant + Send + Sync + 'static) -> Self {
+        Self {
+            clock: Arc::new(clock),
+            ..self
+        }
+    }
+}
+
+impl<C> TtlCache<C> {
+    fn create_storage<K, V>(
+        &self,
+        storage: Box<dyn CacheStorage<Key = K, Value = (V, Instant)>>,
+    ) -> Box<dyn CacheStorage<Key = K, Value = V>>
+    where
+        K: Send + Sync + Clone + Eq + Hash + 'static,
+        V: Send + Sync + Clone + 'static,
+    {
+        Box::new(TtlCacheImpl {
+            ttl: self.ttl,
+            clock: self.clock.clone(),
+            storage,
+            expirations: VecDeque::new(),
+        })
+    }
+}
+
+impl<C: CacheFactory> CacheFactory for TtlCache<C> {
+    fn create<K, V>(&self) -> Box<dyn CacheStorage<Key = K, Value = V>>
+    where
+        K: Send + Sync + Clone + Eq + Hash + 'static,
+        V: Send + Sync + Clone + 'static,
+    {
+        self.create_storage(self.cache_factory.create::<K, (V, Instant)>())
+    }
+
+    fn create_for_loader<K, V>(&self, loader: TypeId) -> Box<dyn CacheStorage<Key = K, Value = V>>
+    where
+        K: Send + Sync + Clone + Eq + Hash + 'static,
+        V: Send + Sync + Clone + 'static,
+    {
+        self.create_storage(
+            self.cache_factory
+                .create_for_loader::<K, (V, Instant)>(loader),
+        )
+    }
+}
+
+struct TtlCacheImpl<K, V> {
+    ttl: Duration,
+    clock: Clock,
+    storage: Box<dyn CacheStorage<Key = K, Value = (V, Instant)>>,
+    // The keys in the order they expire, a key inserted again is in it once
+    // for each insertion.
+    expirations: VecDeque<(Instant, K)>,
+}
+
+impl<K, V> TtlCacheImpl<K, V>
+where
+    K: Send + Sync + Clone + Eq + Hash + 'static,
+    V: Send + Sync + Clone + 'static,
+{
+    /// Removes the values expired at `now`.
+    fn prune(&mut self, now: Instant) {
+        while let Some((expires_at, _)) = self.expirations.front() {
+            if *expires_at > now {
+                break;
+            }
+            let (_, key) = self.expirations.pop_front().unwrap();
+            if matches!(self.storage.get(&key), Some((_, expires_at)) if *expires_at <= now) {
+                self.storage.remove(&key);
+            }
+        }
+    }
+}
+
+impl<K, V> CacheStorage for TtlCacheImpl<K, V>
+where
+    K: Send + Sync + Clone + Eq + Hash + 'static,
+    V: Send + Sync + Clone + 'static,
+{
+    type Key = K;
+    type Value = V;
+
+    fn get(&mut self, key: &Self::Key) -> Option<&Self::Value> {
+        let now = (self.clock)();
+        if matches!(self.storage.get(key), Some((_, expires_at)) if *expires_at <= now) {
+            self.storage.remove(key);
+            return None;
+        }
+        self.storage.get(key).map(|(value, _)| value)
+    }
+
+    fn insert(&mut self, key: Cow<'_, Self::Key>, val: Cow<'_, Self::Value>) {
+        let now = (self.clock)();
+        self.prune(now);
+        let expires_at = now + self.ttl;
+        self.expirations
+            .push_back((expires_at, key.clone().into_owned()));
+        self.storage
+            .insert(key, Cow::Owned((val.into_owned(), expires_at)));
+    }
+
+    #[inline]
+    fn remove(&mut self, key: &Self::Key) {
+        self.storage.remove(key);
+    }
+
+    #[inline]
+    fn clear(&mut self) {
+        self.storage.clear();
+        self.expirations.clear();
+    }
+}
+
+type SharedStorage<K, V> = Arc<Mutex<Box<dyn CacheStorage<Key = K, Value = V>>>>;
+
+// The storages by the type ids of the loader and of the key and value.
+type SharedStorages = HashMap<(TypeId, TypeId), Box<dyn Any + Send + Sync>>;
+
+/// Cache shared by the data loaders created with clones of it, for example
+/// the data loaders of each request.
+///
+/// There is one cache storage for each loader and key type, created by another
+/// factory, [HashMapCache] by default. The values can be invalidated from
+/// outside of the data loaders with [SharedCache::invalidate] and
+/// [SharedCache::clear].
+///
+/// # Examples
+///
+/// ```rust
+/// use std::{collections::HashMap, convert::Infallible, time::Duration};
+///
+/// use async_graphql::dataloader::*;
+///
+/// struct UserNameLoader;
+///
+/// #[async_trait::async_trait]
+/// impl Loader<i32> for UserNameLoader {
+///     type Value = String;
+///     type Error = Infallible;
+///
+///     async fn load(&self, keys: &[i32]) -> Result<HashMap<i32, Self::Value>, Self::Error> {
+///         Ok(keys.iter().map(|id| (*id, format!("User {}", id))).collect())
+///     }
+/// }
+///
+/// let cache = SharedCache::new(TtlCache::new(Duration::from_secs(60)));
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async move {
+/// let loader = DataLoader::with_cache(UserNameLoader, tokio::spawn, cache.clone());
+/// loader.feed_one(1, "Alice".to_string()).await;
+///
+/// // The loader of another request reads the same cache.
+/// let loader = DataLoader::with_cache(UserNameLoader, tokio::spawn, cache.clone());
+/// assert_eq!(loader.load_one(1).await.unwrap(), Some("Alice".to_string()));
+///
+/// cache.invalidate::<UserNameLoader, i32>(&1);
+/// assert_eq!(loader.load_one(1).await.unwrap(), Some("User 1".to_string()));
+/// # });
+/// ```
+pub struct SharedCache<C = HashMapCache> {
+    cache_factory: Arc<C>,
+    storages: Arc<Mutex<SharedStorages>>,
+}
+
+impl<C> Clone for SharedCache<C> {
+    fn clone(&self) -> Self {
+        Self {
+            cache_factory: self.cache_factory.clone(),
+            storages: self.storages.clone(),
+        }
+    }
+}
+
+impl Default for SharedCache<HashMapCache> {
+    fn default() -> Self {
+        Self::new(HashMapCache::default())
+    }
+}
+
+impl<C: CacheFactory> SharedCache<C> {
+    /// Creates a new shared cache, the values are stored in the cache storages
+    /// created by `cache_factory`.
+    pub fn new(cache_factory: C) -> Self {
+        Self {
+            cache_factory: Arc::new(cache_factory),
+            storages: Default::default(),
+        }
+    }
+
+    fn storage<K, V>(&self, loader: TypeId) -> SharedStorage<K, V>
+    where
+        K: Send + Sync + Clone + Eq + Hash + 'static,
+        V: Send + Sync + Clone + 'static,
+    {
+        self.storages
+            .lock()
+            .unwrap()
+            .entry((loader, TypeId::of::<(K, V)>()))
+            .or_insert_with(|| {
+                let storage: SharedStorage<K, V> = Arc::new(Mutex::new(
+                    self.cache_factory.create_for_loader::<K, V>(loader),
+                ));
+                Box::new(storage)
+            })
+            .downcast_ref::<SharedStorage<K, V>>()
+            .unwrap()
+            .clone()
+    }
+
+    /// Removes the value of a key from the cache of the data loaders using
+    /// the loader `T` with keys of type `K`.
+    pub fn invalidate<T, K>(&self, key: &K)
+    where
+        T: Loader<K>,
+        K: Send + Sync + Clone + Eq + Hash + 'static,
+    {
+        self.storage::<K, T::Value>(TypeId::of::<T>())
+            .lock()
+            .unwrap()
+            .remove(key);
+    }
+
+    /// Clears the cache of the data loaders using the loader `T` with keys of
+    /// type `K`.
+    pub fn clear<T, K>(&self)
+    where
+        T: Loader<K>,
+        K: Send + Sync + Clone + Eq + Hash + 'static,
+    {
+        self.storage::<K, T::Value>(TypeId::of::<T>())
+            .lock()
+            .unwrap()
+            .clear();
+    }
+}
+
+impl<C: CacheFactory> CacheFactory for SharedCache<C> {
+    /// Creates a cache storage shared by all the storages created without a
+    /// loader for the same key and value types.
+    fn create<K, V>(&self) -> Box<dyn CacheStorage<Key = K, Value = V>>
+    where
+        K: Send + Sync + Clone + Eq + Hash + 'static,
+        V: Send + Sync + Clone + 'static,
+    {
+        self.create_for_loader::<K, V>(TypeId::of::<()>())
+    }
+
+    fn create_for_loader<K, V>(&self, loader: TypeId) -> Box<dyn CacheStorage<Key = K, Value = V>>
+    where
+        K: Send + Sync + Clone + Eq + Hash + 'static,
+        V: Send + Sync + Clone + 'static,
+    {
+        Box::new(SharedCacheImpl {
+            storage: self.storage::<K, V>(loader),
+            value: None,
+        })
+    }
+}
+
+struct SharedCacheImpl<K, V> {
+    storage: SharedStorage<K, V>,
+    // The last value read from the shared storage.
+    value: Option<V>,
+}
+
+impl<K, V> CacheStorage for SharedCacheImpl<K, V>
+where
+    K: Send + Sync + Clone + Eq + Hash + 'static,
+    V: Send + Sync + Clone + 'static,
+{
+    type Key = K;
+    type Value = V;
+
+    fn get(&mut self, key: &Self::Key) -> Option<&Self::Value> {
+        self.value = self.storage.lock().unwrap().get(key).cloned();
+        self.value.as_ref()
+    }
+
+    #[inline]
+    fn insert(&mut self, key: Cow<'_, Self::Key>, val: Cow<'_, Self::Value>) {
+        self.storage.lock().unwrap().insert(key, val);
+    }
+
+    #[inline]
+    fn remove(&mut self, key: &Self::Key) {
+        self.storage.lock().unwrap().remove(key);
+    }
+
+    #[inline]
+    fn clear(&mut self) {
+        self.storage.lock().unwrap().clear();
+    }
+}
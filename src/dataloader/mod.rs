@@ -57,29 +57,40 @@
 //! ```
 
 mod cache;
+mod observer;
 
 use std::{
     any::{Any, TypeId},
     borrow::Cow,
     collections::{HashMap, HashSet},
+    convert::Infallible,
     hash::Hash,
     sync::{
         atomic::{AtomicBool, Ordering},
         Arc, Mutex,
     },
-    time::Duration,
+    time::{Duration, Instant},
 };
 
-pub use cache::{CacheFactory, CacheStorage, HashMapCache, LruCache, NoCache};
+pub use cache::{
+    CacheFactory, CacheStorage, HashMapCache, LruCache, NoCache, SharedCache, TtlCache,
+};
 use fnv::FnvHashMap;
 use futures_channel::oneshot;
 use futures_timer::Delay;
 use futures_util::future::BoxFuture;
+pub use observer::{BatchMetrics, DataLoaderObserver, LoaderType};
+#[cfg(feature = "tracing")]
+use tracing_futures::Instrument;
+#[cfg(feature = "tracing")]
+use tracinglib::{info_span, Span};
 
 #[allow(clippy::type_complexity)]
 struct ResSender<K: Send + Sync + Hash + Eq + Clone + 'static, T: Loader<K>> {
     use_cache_values: HashMap<K, T::Value>,
     tx: oneshot::Sender<Result<HashMap<K, T::Value>, T::Error>>,
+    #[cfg(feature = "tracing")]
+    span: Span,
 }
 
 struct Requests<K: Send + Sync + Hash + Eq + Clone + 'static, T: Loader<K>> {
@@ -96,7 +107,7 @@ impl<K: Send + Sync + Hash + Eq + Clone + 'static, T: Loader<K>> Requests<K, T>
         Self {
             keys: Default::default(),
             pending: Vec::new(),
-            cache_storage: cache_factory.create::<K, T::Value>(),
+            cache_storage: cache_factory.create_for_loader::<K, T::Value>(TypeId::of::<T>()),
             disable_cache: false,
         }
     }
@@ -120,6 +131,107 @@ pub trait Loader<K: Send + Sync + Hash + Eq + Clone + 'static>: Send + Sync + 's
 
     /// Load the data set specified by the `keys`.
     async fn load(&self, keys: &[K]) -> Result<HashMap<K, Self::Value>, Self::Error>;
+
+    /// Returns `false` if a loaded value must not be cached, the default is
+    /// `true`.
+    fn is_cacheable(&self, value: &Self::Value) -> bool {
+        let _ = value;
+        true
+    }
+}
+
+/// Trait for batch loading where each key has its own result.
+///
+/// A key that fails to load doesn't fail the other keys of the batch, and its
+/// error is not cached. The errors are shared by the requests of the key, so
+/// they don't have to be `Clone`.
+///
+/// Every `FallibleLoader` is a [Loader] whose values are the results of the
+/// keys, they are loaded with [DataLoader::try_load_one] and
+/// [DataLoader::try_load_many]. The results are loaded by
+/// [FallibleLoader::try_load], which is named differently from
+/// [Loader::load] so that calling either on a loader is not ambiguous.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::{collections::HashMap, sync::Arc};
+///
+/// use async_graphql::{dataloader::*, *};
+///
+/// struct UserNameLoader;
+///
+/// #[async_trait::async_trait]
+/// impl FallibleLoader<i32> for UserNameLoader {
+///     type Value = String;
+///     type Error = String;
+///
+///     async fn try_load(&self, keys: &[i32]) -> HashMap<i32, Result<Self::Value, Self::Error>> {
+///         keys.iter()
+///             .map(|id| match *id {
+///                 id if id < 0 => (id, Err(format!("Invalid id {}.", id))),
+///                 id => (id, Ok(format!("User {}", id))),
+///             })
+///             .collect()
+///     }
+/// }
+///
+/// struct Query;
+///
+/// #[Object]
+/// impl Query {
+///     async fn user_name(&self, ctx: &Context<'_>, id: i32) -> Result<Option<String>> {
+///         let loader = ctx.data_unchecked::<DataLoader<UserNameLoader>>();
+///         Ok(loader.try_load_one(id).await?)
+///     }
+/// }
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async move {
+/// let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+/// let request = |query| Request::new(query).data(DataLoader::new(UserNameLoader, tokio::spawn));
+///
+/// let response = schema.execute(request("{ userName(id: 1) }")).await;
+/// assert_eq!(response.data, value!({ "userName": "User 1" }));
+///
+/// let response = schema.execute(request("{ userName(id: -1) }")).await;
+/// assert_eq!(response.errors[0].message, "Invalid id -1.");
+/// # });
+/// ```
+#[async_trait::async_trait]
+pub trait FallibleLoader<K: Send + Sync + Hash + Eq + Clone + 'static>:
+    Send + Sync + 'static
+{
+    /// type of value.
+    type Value: Send + Sync + Clone + 'static;
+
+    /// Type of error.
+    type Error: Send + Sync + 'static;
+
+    /// Load the results of the `keys`, a missing key has no value.
+    async fn try_load(&self, keys: &[K]) -> HashMap<K, Result<Self::Value, Self::Error>>;
+}
+
+#[async_trait::async_trait]
+impl<K, T> Loader<K> for T
+where
+    K: Send + Sync + Hash + Eq + Clone + 'static,
+    T: FallibleLoader<K>,
+{
+    type Value = Result<T::Value, Arc<T::Error>>;
+    type Error = Infallible;
+
+    async fn load(&self, keys: &[K]) -> Result<HashMap<K, Self::Value>, Self::Error> {
+        Ok(self
+            .try_load(keys)
+            .await
+            .into_iter()
+            .map(|(key, res)| (key, res.map_err(Arc::new)))
+            .collect())
+    }
+
+    fn is_cacheable(&self, value: &Self::Value) -> bool {
+        value.is_ok()
+    }
 }
 
 struct DataLoaderInner<T> {
@@ -128,15 +240,60 @@ struct DataLoaderInner<T> {
 }
 
 impl<T> DataLoaderInner<T> {
-    async fn do_load<K>(&self, disable_cache: bool, (keys, senders): KeysAndSender<K, T>)
-    where
+    async fn do_load<K>(
+        &self,
+        disable_cache: bool,
+        observer: Option<Arc<dyn DataLoaderObserver>>,
+        (keys, senders): KeysAndSender<K, T>,
+    ) where
         K: Send + Sync + Hash + Eq + Clone + 'static,
         T: Loader<K>,
     {
         let tid = TypeId::of::<K>();
         let keys = keys.into_iter().collect::<Vec<_>>();
+        let loader_type = LoaderType::of::<T, K>();
+
+        let start_time = Instant::now();
+        let load_fut = self.loader.load(&keys);
+        #[cfg(feature = "tracing")]
+        let load_fut = {
+            // The batch is loaded for the resolvers of all the pending requests.
+            let span = info_span!(
+                target: "async_graphql::dataloader",
+                "load",
+                loader = loader_type.loader,
+                key = loader_type.key,
+                batch_size = keys.len(),
+            );
+            for (_, sender) in &senders {
+                span.follows_from(&sender.span);
+            }
+            load_fut.instrument(span)
+        };
+        let res = load_fut.await;
+
+        #[cfg(feature = "tracing")]
+        if res.is_err() {
+            tracinglib::warn!(
+                target: "async_graphql::dataloader",
+                loader = loader_type.loader,
+                key = loader_type.key,
+                batch_size = keys.len(),
+                "failed to load the batch"
+            );
+        }
+        if let Some(observer) = &observer {
+            observer.batch_loaded(
+                &loader_type,
+                &BatchMetrics {
+                    size: keys.len(),
+                    duration: start_time.elapsed(),
+                    error: res.is_err(),
+                },
+            );
+        }
 
-        match self.loader.load(&keys).await {
+        match res {
             Ok(values) => {
                 // update cache
                 let mut request = self.requests.lock().unwrap();
@@ -148,9 +305,11 @@ impl<T> DataLoaderInner<T> {
                 let disable_cache = typed_requests.disable_cache || disable_cache;
                 if !disable_cache {
                     for (key, value) in &values {
-                        typed_requests
-                            .cache_storage
-                            .insert(Cow::Borrowed(key), Cow::Borrowed(value));
+                        if self.loader.is_cacheable(value) {
+                            typed_requests
+                                .cache_storage
+                                .insert(Cow::Borrowed(key), Cow::Borrowed(value));
+                        }
                     }
                 }
 
@@ -182,6 +341,7 @@ pub struct DataLoader<T, C = NoCache> {
     delay: Duration,
     max_batch_size: usize,
     disable_cache: AtomicBool,
+    observer: Option<Arc<dyn DataLoaderObserver>>,
     spawner: Box<dyn Fn(BoxFuture<'static, ()>) + Send + Sync>,
 }
 
@@ -200,6 +360,7 @@ impl<T> DataLoader<T, NoCache> {
             delay: Duration::from_millis(1),
             max_batch_size: 1000,
             disable_cache: false.into(),
+            observer: None,
             spawner: Box::new(move |fut| {
                 spawner(fut);
             }),
@@ -222,6 +383,7 @@ impl<T, C: CacheFactory> DataLoader<T, C> {
             delay: Duration::from_millis(1),
             max_batch_size: 1000,
             disable_cache: false.into(),
+            observer: None,
             spawner: Box::new(move |fut| {
                 spawner(fut);
             }),
@@ -247,6 +409,15 @@ impl<T, C: CacheFactory> DataLoader<T, C> {
         }
     }
 
+    /// Set the observer of the batches of this `DataLoader`.
+    #[must_use]
+    pub fn observer(self, observer: impl DataLoaderObserver) -> Self {
+        Self {
+            observer: Some(Arc::new(observer)),
+            ..self
+        }
+    }
+
     /// Get the loader.
     #[inline]
     pub fn loader(&self) -> &T {
@@ -323,6 +494,10 @@ impl<T, C: CacheFactory> DataLoader<T, C> {
                 }
             }
 
+            if let (Some(observer), false) = (&self.observer, use_cache_values.is_empty()) {
+                observer.cache_hits(&LoaderType::of::<T, K>(), use_cache_values.len());
+            }
+
             if !use_cache_values.is_empty() && keys_set.is_empty() {
                 return Ok(use_cache_values);
             } else if use_cache_values.is_empty() && keys_set.is_empty() {
@@ -336,6 +511,8 @@ impl<T, C: CacheFactory> DataLoader<T, C> {
                 ResSender {
                     use_cache_values,
                     tx,
+                    #[cfg(feature = "tracing")]
+                    span: Span::current(),
                 },
             ));
 
@@ -357,13 +534,15 @@ impl<T, C: CacheFactory> DataLoader<T, C> {
             Action::ImmediateLoad(keys) => {
                 let inner = self.inner.clone();
                 let disable_cache = self.disable_cache.load(Ordering::SeqCst);
-                (self.spawner)(Box::pin(
-                    async move { inner.do_load(disable_cache, keys).await },
-                ));
+                let observer = self.observer.clone();
+                (self.spawner)(Box::pin(async move {
+                    inner.do_load(disable_cache, observer, keys).await
+                }));
             }
             Action::StartFetch => {
                 let inner = self.inner.clone();
                 let disable_cache = self.disable_cache.load(Ordering::SeqCst);
+                let observer = self.observer.clone();
                 let delay = self.delay;
 
                 (self.spawner)(Box::pin(async move {
@@ -380,7 +559,7 @@ impl<T, C: CacheFactory> DataLoader<T, C> {
                     };
 
                     if !keys.0.is_empty() {
-                        inner.do_load(disable_cache, keys).await
+                        inner.do_load(disable_cache, observer, keys).await
                     }
                 }))
             }
@@ -390,6 +569,32 @@ impl<T, C: CacheFactory> DataLoader<T, C> {
         rx.await.unwrap()
     }
 
+    /// Use this `DataLoader` to load a data with a [FallibleLoader].
+    pub async fn try_load_one<K>(&self, key: K) -> Result<Option<T::Value>, Arc<T::Error>>
+    where
+        K: Send + Sync + Hash + Eq + Clone + 'static,
+        T: FallibleLoader<K>,
+    {
+        match self.load_one(key).await {
+            Ok(res) => res.transpose(),
+            Err(err) => match err {},
+        }
+    }
+
+    /// Use this `DataLoader` to load some data with a [FallibleLoader], each
+    /// key has its own result.
+    pub async fn try_load_many<K, I>(&self, keys: I) -> HashMap<K, Result<T::Value, Arc<T::Error>>>
+    where
+        K: Send + Sync + Hash + Eq + Clone + 'static,
+        I: IntoIterator<Item = K>,
+        T: FallibleLoader<K>,
+    {
+        match self.load_many(keys).await {
+            Ok(values) => values,
+            Err(err) => match err {},
+        }
+    }
+
     /// Feed some data into the cache.
     ///
     /// **NOTE: If the cache type is [NoCache], this function will not take
@@ -426,6 +631,42 @@ impl<T, C: CacheFactory> DataLoader<T, C> {
         self.feed_many(std::iter::once((key, value))).await;
     }
 
+    /// Removes the values of some keys from the cache, they are loaded again
+    /// the next time they are requested.
+    ///
+    /// **NOTE: If the cache type is [NoCache], this function will not take
+    /// effect. **
+    pub fn clear_many<'a, K, I>(&self, keys: I)
+    where
+        K: Send + Sync + Hash + Eq + Clone + 'static,
+        I: IntoIterator<Item = &'a K>,
+        T: Loader<K>,
+    {
+        let tid = TypeId::of::<K>();
+        let mut requests = self.inner.requests.lock().unwrap();
+        let typed_requests = requests
+            .entry(tid)
+            .or_insert_with(|| Box::new(Requests::<K, T>::new(&self.cache_factory)))
+            .downcast_mut::<Requests<K, T>>()
+            .unwrap();
+        for key in keys {
+            typed_requests.cache_storage.remove(key);
+        }
+    }
+
+    /// Removes the value of a key from the cache, it is loaded again the next
+    /// time it is requested.
+    ///
+    /// **NOTE: If the cache type is [NoCache], this function will not take
+    /// effect. **
+    pub fn clear_one<K>(&self, key: &K)
+    where
+        K: Send + Sync + Hash + Eq + Clone + 'static,
+        T: Loader<K>,
+    {
+        self.clear_many(std::iter::once(key));
+    }
+
     /// Clears the cache.
     ///
     /// **NOTE: If the cache type is [NoCache], this function will not take
@@ -601,6 +842,270 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_dataloader_clear_one() {
+        let loader = DataLoader::with_cache(MyLoader, tokio::spawn, HashMapCache::default());
+        loader.feed_many(vec![(1, 10), (2, 20), (3, 30)]).await;
+
+        // Only the stale values are loaded again
+        loader.clear_one(&1);
+        loader.clear_many(&[3]);
+        assert_eq!(
+            loader.load_many(vec![1, 2, 3]).await.unwrap(),
+            vec![(1, 1), (2, 20), (3, 3)].into_iter().collect()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_dataloader_with_ttl_cache() {
+        let now = Arc::new(Mutex::new(Instant::now()));
+        let loader = DataLoader::with_cache(
+            MyLoader,
+            tokio::spawn,
+            TtlCache::with_cache(Duration::from_secs(60), LruCache::new(10)).with_clock({
+                let now = now.clone();
+                move || *now.lock().unwrap()
+            }),
+        );
+        loader.feed_many(vec![(1, 10), (2, 20)]).await;
+        assert_eq!(
+            loader.load_many(vec![1, 2]).await.unwrap(),
+            vec![(1, 10), (2, 20)].into_iter().collect()
+        );
+
+        // The fed values expire, the loaded values are cached again
+        *now.lock().unwrap() += Duration::from_secs(60);
+        loader.feed_one(2, 200).await;
+        assert_eq!(
+            loader.load_many(vec![1, 2]).await.unwrap(),
+            vec![(1, 1), (2, 200)].into_iter().collect()
+        );
+        loader.feed_one(1, 100).await;
+        assert_eq!(loader.load_one(1).await.unwrap(), Some(100));
+    }
+
+    #[tokio::test]
+    async fn test_dataloader_with_ttl_cache_prunes_expired_values() {
+        // A cache storage reporting the number of values it stores.
+        struct LenCache(Arc<Mutex<usize>>);
+
+        struct LenCacheImpl<K, V> {
+            values: HashMap<K, V>,
+            len: Arc<Mutex<usize>>,
+        }
+
+        impl CacheFactory for LenCache {
+            fn create<K, V>(&self) -> Box<dyn CacheStorage<Key = K, Value = V>>
+            where
+                K: Send + Sync + Clone + Eq + Hash + 'static,
+                V: Send + Sync + Clone + 'static,
+            {
+                Box::new(LenCacheImpl {
+                    values: HashMap::new(),
+                    len: self.0.clone(),
+                })
+            }
+        }
+
+        impl<K, V> CacheStorage for LenCacheImpl<K, V>
+        where
+            K: Send + Sync + Clone + Eq + Hash + 'static,
+            V: Send + Sync + Clone + 'static,
+        {
+            type Key = K;
+            type Value = V;
+
+            fn get(&mut self, key: &K) -> Option<&V> {
+                self.values.get(key)
+            }
+
+            fn insert(&mut self, key: Cow<'_, K>, val: Cow<'_, V>) {
+                self.values.insert(key.into_owned(), val.into_owned());
+                *self.len.lock().unwrap() = self.values.len();
+            }
+
+            fn remove(&mut self, key: &K) {
+                self.values.remove(key);
+                *self.len.lock().unwrap() = self.values.len();
+            }
+
+            fn clear(&mut self) {
+                self.values.clear();
+                *self.len.lock().unwrap() = 0;
+            }
+        }
+
+        let now = Arc::new(Mutex::new(Instant::now()));
+        let len = Arc::new(Mutex::new(0));
+        let loader = DataLoader::with_cache(
+            MyLoader,
+            tokio::spawn,
+            TtlCache::with_cache(Duration::from_secs(60), LenCache(len.clone())).with_clock({
+                let now = now.clone();
+                move || *now.lock().unwrap()
+            }),
+        );
+        loader.feed_many(vec![(1, 10), (2, 20)]).await;
+        *now.lock().unwrap() += Duration::from_secs(30);
+        loader.feed_one(2, 200).await;
+        assert_eq!(*len.lock().unwrap(), 2);
+
+        // The expired values are removed without being read again
+        *now.lock().unwrap() += Duration::from_secs(30);
+        loader.feed_one(3, 30).await;
+        assert_eq!(*len.lock().unwrap(), 2);
+        *now.lock().unwrap() += Duration::from_secs(30);
+        loader.feed_one(4, 40).await;
+        assert_eq!(*len.lock().unwrap(), 2);
+        assert_eq!(
+            loader.load_many(vec![2, 3, 4]).await.unwrap(),
+            vec![(2, 2), (3, 30), (4, 40)].into_iter().collect()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_dataloader_with_shared_cache() {
+        let cache = SharedCache::default();
+        let loader1 = DataLoader::with_cache(MyLoader, tokio::spawn, cache.clone());
+        let loader2 = DataLoader::with_cache(MyLoader, tokio::spawn, cache.clone());
+
+        loader1.feed_many(vec![(1, 10), (2, 20), (3, 30)]).await;
+        loader1.feed_one(1i64, 100).await;
+        assert_eq!(
+            loader2.load_many(vec![1, 2, 3]).await.unwrap(),
+            vec![(1, 10), (2, 20), (3, 30)].into_iter().collect()
+        );
+        assert_eq!(loader2.load_one(1i64).await.unwrap(), Some(100));
+
+        // Invalidate a key from outside of the loaders
+        cache.invalidate::<MyLoader, i32>(&1);
+        loader2.clear_one(&2);
+        assert_eq!(
+            loader1.load_many(vec![1, 2, 3]).await.unwrap(),
+            vec![(1, 1), (2, 2), (3, 30)].into_iter().collect()
+        );
+        assert_eq!(loader1.load_one(1i64).await.unwrap(), Some(100));
+
+        cache.clear::<MyLoader, i64>();
+        assert_eq!(loader1.load_one(1i64).await.unwrap(), Some(1));
+    }
+
+    #[tokio::test]
+    async fn test_dataloader_with_shared_cache_loaders() {
+        struct NegLoader;
+
+        #[async_trait::async_trait]
+        impl Loader<i32> for NegLoader {
+            type Value = i32;
+            type Error = ();
+
+            async fn load(&self, keys: &[i32]) -> Result<HashMap<i32, Self::Value>, Self::Error> {
+                Ok(keys.iter().copied().map(|k| (k, -k)).collect())
+            }
+        }
+
+        // The loaders with the same key and value types have their own cache.
+        let cache = SharedCache::default();
+        let loader = DataLoader::with_cache(MyLoader, tokio::spawn, cache.clone());
+        let neg_loader = DataLoader::with_cache(NegLoader, tokio::spawn, cache.clone());
+        assert_eq!(loader.load_one(1).await.unwrap(), Some(1));
+        assert_eq!(neg_loader.load_one(1).await.unwrap(), Some(-1));
+
+        neg_loader.feed_one(2, 20).await;
+        cache.invalidate::<MyLoader, i32>(&1);
+        assert_eq!(loader.load_one(2).await.unwrap(), Some(2));
+        assert_eq!(neg_loader.load_one(1).await.unwrap(), Some(-1));
+        assert_eq!(neg_loader.load_one(2).await.unwrap(), Some(20));
+    }
+
+    #[tokio::test]
+    async fn test_dataloader_observer() {
+        #[derive(Clone, Default)]
+        struct Events(Arc<Mutex<Vec<(usize, bool)>>>);
+
+        impl DataLoaderObserver for Events {
+            fn cache_hits(&self, loader: &LoaderType, count: usize) {
+                assert_eq!(loader.key, "i32");
+                self.0.lock().unwrap().push((count, true));
+            }
+
+            fn batch_loaded(&self, loader: &LoaderType, batch: &BatchMetrics) {
+                assert_eq!(loader.key, "i32");
+                assert!(!batch.error);
+                self.0.lock().unwrap().push((batch.size, false));
+            }
+        }
+
+        let events = Events::default();
+        let loader = DataLoader::with_cache(MyLoader, tokio::spawn, HashMapCache::default())
+            .observer(events.clone());
+        loader.feed_many(vec![(1, 10), (2, 20)]).await;
+
+        loader.load_many(vec![1, 2, 3, 4]).await.unwrap();
+        loader.load_many(vec![1, 2, 3]).await.unwrap();
+        assert_eq!(
+            *events.0.lock().unwrap(),
+            vec![(2, true), (2, false), (3, true)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_dataloader_fallible() {
+        #[derive(Default)]
+        struct MyFallibleLoader(Mutex<Vec<Vec<i32>>>);
+
+        #[async_trait::async_trait]
+        impl FallibleLoader<i32> for MyFallibleLoader {
+            type Value = i32;
+            type Error = String;
+
+            async fn try_load(
+                &self,
+                keys: &[i32],
+            ) -> HashMap<i32, Result<Self::Value, Self::Error>> {
+                let mut keys = keys.to_vec();
+                keys.sort_unstable();
+                self.0.lock().unwrap().push(keys.clone());
+                keys.into_iter()
+                    .filter(|k| *k != 0)
+                    .map(|k| match k {
+                        k if k < 0 => (k, Err(format!("error {}", k))),
+                        k => (k, Ok(k * 10)),
+                    })
+                    .collect()
+            }
+        }
+
+        let loader = DataLoader::with_cache(
+            MyFallibleLoader::default(),
+            tokio::spawn,
+            HashMapCache::default(),
+        );
+        let (a, b, c) = futures_util::future::join3(
+            loader.try_load_one(1),
+            loader.try_load_one(-1),
+            loader.try_load_one(0),
+        )
+        .await;
+        assert_eq!(a, Ok(Some(10)));
+        assert_eq!(b.unwrap_err().as_str(), "error -1");
+        assert_eq!(c, Ok(None));
+
+        // The errors are not cached
+        let values = loader.try_load_many(vec![1, 2, -1]).await;
+        assert_eq!(values[&1], Ok(10));
+        assert_eq!(values[&2], Ok(20));
+        assert_eq!(values[&-1].as_ref().unwrap_err().as_str(), "error -1");
+        assert_eq!(
+            *loader.loader().0.lock().unwrap(),
+            vec![vec![-1, 0, 1], vec![-1, 2]]
+        );
+
+        // Both methods can be called on the loader without disambiguation.
+        assert_eq!(loader.loader().try_load(&[3]).await[&3], Ok(30));
+        assert_eq!(loader.loader().load(&[3]).await.unwrap()[&3], Ok(30));
+    }
+
     #[tokio::test]
     async fn test_dataloader_disable_all_cache() {
         let loader = DataLoader::with_cache(MyLoader, tokio::spawn, HashMapCache::default());
@@ -0,0 +1,78 @@
+use std::time::Duration;
+
+/// The loader type and the key type of the values loaded by a
+/// [DataLoader](super::DataLoader).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LoaderType {
+    /// The name of the loader type, returned by [std::any::type_name].
+    pub loader: &'static str,
+
+    /// The name of the key type, returned by [std::any::type_name].
+    pub key: &'static str,
+}
+
+impl LoaderType {
+    pub(crate) fn of<T, K>() -> Self {
+        Self {
+            loader: std::any::type_name::<T>(),
+            key: std::any::type_name::<K>(),
+        }
+    }
+}
+
+/// The metrics of a call of [Loader::load](super::Loader::load).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BatchMetrics {
+    /// The number of keys loaded in the batch.
+    pub size: usize,
+
+    /// The time taken by the loader.
+    pub duration: Duration,
+
+    /// The loader returned an error, failing all the keys of the batch.
+    ///
+    /// The errors of some keys of a [FallibleLoader](super::FallibleLoader)
+    /// are loaded values, they are not reported here.
+    pub error: bool,
+}
+
+/// Observer of the batches of a [DataLoader](super::DataLoader), to measure
+/// how effective the batching is.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+///
+/// use async_graphql::dataloader::*;
+///
+/// #[derive(Default)]
+/// struct Metrics {
+///     batches: AtomicUsize,
+///     keys: AtomicUsize,
+///     cache_hits: AtomicUsize,
+/// }
+///
+/// impl DataLoaderObserver for Metrics {
+///     fn cache_hits(&self, _loader: &LoaderType, count: usize) {
+///         self.cache_hits.fetch_add(count, Ordering::Relaxed);
+///     }
+///
+///     fn batch_loaded(&self, _loader: &LoaderType, batch: &BatchMetrics) {
+///         self.batches.fetch_add(1, Ordering::Relaxed);
+///         self.keys.fetch_add(batch.size, Ordering::Relaxed);
+///     }
+/// }
+/// ```
+pub trait DataLoaderObserver: Send + Sync + 'static {
+    /// Called when some of the requested keys are served from the
+    /// [CacheStorage](super::CacheStorage).
+    fn cache_hits(&self, loader: &LoaderType, count: usize) {
+        let _ = (loader, count);
+    }
+
+    /// Called after each call of [Loader::load](super::Loader::load).
+    fn batch_loaded(&self, loader: &LoaderType, batch: &BatchMetrics) {
+        let _ = (loader, batch);
+    }
+}
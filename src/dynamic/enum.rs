@@ -0,0 +1,142 @@
+use indexmap::IndexMap;
+
+use crate::registry::{Deprecation, MetaEnumValue, MetaType, Registry};
+
+/// A GraphQL enum item
+#[derive(Debug)]
+pub struct EnumItem {
+    pub(crate) name: String,
+    pub(crate) description: Option<String>,
+    pub(crate) deprecation: Deprecation,
+}
+
+impl<T: Into<String>> From<T> for EnumItem {
+    #[inline]
+    fn from(name: T) -> Self {
+        EnumItem::new(name)
+    }
+}
+
+impl EnumItem {
+    /// Create a GraphQL enum item
+    #[inline]
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            description: None,
+            deprecation: Deprecation::NoDeprecated,
+        }
+    }
+
+    /// Set the description
+    #[inline]
+    #[must_use]
+    pub fn description(self, description: impl Into<String>) -> Self {
+        Self {
+            description: Some(description.into()),
+            ..self
+        }
+    }
+
+    /// Mark the item as deprecated, with an optional reason
+    #[inline]
+    #[must_use]
+    pub fn deprecation(self, reason: Option<&str>) -> Self {
+        Self {
+            deprecation: Deprecation::Deprecated {
+                reason: reason.map(super::intern_str),
+            },
+            ..self
+        }
+    }
+}
+
+/// A GraphQL enum type
+///
+/// Resolvers return the items as [`Value::Enum`](crate::Value::Enum) or
+/// [`Value::String`](crate::Value::String).
+#[derive(Debug)]
+pub struct Enum {
+    pub(crate) name: String,
+    pub(crate) description: Option<String>,
+    pub(crate) items: IndexMap<String, EnumItem>,
+}
+
+impl Enum {
+    /// Create a GraphQL enum type
+    #[inline]
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            description: None,
+            items: Default::default(),
+        }
+    }
+
+    /// Set the description
+    #[inline]
+    #[must_use]
+    pub fn description(self, description: impl Into<String>) -> Self {
+        Self {
+            description: Some(description.into()),
+            ..self
+        }
+    }
+
+    /// Add an item
+    #[inline]
+    #[must_use]
+    pub fn item(mut self, item: impl Into<EnumItem>) -> Self {
+        let item = item.into();
+        self.items.insert(item.name.clone(), item);
+        self
+    }
+
+    /// Add items
+    #[must_use]
+    pub fn items(mut self, items: impl IntoIterator<Item = impl Into<EnumItem>>) -> Self {
+        for item in items {
+            let item = item.into();
+            self.items.insert(item.name.clone(), item);
+        }
+        self
+    }
+
+    /// Returns the type name
+    #[inline]
+    pub fn type_name(&self) -> &str {
+        &self.name
+    }
+
+    pub(crate) fn register(&self, registry: &mut Registry) {
+        registry.types.insert(
+            self.name.clone(),
+            MetaType::Enum {
+                name: self.name.clone(),
+                description: self.description.as_deref().map(super::intern_str),
+                enum_values: self
+                    .items
+                    .values()
+                    .map(|item| {
+                        let name = super::intern_str(&item.name);
+                        (
+                            name,
+                            MetaEnumValue {
+                                name,
+                                description: item.description.as_deref().map(super::intern_str),
+                                deprecation: item.deprecation.clone(),
+                                visible: None,
+                                inaccessible: false,
+                                tags: Default::default(),
+                            },
+                        )
+                    })
+                    .collect(),
+                visible: None,
+                inaccessible: false,
+                tags: Default::default(),
+                rust_typename: super::intern_str(&self.name),
+            },
+        );
+    }
+}
@@ -0,0 +1,309 @@
+use std::{
+    any::Any,
+    borrow::Cow,
+    fmt::{self, Debug, Formatter},
+    ops::Deref,
+};
+
+use futures_util::{future::BoxFuture, Future, FutureExt};
+use indexmap::IndexMap;
+
+use crate::{
+    dynamic::{InputValue, ObjectAccessor, TypeRef},
+    registry::{Deprecation, MetaField},
+    Context, Error, Result, Value,
+};
+
+/// A value returned by a field resolver.
+pub enum FieldValue<'a> {
+    /// A GraphQL value, used for scalars and enums, or as the parent value of
+    /// an object.
+    Value(Value),
+    /// An owned value, the resolvers of the object fields can downcast it.
+    OwnedAny(Box<dyn Any + Send + Sync>),
+    /// A borrowed value, the resolvers of the object fields can downcast it.
+    BorrowedAny(&'a (dyn Any + Send + Sync)),
+    /// A list of values
+    List(Vec<FieldValue<'a>>),
+    /// A value with the name of its concrete object type, required for fields
+    /// returning interfaces or unions.
+    WithType {
+        /// The value
+        value: Box<FieldValue<'a>>,
+        /// The name of the object type
+        ty: Cow<'static, str>,
+    },
+}
+
+impl<'a> Debug for FieldValue<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            FieldValue::Value(value) => write!(f, "{}", value),
+            FieldValue::OwnedAny(_) | FieldValue::BorrowedAny(_) => write!(f, "Any"),
+            FieldValue::List(values) => f.debug_list().entries(values).finish(),
+            FieldValue::WithType { value, ty } => write!(f, "{:?} on {}", value, ty),
+        }
+    }
+}
+
+impl<'a> FieldValue<'a> {
+    /// A null value
+    pub const NULL: FieldValue<'a> = FieldValue::Value(Value::Null);
+
+    /// Create a `FieldValue` from a GraphQL value.
+    #[inline]
+    pub fn value(value: impl Into<Value>) -> Self {
+        FieldValue::Value(value.into())
+    }
+
+    /// Create a `FieldValue` owning any value.
+    #[inline]
+    pub fn owned_any(obj: impl Any + Send + Sync) -> Self {
+        FieldValue::OwnedAny(Box::new(obj))
+    }
+
+    /// Create a `FieldValue` borrowing any value.
+    #[inline]
+    pub fn borrowed_any(obj: &'a (dyn Any + Send + Sync)) -> Self {
+        FieldValue::BorrowedAny(obj)
+    }
+
+    /// Create a list of `FieldValue`.
+    #[inline]
+    pub fn list<I, T>(values: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<FieldValue<'a>>,
+    {
+        FieldValue::List(values.into_iter().map(Into::into).collect())
+    }
+
+    /// Set the name of the concrete object type of this value.
+    #[inline]
+    #[must_use]
+    pub fn with_type(self, ty: impl Into<Cow<'static, str>>) -> Self {
+        FieldValue::WithType {
+            value: Box::new(self),
+            ty: ty.into(),
+        }
+    }
+
+    /// Returns `true` if this is a null value.
+    #[inline]
+    pub fn is_null(&self) -> bool {
+        matches!(self, FieldValue::Value(Value::Null))
+    }
+
+    /// Returns the GraphQL value if this is a [`FieldValue::Value`].
+    pub fn as_value(&self) -> Option<&Value> {
+        match self {
+            FieldValue::Value(value) => Some(value),
+            FieldValue::WithType { value, .. } => value.as_value(),
+            _ => None,
+        }
+    }
+
+    /// Like [`FieldValue::as_value`], but returns an error if this is not a
+    /// GraphQL value.
+    pub fn try_to_value(&self) -> Result<&Value> {
+        self.as_value()
+            .ok_or_else(|| Error::new(format!("internal: \"{:?}\" is not a value", self)))
+    }
+
+    /// Returns the items if this is a list.
+    pub fn as_list(&self) -> Option<&[FieldValue<'a>]> {
+        match self {
+            FieldValue::List(values) => Some(values),
+            FieldValue::WithType { value, .. } => value.as_list(),
+            _ => None,
+        }
+    }
+
+    /// Downcasts an owned or borrowed value to `T`.
+    pub fn downcast_ref<T: Any>(&self) -> Option<&T> {
+        match self {
+            FieldValue::OwnedAny(obj) => obj.downcast_ref::<T>(),
+            FieldValue::BorrowedAny(obj) => obj.downcast_ref::<T>(),
+            FieldValue::WithType { value, .. } => value.downcast_ref::<T>(),
+            _ => None,
+        }
+    }
+
+    /// Like [`FieldValue::downcast_ref`], but returns an error if the value
+    /// is not a `T`.
+    pub fn try_downcast_ref<T: Any>(&self) -> Result<&T> {
+        self.downcast_ref().ok_or_else(|| {
+            Error::new(format!(
+                "internal: \"{:?}\" is not of the expected type \"{}\"",
+                self,
+                std::any::type_name::<T>()
+            ))
+        })
+    }
+
+    /// Returns the value without its concrete type name.
+    pub(crate) fn without_type(&self) -> (&FieldValue<'a>, Option<&str>) {
+        match self {
+            FieldValue::WithType { value, ty } => (value.without_type().0, Some(ty)),
+            _ => (self, None),
+        }
+    }
+}
+
+impl<'a> From<Value> for FieldValue<'a> {
+    #[inline]
+    fn from(value: Value) -> Self {
+        FieldValue::Value(value)
+    }
+}
+
+impl<'a, T: Into<FieldValue<'a>>> From<Vec<T>> for FieldValue<'a> {
+    #[inline]
+    fn from(values: Vec<T>) -> Self {
+        FieldValue::list(values)
+    }
+}
+
+/// The future returned by a field resolver.
+pub enum FieldFuture<'a> {
+    /// A future resolving the value
+    Future(BoxFuture<'a, Result<Option<FieldValue<'a>>>>),
+    /// An already resolved value
+    Value(Option<FieldValue<'a>>),
+}
+
+impl<'a> FieldFuture<'a> {
+    /// Create a `FieldFuture` from a future.
+    pub fn new<Fut>(future: Fut) -> Self
+    where
+        Fut: Future<Output = Result<Option<FieldValue<'a>>>> + Send + 'a,
+    {
+        FieldFuture::Future(future.boxed())
+    }
+
+    /// Create a `FieldFuture` from an already resolved value.
+    #[inline]
+    pub fn from_value(value: Option<FieldValue<'a>>) -> Self {
+        FieldFuture::Value(value)
+    }
+}
+
+/// The context of a field resolver.
+pub struct ResolverContext<'a> {
+    /// The GraphQL context
+    pub ctx: &'a Context<'a>,
+    /// The arguments of the field, including the default values of the ones
+    /// which were not provided.
+    pub args: ObjectAccessor<'a>,
+    /// The value returned by the resolver of the parent field.
+    pub parent_value: &'a FieldValue<'a>,
+}
+
+impl<'a> Deref for ResolverContext<'a> {
+    type Target = Context<'a>;
+
+    fn deref(&self) -> &Self::Target {
+        self.ctx
+    }
+}
+
+pub(crate) type BoxResolverFn =
+    Box<dyn for<'a> Fn(ResolverContext<'a>) -> FieldFuture<'a> + Send + Sync>;
+
+/// A GraphQL field of an object
+pub struct Field {
+    pub(crate) name: String,
+    pub(crate) description: Option<String>,
+    pub(crate) arguments: IndexMap<String, InputValue>,
+    pub(crate) ty: TypeRef,
+    pub(crate) resolver_fn: BoxResolverFn,
+    pub(crate) deprecation: Deprecation,
+}
+
+impl Debug for Field {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Field")
+            .field("name", &self.name)
+            .field("description", &self.description)
+            .field("arguments", &self.arguments)
+            .field("ty", &self.ty)
+            .field("deprecation", &self.deprecation)
+            .finish()
+    }
+}
+
+impl Field {
+    /// Create a GraphQL field
+    pub fn new<N, F>(name: N, ty: TypeRef, resolver_fn: F) -> Self
+    where
+        N: Into<String>,
+        F: for<'a> Fn(ResolverContext<'a>) -> FieldFuture<'a> + Send + Sync + 'static,
+    {
+        Self {
+            name: name.into(),
+            description: None,
+            arguments: Default::default(),
+            ty,
+            resolver_fn: Box::new(resolver_fn),
+            deprecation: Deprecation::NoDeprecated,
+        }
+    }
+
+    /// Set the description
+    #[inline]
+    #[must_use]
+    pub fn description(self, description: impl Into<String>) -> Self {
+        Self {
+            description: Some(description.into()),
+            ..self
+        }
+    }
+
+    /// Add an argument to the field
+    #[inline]
+    #[must_use]
+    pub fn argument(mut self, input_value: InputValue) -> Self {
+        self.arguments.insert(input_value.name.clone(), input_value);
+        self
+    }
+
+    /// Mark the field as deprecated, with an optional reason
+    #[inline]
+    #[must_use]
+    pub fn deprecation(self, reason: Option<&str>) -> Self {
+        Self {
+            deprecation: Deprecation::Deprecated {
+                reason: reason.map(super::intern_str),
+            },
+            ..self
+        }
+    }
+
+    pub(crate) fn to_meta_field(&self) -> MetaField {
+        MetaField {
+            name: self.name.clone(),
+            description: self.description.as_deref().map(super::intern_str),
+            args: self
+                .arguments
+                .values()
+                .map(|arg| (arg.name.clone(), arg.to_meta_input_value()))
+                .collect(),
+            ty: self.ty.to_string(),
+            deprecation: self.deprecation.clone(),
+            cache_control: Default::default(),
+            external: false,
+            requires: None,
+            provides: None,
+            visible: None,
+            shareable: false,
+            inaccessible: false,
+            tags: Default::default(),
+            override_from: None,
+            cost: None,
+            list_size: None,
+            auth: None,
+            redact: None,
+            compute_complexity: None,
+        }
+    }
+}
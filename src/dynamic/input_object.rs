@@ -0,0 +1,70 @@
+use indexmap::IndexMap;
+
+use crate::{
+    dynamic::InputValue,
+    registry::{MetaType, Registry},
+};
+
+/// A GraphQL input object type
+#[derive(Debug)]
+pub struct InputObject {
+    pub(crate) name: String,
+    pub(crate) description: Option<String>,
+    pub(crate) fields: IndexMap<String, InputValue>,
+}
+
+impl InputObject {
+    /// Create a GraphQL input object type
+    #[inline]
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            description: None,
+            fields: Default::default(),
+        }
+    }
+
+    /// Set the description
+    #[inline]
+    #[must_use]
+    pub fn description(self, description: impl Into<String>) -> Self {
+        Self {
+            description: Some(description.into()),
+            ..self
+        }
+    }
+
+    /// Add a field
+    #[inline]
+    #[must_use]
+    pub fn field(mut self, field: InputValue) -> Self {
+        self.fields.insert(field.name.clone(), field);
+        self
+    }
+
+    /// Returns the type name
+    #[inline]
+    pub fn type_name(&self) -> &str {
+        &self.name
+    }
+
+    pub(crate) fn register(&self, registry: &mut Registry) {
+        registry.types.insert(
+            self.name.clone(),
+            MetaType::InputObject {
+                name: self.name.clone(),
+                description: self.description.as_deref().map(super::intern_str),
+                input_fields: self
+                    .fields
+                    .values()
+                    .map(|field| (field.name.clone(), field.to_meta_input_value()))
+                    .collect(),
+                visible: None,
+                inaccessible: false,
+                tags: Default::default(),
+                rust_typename: super::intern_str(&self.name),
+                oneof: false,
+            },
+        );
+    }
+}
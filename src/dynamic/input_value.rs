@@ -0,0 +1,56 @@
+use crate::{dynamic::TypeRef, registry::MetaInputValue, Value};
+
+/// A field argument, or a field of an input object.
+#[derive(Debug, Clone)]
+pub struct InputValue {
+    pub(crate) name: String,
+    pub(crate) description: Option<String>,
+    pub(crate) ty: TypeRef,
+    pub(crate) default_value: Option<Value>,
+}
+
+impl InputValue {
+    /// Create a GraphQL input value type
+    #[inline]
+    pub fn new(name: impl Into<String>, ty: TypeRef) -> Self {
+        Self {
+            name: name.into(),
+            description: None,
+            ty,
+            default_value: None,
+        }
+    }
+
+    /// Set the description
+    #[inline]
+    #[must_use]
+    pub fn description(self, description: impl Into<String>) -> Self {
+        Self {
+            description: Some(description.into()),
+            ..self
+        }
+    }
+
+    /// Set the default value
+    #[inline]
+    #[must_use]
+    pub fn default_value(self, value: impl Into<Value>) -> Self {
+        Self {
+            default_value: Some(value.into()),
+            ..self
+        }
+    }
+
+    pub(crate) fn to_meta_input_value(&self) -> MetaInputValue {
+        MetaInputValue {
+            name: super::intern_str(&self.name),
+            description: self.description.as_deref().map(super::intern_str),
+            ty: self.ty.to_string(),
+            default_value: self.default_value.as_ref().map(ToString::to_string),
+            visible: None,
+            inaccessible: false,
+            tags: Default::default(),
+            is_secret: false,
+        }
+    }
+}
@@ -0,0 +1,158 @@
+use indexmap::IndexMap;
+
+use crate::{
+    dynamic::{InputValue, TypeRef},
+    registry::{Deprecation, MetaField, MetaType, Registry},
+};
+
+/// A GraphQL field of an interface
+#[derive(Debug)]
+pub struct InterfaceField {
+    pub(crate) name: String,
+    pub(crate) description: Option<String>,
+    pub(crate) arguments: IndexMap<String, InputValue>,
+    pub(crate) ty: TypeRef,
+    pub(crate) deprecation: Deprecation,
+}
+
+impl InterfaceField {
+    /// Create a GraphQL interface field
+    #[inline]
+    pub fn new(name: impl Into<String>, ty: TypeRef) -> Self {
+        Self {
+            name: name.into(),
+            description: None,
+            arguments: Default::default(),
+            ty,
+            deprecation: Deprecation::NoDeprecated,
+        }
+    }
+
+    /// Set the description
+    #[inline]
+    #[must_use]
+    pub fn description(self, description: impl Into<String>) -> Self {
+        Self {
+            description: Some(description.into()),
+            ..self
+        }
+    }
+
+    /// Add an argument to the field
+    #[inline]
+    #[must_use]
+    pub fn argument(mut self, input_value: InputValue) -> Self {
+        self.arguments.insert(input_value.name.clone(), input_value);
+        self
+    }
+
+    /// Mark the field as deprecated, with an optional reason
+    #[inline]
+    #[must_use]
+    pub fn deprecation(self, reason: Option<&str>) -> Self {
+        Self {
+            deprecation: Deprecation::Deprecated {
+                reason: reason.map(super::intern_str),
+            },
+            ..self
+        }
+    }
+
+    fn to_meta_field(&self) -> MetaField {
+        MetaField {
+            name: self.name.clone(),
+            description: self.description.as_deref().map(super::intern_str),
+            args: self
+                .arguments
+                .values()
+                .map(|arg| (arg.name.clone(), arg.to_meta_input_value()))
+                .collect(),
+            ty: self.ty.to_string(),
+            deprecation: self.deprecation.clone(),
+            cache_control: Default::default(),
+            external: false,
+            requires: None,
+            provides: None,
+            visible: None,
+            shareable: false,
+            inaccessible: false,
+            tags: Default::default(),
+            override_from: None,
+            cost: None,
+            list_size: None,
+            auth: None,
+            redact: None,
+            compute_complexity: None,
+        }
+    }
+}
+
+/// A GraphQL interface type
+///
+/// The fields are resolved by the objects implementing the interface, a field
+/// returning an interface must give the concrete type of its value with
+/// [`FieldValue::with_type`](crate::dynamic::FieldValue::with_type).
+#[derive(Debug)]
+pub struct Interface {
+    pub(crate) name: String,
+    pub(crate) description: Option<String>,
+    pub(crate) fields: IndexMap<String, InterfaceField>,
+}
+
+impl Interface {
+    /// Create a GraphQL interface type
+    #[inline]
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            description: None,
+            fields: Default::default(),
+        }
+    }
+
+    /// Set the description
+    #[inline]
+    #[must_use]
+    pub fn description(self, description: impl Into<String>) -> Self {
+        Self {
+            description: Some(description.into()),
+            ..self
+        }
+    }
+
+    /// Add a field to the interface
+    #[inline]
+    #[must_use]
+    pub fn field(mut self, field: InterfaceField) -> Self {
+        self.fields.insert(field.name.clone(), field);
+        self
+    }
+
+    /// Returns the type name
+    #[inline]
+    pub fn type_name(&self) -> &str {
+        &self.name
+    }
+
+    pub(crate) fn register(&self, registry: &mut Registry) {
+        registry.types.insert(
+            self.name.clone(),
+            MetaType::Interface {
+                name: self.name.clone(),
+                description: self.description.as_deref().map(super::intern_str),
+                fields: self
+                    .fields
+                    .values()
+                    .map(|field| (field.name.clone(), field.to_meta_field()))
+                    .collect(),
+                possible_types: Default::default(),
+                extends: false,
+                keys: None,
+                visible: None,
+                inaccessible: false,
+                tags: Default::default(),
+                rust_typename: super::intern_str(&self.name),
+            },
+        );
+    }
+}
@@ -0,0 +1,98 @@
+//! Dynamic schema support.
+//!
+//! A dynamic schema is built at runtime from type definitions and resolver
+//! closures, for APIs which cannot be described with the derive macros, such
+//! as types generated from database metadata.
+//!
+//! The types are registered in a [`Registry`](crate::registry::Registry) like
+//! the ones of a static [`Schema`](crate::Schema), and the requests go through
+//! the same extensions, validation and [`Request`](crate::Request) /
+//! [`Response`](crate::Response) types.
+//!
+//! Subscriptions are not supported by dynamic schemas.
+//!
+//! A remote GraphQL service can be part of a dynamic schema with
+//! [`RemoteSchema`], which forwards the selections on its fields to the
+//! service.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use async_graphql::{dynamic::*, value, Value};
+//!
+//! # tokio::runtime::Runtime::new().unwrap().block_on(async move {
+//! let query = Object::new("Query").field(Field::new(
+//!     "add",
+//!     TypeRef::named_nn(TypeRef::INT),
+//!     |ctx| {
+//!         FieldFuture::new(async move {
+//!             let a = ctx.args.try_get("a")?.i64()?;
+//!             let b = ctx.args.try_get("b")?.i64()?;
+//!             Ok(Some(FieldValue::value(a + b)))
+//!         })
+//!     },
+//! )
+//! .argument(InputValue::new("a", TypeRef::named_nn(TypeRef::INT)))
+//! .argument(InputValue::new("b", TypeRef::named_nn(TypeRef::INT))));
+//!
+//! let schema = Schema::build(query.type_name(), None)
+//!     .register(query)
+//!     .finish()
+//!     .unwrap();
+//!
+//! assert_eq!(
+//!     schema.execute("{ add(a: 10, b: 20) }").await.into_result().unwrap().data,
+//!     value!({ "add": 30 })
+//! );
+//! # });
+//! ```
+
+use std::{collections::HashSet, sync::Mutex};
+
+use once_cell::sync::Lazy;
+
+mod r#enum;
+mod field;
+mod input_object;
+mod input_value;
+mod interface;
+mod object;
+mod remote;
+mod resolve;
+mod scalar;
+mod schema;
+mod r#type;
+mod type_ref;
+mod union;
+mod value_accessor;
+
+pub use field::{Field, FieldFuture, FieldValue, ResolverContext};
+pub use input_object::InputObject;
+pub use input_value::InputValue;
+pub use interface::{Interface, InterfaceField};
+pub use object::Object;
+pub use r#enum::{Enum, EnumItem};
+pub use r#type::Type;
+pub use remote::RemoteSchema;
+pub use scalar::Scalar;
+pub use schema::{Schema, SchemaBuilder, SchemaError};
+pub use type_ref::TypeRef;
+pub use union::Union;
+pub use value_accessor::{ListAccessor, ObjectAccessor, ValueAccessor};
+
+/// The registry stores names and descriptions as `&'static str`, they are
+/// leaked the first time they are seen so that building or importing the same
+/// schema again does not leak more memory.
+pub(crate) fn intern_str(s: &str) -> &'static str {
+    static STRINGS: Lazy<Mutex<HashSet<&'static str>>> = Lazy::new(Default::default);
+
+    let mut strings = STRINGS.lock().unwrap();
+    match strings.get(s) {
+        Some(s) => s,
+        None => {
+            let s = Box::leak(s.to_string().into_boxed_str());
+            strings.insert(s);
+            s
+        }
+    }
+}
@@ -0,0 +1,88 @@
+use indexmap::{IndexMap, IndexSet};
+
+use crate::{
+    dynamic::Field,
+    registry::{MetaType, Registry},
+};
+
+/// A GraphQL object type
+#[derive(Debug)]
+pub struct Object {
+    pub(crate) name: String,
+    pub(crate) description: Option<String>,
+    pub(crate) fields: IndexMap<String, Field>,
+    pub(crate) implements: IndexSet<String>,
+}
+
+impl Object {
+    /// Create a GraphQL object type
+    #[inline]
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            description: None,
+            fields: Default::default(),
+            implements: Default::default(),
+        }
+    }
+
+    /// Set the description
+    #[inline]
+    #[must_use]
+    pub fn description(self, description: impl Into<String>) -> Self {
+        Self {
+            description: Some(description.into()),
+            ..self
+        }
+    }
+
+    /// Add a field to the object
+    #[inline]
+    #[must_use]
+    pub fn field(mut self, field: Field) -> Self {
+        self.fields.insert(field.name.clone(), field);
+        self
+    }
+
+    /// Add an interface implemented by the object
+    #[inline]
+    #[must_use]
+    pub fn implement(mut self, interface: impl Into<String>) -> Self {
+        self.implements.insert(interface.into());
+        self
+    }
+
+    /// Returns the type name
+    #[inline]
+    pub fn type_name(&self) -> &str {
+        &self.name
+    }
+
+    pub(crate) fn register(&self, registry: &mut Registry) {
+        let rust_typename = super::intern_str(&self.name);
+        registry.types.insert(
+            self.name.clone(),
+            MetaType::Object {
+                name: self.name.clone(),
+                description: self.description.as_deref().map(super::intern_str),
+                fields: self
+                    .fields
+                    .values()
+                    .map(|field| (field.name.clone(), field.to_meta_field()))
+                    .collect(),
+                cache_control: Default::default(),
+                extends: false,
+                keys: None,
+                visible: None,
+                shareable: false,
+                inaccessible: false,
+                tags: Default::default(),
+                is_subscription: false,
+                rust_typename,
+            },
+        );
+        for interface in &self.implements {
+            registry.add_implements(&self.name, interface);
+        }
+    }
+}
@@ -0,0 +1,574 @@
+use std::{collections::HashSet, future::Future, sync::Arc};
+
+use futures_util::{future::BoxFuture, FutureExt};
+use indexmap::IndexMap;
+
+use crate::{
+    dynamic::{
+        Enum, EnumItem, Field, FieldFuture, FieldValue, InputObject, InputValue, Interface,
+        InterfaceField, Object, Scalar, SchemaError, Type, TypeRef, Union,
+    },
+    parser::{
+        self,
+        types::{
+            BaseType, ConstDirective, FieldDefinition, InputValueDefinition, OperationType,
+            Selection, ServiceDocument, TypeDefinition, TypeKind, TypeSystemDefinition,
+        },
+    },
+    registry::Registry,
+    Context, Error, PathSegment, Positioned, Request, Response, Result, ServerError, Value,
+    Variables,
+};
+
+type ExecutorFn = Box<dyn Fn(Request) -> BoxFuture<'static, Response> + Send + Sync>;
+
+struct RemoteInner {
+    executor: ExecutorFn,
+    /// The interfaces and unions of the remote schema.
+    abstract_types: HashSet<String>,
+}
+
+/// A remote GraphQL service exposed by a dynamic schema.
+///
+/// The remote schema is described by its SDL, and the requests are sent by
+/// an executor callback, for example with an HTTP client. Its root fields
+/// are merged into the root types of the schema with
+/// [`SchemaBuilder::merge_remote`](crate::dynamic::SchemaBuilder::merge_remote),
+/// or its query root is mounted under a field with
+/// [`SchemaBuilder::mount_remote`](crate::dynamic::SchemaBuilder::mount_remote).
+///
+/// Each remote field executes a request with its sub-selection and the
+/// variables it uses, the errors of the remote response are added to the
+/// response of the schema. Subscriptions are not forwarded.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::{dynamic::*, value, EmptyMutation, EmptySubscription, Object};
+///
+/// struct Inventory;
+///
+/// #[Object]
+/// impl Inventory {
+///     async fn stock(&self, product: String) -> i32 {
+///         product.len() as i32
+///     }
+/// }
+///
+/// let inventory = async_graphql::Schema::new(Inventory, EmptyMutation, EmptySubscription);
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async move {
+/// let remote = RemoteSchema::new(&inventory.sdl(), move |request| {
+///     let inventory = inventory.clone();
+///     async move { inventory.execute(request).await }
+/// })
+/// .unwrap();
+///
+/// let query = Object::new("Query").field(Field::new(
+///     "version",
+///     TypeRef::named_nn(TypeRef::INT),
+///     |_| FieldFuture::new(async move { Ok(Some(FieldValue::value(1))) }),
+/// ));
+/// let schema = Schema::build("Query", None)
+///     .register(query)
+///     .merge_remote(remote)
+///     .finish()
+///     .unwrap();
+///
+/// assert_eq!(
+///     schema
+///         .execute(r#"{ version stock(product: "abc") }"#)
+///         .await
+///         .into_result()
+///         .unwrap()
+///         .data,
+///     value!({ "version": 1, "stock": 3 })
+/// );
+/// # });
+/// ```
+pub struct RemoteSchema {
+    inner: Arc<RemoteInner>,
+    doc: ServiceDocument,
+    query_type: String,
+    mutation_type: Option<String>,
+    subscription_type: Option<String>,
+}
+
+/// The types of a remote schema, and the fields to add to the root types of
+/// the schema.
+pub(crate) struct RemoteTypes {
+    pub(crate) types: Vec<Type>,
+    pub(crate) query_fields: Vec<Field>,
+    pub(crate) mutation_fields: Vec<Field>,
+}
+
+impl RemoteSchema {
+    /// Create a remote schema from its SDL, and a callback executing the
+    /// requests.
+    pub fn new<F, Fut>(sdl: &str, executor: F) -> Result<Self, SchemaError>
+    where
+        F: Fn(Request) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Response> + Send + 'static,
+    {
+        let doc = parser::parse_schema(sdl).map_err(|err| SchemaError(err.to_string()))?;
+        let registry = Registry::from_service_document(&doc)?;
+        Ok(Self {
+            inner: Arc::new(RemoteInner {
+                executor: Box::new(move |request| executor(request).boxed()),
+                abstract_types: registry
+                    .types
+                    .values()
+                    .filter(|ty| ty.is_abstract())
+                    .map(|ty| ty.name().to_string())
+                    .collect(),
+            }),
+            doc,
+            query_type: registry.query_type,
+            mutation_type: registry.mutation_type,
+            subscription_type: registry.subscription_type,
+        })
+    }
+
+    /// Converts the remote schema to the types of a dynamic schema.
+    ///
+    /// When the query root is mounted, it is registered as an object type
+    /// named `mount.1`, which is returned by the `mount.0` field, otherwise
+    /// the fields of the root types are returned to be merged.
+    pub(crate) fn into_types(self, mount: Option<(&str, &str)>) -> RemoteTypes {
+        let mut definitions: IndexMap<&str, Vec<&TypeDefinition>> = IndexMap::new();
+        for definition in &self.doc.definitions {
+            if let TypeSystemDefinition::Type(definition) = definition {
+                definitions
+                    .entry(definition.node.name.node.as_str())
+                    .or_default()
+                    .push(&definition.node);
+            }
+        }
+
+        let mut remote_types = RemoteTypes {
+            types: Vec::new(),
+            query_fields: Vec::new(),
+            mutation_fields: Vec::new(),
+        };
+        for (name, definitions) in definitions {
+            if is_builtin_type(name) || Some(name) == self.subscription_type.as_deref() {
+                continue;
+            }
+
+            if name == self.query_type {
+                match mount {
+                    Some((field_name, type_name)) => {
+                        let object = self.object(type_name, &definitions);
+                        remote_types.types.push(Type::Object(object));
+                        remote_types.query_fields.push(mount_field(
+                            self.inner.clone(),
+                            field_name,
+                            type_name,
+                            &self.query_type,
+                        ));
+                    }
+                    None => {
+                        remote_types
+                            .query_fields
+                            .extend(object_fields(&definitions).map(|field| {
+                                forward_field(self.inner.clone(), OperationType::Query, field)
+                            }));
+                    }
+                }
+                continue;
+            }
+            if Some(name) == self.mutation_type.as_deref() {
+                if mount.is_none() {
+                    remote_types
+                        .mutation_fields
+                        .extend(object_fields(&definitions).map(|field| {
+                            forward_field(self.inner.clone(), OperationType::Mutation, field)
+                        }));
+                }
+                continue;
+            }
+
+            let description = definitions
+                .iter()
+                .find_map(|definition| definition.description.as_ref())
+                .map(|description| description.node.as_str());
+            let ty = match &definitions[0].kind {
+                TypeKind::Scalar => {
+                    let mut scalar = Scalar::new(name);
+                    if let Some(url) = definitions
+                        .iter()
+                        .find_map(|definition| directive(&definition.directives, "specifiedBy"))
+                        .and_then(|directive| directive.get_argument("url"))
+                    {
+                        if let Value::String(url) = &url.node {
+                            scalar = scalar.specified_by_url(url);
+                        }
+                    }
+                    Type::Scalar(with_description!(scalar, description))
+                }
+                TypeKind::Object(_) => Type::Object(self.object(name, &definitions)),
+                TypeKind::Interface(_) => {
+                    let mut interface = Interface::new(name);
+                    for definition in &definitions {
+                        if let TypeKind::Interface(ty) = &definition.kind {
+                            for field in &ty.fields {
+                                interface = interface.field(interface_field(&field.node));
+                            }
+                        }
+                    }
+                    Type::Interface(with_description!(interface, description))
+                }
+                TypeKind::Union(_) => {
+                    let mut union = Union::new(name);
+                    for definition in &definitions {
+                        if let TypeKind::Union(ty) = &definition.kind {
+                            for member in &ty.members {
+                                union = union.possible_type(member.node.as_str());
+                            }
+                        }
+                    }
+                    Type::Union(with_description!(union, description))
+                }
+                TypeKind::Enum(_) => {
+                    let mut enum_type = Enum::new(name);
+                    for definition in &definitions {
+                        if let TypeKind::Enum(ty) = &definition.kind {
+                            for value in &ty.values {
+                                let mut item = EnumItem::new(value.node.value.node.as_str());
+                                if let Some(description) = &value.node.description {
+                                    item = item.description(&description.node);
+                                }
+                                if let Some(reason) = deprecation(&value.node.directives) {
+                                    item = item.deprecation(reason);
+                                }
+                                enum_type = enum_type.item(item);
+                            }
+                        }
+                    }
+                    Type::Enum(with_description!(enum_type, description))
+                }
+                TypeKind::InputObject(_) => {
+                    let mut input_object = InputObject::new(name);
+                    for definition in &definitions {
+                        if let TypeKind::InputObject(ty) = &definition.kind {
+                            for field in &ty.fields {
+                                input_object = input_object.field(input_value(&field.node));
+                            }
+                        }
+                    }
+                    Type::InputObject(with_description!(input_object, description))
+                }
+            };
+            remote_types.types.push(ty);
+        }
+        remote_types
+    }
+
+    fn object(&self, name: &str, definitions: &[&TypeDefinition]) -> Object {
+        let mut object = Object::new(name);
+        if let Some(description) = definitions
+            .iter()
+            .find_map(|definition| definition.description.as_ref())
+        {
+            object = object.description(&description.node);
+        }
+        for definition in definitions {
+            if let TypeKind::Object(ty) = &definition.kind {
+                for interface in &ty.implements {
+                    object = object.implement(interface.node.as_str());
+                }
+            }
+        }
+        for field in object_fields(definitions) {
+            object = object.field(remote_field(self.inner.clone(), field));
+        }
+        object
+    }
+}
+
+macro_rules! with_description {
+    ($ty:expr, $description:expr) => {
+        match $description {
+            Some(description) => $ty.description(description),
+            None => $ty,
+        }
+    };
+}
+use with_description;
+
+fn is_builtin_type(name: &str) -> bool {
+    name.starts_with("__") || matches!(name, "Int" | "Float" | "String" | "Boolean" | "ID")
+}
+
+fn object_fields<'a>(
+    definitions: &'a [&'a TypeDefinition],
+) -> impl Iterator<Item = &'a FieldDefinition> {
+    definitions
+        .iter()
+        .filter_map(|definition| match &definition.kind {
+            TypeKind::Object(ty) => Some(&ty.fields),
+            _ => None,
+        })
+        .flatten()
+        .map(|field| &field.node)
+}
+
+fn directive<'a>(
+    directives: &'a [Positioned<ConstDirective>],
+    name: &str,
+) -> Option<&'a ConstDirective> {
+    directives
+        .iter()
+        .map(|directive| &directive.node)
+        .find(|directive| directive.name.node == name)
+}
+
+fn deprecation(directives: &[Positioned<ConstDirective>]) -> Option<Option<&str>> {
+    directive(directives, "deprecated").map(|directive| {
+        match directive.get_argument("reason").map(|reason| &reason.node) {
+            Some(Value::String(reason)) => Some(reason.as_str()),
+            _ => None,
+        }
+    })
+}
+
+fn type_ref(ty: &parser::types::Type) -> TypeRef {
+    let type_ref = match &ty.base {
+        BaseType::Named(name) => TypeRef::Named(name.to_string()),
+        BaseType::List(ty) => TypeRef::List(Box::new(type_ref(ty))),
+    };
+    if ty.nullable {
+        type_ref
+    } else {
+        TypeRef::NonNull(Box::new(type_ref))
+    }
+}
+
+fn input_value(definition: &InputValueDefinition) -> InputValue {
+    let mut input_value =
+        InputValue::new(definition.name.node.as_str(), type_ref(&definition.ty.node));
+    if let Some(description) = &definition.description {
+        input_value = input_value.description(&description.node);
+    }
+    if let Some(default_value) = &definition.default_value {
+        input_value = input_value.default_value(default_value.node.clone());
+    }
+    input_value
+}
+
+fn interface_field(definition: &FieldDefinition) -> InterfaceField {
+    let mut field =
+        InterfaceField::new(definition.name.node.as_str(), type_ref(&definition.ty.node));
+    if let Some(description) = &definition.description {
+        field = field.description(&description.node);
+    }
+    for argument in &definition.arguments {
+        field = field.argument(input_value(&argument.node));
+    }
+    if let Some(reason) = deprecation(&definition.directives) {
+        field = field.deprecation(reason);
+    }
+    field
+}
+
+fn field(
+    definition: &FieldDefinition,
+    resolver_fn: impl for<'a> Fn(crate::dynamic::ResolverContext<'a>) -> FieldFuture<'a>
+        + Send
+        + Sync
+        + 'static,
+) -> Field {
+    let mut field = Field::new(
+        definition.name.node.as_str(),
+        type_ref(&definition.ty.node),
+        resolver_fn,
+    );
+    if let Some(description) = &definition.description {
+        field = field.description(&description.node);
+    }
+    for argument in &definition.arguments {
+        field = field.argument(input_value(&argument.node));
+    }
+    if let Some(reason) = deprecation(&definition.directives) {
+        field = field.deprecation(reason);
+    }
+    field
+}
+
+/// A field of a remote object, resolved from the response of the remote
+/// service.
+fn remote_field(remote: Arc<RemoteInner>, definition: &FieldDefinition) -> Field {
+    let ty = type_ref(&definition.ty.node);
+    field(definition, move |ctx| {
+        let value = match ctx.parent_value.as_value() {
+            Some(Value::Object(obj)) => obj.get(ctx.item.node.response_key().node.as_str()),
+            _ => None,
+        };
+        FieldFuture::from_value(value.map(|value| remote.field_value(&ty, value.clone())))
+    })
+}
+
+/// A root field of the remote schema, executed by the remote service.
+fn forward_field(
+    remote: Arc<RemoteInner>,
+    operation_type: OperationType,
+    definition: &FieldDefinition,
+) -> Field {
+    let ty = type_ref(&definition.ty.node);
+    field(definition, move |ctx| {
+        let remote = remote.clone();
+        let ty = ty.clone();
+        FieldFuture::new(async move {
+            let registry = &ctx.schema_env.registry;
+            let parent_type = match operation_type {
+                OperationType::Mutation => registry.mutation_type.as_deref(),
+                _ => Some(registry.query_type.as_str()),
+            }
+            .unwrap_or_default();
+            let selections = [Positioned::new(
+                Selection::Field(ctx.item.clone()),
+                ctx.item.pos,
+            )];
+            let mut path = current_path(&ctx);
+            path.pop();
+
+            let (data, errors) = remote
+                .execute(&ctx, operation_type, parent_type, &selections, None, &path)
+                .await?;
+            let value = match data {
+                Value::Object(mut obj) => obj.remove(ctx.item.node.response_key().node.as_str()),
+                _ => None,
+            };
+            Ok(report_errors(&ctx, value, errors)?.map(|value| remote.field_value(&ty, value)))
+        })
+    })
+}
+
+/// The field returning the query root of the remote schema.
+fn mount_field(
+    remote: Arc<RemoteInner>,
+    field_name: &str,
+    type_name: &str,
+    remote_type_name: &str,
+) -> Field {
+    let type_name = type_name.to_string();
+    let remote_type_name = remote_type_name.to_string();
+    Field::new(field_name, TypeRef::named(&type_name), move |ctx| {
+        let remote = remote.clone();
+        let type_name = type_name.clone();
+        let remote_type_name = remote_type_name.clone();
+        FieldFuture::new(async move {
+            let path = current_path(&ctx);
+            let (data, errors) = remote
+                .execute(
+                    &ctx,
+                    OperationType::Query,
+                    &type_name,
+                    &ctx.item.node.selection_set.node.items,
+                    Some((&type_name, &remote_type_name)),
+                    &path,
+                )
+                .await?;
+            Ok(report_errors(&ctx, Some(data), errors)?.map(FieldValue::Value))
+        })
+    })
+}
+
+fn current_path(ctx: &Context<'_>) -> Vec<PathSegment> {
+    ctx.set_error_path(ServerError::new("", None)).path
+}
+
+/// Adds the errors of a remote response to the response, the first one is
+/// returned if the value is `null`.
+fn report_errors(
+    ctx: &Context<'_>,
+    value: Option<Value>,
+    errors: Vec<ServerError>,
+) -> Result<Option<Value>> {
+    let value = value.filter(|value| *value != Value::Null);
+    let mut errors = errors.into_iter();
+    if value.is_none() {
+        if let Some(err) = errors.next() {
+            errors.for_each(|err| ctx.add_error(err));
+            return Err(Error {
+                message: err.message,
+                source: None,
+                extensions: err.extensions,
+            });
+        }
+    }
+    errors.for_each(|err| ctx.add_error(err));
+    Ok(value)
+}
+
+impl RemoteInner {
+    /// Executes selections on the remote service, the paths of the errors
+    /// are prefixed with the path of the parent of the selections.
+    async fn execute(
+        &self,
+        ctx: &Context<'_>,
+        operation_type: OperationType,
+        parent_type: &str,
+        selections: &[Positioned<Selection>],
+        renamed_type: Option<(&str, &str)>,
+        path: &[PathSegment],
+    ) -> Result<(Value, Vec<ServerError>)> {
+        let operation = ctx
+            .schema_env
+            .registry
+            .stringify_remote_operation(
+                operation_type,
+                parent_type,
+                selections,
+                &ctx.query_env.operation.node.variable_definitions,
+                &ctx.query_env.fragments,
+                renamed_type,
+            )
+            .map_err(|_| Error::new("internal: failed to stringify the remote operation"))?;
+
+        let mut variables = Variables::default();
+        for name in operation.variables {
+            if let Some(value) = ctx.query_env.variables.get(&name) {
+                variables.insert(name, value.clone());
+            }
+        }
+        let response = (self.executor)(Request::new(operation.query).variables(variables)).await;
+
+        let errors = response
+            .errors
+            .into_iter()
+            .map(|err| ServerError {
+                locations: vec![ctx.item.pos],
+                path: path.iter().cloned().chain(err.path).collect(),
+                ..err
+            })
+            .collect();
+        Ok((response.data, errors))
+    }
+
+    /// Converts a value of the remote response, setting the concrete type of
+    /// the values of abstract types from their `__typename`.
+    fn field_value(&self, ty: &TypeRef, value: Value) -> FieldValue<'static> {
+        match (ty, value) {
+            (TypeRef::NonNull(ty), value) => self.field_value(ty, value),
+            (TypeRef::List(ty), Value::List(items)) => FieldValue::list(
+                items
+                    .into_iter()
+                    .map(|item| self.field_value(ty, item))
+                    .collect::<Vec<_>>(),
+            ),
+            (TypeRef::Named(name), Value::Object(obj)) if self.abstract_types.contains(name) => {
+                let typename = match obj.get("__typename") {
+                    Some(Value::String(typename)) => Some(typename.clone()),
+                    _ => None,
+                };
+                let value = FieldValue::value(Value::Object(obj));
+                match typename {
+                    Some(typename) => value.with_type(typename),
+                    None => value,
+                }
+            }
+            (_, value) => FieldValue::value(value),
+        }
+    }
+}
@@ -0,0 +1,412 @@
+use std::{future::Future, pin::Pin};
+
+use futures_util::{future::BoxFuture, FutureExt};
+use indexmap::IndexMap;
+
+use crate::{
+    dynamic::{
+        field::FieldValue, schema::SchemaInner, Field, FieldFuture, Object, ObjectAccessor,
+        ResolverContext, Type, TypeRef,
+    },
+    extensions::ResolveInfo,
+    parser::types::Selection,
+    resolver_utils::insert_value,
+    types::resolve_introspection_field,
+    Context, ContextSelectionSet, IntrospectionMode, Name, Pos, ServerError, ServerResult, Value,
+};
+
+type BoxFieldFuture<'a> = Pin<Box<dyn Future<Output = ServerResult<(Name, Value)>> + Send + 'a>>;
+
+/// Resolves the selection set of an object, executing the fields concurrently
+/// or serially for the mutation root.
+pub(crate) async fn resolve_container<'a>(
+    schema: &'a SchemaInner,
+    object: &'a Object,
+    ctx: &ContextSelectionSet<'a>,
+    parent_value: &'a FieldValue<'a>,
+    serial: bool,
+) -> ServerResult<Value> {
+    let mut fields = Vec::new();
+    collect_fields(&mut fields, schema, object, ctx, parent_value)?;
+
+    let res = if serial {
+        let mut results = Vec::with_capacity(fields.len());
+        for field in fields {
+            results.push(field.await?);
+        }
+        results
+    } else {
+        futures_util::future::try_join_all(fields).await?
+    };
+
+    let mut map = IndexMap::new();
+    for (name, value) in res {
+        insert_value(&mut map, name, value);
+    }
+    Ok(Value::Object(map))
+}
+
+fn collect_fields<'a>(
+    fields: &mut Vec<BoxFieldFuture<'a>>,
+    schema: &'a SchemaInner,
+    object: &'a Object,
+    ctx: &ContextSelectionSet<'a>,
+    parent_value: &'a FieldValue<'a>,
+) -> ServerResult<()> {
+    for selection in &ctx.item.node.items {
+        match &selection.node {
+            Selection::Field(field) => {
+                if field.node.name.node == "__typename" {
+                    let field_name = field.node.response_key().node.clone();
+                    let typename = object.name.clone();
+                    fields.push(Box::pin(async move {
+                        Ok((field_name, Value::String(typename)))
+                    }));
+                    continue;
+                }
+
+                let ctx = ctx.clone();
+                fields.push(Box::pin(async move {
+                    let ctx_field = ctx.with_field(field);
+                    let field_name = ctx_field.item.node.response_key().node.clone();
+                    let value = resolve_field(schema, object, &ctx_field, parent_value).await?;
+                    Ok((field_name, value))
+                }));
+            }
+            selection => {
+                let (type_condition, selection_set) = match selection {
+                    Selection::Field(_) => unreachable!(),
+                    Selection::FragmentSpread(spread) => {
+                        let fragment = ctx
+                            .query_env
+                            .fragments
+                            .get(&spread.node.fragment_name.node)
+                            .ok_or_else(|| {
+                                ServerError::new(
+                                    format!(
+                                        r#"Unknown fragment "{}"."#,
+                                        spread.node.fragment_name.node
+                                    ),
+                                    Some(spread.pos),
+                                )
+                            })?;
+                        (
+                            Some(&fragment.node.type_condition),
+                            &fragment.node.selection_set,
+                        )
+                    }
+                    Selection::InlineFragment(fragment) => (
+                        fragment.node.type_condition.as_ref(),
+                        &fragment.node.selection_set,
+                    ),
+                };
+
+                let applies = type_condition.map_or(true, |condition| {
+                    let condition = condition.node.on.node.as_str();
+                    object.name == condition
+                        || object.implements.contains(condition)
+                        || matches!(
+                            schema.types.get(condition),
+                            Some(Type::Union(union)) if union.possible_types.contains(&object.name)
+                        )
+                });
+                if applies {
+                    collect_fields(
+                        fields,
+                        schema,
+                        object,
+                        &ctx.with_selection_set(selection_set),
+                        parent_value,
+                    )?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+async fn resolve_field(
+    schema: &SchemaInner,
+    object: &Object,
+    ctx: &Context<'_>,
+    parent_value: &FieldValue<'_>,
+) -> ServerResult<Value> {
+    let field_name = ctx.item.node.name.node.as_str();
+
+    if object.name == ctx.schema_env.registry.query_type {
+        if let Some(value) = resolve_introspection_field(ctx).await? {
+            return Ok(value);
+        }
+
+        if ctx.schema_env.registry.introspection_mode == IntrospectionMode::IntrospectionOnly
+            || ctx.query_env.introspection_mode == IntrospectionMode::IntrospectionOnly
+        {
+            return Ok(Value::Null);
+        }
+    }
+
+    let field = object.fields.get(field_name).ok_or_else(|| {
+        ServerError::new(
+            format!(
+                r#"Cannot query field "{}" on type "{}"."#,
+                field_name, object.name
+            ),
+            Some(ctx.item.pos),
+        )
+    })?;
+
+    let resolve_fut = async {
+        resolve_field_value(schema, field, ctx, parent_value)
+            .await
+            .map(Some)
+    };
+    futures_util::pin_mut!(resolve_fut);
+
+    let extensions = &ctx.query_env.extensions;
+    let value = if extensions.is_empty() {
+        resolve_fut.await?
+    } else {
+        let return_type = field.ty.to_string();
+        let resolve_info = ResolveInfo {
+            path_node: ctx.path_node.as_ref().unwrap(),
+            parent_type: &object.name,
+            return_type: &return_type,
+            name: field_name,
+            alias: ctx
+                .item
+                .node
+                .alias
+                .as_ref()
+                .map(|alias| alias.node.as_str()),
+        };
+        extensions.resolve(resolve_info, &mut resolve_fut).await?
+    };
+    Ok(value.unwrap_or_default())
+}
+
+async fn resolve_field_value(
+    schema: &SchemaInner,
+    field: &Field,
+    ctx: &Context<'_>,
+    parent_value: &FieldValue<'_>,
+) -> ServerResult<Value> {
+    let args = collect_arguments(schema, field, ctx)?;
+    let resolver_ctx = ResolverContext {
+        ctx,
+        args: ObjectAccessor(&args),
+        parent_value,
+    };
+    let value = match (field.resolver_fn)(resolver_ctx) {
+        FieldFuture::Future(fut) => fut.await,
+        FieldFuture::Value(value) => Ok(value),
+    };
+
+    let ctx_selection_set = ctx.with_selection_set(&ctx.item.node.selection_set);
+    match value {
+        Ok(value) => {
+            resolve_value(
+                schema,
+                &field.ty,
+                &ctx_selection_set,
+                ctx.item.pos,
+                value.as_ref(),
+            )
+            .await
+        }
+        Err(err) => {
+            let err = ctx.set_error_path(err.into_server_error(ctx.item.pos));
+            if field.ty.is_nullable() {
+                ctx.add_error(err);
+                Ok(Value::Null)
+            } else {
+                Err(err)
+            }
+        }
+    }
+}
+
+/// Evaluates the arguments of a field, filling in the default values of the
+/// ones which were not provided.
+fn collect_arguments(
+    schema: &SchemaInner,
+    field: &Field,
+    ctx: &Context<'_>,
+) -> ServerResult<IndexMap<Name, Value>> {
+    let mut args = IndexMap::new();
+    for arg in field.arguments.values() {
+        let value = match ctx.item.node.get_argument(&arg.name) {
+            Some(value) => Some(ctx.resolve_input_value(value.clone())?),
+            None => arg.default_value.clone(),
+        };
+        if let Some(value) = value {
+            args.insert(
+                Name::new(&arg.name),
+                fill_input_defaults(schema, &arg.ty, value),
+            );
+        }
+    }
+    Ok(args)
+}
+
+fn fill_input_defaults(schema: &SchemaInner, ty: &TypeRef, value: Value) -> Value {
+    match (ty, value) {
+        (TypeRef::NonNull(ty), value) => fill_input_defaults(schema, ty, value),
+        (TypeRef::List(ty), Value::List(items)) => Value::List(
+            items
+                .into_iter()
+                .map(|item| fill_input_defaults(schema, ty, item))
+                .collect(),
+        ),
+        (TypeRef::Named(name), Value::Object(mut obj)) => {
+            if let Some(Type::InputObject(input_object)) = schema.types.get(name) {
+                for field in input_object.fields.values() {
+                    let field_name = Name::new(&field.name);
+                    match obj.remove(&field_name) {
+                        Some(value) => {
+                            obj.insert(field_name, fill_input_defaults(schema, &field.ty, value));
+                        }
+                        None => {
+                            if let Some(default_value) = &field.default_value {
+                                obj.insert(field_name, default_value.clone());
+                            }
+                        }
+                    }
+                }
+            }
+            Value::Object(obj)
+        }
+        (_, value) => value,
+    }
+}
+
+/// Completes a value returned by a resolver according to the type of the
+/// field, an error of a nullable value is reported and replaced with `null`.
+fn resolve_value<'a>(
+    schema: &'a SchemaInner,
+    ty: &'a TypeRef,
+    ctx: &'a ContextSelectionSet<'a>,
+    pos: Pos,
+    value: Option<&'a FieldValue<'a>>,
+) -> BoxFuture<'a, ServerResult<Value>> {
+    async move {
+        let value = value.filter(|value| !value.is_null());
+        match (ty, value) {
+            (TypeRef::NonNull(ty), Some(value)) => {
+                resolve_non_null(schema, ty, ctx, pos, value).await
+            }
+            (TypeRef::NonNull(_), None) => Err(ctx.set_error_path(ServerError::new(
+                "internal: non-null types require a return value",
+                Some(pos),
+            ))),
+            (_, Some(value)) => match resolve_non_null(schema, ty, ctx, pos, value).await {
+                Ok(value) => Ok(value),
+                Err(err) => {
+                    ctx.add_error(err);
+                    Ok(Value::Null)
+                }
+            },
+            (_, None) => Ok(Value::Null),
+        }
+    }
+    .boxed()
+}
+
+async fn resolve_non_null<'a>(
+    schema: &'a SchemaInner,
+    ty: &'a TypeRef,
+    ctx: &'a ContextSelectionSet<'a>,
+    pos: Pos,
+    value: &'a FieldValue<'a>,
+) -> ServerResult<Value> {
+    let (value, concrete_type) = value.without_type();
+    let type_name = match ty {
+        TypeRef::NonNull(_) => return resolve_value(schema, ty, ctx, pos, Some(value)).await,
+        TypeRef::List(item_ty) => return resolve_list(schema, item_ty, ctx, pos, value).await,
+        TypeRef::Named(type_name) => type_name,
+    };
+
+    match schema.types.get(type_name) {
+        Some(Type::Object(object)) => resolve_container(schema, object, ctx, value, false).await,
+        Some(Type::Interface(_)) | Some(Type::Union(_)) => {
+            let object = concrete_type
+                .and_then(|name| schema.object(name))
+                .filter(|object| {
+                    schema.env.registry.types[type_name.as_str()].is_possible_type(&object.name)
+                })
+                .ok_or_else(|| {
+                    ctx.set_error_path(ServerError::new(
+                        format!(
+                            r#"internal: the value of abstract type "{}" must have a concrete object type, use `FieldValue::with_type`"#,
+                            type_name
+                        ),
+                        Some(pos),
+                    ))
+                })?;
+            resolve_container(schema, object, ctx, value, false).await
+        }
+        Some(Type::Enum(enum_type)) => {
+            let name = match value.as_value() {
+                Some(Value::Enum(name)) => Some(name.as_str()),
+                Some(Value::String(name)) => Some(name.as_str()),
+                _ => None,
+            };
+            match name {
+                Some(name) if enum_type.items.contains_key(name) => {
+                    Ok(Value::Enum(Name::new(name)))
+                }
+                _ => Err(ctx.set_error_path(ServerError::new(
+                    format!(
+                        r#"internal: invalid value for enum "{}""#,
+                        enum_type.name
+                    ),
+                    Some(pos),
+                ))),
+            }
+        }
+        Some(Type::Scalar(_)) | None => value.as_value().cloned().ok_or_else(|| {
+            ctx.set_error_path(ServerError::new(
+                format!(r#"internal: invalid value for scalar "{}""#, type_name),
+                Some(pos),
+            ))
+        }),
+        Some(Type::InputObject(_)) => unreachable!(),
+    }
+}
+
+async fn resolve_list<'a>(
+    schema: &'a SchemaInner,
+    item_ty: &'a TypeRef,
+    ctx: &'a ContextSelectionSet<'a>,
+    pos: Pos,
+    value: &'a FieldValue<'a>,
+) -> ServerResult<Value> {
+    let values;
+    let items = match value {
+        FieldValue::List(items) => items.iter().collect::<Vec<_>>(),
+        FieldValue::Value(Value::List(list)) => {
+            values = list
+                .iter()
+                .cloned()
+                .map(FieldValue::Value)
+                .collect::<Vec<_>>();
+            values.iter().collect()
+        }
+        _ => {
+            return Err(ctx.set_error_path(ServerError::new(
+                "internal: a list field must return a list",
+                Some(pos),
+            )))
+        }
+    };
+
+    let futures = items.into_iter().enumerate().map(|(idx, item)| {
+        let ctx = ctx.clone();
+        async move {
+            let ctx_idx = ctx.with_index(idx);
+            resolve_value(schema, item_ty, &ctx_idx, pos, Some(item)).await
+        }
+    });
+    Ok(Value::List(
+        futures_util::future::try_join_all(futures).await?,
+    ))
+}
@@ -0,0 +1,88 @@
+use std::fmt::{self, Debug, Formatter};
+
+use crate::{
+    registry::{MetaType, Registry},
+    Value,
+};
+
+/// A GraphQL scalar type
+///
+/// Resolvers return the values of a custom scalar as
+/// [`FieldValue::value`](crate::dynamic::FieldValue::value).
+pub struct Scalar {
+    pub(crate) name: String,
+    pub(crate) description: Option<String>,
+    pub(crate) specified_by_url: Option<String>,
+    pub(crate) validator: fn(&Value) -> bool,
+}
+
+impl Debug for Scalar {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Scalar")
+            .field("name", &self.name)
+            .field("description", &self.description)
+            .field("specified_by_url", &self.specified_by_url)
+            .finish()
+    }
+}
+
+impl Scalar {
+    /// Create a GraphQL scalar type, accepting any input value
+    #[inline]
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            description: None,
+            specified_by_url: None,
+            validator: |_| true,
+        }
+    }
+
+    /// Set the description
+    #[inline]
+    #[must_use]
+    pub fn description(self, description: impl Into<String>) -> Self {
+        Self {
+            description: Some(description.into()),
+            ..self
+        }
+    }
+
+    /// Set the URL of the specification of the scalar
+    #[inline]
+    #[must_use]
+    pub fn specified_by_url(self, url: impl Into<String>) -> Self {
+        Self {
+            specified_by_url: Some(url.into()),
+            ..self
+        }
+    }
+
+    /// Set the function checking that an input value is valid for this scalar
+    #[inline]
+    #[must_use]
+    pub fn validator(self, validator: fn(&Value) -> bool) -> Self {
+        Self { validator, ..self }
+    }
+
+    /// Returns the type name
+    #[inline]
+    pub fn type_name(&self) -> &str {
+        &self.name
+    }
+
+    pub(crate) fn register(&self, registry: &mut Registry) {
+        registry.types.insert(
+            self.name.clone(),
+            MetaType::Scalar {
+                name: self.name.clone(),
+                description: self.description.as_deref().map(super::intern_str),
+                is_valid: self.validator,
+                visible: None,
+                inaccessible: false,
+                tags: Default::default(),
+                specified_by_url: self.specified_by_url.as_deref().map(super::intern_str),
+            },
+        );
+    }
+}
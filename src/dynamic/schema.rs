@@ -0,0 +1,524 @@
+use std::{any::Any, sync::Arc};
+
+use futures_util::StreamExt;
+use indexmap::IndexMap;
+
+use crate::{
+    context::Data,
+    dynamic::{resolve, FieldValue, Object, RemoteSchema, Type, TypeRef},
+    extensions::{ExtensionFactory, Extensions},
+    parser::{self, types::OperationType},
+    registry::{MetaType, Registry, SDLExportOptions, SchemaDiff},
+    schema::{prepare_request, register_builtin_types, SchemaEnvInner},
+    types::register_introspection_fields,
+    validation::ValidationMode,
+    BatchRequest, BatchResponse, ContextBase, IntrospectionMode, QueryEnv, Request, Response,
+    SchemaEnv, ServerError,
+};
+
+/// An error that occurs when building a dynamic schema.
+#[derive(Debug, thiserror::Error, Clone, PartialEq, Eq)]
+#[error("{0}")]
+pub struct SchemaError(pub String);
+
+impl From<String> for SchemaError {
+    fn from(err: String) -> Self {
+        SchemaError(err)
+    }
+}
+
+/// Dynamic schema builder
+pub struct SchemaBuilder {
+    query_type: String,
+    mutation_type: Option<String>,
+    types: Vec<Type>,
+    data: Data,
+    extensions: Vec<Box<dyn ExtensionFactory>>,
+    validation_mode: ValidationMode,
+    introspection_mode: IntrospectionMode,
+    complexity: Option<usize>,
+    depth: Option<usize>,
+    cost: Option<usize>,
+    remotes: Vec<(RemoteSchema, Option<(String, String)>)>,
+}
+
+impl SchemaBuilder {
+    /// Register a GraphQL type
+    #[must_use]
+    pub fn register(mut self, ty: impl Into<Type>) -> Self {
+        self.types.push(ty.into());
+        self
+    }
+
+    /// Merge the root fields of a remote schema into the query and mutation
+    /// root types, along with the types of the remote schema.
+    ///
+    /// The root fields are executed by the remote service, and their names
+    /// must not conflict with the fields of the root types.
+    #[must_use]
+    pub fn merge_remote(mut self, remote: RemoteSchema) -> Self {
+        self.remotes.push((remote, None));
+        self
+    }
+
+    /// Mount the query root of a remote schema under a field of the query
+    /// root type, as an object type named `type_name`, along with the types
+    /// of the remote schema.
+    ///
+    /// The selection of the field is executed by the remote service in a
+    /// single request.
+    #[must_use]
+    pub fn mount_remote(
+        mut self,
+        field: impl Into<String>,
+        type_name: impl Into<String>,
+        remote: RemoteSchema,
+    ) -> Self {
+        self.remotes
+            .push((remote, Some((field.into(), type_name.into()))));
+        self
+    }
+
+    /// Disable introspection queries.
+    #[must_use]
+    pub fn disable_introspection(mut self) -> Self {
+        self.introspection_mode = IntrospectionMode::Disabled;
+        self
+    }
+
+    /// Only process introspection queries, everything else is processed as an
+    /// error.
+    #[must_use]
+    pub fn introspection_only(mut self) -> Self {
+        self.introspection_mode = IntrospectionMode::IntrospectionOnly;
+        self
+    }
+
+    /// Set the maximum complexity a query can have. By default, there is no
+    /// limit.
+    #[must_use]
+    pub fn limit_complexity(mut self, complexity: usize) -> Self {
+        self.complexity = Some(complexity);
+        self
+    }
+
+    /// Set the maximum depth a query can have. By default, there is no limit.
+    #[must_use]
+    pub fn limit_depth(mut self, depth: usize) -> Self {
+        self.depth = Some(depth);
+        self
+    }
+
+    /// Set the maximum cost a query can have. By default, there is no limit.
+    #[must_use]
+    pub fn limit_cost(mut self, cost: usize) -> Self {
+        self.cost = Some(cost);
+        self
+    }
+
+    /// Add an extension to the schema.
+    #[must_use]
+    pub fn extension(mut self, extension: impl ExtensionFactory) -> Self {
+        self.extensions.push(Box::new(extension));
+        self
+    }
+
+    /// Add a global data that can be accessed in the `Schema`. You access it
+    /// with `Context::data`.
+    #[must_use]
+    pub fn data<D: Any + Send + Sync>(mut self, data: D) -> Self {
+        self.data.insert(data);
+        self
+    }
+
+    /// Set the validation mode, default is `ValidationMode::Strict`.
+    #[must_use]
+    pub fn validation_mode(mut self, validation_mode: ValidationMode) -> Self {
+        self.validation_mode = validation_mode;
+        self
+    }
+
+    /// Build the schema, checking that every referenced type is registered.
+    pub fn finish(mut self) -> Result<Schema, SchemaError> {
+        for (remote, mount) in std::mem::take(&mut self.remotes) {
+            let remote_types = remote.into_types(
+                mount
+                    .as_ref()
+                    .map(|(field, type_name)| (field.as_str(), type_name.as_str())),
+            );
+            self.types.extend(remote_types.types);
+            add_root_fields(&mut self.types, &self.query_type, remote_types.query_fields)?;
+            if !remote_types.mutation_fields.is_empty() {
+                let mutation_type = self.mutation_type.as_deref().ok_or_else(|| {
+                    SchemaError(
+                        "The remote schema has mutations, but the schema has no mutation type."
+                            .to_string(),
+                    )
+                })?;
+                add_root_fields(&mut self.types, mutation_type, remote_types.mutation_fields)?;
+            }
+        }
+
+        let mut registry = Registry {
+            query_type: self.query_type.clone(),
+            mutation_type: self.mutation_type.clone(),
+            introspection_mode: self.introspection_mode,
+            ..Default::default()
+        };
+        register_builtin_types(&mut registry);
+
+        let mut types = IndexMap::new();
+        for ty in self.types {
+            if registry.types.contains_key(ty.name()) || types.contains_key(ty.name()) {
+                return Err(format!(r#"Type "{}" is already defined."#, ty.name()).into());
+            }
+            ty.register(&mut registry);
+            types.insert(ty.name().to_string(), ty);
+        }
+
+        check_types(&registry, &types)?;
+
+        for ty in types.values() {
+            if let Type::Object(object) = ty {
+                for interface in &object.implements {
+                    if let Some(MetaType::Interface { possible_types, .. }) =
+                        registry.types.get_mut(interface)
+                    {
+                        possible_types.insert(object.name.clone());
+                    }
+                }
+            }
+        }
+
+        register_introspection_fields(&mut registry, &self.query_type);
+
+        Ok(Schema(Arc::new(SchemaInner {
+            env: SchemaEnv(Arc::new(SchemaEnvInner {
+                registry,
+                data: self.data,
+                custom_directives: Default::default(),
+                auth_policy: None,
+                auth_mode: Default::default(),
+                hide_denied_fields: false,
+            })),
+            types,
+            extensions: self.extensions,
+            validation_mode: self.validation_mode,
+            complexity: self.complexity,
+            depth: self.depth,
+            cost: self.cost,
+        })))
+    }
+}
+
+fn add_root_fields(
+    types: &mut [Type],
+    root_type: &str,
+    fields: Vec<crate::dynamic::Field>,
+) -> Result<(), SchemaError> {
+    let object = types
+        .iter_mut()
+        .find_map(|ty| match ty {
+            Type::Object(object) if object.name == root_type => Some(object),
+            _ => None,
+        })
+        .ok_or_else(|| SchemaError(format!(r#"Unknown root type "{}"."#, root_type)))?;
+    for field in fields {
+        if object.fields.contains_key(&field.name) {
+            return Err(SchemaError(format!(
+                r#"Field "{}.{}" is already defined."#,
+                root_type, field.name
+            )));
+        }
+        object.fields.insert(field.name.clone(), field);
+    }
+    Ok(())
+}
+
+fn check_types(registry: &Registry, types: &IndexMap<String, Type>) -> Result<(), SchemaError> {
+    let check_root = |name: &str| match types.get(name) {
+        Some(Type::Object(_)) => Ok(()),
+        Some(_) => Err(SchemaError(format!(
+            r#"Root type "{}" must be an object."#,
+            name
+        ))),
+        None => Err(SchemaError(format!(r#"Unknown root type "{}"."#, name))),
+    };
+    check_root(&registry.query_type)?;
+    if let Some(mutation_type) = &registry.mutation_type {
+        check_root(mutation_type)?;
+    }
+
+    let check_output = |ty: &TypeRef, location: &str| match registry.types.get(ty.type_name()) {
+        Some(MetaType::InputObject { .. }) => Err(SchemaError(format!(
+            r#"Type "{}" of {} must be an output type."#,
+            ty, location
+        ))),
+        Some(_) => Ok(()),
+        None => Err(SchemaError(format!(
+            r#"Unknown type "{}" of {}."#,
+            ty, location
+        ))),
+    };
+    let check_input = |ty: &TypeRef, location: &str| match registry.types.get(ty.type_name()) {
+        Some(meta_type) if meta_type.is_input() => Ok(()),
+        Some(_) => Err(SchemaError(format!(
+            r#"Type "{}" of {} must be an input type."#,
+            ty, location
+        ))),
+        None => Err(SchemaError(format!(
+            r#"Unknown type "{}" of {}."#,
+            ty, location
+        ))),
+    };
+
+    for ty in types.values() {
+        match ty {
+            Type::Object(object) => {
+                for field in object.fields.values() {
+                    let location = format!(r#"field "{}.{}""#, object.name, field.name);
+                    check_output(&field.ty, &location)?;
+                    for arg in field.arguments.values() {
+                        check_input(
+                            &arg.ty,
+                            &format!(r#"argument "{}" of {}"#, arg.name, location),
+                        )?;
+                    }
+                }
+
+                for interface_name in &object.implements {
+                    let interface = match types.get(interface_name) {
+                        Some(Type::Interface(interface)) => interface,
+                        _ => {
+                            return Err(SchemaError(format!(
+                                r#"Object "{}" implements "{}" which is not an interface."#,
+                                object.name, interface_name
+                            )))
+                        }
+                    };
+                    for field in interface.fields.values() {
+                        match object.fields.get(&field.name) {
+                            Some(object_field) if object_field.ty == field.ty => {}
+                            _ => {
+                                return Err(SchemaError(format!(
+                                    r#"Object "{}" must have a field "{}" of type "{}" to implement "{}"."#,
+                                    object.name, field.name, field.ty, interface.name
+                                )))
+                            }
+                        }
+                    }
+                }
+            }
+            Type::Interface(interface) => {
+                for field in interface.fields.values() {
+                    let location = format!(r#"field "{}.{}""#, interface.name, field.name);
+                    check_output(&field.ty, &location)?;
+                    for arg in field.arguments.values() {
+                        check_input(
+                            &arg.ty,
+                            &format!(r#"argument "{}" of {}"#, arg.name, location),
+                        )?;
+                    }
+                }
+            }
+            Type::InputObject(input_object) => {
+                for field in input_object.fields.values() {
+                    check_input(
+                        &field.ty,
+                        &format!(r#"field "{}.{}""#, input_object.name, field.name),
+                    )?;
+                }
+            }
+            Type::Union(union) => {
+                for possible_type in &union.possible_types {
+                    if !matches!(types.get(possible_type), Some(Type::Object(_))) {
+                        return Err(SchemaError(format!(
+                            r#"Member "{}" of union "{}" must be an object."#,
+                            possible_type, union.name
+                        )));
+                    }
+                }
+            }
+            Type::Enum(_) | Type::Scalar(_) => {}
+        }
+    }
+
+    Ok(())
+}
+
+pub(crate) struct SchemaInner {
+    pub(crate) env: SchemaEnv,
+    pub(crate) types: IndexMap<String, Type>,
+    extensions: Vec<Box<dyn ExtensionFactory>>,
+    validation_mode: ValidationMode,
+    complexity: Option<usize>,
+    depth: Option<usize>,
+    cost: Option<usize>,
+}
+
+impl SchemaInner {
+    pub(crate) fn object(&self, name: &str) -> Option<&Object> {
+        match self.types.get(name) {
+            Some(Type::Object(object)) => Some(object),
+            _ => None,
+        }
+    }
+}
+
+/// Dynamic GraphQL schema.
+///
+/// Cloning a schema is cheap, so it can be easily shared.
+#[derive(Clone)]
+pub struct Schema(Arc<SchemaInner>);
+
+impl Schema {
+    /// Create a schema builder
+    ///
+    /// The types of the query root, and of the mutation root if any, must be
+    /// registered with [`SchemaBuilder::register`].
+    pub fn build(query: &str, mutation: Option<&str>) -> SchemaBuilder {
+        SchemaBuilder {
+            query_type: query.to_string(),
+            mutation_type: mutation.map(ToString::to_string),
+            types: Default::default(),
+            data: Default::default(),
+            extensions: Default::default(),
+            validation_mode: ValidationMode::Strict,
+            introspection_mode: IntrospectionMode::Enabled,
+            complexity: None,
+            depth: None,
+            cost: None,
+            remotes: Default::default(),
+        }
+    }
+
+    /// Returns SDL(Schema Definition Language) of this schema.
+    pub fn sdl(&self) -> String {
+        self.0.env.registry.export_sdl(Default::default())
+    }
+
+    /// Returns SDL(Schema Definition Language) of this schema with options.
+    pub fn sdl_with_options(&self, options: SDLExportOptions) -> String {
+        self.0.env.registry.export_sdl(options)
+    }
+
+    /// Compare a previous version of this schema written in SDL, such as the
+    /// schema deployed in production, with this schema.
+    pub fn diff_sdl(&self, old_sdl: &str) -> Result<SchemaDiff, parser::Error> {
+        self.0.env.registry.diff_sdl(old_sdl)
+    }
+
+    /// Get all names in this schema
+    pub fn names(&self) -> Vec<String> {
+        self.0.env.registry.names()
+    }
+
+    fn create_extensions(&self, session_data: Arc<Data>) -> Extensions {
+        Extensions::new(
+            self.0.extensions.iter().map(|f| f.create()),
+            self.0.env.clone(),
+            session_data,
+        )
+    }
+
+    async fn execute_once(&self, env: QueryEnv) -> Response {
+        let ctx = ContextBase {
+            path_node: None,
+            item: &env.operation.node.selection_set,
+            schema_env: &self.0.env,
+            query_env: &env,
+        };
+        let root_value = FieldValue::NULL;
+
+        let res = match &env.operation.node.ty {
+            OperationType::Query => {
+                let query = self.0.object(&self.0.env.registry.query_type).unwrap();
+                resolve::resolve_container(&self.0, query, &ctx, &root_value, false).await
+            }
+            OperationType::Mutation => {
+                match self
+                    .0
+                    .env
+                    .registry
+                    .mutation_type
+                    .as_deref()
+                    .and_then(|name| self.0.object(name))
+                {
+                    Some(mutation) => {
+                        resolve::resolve_container(&self.0, mutation, &ctx, &root_value, true).await
+                    }
+                    None => Err(ServerError::new(
+                        "Schema is not configured for mutations.",
+                        None,
+                    )),
+                }
+            }
+            OperationType::Subscription => Err(ServerError::new(
+                "Subscriptions are not supported by dynamic schemas.",
+                None,
+            )),
+        };
+
+        let mut resp = match res {
+            Ok(value) => Response::new(value),
+            Err(err) => Response::from_errors(vec![err]),
+        }
+        .http_headers(std::mem::take(&mut *env.http_headers.lock().unwrap()));
+
+        resp.errors
+            .extend(std::mem::take(&mut *env.errors.lock().unwrap()));
+        resp
+    }
+
+    /// Execute a GraphQL query.
+    pub async fn execute(&self, request: impl Into<Request>) -> Response {
+        let request = request.into();
+        let extensions = self.create_extensions(Default::default());
+        let request_fut = {
+            let extensions = extensions.clone();
+            async move {
+                match prepare_request(
+                    extensions,
+                    request,
+                    Default::default(),
+                    &self.0.env.registry,
+                    self.0.validation_mode,
+                    self.0.complexity,
+                    self.0.depth,
+                    self.0.cost,
+                    false,
+                )
+                .await
+                {
+                    Ok((env, cache_control)) => {
+                        let fut = async {
+                            self.execute_once(env.clone())
+                                .await
+                                .cache_control(cache_control)
+                        };
+                        futures_util::pin_mut!(fut);
+                        env.extensions
+                            .execute(env.operation_name.as_deref(), &mut fut)
+                            .await
+                    }
+                    Err(errors) => Response::from_request_errors(errors),
+                }
+            }
+        };
+        futures_util::pin_mut!(request_fut);
+        extensions.request(&mut request_fut).await
+    }
+
+    /// Execute a GraphQL batch query.
+    pub async fn execute_batch(&self, batch_request: BatchRequest) -> BatchResponse {
+        match batch_request {
+            BatchRequest::Single(request) => BatchResponse::Single(self.execute(request).await),
+            BatchRequest::Batch(requests) => BatchResponse::Batch(
+                futures_util::stream::iter(requests)
+                    .then(|request| self.execute(request))
+                    .collect()
+                    .await,
+            ),
+        }
+    }
+}
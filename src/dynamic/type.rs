@@ -0,0 +1,88 @@
+use crate::{
+    dynamic::{Enum, InputObject, Interface, Object, Scalar, Union},
+    registry::Registry,
+};
+
+/// A GraphQL type of a dynamic schema
+#[derive(Debug)]
+pub enum Type {
+    /// Object
+    Object(Object),
+    /// Input object
+    InputObject(InputObject),
+    /// Enum
+    Enum(Enum),
+    /// Interface
+    Interface(Interface),
+    /// Union
+    Union(Union),
+    /// Scalar
+    Scalar(Scalar),
+}
+
+impl Type {
+    /// Returns the type name
+    pub fn name(&self) -> &str {
+        match self {
+            Type::Object(ty) => ty.type_name(),
+            Type::InputObject(ty) => ty.type_name(),
+            Type::Enum(ty) => ty.type_name(),
+            Type::Interface(ty) => ty.type_name(),
+            Type::Union(ty) => ty.type_name(),
+            Type::Scalar(ty) => ty.type_name(),
+        }
+    }
+
+    pub(crate) fn register(&self, registry: &mut Registry) {
+        match self {
+            Type::Object(ty) => ty.register(registry),
+            Type::InputObject(ty) => ty.register(registry),
+            Type::Enum(ty) => ty.register(registry),
+            Type::Interface(ty) => ty.register(registry),
+            Type::Union(ty) => ty.register(registry),
+            Type::Scalar(ty) => ty.register(registry),
+        }
+    }
+}
+
+impl From<Object> for Type {
+    #[inline]
+    fn from(ty: Object) -> Self {
+        Type::Object(ty)
+    }
+}
+
+impl From<InputObject> for Type {
+    #[inline]
+    fn from(ty: InputObject) -> Self {
+        Type::InputObject(ty)
+    }
+}
+
+impl From<Enum> for Type {
+    #[inline]
+    fn from(ty: Enum) -> Self {
+        Type::Enum(ty)
+    }
+}
+
+impl From<Interface> for Type {
+    #[inline]
+    fn from(ty: Interface) -> Self {
+        Type::Interface(ty)
+    }
+}
+
+impl From<Union> for Type {
+    #[inline]
+    fn from(ty: Union) -> Self {
+        Type::Union(ty)
+    }
+}
+
+impl From<Scalar> for Type {
+    #[inline]
+    fn from(ty: Scalar) -> Self {
+        Type::Scalar(ty)
+    }
+}
@@ -0,0 +1,116 @@
+use std::fmt::{self, Display, Formatter};
+
+/// A reference to a type of a dynamic schema, with its list and non-null
+/// modifiers.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum TypeRef {
+    /// A named type
+    Named(String),
+    /// A non-null type
+    NonNull(Box<TypeRef>),
+    /// A list type
+    List(Box<TypeRef>),
+}
+
+impl Display for TypeRef {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            TypeRef::Named(name) => write!(f, "{}", name),
+            TypeRef::NonNull(ty) => write!(f, "{}!", ty),
+            TypeRef::List(ty) => write!(f, "[{}]", ty),
+        }
+    }
+}
+
+impl TypeRef {
+    /// The `Int` scalar
+    pub const INT: &'static str = "Int";
+
+    /// The `Float` scalar
+    pub const FLOAT: &'static str = "Float";
+
+    /// The `String` scalar
+    pub const STRING: &'static str = "String";
+
+    /// The `Boolean` scalar
+    pub const BOOLEAN: &'static str = "Boolean";
+
+    /// The `ID` scalar
+    pub const ID: &'static str = "ID";
+
+    /// Returns the nullable type reference
+    ///
+    /// GraphQL Type: `T`
+    #[inline]
+    pub fn named(type_name: impl Into<String>) -> TypeRef {
+        TypeRef::Named(type_name.into())
+    }
+
+    /// Returns the non-null type reference
+    ///
+    /// GraphQL Type: `T!`
+    #[inline]
+    pub fn named_nn(type_name: impl Into<String>) -> TypeRef {
+        TypeRef::NonNull(Box::new(TypeRef::named(type_name)))
+    }
+
+    /// Returns a nullable list of nullable types
+    ///
+    /// GraphQL Type: `[T]`
+    #[inline]
+    pub fn named_list(type_name: impl Into<String>) -> TypeRef {
+        TypeRef::List(Box::new(TypeRef::named(type_name)))
+    }
+
+    /// Returns a nullable list of non-null types
+    ///
+    /// GraphQL Type: `[T!]`
+    #[inline]
+    pub fn named_nn_list(type_name: impl Into<String>) -> TypeRef {
+        TypeRef::List(Box::new(TypeRef::named_nn(type_name)))
+    }
+
+    /// Returns a non-null list of nullable types
+    ///
+    /// GraphQL Type: `[T]!`
+    #[inline]
+    pub fn named_list_nn(type_name: impl Into<String>) -> TypeRef {
+        TypeRef::NonNull(Box::new(TypeRef::named_list(type_name)))
+    }
+
+    /// Returns a non-null list of non-null types
+    ///
+    /// GraphQL Type: `[T!]!`
+    #[inline]
+    pub fn named_nn_list_nn(type_name: impl Into<String>) -> TypeRef {
+        TypeRef::NonNull(Box::new(TypeRef::named_nn_list(type_name)))
+    }
+
+    /// Returns the name of the innermost named type
+    pub fn type_name(&self) -> &str {
+        match self {
+            TypeRef::Named(name) => name,
+            TypeRef::NonNull(ty) | TypeRef::List(ty) => ty.type_name(),
+        }
+    }
+
+    pub(crate) fn is_nullable(&self) -> bool {
+        !matches!(self, TypeRef::NonNull(_))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_type_ref_display() {
+        assert_eq!(TypeRef::named("Int").to_string(), "Int");
+        assert_eq!(TypeRef::named_nn("Int").to_string(), "Int!");
+        assert_eq!(TypeRef::named_list("Int").to_string(), "[Int]");
+        assert_eq!(TypeRef::named_nn_list("Int").to_string(), "[Int!]");
+        assert_eq!(TypeRef::named_list_nn("Int").to_string(), "[Int]!");
+        assert_eq!(TypeRef::named_nn_list_nn("Int").to_string(), "[Int!]!");
+        assert_eq!(TypeRef::named_nn_list_nn("Int").type_name(), "Int");
+    }
+}
@@ -0,0 +1,65 @@
+use indexmap::IndexSet;
+
+use crate::registry::{MetaType, Registry};
+
+/// A GraphQL union type
+///
+/// A field returning a union must give the concrete type of its value with
+/// [`FieldValue::with_type`](crate::dynamic::FieldValue::with_type).
+#[derive(Debug)]
+pub struct Union {
+    pub(crate) name: String,
+    pub(crate) description: Option<String>,
+    pub(crate) possible_types: IndexSet<String>,
+}
+
+impl Union {
+    /// Create a GraphQL union type
+    #[inline]
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            description: None,
+            possible_types: Default::default(),
+        }
+    }
+
+    /// Set the description
+    #[inline]
+    #[must_use]
+    pub fn description(self, description: impl Into<String>) -> Self {
+        Self {
+            description: Some(description.into()),
+            ..self
+        }
+    }
+
+    /// Add an object type to the union
+    #[inline]
+    #[must_use]
+    pub fn possible_type(mut self, ty: impl Into<String>) -> Self {
+        self.possible_types.insert(ty.into());
+        self
+    }
+
+    /// Returns the type name
+    #[inline]
+    pub fn type_name(&self) -> &str {
+        &self.name
+    }
+
+    pub(crate) fn register(&self, registry: &mut Registry) {
+        registry.types.insert(
+            self.name.clone(),
+            MetaType::Union {
+                name: self.name.clone(),
+                description: self.description.as_deref().map(super::intern_str),
+                possible_types: self.possible_types.clone(),
+                visible: None,
+                inaccessible: false,
+                tags: Default::default(),
+                rust_typename: super::intern_str(&self.name),
+            },
+        );
+    }
+}
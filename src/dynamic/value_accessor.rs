@@ -0,0 +1,179 @@
+use indexmap::IndexMap;
+use serde::de::DeserializeOwned;
+
+use crate::{Error, Name, Result, Value};
+
+/// A read-only accessor for an input value, such as a field argument.
+#[derive(Debug, Clone, Copy)]
+pub struct ValueAccessor<'a>(pub(crate) &'a Value);
+
+impl<'a> ValueAccessor<'a> {
+    /// Returns `true` if the value is null.
+    #[inline]
+    pub fn is_null(&self) -> bool {
+        matches!(self.0, Value::Null)
+    }
+
+    /// Returns the boolean value.
+    pub fn boolean(&self) -> Result<bool> {
+        match self.0 {
+            Value::Boolean(b) => Ok(*b),
+            _ => Err(Error::new("internal: not a boolean")),
+        }
+    }
+
+    /// Returns the value as an `i64`.
+    pub fn i64(&self) -> Result<i64> {
+        if let Value::Number(number) = self.0 {
+            if let Some(value) = number.as_i64() {
+                return Ok(value);
+            }
+        }
+        Err(Error::new("internal: not a signed integer"))
+    }
+
+    /// Returns the value as an `u64`.
+    pub fn u64(&self) -> Result<u64> {
+        if let Value::Number(number) = self.0 {
+            if let Some(value) = number.as_u64() {
+                return Ok(value);
+            }
+        }
+        Err(Error::new("internal: not an unsigned integer"))
+    }
+
+    /// Returns the value as a `f64`.
+    pub fn f64(&self) -> Result<f64> {
+        if let Value::Number(number) = self.0 {
+            if let Some(value) = number.as_f64() {
+                return Ok(value);
+            }
+        }
+        Err(Error::new("internal: not a float"))
+    }
+
+    /// Returns the string value.
+    pub fn string(&self) -> Result<&'a str> {
+        match self.0 {
+            Value::String(value) => Ok(value),
+            _ => Err(Error::new("internal: not a string")),
+        }
+    }
+
+    /// Returns the name of an enum value.
+    ///
+    /// Enum values passed in variables are strings, both forms are accepted.
+    pub fn enum_name(&self) -> Result<&'a str> {
+        match self.0 {
+            Value::Enum(name) => Ok(name),
+            Value::String(name) => Ok(name),
+            _ => Err(Error::new("internal: not an enum name")),
+        }
+    }
+
+    /// Returns an accessor for the fields of an input object.
+    pub fn object(&self) -> Result<ObjectAccessor<'a>> {
+        match self.0 {
+            Value::Object(obj) => Ok(ObjectAccessor(obj)),
+            _ => Err(Error::new("internal: not an object")),
+        }
+    }
+
+    /// Returns an accessor for the items of a list.
+    pub fn list(&self) -> Result<ListAccessor<'a>> {
+        match self.0 {
+            Value::List(list) => Ok(ListAccessor(list)),
+            _ => Err(Error::new("internal: not a list")),
+        }
+    }
+
+    /// Deserializes the value to `T`.
+    pub fn deserialize<T: DeserializeOwned>(&self) -> Result<T> {
+        T::deserialize(self.0.clone()).map_err(|err| Error::new(err.to_string()))
+    }
+
+    /// Returns the raw value.
+    #[inline]
+    pub fn as_value(&self) -> &'a Value {
+        self.0
+    }
+}
+
+/// A read-only accessor for the fields of an input object, or the arguments
+/// of a field.
+#[derive(Debug, Clone, Copy)]
+pub struct ObjectAccessor<'a>(pub(crate) &'a IndexMap<Name, Value>);
+
+impl<'a> ObjectAccessor<'a> {
+    /// Returns the value of the field with the given name, `None` if it was
+    /// not provided and has no default value.
+    #[inline]
+    pub fn get(&self, name: &str) -> Option<ValueAccessor<'a>> {
+        self.0.get(name).map(ValueAccessor)
+    }
+
+    /// Like [`ObjectAccessor::get`], but returns an error if the field is
+    /// missing.
+    pub fn try_get(&self, name: &str) -> Result<ValueAccessor<'a>> {
+        self.get(name)
+            .ok_or_else(|| Error::new(format!("internal: key \"{}\" not found", name)))
+    }
+
+    /// Returns an iterator over the field names and values.
+    pub fn iter(&self) -> impl Iterator<Item = (&'a Name, ValueAccessor<'a>)> + 'a {
+        self.0.iter().map(|(name, value)| (name, ValueAccessor(value)))
+    }
+
+    /// Returns the number of fields.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if there are no fields.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the raw fields.
+    #[inline]
+    pub fn as_index_map(&self) -> &'a IndexMap<Name, Value> {
+        self.0
+    }
+}
+
+/// A read-only accessor for the items of a list.
+#[derive(Debug, Clone, Copy)]
+pub struct ListAccessor<'a>(pub(crate) &'a [Value]);
+
+impl<'a> ListAccessor<'a> {
+    /// Returns the item at the given index.
+    #[inline]
+    pub fn get(&self, idx: usize) -> Option<ValueAccessor<'a>> {
+        self.0.get(idx).map(ValueAccessor)
+    }
+
+    /// Returns an iterator over the items.
+    pub fn iter(&self) -> impl Iterator<Item = ValueAccessor<'a>> + 'a {
+        self.0.iter().map(ValueAccessor)
+    }
+
+    /// Returns the number of items.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if the list is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the raw items.
+    #[inline]
+    pub fn as_slice(&self) -> &'a [Value] {
+        self.0
+    }
+}
@@ -160,7 +160,7 @@ impl From<parser::Error> for ServerError {
 ///
 /// This is like [`QueryPathSegment`](enum.QueryPathSegment.html), but owned and
 /// used as a part of errors instead of during execution.
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum PathSegment {
     /// A field in an object.
@@ -355,6 +355,11 @@ pub enum ParseRequestError {
     /// requests.
     #[error("Batch requests are not supported")]
     UnsupportedBatch,
+
+    /// The request is a `GET` request executing a mutation, mutations must be
+    /// sent with `POST`.
+    #[error("Mutations are not allowed in GET requests")]
+    MutationInGetRequest,
 }
 
 impl From<multer::Error> for ParseRequestError {
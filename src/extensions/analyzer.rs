@@ -9,8 +9,8 @@ use crate::{
 
 /// Analyzer extension
 ///
-/// This extension will output the `analyzer` field containing `complexity` and
-/// `depth` in the response extension of each query.
+/// This extension will output the `analyzer` field containing `complexity`,
+/// `depth` and `cost` in the response extension of each query.
 pub struct Analyzer;
 
 impl ExtensionFactory for Analyzer {
@@ -35,6 +35,7 @@ impl Extension for AnalyzerExtension {
                 value! ({
                     "complexity": validation_result.complexity,
                     "depth": validation_result.depth,
+                    "cost": validation_result.cost,
                 }),
             );
         }
@@ -115,6 +116,7 @@ mod tests {
             Some(value!({
                 "complexity": 5 + 10,
                 "depth": 3,
+                "cost": 3,
             }))
         );
     }
@@ -1,15 +1,28 @@
 //! Apollo persisted queries extension.
 
-use std::sync::Arc;
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+#[cfg(feature = "unblock")]
+use std::{
+    io::Write,
+    path::{Path, PathBuf},
+};
 
 use async_graphql_parser::types::ExecutableDocument;
 use futures_util::lock::Mutex;
 use serde::Deserialize;
 use sha2::{Digest, Sha256};
+use thiserror::Error;
 
 use crate::{
     extensions::{Extension, ExtensionContext, ExtensionFactory, NextPrepareRequest},
-    from_value, Request, ServerError, ServerResult,
+    from_value, Request, ServerError, ServerResult, Value,
 };
 
 #[derive(Deserialize)]
@@ -20,18 +33,22 @@ struct PersistedQuery {
 }
 
 /// Cache storage for persisted queries.
+///
+/// The queries are stored as strings by their SHA-256 hash, so that a storage
+/// shared by several replicas of a service lets all of them execute the
+/// queries registered with any of them.
 #[async_trait::async_trait]
 pub trait CacheStorage: Send + Sync + Clone + 'static {
     /// Load the query by `key`.
-    async fn get(&self, key: String) -> Option<ExecutableDocument>;
+    async fn get(&self, key: String) -> Option<String>;
 
     /// Save the query by `key`.
-    async fn set(&self, key: String, query: ExecutableDocument);
+    async fn set(&self, key: String, query: String);
 }
 
 /// Memory-based LRU cache.
 #[derive(Clone)]
-pub struct LruCacheStorage(Arc<Mutex<lru::LruCache<String, ExecutableDocument>>>);
+pub struct LruCacheStorage(Arc<Mutex<lru::LruCache<String, String>>>);
 
 impl LruCacheStorage {
     /// Creates a new LRU Cache that holds at most `cap` items.
@@ -42,17 +59,207 @@ impl LruCacheStorage {
 
 #[async_trait::async_trait]
 impl CacheStorage for LruCacheStorage {
-    async fn get(&self, key: String) -> Option<ExecutableDocument> {
+    async fn get(&self, key: String) -> Option<String> {
         let mut cache = self.0.lock().await;
         cache.get(&key).cloned()
     }
 
-    async fn set(&self, key: String, query: ExecutableDocument) {
+    async fn set(&self, key: String, query: String) {
         let mut cache = self.0.lock().await;
         cache.put(key, query);
     }
 }
 
+/// Filesystem-based cache, storing each query in a file of a directory.
+///
+/// The directory can be shared by several replicas of a service, for example
+/// with a network filesystem. The files are written atomically, by renaming a
+/// temporary file of the same directory, and the oldest ones are removed when
+/// the directory holds more than `cap` queries.
+///
+/// The number of queries is counted when the cache is created and then
+/// tracked in memory, the directory is only listed when it exceeds the
+/// capacity, and a tenth of the queries are then removed at once. The
+/// queries written by other replicas are counted the next time the directory
+/// is listed.
+///
+/// The files are accessed on the thread pool of the `blocking` crate.
+#[cfg(feature = "unblock")]
+#[cfg_attr(docsrs, doc(cfg(feature = "unblock")))]
+#[derive(Clone)]
+pub struct FsCacheStorage {
+    dir: Arc<PathBuf>,
+    cap: usize,
+    len: Arc<AtomicUsize>,
+}
+
+#[cfg(feature = "unblock")]
+impl FsCacheStorage {
+    /// Creates a cache storing at most `cap` queries in the `dir` directory,
+    /// creating it if needed.
+    pub fn new(dir: impl Into<PathBuf>, cap: usize) -> std::io::Result<Self> {
+        let dir = dir.into();
+        std::fs::create_dir_all(&dir)?;
+        let len = list_queries(&dir)?.len();
+        Ok(Self {
+            dir: Arc::new(dir),
+            cap,
+            len: Arc::new(AtomicUsize::new(len)),
+        })
+    }
+
+    fn is_key(key: &str) -> bool {
+        !key.is_empty() && key.chars().all(|c| c.is_ascii_hexdigit())
+    }
+
+    fn path(&self, key: &str) -> Option<PathBuf> {
+        // The keys sent by the clients are used as file names, only accept
+        // hashes to prevent path traversals.
+        if !Self::is_key(key) {
+            return None;
+        }
+        Some(self.dir.join(key))
+    }
+}
+
+/// Lists the queries of `dir` with the time they were written.
+#[cfg(feature = "unblock")]
+fn list_queries(dir: &Path) -> std::io::Result<Vec<(std::time::SystemTime, PathBuf)>> {
+    let mut files = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        if matches!(entry.file_name().to_str(), Some(name) if FsCacheStorage::is_key(name)) {
+            files.push((entry.metadata()?.modified()?, entry.path()));
+        }
+    }
+    Ok(files)
+}
+
+/// Removes the oldest queries of `dir` until at most `keep` are left, returns
+/// the number of queries left.
+#[cfg(feature = "unblock")]
+fn evict_queries(dir: &Path, keep: usize) -> std::io::Result<usize> {
+    let mut files = list_queries(dir)?;
+    if files.len() <= keep {
+        return Ok(files.len());
+    }
+
+    files.sort();
+    for (_, path) in &files[..files.len() - keep] {
+        // Another replica may have removed it already.
+        std::fs::remove_file(path).ok();
+    }
+    Ok(keep)
+}
+
+#[cfg(feature = "unblock")]
+#[async_trait::async_trait]
+impl CacheStorage for FsCacheStorage {
+    async fn get(&self, key: String) -> Option<String> {
+        let path = self.path(&key)?;
+        blocking::unblock(move || std::fs::read_to_string(path).ok()).await
+    }
+
+    async fn set(&self, key: String, query: String) {
+        let path = match self.path(&key) {
+            Some(path) => path,
+            None => return,
+        };
+        if self.cap == 0 {
+            return;
+        }
+        let (dir, cap, len) = (self.dir.clone(), self.cap, self.len.clone());
+        blocking::unblock(move || -> std::io::Result<()> {
+            if !path.exists() && len.fetch_add(1, Ordering::Relaxed) >= cap {
+                // Make room for the new query and a tenth of the capacity.
+                let keep = (cap - 1).saturating_sub(cap / 10);
+                len.store(evict_queries(&dir, keep)? + 1, Ordering::Relaxed);
+            }
+            let mut file = tempfile::NamedTempFile::new_in(&*dir)?;
+            file.write_all(query.as_bytes())?;
+            file.persist(path)?;
+            Ok(())
+        })
+        .await
+        .ok();
+    }
+}
+
+/// A key/value store, such as Redis or Memcached.
+///
+/// Implement this trait for the client of a store to share the persisted
+/// queries between replicas with a [`KeyValueCacheStorage`].
+#[async_trait::async_trait]
+pub trait KeyValueStore: Send + Sync + 'static {
+    /// Load the value of `key`.
+    async fn get(&self, key: &str) -> Option<String>;
+
+    /// Save the value of `key`, expiring after `ttl` if any.
+    async fn set(&self, key: &str, value: String, ttl: Option<Duration>);
+}
+
+/// Cache storage adapter for a [`KeyValueStore`].
+///
+/// The keys are prefixed with `apq:` by default, to share the store with
+/// other data.
+pub struct KeyValueCacheStorage<S> {
+    store: Arc<S>,
+    prefix: String,
+    ttl: Option<Duration>,
+}
+
+impl<S> Clone for KeyValueCacheStorage<S> {
+    fn clone(&self) -> Self {
+        Self {
+            store: self.store.clone(),
+            prefix: self.prefix.clone(),
+            ttl: self.ttl,
+        }
+    }
+}
+
+impl<S: KeyValueStore> KeyValueCacheStorage<S> {
+    /// Creates a cache storage for the key/value store.
+    pub fn new(store: S) -> Self {
+        Self {
+            store: Arc::new(store),
+            prefix: "apq:".to_string(),
+            ttl: None,
+        }
+    }
+
+    /// Set the prefix of the keys.
+    #[must_use]
+    pub fn prefix(self, prefix: impl Into<String>) -> Self {
+        Self {
+            prefix: prefix.into(),
+            ..self
+        }
+    }
+
+    /// Set the expiration of the queries. By default, they do not expire.
+    #[must_use]
+    pub fn ttl(self, ttl: Duration) -> Self {
+        Self {
+            ttl: Some(ttl),
+            ..self
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl<S: KeyValueStore> CacheStorage for KeyValueCacheStorage<S> {
+    async fn get(&self, key: String) -> Option<String> {
+        self.store.get(&format!("{}{}", self.prefix, key)).await
+    }
+
+    async fn set(&self, key: String, query: String) {
+        self.store
+            .set(&format!("{}{}", self.prefix, key), query, self.ttl)
+            .await;
+    }
+}
+
 /// Apollo persisted queries extension.
 ///
 /// [Reference](https://www.apollographql.com/docs/react/api/link/persisted-queries/)
@@ -97,11 +304,8 @@ impl<T: CacheStorage> Extension for ApolloPersistedQueriesExtension<T> {
             }
 
             if request.query.is_empty() {
-                if let Some(doc) = self.storage.get(persisted_query.sha256_hash).await {
-                    Ok(Request {
-                        parsed_query: Some(doc),
-                        ..request
-                    })
+                if let Some(query) = self.storage.get(persisted_query.sha256_hash).await {
+                    Ok(Request { query, ..request })
                 } else {
                     Err(ServerError::new("PersistedQueryNotFound", None))
                 }
@@ -112,9 +316,10 @@ impl<T: CacheStorage> Extension for ApolloPersistedQueriesExtension<T> {
                     Err(ServerError::new("provided sha does not match query", None))
                 } else {
                     let doc = async_graphql_parser::parse_query(&request.query)?;
-                    self.storage.set(sha256_hash, doc.clone()).await;
+                    self.storage
+                        .set(sha256_hash, std::mem::take(&mut request.query))
+                        .await;
                     Ok(Request {
-                        query: String::new(),
                         parsed_query: Some(doc),
                         ..request
                     })
@@ -127,6 +332,306 @@ impl<T: CacheStorage> Extension for ApolloPersistedQueriesExtension<T> {
     }
 }
 
+/// An error loading a [`PersistedQueryManifest`].
+#[derive(Debug, Error)]
+pub enum ManifestError {
+    /// The manifest is not valid JSON, or does not have the expected format.
+    #[error("Invalid manifest: {0}")]
+    Json(#[from] serde_json::Error),
+
+    /// The format of an Apollo manifest is not supported.
+    #[error("Unsupported manifest format \"{format}\" version {version}")]
+    UnsupportedFormat {
+        /// The `format` field of the manifest.
+        format: String,
+        /// The `version` field of the manifest.
+        version: i32,
+    },
+
+    /// A document of the manifest cannot be parsed.
+    #[error("Failed to parse the document \"{id}\": {error}")]
+    Parse {
+        /// The id of the document.
+        id: String,
+        /// The parse error.
+        error: async_graphql_parser::Error,
+    },
+}
+
+#[derive(Deserialize)]
+struct ApolloManifest {
+    format: String,
+    version: i32,
+    operations: Vec<ApolloManifestOperation>,
+}
+
+#[derive(Deserialize)]
+struct ApolloManifestOperation {
+    id: String,
+    body: String,
+}
+
+/// A manifest of trusted documents, mapping operation ids to documents.
+///
+/// The documents are parsed when they are loaded, so that an invalid manifest
+/// is detected at startup.
+#[derive(Default, Clone)]
+pub struct PersistedQueryManifest(HashMap<String, ExecutableDocument>);
+
+impl PersistedQueryManifest {
+    /// Create an empty manifest.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Load a manifest in the format generated by
+    /// `@apollo/generate-persisted-query-manifest`.
+    ///
+    /// ```json
+    /// {
+    ///   "format": "apollo-persisted-query-manifest",
+    ///   "version": 1,
+    ///   "operations": [{ "id": "...", "name": "...", "type": "query", "body": "..." }]
+    /// }
+    /// ```
+    pub fn from_apollo_json(json: &str) -> Result<Self, ManifestError> {
+        let manifest: ApolloManifest = serde_json::from_str(json)?;
+        if manifest.format != "apollo-persisted-query-manifest" || manifest.version != 1 {
+            return Err(ManifestError::UnsupportedFormat {
+                format: manifest.format,
+                version: manifest.version,
+            });
+        }
+        let mut res = Self::new();
+        for operation in manifest.operations {
+            res.insert(operation.id, &operation.body)?;
+        }
+        Ok(res)
+    }
+
+    /// Load a manifest in the format generated by the Relay compiler, a JSON
+    /// object mapping the ids to the documents.
+    pub fn from_relay_json(json: &str) -> Result<Self, ManifestError> {
+        let operations: HashMap<String, String> = serde_json::from_str(json)?;
+        let mut res = Self::new();
+        for (id, query) in operations {
+            res.insert(id, &query)?;
+        }
+        Ok(res)
+    }
+
+    /// Add a document to the manifest.
+    pub fn insert(&mut self, id: impl Into<String>, query: &str) -> Result<(), ManifestError> {
+        let id = id.into();
+        match async_graphql_parser::parse_query(query) {
+            Ok(doc) => {
+                self.0.insert(id, doc);
+                Ok(())
+            }
+            Err(error) => Err(ManifestError::Parse { id, error }),
+        }
+    }
+
+    /// Returns the document of an operation id.
+    pub fn get(&self, id: &str) -> Option<&ExecutableDocument> {
+        self.0.get(id)
+    }
+
+    /// Returns the number of documents in the manifest.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if the manifest has no documents.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+/// Metrics of the [`TrustedDocuments`] extension.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct TrustedDocumentsMetrics {
+    /// The number of requests executing a document of the manifest.
+    pub trusted: u64,
+    /// The number of requests with an id missing from the manifest.
+    pub unknown_ids: u64,
+    /// The number of requests rejected because they have no id.
+    pub free_form: u64,
+}
+
+#[derive(Default)]
+struct Counters {
+    trusted: AtomicU64,
+    unknown_ids: AtomicU64,
+    free_form: AtomicU64,
+}
+
+type UnknownIdCallback = Arc<dyn Fn(&str) + Send + Sync>;
+
+struct TrustedDocumentsInner {
+    manifest: PersistedQueryManifest,
+    counters: Counters,
+}
+
+/// Trusted documents extension.
+///
+/// Unlike [`ApolloPersistedQueries`], which caches any query sent by the
+/// clients, this extension only executes the documents of a
+/// [`PersistedQueryManifest`] loaded at startup, and rejects all the other
+/// requests.
+///
+/// The id of the document is read from the `sha256Hash` of the
+/// `persistedQuery` extension sent by Apollo clients, or from the
+/// `documentId` extension. The query text of the request is ignored.
+///
+/// Cloning the extension is cheap, and the clones share the same
+/// [`metrics`](TrustedDocuments::metrics).
+///
+/// # Examples
+///
+/// ```
+/// use async_graphql::{
+///     extensions::apollo_persisted_queries::{PersistedQueryManifest, TrustedDocuments},
+///     *,
+/// };
+///
+/// struct Query;
+///
+/// #[Object]
+/// impl Query {
+///     async fn value(&self) -> i32 {
+///         100
+///     }
+/// }
+///
+/// let manifest = PersistedQueryManifest::from_relay_json(r#"{ "1": "{ value }" }"#).unwrap();
+/// let trusted_documents = TrustedDocuments::new(manifest);
+/// let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+///     .extension(trusted_documents.clone())
+///     .finish();
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// let mut request = Request::new("");
+/// request.extensions.insert("documentId".to_string(), value!("1"));
+/// assert_eq!(
+///     schema.execute(request).await.into_result().unwrap().data,
+///     value!({ "value": 100 })
+/// );
+/// assert!(schema.execute("{ value }").await.is_err());
+/// assert_eq!(trusted_documents.metrics().free_form, 1);
+/// # });
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "apollo_persisted_queries")))]
+#[derive(Clone)]
+pub struct TrustedDocuments {
+    inner: Arc<TrustedDocumentsInner>,
+    on_unknown_id: Option<UnknownIdCallback>,
+}
+
+impl TrustedDocuments {
+    /// Creates a trusted documents extension executing the documents of the
+    /// manifest.
+    pub fn new(manifest: PersistedQueryManifest) -> Self {
+        Self {
+            inner: Arc::new(TrustedDocumentsInner {
+                manifest,
+                counters: Default::default(),
+            }),
+            on_unknown_id: None,
+        }
+    }
+
+    /// Call the specified function with each id missing from the manifest,
+    /// for example to report it to a metrics system.
+    ///
+    /// The function is not shared with the clones made before this call.
+    #[must_use]
+    pub fn on_unknown_id(self, f: impl Fn(&str) + Send + Sync + 'static) -> Self {
+        Self {
+            on_unknown_id: Some(Arc::new(f)),
+            ..self
+        }
+    }
+
+    /// Returns the metrics of the extension.
+    pub fn metrics(&self) -> TrustedDocumentsMetrics {
+        let counters = &self.inner.counters;
+        TrustedDocumentsMetrics {
+            trusted: counters.trusted.load(Ordering::Relaxed),
+            unknown_ids: counters.unknown_ids.load(Ordering::Relaxed),
+            free_form: counters.free_form.load(Ordering::Relaxed),
+        }
+    }
+}
+
+impl ExtensionFactory for TrustedDocuments {
+    fn create(&self) -> Arc<dyn Extension> {
+        Arc::new(TrustedDocumentsExtension(self.clone()))
+    }
+}
+
+struct TrustedDocumentsExtension(TrustedDocuments);
+
+fn document_id(request: &mut Request) -> ServerResult<Option<String>> {
+    if let Some(value) = request.extensions.remove("persistedQuery") {
+        let persisted_query: PersistedQuery = from_value(value).map_err(|_| {
+            ServerError::new("Invalid \"PersistedQuery\" extension configuration.", None)
+        })?;
+        if persisted_query.version != 1 {
+            return Err(ServerError::new(
+                format!(
+                    "Only the \"PersistedQuery\" extension of version \"1\" is supported, and the current version is \"{}\".",
+                    persisted_query.version
+                ),
+                None,
+            ));
+        }
+        return Ok(Some(persisted_query.sha256_hash));
+    }
+    match request.extensions.remove("documentId") {
+        Some(Value::String(id)) => Ok(Some(id)),
+        Some(_) => Err(ServerError::new("Invalid \"documentId\" extension.", None)),
+        None => Ok(None),
+    }
+}
+
+#[async_trait::async_trait]
+impl Extension for TrustedDocumentsExtension {
+    async fn prepare_request(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        mut request: Request,
+        next: NextPrepareRequest<'_>,
+    ) -> ServerResult<Request> {
+        let counters = &self.0.inner.counters;
+        let id = match document_id(&mut request)? {
+            Some(id) => id,
+            None => {
+                counters.free_form.fetch_add(1, Ordering::Relaxed);
+                return Err(ServerError::new("PersistedQueryIdRequired", None));
+            }
+        };
+        let doc = match self.0.inner.manifest.get(&id) {
+            Some(doc) => doc.clone(),
+            None => {
+                counters.unknown_ids.fetch_add(1, Ordering::Relaxed);
+                if let Some(on_unknown_id) = &self.0.on_unknown_id {
+                    on_unknown_id(&id);
+                }
+                return Err(ServerError::new("PersistedQueryNotFound", None));
+            }
+        };
+        counters.trusted.fetch_add(1, Ordering::Relaxed);
+
+        let request = Request {
+            query: String::new(),
+            parsed_query: Some(doc),
+            ..request
+        };
+        next.run(ctx, request).await
+    }
+}
+
 #[cfg(test)]
 mod tests {
     #[tokio::test]
@@ -193,4 +698,226 @@ mod tests {
             vec![ServerError::new("PersistedQueryNotFound", None)]
         );
     }
+
+    #[tokio::test]
+    async fn test_trusted_documents() {
+        use std::sync::Mutex;
+
+        use super::*;
+        use crate::*;
+
+        struct Query;
+
+        #[Object(internal)]
+        impl Query {
+            async fn value(&self) -> i32 {
+                100
+            }
+        }
+
+        let manifest = PersistedQueryManifest::from_apollo_json(
+            r#"{
+                "format": "apollo-persisted-query-manifest",
+                "version": 1,
+                "operations": [
+                    { "id": "abc", "name": "Value", "type": "query", "body": "query Value { value }" }
+                ]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(manifest.len(), 1);
+
+        let unknown_ids = Arc::new(Mutex::new(Vec::new()));
+        let trusted_documents = TrustedDocuments::new(manifest);
+        let _clone = trusted_documents.clone();
+        let trusted_documents = trusted_documents.on_unknown_id({
+            let unknown_ids = unknown_ids.clone();
+            move |id| unknown_ids.lock().unwrap().push(id.to_string())
+        });
+        let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+            .extension(trusted_documents.clone())
+            .finish();
+
+        let mut request = Request::new("");
+        request.extensions.insert(
+            "persistedQuery".to_string(),
+            value!({
+                "version": 1,
+                "sha256Hash": "abc",
+            }),
+        );
+        assert_eq!(
+            schema.execute(request).await.into_result().unwrap().data,
+            value!({
+                "value": 100
+            })
+        );
+
+        let mut request = Request::new("{ value }");
+        request
+            .extensions
+            .insert("documentId".to_string(), value!("def"));
+        assert_eq!(
+            schema.execute(request).await.into_result().unwrap_err(),
+            vec![ServerError::new("PersistedQueryNotFound", None)]
+        );
+
+        assert_eq!(
+            schema.execute("{ value }").await.into_result().unwrap_err(),
+            vec![ServerError::new("PersistedQueryIdRequired", None)]
+        );
+
+        let mut request = Request::new("");
+        request.extensions.insert(
+            "persistedQuery".to_string(),
+            value!({
+                "version": 2,
+                "sha256Hash": "abc",
+            }),
+        );
+        assert_eq!(
+            schema.execute(request).await.into_result().unwrap_err(),
+            vec![ServerError::new(
+                "Only the \"PersistedQuery\" extension of version \"1\" is supported, and the current version is \"2\".",
+                None
+            )]
+        );
+
+        assert_eq!(
+            trusted_documents.metrics(),
+            TrustedDocumentsMetrics {
+                trusted: 1,
+                unknown_ids: 1,
+                free_form: 1,
+            }
+        );
+        assert_eq!(*unknown_ids.lock().unwrap(), vec!["def".to_string()]);
+    }
+
+    #[test]
+    fn test_manifest() {
+        use super::*;
+
+        let manifest = PersistedQueryManifest::from_relay_json(
+            r#"{ "1": "{ value }", "2": "query A { a }" }"#,
+        )
+        .unwrap();
+        assert_eq!(manifest.len(), 2);
+        assert!(manifest.get("2").is_some());
+
+        assert!(matches!(
+            PersistedQueryManifest::from_relay_json(r#"{ "1": "{ value" }"#),
+            Err(ManifestError::Parse { id, .. }) if id == "1"
+        ));
+        assert!(matches!(
+            PersistedQueryManifest::from_apollo_json(
+                r#"{ "format": "other", "version": 1, "operations": [] }"#
+            ),
+            Err(ManifestError::UnsupportedFormat { .. })
+        ));
+    }
+
+    #[cfg(feature = "unblock")]
+    #[tokio::test]
+    async fn test_fs_cache_storage() {
+        use super::*;
+
+        let dir = tempfile::tempdir().unwrap();
+        let storage = FsCacheStorage::new(dir.path().join("apq"), 2).unwrap();
+        storage
+            .set("abc".to_string(), "{ value }".to_string())
+            .await;
+        assert_eq!(
+            storage.get("abc".to_string()).await,
+            Some("{ value }".to_string())
+        );
+        assert_eq!(storage.get("def".to_string()).await, None);
+
+        // Another storage sharing the directory sees the same queries.
+        let storage = FsCacheStorage::new(dir.path().join("apq"), 2).unwrap();
+        assert_eq!(
+            storage.get("abc".to_string()).await,
+            Some("{ value }".to_string())
+        );
+
+        // Keys which are not hashes are ignored.
+        std::fs::write(dir.path().join("secret"), "secret").unwrap();
+        assert_eq!(storage.get("../secret".to_string()).await, None);
+        storage
+            .set("../other".to_string(), "{ value }".to_string())
+            .await;
+        assert!(!dir.path().join("other").exists());
+
+        // The oldest queries are removed beyond the capacity.
+        std::thread::sleep(Duration::from_millis(10));
+        storage.set("def".to_string(), "{ a }".to_string()).await;
+        std::thread::sleep(Duration::from_millis(10));
+        storage.set("123".to_string(), "{ b }".to_string()).await;
+        assert_eq!(storage.get("abc".to_string()).await, None);
+        assert_eq!(
+            storage.get("def".to_string()).await,
+            Some("{ a }".to_string())
+        );
+        assert_eq!(
+            storage.get("123".to_string()).await,
+            Some("{ b }".to_string())
+        );
+
+        // A tenth of the queries are removed at once.
+        let storage = FsCacheStorage::new(dir.path().join("batch"), 10).unwrap();
+        for idx in 0..11 {
+            std::thread::sleep(Duration::from_millis(10));
+            storage
+                .set(format!("{:x}", idx), "{ value }".to_string())
+                .await;
+        }
+        let count = || std::fs::read_dir(dir.path().join("batch")).unwrap().count();
+        assert_eq!(count(), 9);
+        assert_eq!(storage.get("0".to_string()).await, None);
+        assert_eq!(storage.get("1".to_string()).await, None);
+        assert!(storage.get("2".to_string()).await.is_some());
+        storage.set("b".to_string(), "{ value }".to_string()).await;
+        assert_eq!(count(), 10);
+    }
+
+    #[tokio::test]
+    async fn test_key_value_cache_storage() {
+        use std::sync::Mutex;
+
+        use super::*;
+
+        #[derive(Default)]
+        struct MemoryStore(Mutex<HashMap<String, (String, Option<Duration>)>>);
+
+        #[async_trait::async_trait]
+        impl KeyValueStore for Arc<MemoryStore> {
+            async fn get(&self, key: &str) -> Option<String> {
+                self.0
+                    .lock()
+                    .unwrap()
+                    .get(key)
+                    .map(|(value, _)| value.clone())
+            }
+
+            async fn set(&self, key: &str, value: String, ttl: Option<Duration>) {
+                self.0.lock().unwrap().insert(key.to_string(), (value, ttl));
+            }
+        }
+
+        let store = Arc::new(MemoryStore::default());
+        let storage = KeyValueCacheStorage::new(store.clone())
+            .prefix("queries:")
+            .ttl(Duration::from_secs(60));
+        storage
+            .set("abc".to_string(), "{ value }".to_string())
+            .await;
+        assert_eq!(
+            storage.get("abc".to_string()).await,
+            Some("{ value }".to_string())
+        );
+        assert_eq!(
+            store.0.lock().unwrap().get("queries:abc"),
+            Some(&("{ value }".to_string(), Some(Duration::from_secs(60))))
+        );
+    }
 }
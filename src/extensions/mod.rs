@@ -9,6 +9,7 @@ mod apollo_tracing;
 mod logger;
 #[cfg(feature = "opentelemetry")]
 mod opentelemetry;
+mod rate_limit;
 #[cfg(feature = "tracing")]
 mod tracing;
 
@@ -27,6 +28,7 @@ pub use self::apollo_tracing::ApolloTracing;
 pub use self::logger::Logger;
 #[cfg(feature = "opentelemetry")]
 pub use self::opentelemetry::OpenTelemetry;
+pub use self::rate_limit::RateLimit;
 #[cfg(feature = "tracing")]
 pub use self::tracing::Tracing;
 use crate::{
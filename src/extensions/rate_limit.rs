@@ -0,0 +1,328 @@
+use std::{
+    collections::HashMap,
+    hash::Hash,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use crate::{
+    extensions::{Extension, ExtensionContext, ExtensionFactory, NextValidation},
+    value, ErrorExtensionValues, ServerError, ValidationResult,
+};
+
+/// The number of buckets above which the full buckets are evicted.
+const MIN_SWEEP_LEN: usize = 64;
+
+struct Bucket {
+    tokens: f64,
+    updated_at: Instant,
+}
+
+impl Bucket {
+    fn refill(&mut self, capacity: f64, rate: f64, now: Instant) {
+        let elapsed = now.duration_since(self.updated_at).as_secs_f64();
+        self.tokens = f64::min(capacity, self.tokens + elapsed * rate);
+        self.updated_at = now;
+    }
+}
+
+struct State<K> {
+    buckets: HashMap<K, Bucket>,
+    sweep_len: usize,
+}
+
+struct Buckets<K> {
+    capacity: usize,
+    period: Duration,
+    state: Mutex<State<K>>,
+}
+
+enum Acquire {
+    Ok,
+    TooCostly,
+    Exceeded { remaining: usize, reset_after: u64 },
+}
+
+impl<K: Hash + Eq + Clone> Buckets<K> {
+    fn acquire(&self, key: &K, cost: usize) -> Acquire {
+        if cost > self.capacity {
+            return Acquire::TooCostly;
+        }
+
+        let capacity = self.capacity as f64;
+        let rate = capacity / self.period.as_secs_f64();
+        let now = Instant::now();
+
+        let mut state = self.state.lock().unwrap();
+        let state = &mut *state;
+
+        // A full bucket is the same as a missing one, so the buckets that were
+        // refilled since their last use are evicted whenever the map has
+        // doubled in size.
+        if state.buckets.len() >= state.sweep_len.max(MIN_SWEEP_LEN) {
+            state.buckets.retain(|_, bucket| {
+                bucket.refill(capacity, rate, now);
+                bucket.tokens < capacity
+            });
+            state.sweep_len = state.buckets.len() * 2;
+        }
+
+        let bucket = state.buckets.entry(key.clone()).or_insert(Bucket {
+            tokens: capacity,
+            updated_at: now,
+        });
+        bucket.refill(capacity, rate, now);
+
+        if cost as f64 <= bucket.tokens {
+            bucket.tokens -= cost as f64;
+            Acquire::Ok
+        } else {
+            Acquire::Exceeded {
+                remaining: bucket.tokens as usize,
+                reset_after: ((cost as f64 - bucket.tokens) / rate).ceil() as u64,
+            }
+        }
+    }
+}
+
+/// Rate limit extension
+///
+/// This extension deducts the complexity of each query from a token bucket
+/// keyed by the data of type `K`, looked up in the request, session and schema
+/// data (for example an API key or a user id). Each bucket holds up to
+/// `capacity` tokens, and is refilled at a constant rate so that an empty
+/// bucket is full again after `period`.
+///
+/// Queries costing more than the remaining tokens are rejected before
+/// execution with an error whose `code` extension is `RATE_LIMITED`, and whose
+/// `rateLimit` extension contains the `limit`, the `remaining` tokens and the
+/// number of seconds until the bucket holds enough tokens for the query
+/// (`resetAfter`). Queries costing more than `capacity` can never pass, and
+/// are rejected with the `RATE_LIMIT_COST_EXCEEDED` code instead. Requests
+/// without the key data are not limited.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use async_graphql::{extensions::RateLimit, *};
+///
+/// #[derive(Clone, PartialEq, Eq, Hash)]
+/// struct ApiKey(String);
+///
+/// struct Query;
+///
+/// #[Object]
+/// impl Query {
+///     async fn value(&self) -> i32 {
+///         100
+///     }
+/// }
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+///     .extension(RateLimit::<ApiKey>::new(1, Duration::from_secs(60)))
+///     .finish();
+///
+/// let request = || Request::new("{ value }").data(ApiKey("key".to_string()));
+/// assert!(schema.execute(request()).await.is_ok());
+/// assert!(schema.execute(request()).await.is_err());
+/// # });
+/// ```
+pub struct RateLimit<K>(Arc<Buckets<K>>);
+
+impl<K: Hash + Eq + Clone + Send + Sync + 'static> RateLimit<K> {
+    /// Create a rate limit extension with buckets of `capacity` tokens, which
+    /// are entirely refilled in `period`.
+    pub fn new(capacity: usize, period: Duration) -> Self {
+        Self(Arc::new(Buckets {
+            capacity,
+            period,
+            state: Mutex::new(State {
+                buckets: HashMap::new(),
+                sweep_len: 0,
+            }),
+        }))
+    }
+}
+
+impl<K: Hash + Eq + Clone + Send + Sync + 'static> ExtensionFactory for RateLimit<K> {
+    fn create(&self) -> Arc<dyn Extension> {
+        Arc::new(RateLimitExtension(self.0.clone()))
+    }
+}
+
+struct RateLimitExtension<K>(Arc<Buckets<K>>);
+
+#[async_trait::async_trait]
+impl<K: Hash + Eq + Clone + Send + Sync + 'static> Extension for RateLimitExtension<K> {
+    async fn validation(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        next: NextValidation<'_>,
+    ) -> Result<ValidationResult, Vec<ServerError>> {
+        let res = next.run(ctx).await?;
+        let key = match ctx.data_opt::<K>() {
+            Some(key) => key,
+            None => return Ok(res),
+        };
+
+        let mut extensions = ErrorExtensionValues::default();
+        let message = match self.0.acquire(key, res.complexity) {
+            Acquire::Ok => return Ok(res),
+            Acquire::TooCostly => {
+                extensions.set("code", "RATE_LIMIT_COST_EXCEEDED");
+                extensions.set(
+                    "rateLimit",
+                    value!({
+                        "limit": self.0.capacity,
+                        "cost": res.complexity,
+                    }),
+                );
+                "Query cost exceeds the rate limit."
+            }
+            Acquire::Exceeded {
+                remaining,
+                reset_after,
+            } => {
+                extensions.set("code", "RATE_LIMITED");
+                extensions.set(
+                    "rateLimit",
+                    value!({
+                        "limit": self.0.capacity,
+                        "remaining": remaining,
+                        "cost": res.complexity,
+                        "resetAfter": reset_after,
+                    }),
+                );
+                "Rate limit exceeded."
+            }
+        };
+        let mut err = ServerError::new(message, None);
+        err.extensions = Some(extensions);
+        Err(vec![err])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{extensions::RateLimit, *};
+
+    #[derive(Clone, PartialEq, Eq, Hash)]
+    struct UserId(i32);
+
+    struct Query;
+
+    #[Object(internal)]
+    impl Query {
+        async fn a(&self) -> i32 {
+            1
+        }
+
+        async fn b(&self) -> i32 {
+            2
+        }
+    }
+
+    #[tokio::test]
+    async fn rate_limit() {
+        let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+            .extension(RateLimit::<UserId>::new(3, Duration::from_secs(3600)))
+            .finish();
+
+        assert_eq!(
+            schema
+                .execute(Request::new("{ a b }").data(UserId(1)))
+                .await
+                .into_result()
+                .unwrap()
+                .data,
+            value!({ "a": 1, "b": 2 })
+        );
+
+        let errors = schema
+            .execute(Request::new("{ a b }").data(UserId(1)))
+            .await
+            .into_result()
+            .unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].message, "Rate limit exceeded.");
+        let extensions = errors[0].extensions.as_ref().unwrap();
+        assert_eq!(extensions.get("code"), Some(&value!("RATE_LIMITED")));
+        let rate_limit = extensions.get("rateLimit").unwrap();
+        assert_eq!(
+            rate_limit,
+            &value!({
+                "limit": 3,
+                "remaining": 1,
+                "cost": 2,
+                "resetAfter": 1200,
+            })
+        );
+
+        // The remaining token is still available.
+        assert!(schema
+            .execute(Request::new("{ a }").data(UserId(1)))
+            .await
+            .is_ok());
+
+        // Other keys have their own bucket, and requests without a key are not
+        // limited.
+        assert!(schema
+            .execute(Request::new("{ a b }").data(UserId(2)))
+            .await
+            .is_ok());
+        assert!(schema.execute("{ a b }").await.is_ok());
+        assert!(schema.execute("{ a b }").await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn rate_limit_cost_exceeded() {
+        let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+            .extension(RateLimit::<UserId>::new(1, Duration::from_secs(3600)))
+            .finish();
+
+        let errors = schema
+            .execute(Request::new("{ a b }").data(UserId(1)))
+            .await
+            .into_result()
+            .unwrap_err();
+        assert_eq!(errors[0].message, "Query cost exceeds the rate limit.");
+        let extensions = errors[0].extensions.as_ref().unwrap();
+        assert_eq!(
+            extensions.get("code"),
+            Some(&value!("RATE_LIMIT_COST_EXCEEDED"))
+        );
+        assert_eq!(
+            extensions.get("rateLimit"),
+            Some(&value!({ "limit": 1, "cost": 2 }))
+        );
+
+        // The rejected query did not consume any token.
+        assert!(schema
+            .execute(Request::new("{ a }").data(UserId(1)))
+            .await
+            .is_ok());
+    }
+
+    #[test]
+    fn evict_full_buckets() {
+        let buckets = Buckets {
+            capacity: 1,
+            period: Duration::from_millis(1),
+            state: Mutex::new(State {
+                buckets: HashMap::new(),
+                sweep_len: 0,
+            }),
+        };
+        for key in 0..MIN_SWEEP_LEN {
+            assert!(matches!(buckets.acquire(&key, 1), Acquire::Ok));
+        }
+        std::thread::sleep(Duration::from_millis(2));
+
+        assert!(matches!(buckets.acquire(&MIN_SWEEP_LEN, 1), Acquire::Ok));
+        assert_eq!(buckets.state.lock().unwrap().buckets.len(), 1);
+    }
+}
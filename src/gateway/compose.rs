@@ -0,0 +1,417 @@
+use std::collections::HashMap;
+
+use indexmap::IndexMap;
+
+use crate::{
+    dynamic::SchemaError,
+    parser::{
+        self,
+        types::{
+            ConstDirective, DirectiveDefinition, DocumentOperations, FieldDefinition, Selection,
+            SelectionSet, ServiceDocument, TypeDefinition, TypeKind, TypeSystemDefinition,
+        },
+        Positioned,
+    },
+    registry::{Registry, SDLExportOptions},
+    Value,
+};
+
+/// The types added to the subgraphs by federation.
+const FEDERATION_TYPES: &[&str] = &[
+    "_Any",
+    "_Entity",
+    "_Service",
+    "_FieldSet",
+    "FieldSet",
+    "link__Import",
+    "link__Purpose",
+];
+
+/// The directives of federation, which are not part of the supergraph.
+const FEDERATION_DIRECTIVES: &[&str] = &[
+    "key",
+    "external",
+    "requires",
+    "provides",
+    "extends",
+    "shareable",
+    "inaccessible",
+    "override",
+    "tag",
+    "link",
+    "composeDirective",
+    "interfaceObject",
+];
+
+/// The federation information of a subgraph.
+pub(crate) struct Subgraph {
+    pub(crate) name: String,
+    pub(crate) types: HashMap<String, SubgraphType>,
+}
+
+#[derive(Default)]
+pub(crate) struct SubgraphType {
+    /// The field sets of the `@key` directives.
+    pub(crate) keys: Vec<SelectionSet>,
+    pub(crate) fields: HashMap<String, SubgraphField>,
+}
+
+pub(crate) struct SubgraphField {
+    /// The field is `@external`, or overridden by another subgraph.
+    external: bool,
+    /// The field set of the `@requires` directive.
+    pub(crate) requires: Option<SelectionSet>,
+}
+
+impl Subgraph {
+    /// Returns `true` if the subgraph can resolve a field of a type.
+    ///
+    /// External fields are only resolved when they are part of a key.
+    pub(crate) fn resolves(&self, type_name: &str, field_name: &str) -> bool {
+        let ty = match self.types.get(type_name) {
+            Some(ty) => ty,
+            None => return false,
+        };
+        match ty.fields.get(field_name) {
+            Some(field) if !field.external => true,
+            Some(_) => ty.keys.iter().any(|key| {
+                key.items.iter().any(|selection| match &selection.node {
+                    Selection::Field(field) => field.node.name.node == field_name,
+                    _ => false,
+                })
+            }),
+            None => false,
+        }
+    }
+
+    /// Returns `true` if the subgraph can resolve the top-level fields of a
+    /// field set.
+    pub(crate) fn provides(&self, type_name: &str, field_set: &SelectionSet) -> bool {
+        field_set
+            .items
+            .iter()
+            .all(|selection| match &selection.node {
+                Selection::Field(field) => self.resolves(type_name, &field.node.name.node),
+                _ => false,
+            })
+    }
+}
+
+/// A supergraph composed from the schemas of several subgraphs.
+///
+/// The types with the same name are merged, the fields of the root types are
+/// resolved by the subgraphs defining them, and the other fields of the
+/// entities are fetched with the `_entities` field of the subgraphs defining
+/// them, with the representations built from the fields of their `@key`
+/// and `@requires` directives.
+///
+/// The subgraph schemas are written in SDL, for example with
+/// `Schema::sdl_with_options(SDLExportOptions::new().federation())`. The
+/// federation types and directives are removed from the supergraph, as well
+/// as the `@inaccessible` fields, and subscriptions are not supported.
+pub struct Supergraph {
+    pub(crate) registry: Registry,
+    pub(crate) subgraphs: Vec<Subgraph>,
+}
+
+impl Supergraph {
+    /// Compose a supergraph from the names and the SDL of the subgraphs.
+    pub fn compose<N, S>(subgraphs: impl IntoIterator<Item = (N, S)>) -> Result<Self, SchemaError>
+    where
+        N: Into<String>,
+        S: AsRef<str>,
+    {
+        let mut types: IndexMap<String, Positioned<TypeDefinition>> = IndexMap::new();
+        let mut directives: IndexMap<String, Positioned<DirectiveDefinition>> = IndexMap::new();
+        let mut overrides = Vec::new();
+        let mut composed = Vec::new();
+
+        for (name, sdl) in subgraphs {
+            let name = name.into();
+            let doc = parser::parse_schema(sdl.as_ref()).map_err(|err| {
+                SchemaError(format!(
+                    r#"Failed to parse the subgraph "{}": {}"#,
+                    name, err
+                ))
+            })?;
+            let mut subgraph = Subgraph {
+                name,
+                types: HashMap::new(),
+            };
+
+            let mut roots = HashMap::new();
+            for definition in &doc.definitions {
+                if let TypeSystemDefinition::Schema(schema) = definition {
+                    let schema = &schema.node;
+                    for (root, name) in [
+                        (&schema.query, "Query"),
+                        (&schema.mutation, "Mutation"),
+                        (&schema.subscription, "Subscription"),
+                    ] {
+                        if let Some(root) = root {
+                            roots.insert(root.node.to_string(), name);
+                        }
+                    }
+                }
+            }
+
+            for definition in doc.definitions {
+                let mut definition = match definition {
+                    TypeSystemDefinition::Type(definition) => definition,
+                    TypeSystemDefinition::Directive(directive) => {
+                        if !FEDERATION_DIRECTIVES.contains(&directive.node.name.node.as_str()) {
+                            directives
+                                .entry(directive.node.name.node.to_string())
+                                .or_insert(directive);
+                        }
+                        continue;
+                    }
+                    TypeSystemDefinition::Schema(_) => continue,
+                };
+                let type_name = match roots.get(definition.node.name.node.as_str()) {
+                    Some(name) => name.to_string(),
+                    None => definition.node.name.node.to_string(),
+                };
+                if FEDERATION_TYPES.contains(&type_name.as_str()) || type_name == "Subscription" {
+                    continue;
+                }
+                definition.node.name.node = crate::Name::new(&type_name);
+
+                let ty = subgraph.types.entry(type_name.clone()).or_default();
+                for directive in directives_named(&definition.node.directives, "key") {
+                    ty.keys.push(field_set(directive, &type_name)?);
+                }
+                if let TypeKind::Object(object) = &mut definition.node.kind {
+                    if type_name == "Query" {
+                        object.fields.retain(|field| {
+                            !matches!(field.node.name.node.as_str(), "_entities" | "_service")
+                        });
+                    }
+                }
+                if let TypeKind::Object(_) | TypeKind::Interface(_) = &definition.node.kind {
+                    for field in fields(&definition.node) {
+                        let field_name = field.node.name.node.to_string();
+                        let requires = directives_named(&field.node.directives, "requires")
+                            .next()
+                            .map(|directive| field_set(directive, &type_name))
+                            .transpose()?;
+                        if let Some(from) = directives_named(&field.node.directives, "override")
+                            .next()
+                            .and_then(|directive| string_argument(directive, "from"))
+                        {
+                            overrides.push((
+                                from.to_string(),
+                                type_name.clone(),
+                                field_name.clone(),
+                            ));
+                        }
+                        ty.fields.insert(
+                            field_name,
+                            SubgraphField {
+                                external: directives_named(&field.node.directives, "external")
+                                    .next()
+                                    .is_some(),
+                                requires,
+                            },
+                        );
+                    }
+                }
+
+                strip_federation(&mut definition.node);
+                match types.get_mut(&type_name) {
+                    Some(existing) => merge_definition(&mut existing.node, definition.node)?,
+                    None => {
+                        types.insert(type_name, definition);
+                    }
+                }
+            }
+
+            composed.push(subgraph);
+        }
+
+        for (from, type_name, field_name) in overrides {
+            if let Some(field) = composed
+                .iter_mut()
+                .filter(|subgraph| subgraph.name == from)
+                .filter_map(|subgraph| subgraph.types.get_mut(&type_name))
+                .find_map(|ty| ty.fields.get_mut(&field_name))
+            {
+                field.external = true;
+            }
+        }
+
+        let doc = ServiceDocument {
+            definitions: directives
+                .into_values()
+                .map(TypeSystemDefinition::Directive)
+                .chain(types.into_values().map(TypeSystemDefinition::Type))
+                .collect(),
+        };
+        Ok(Self {
+            registry: Registry::from_service_document(&doc)?,
+            subgraphs: composed,
+        })
+    }
+
+    /// Returns the SDL of the supergraph.
+    pub fn sdl(&self) -> String {
+        self.registry.export_sdl(SDLExportOptions::new())
+    }
+
+    /// Returns the names of the subgraphs.
+    pub fn subgraphs(&self) -> impl Iterator<Item = &str> {
+        self.subgraphs.iter().map(|subgraph| subgraph.name.as_str())
+    }
+}
+
+fn directives_named<'a>(
+    directives: &'a [Positioned<ConstDirective>],
+    name: &'a str,
+) -> impl Iterator<Item = &'a ConstDirective> {
+    directives
+        .iter()
+        .map(|directive| &directive.node)
+        .filter(move |directive| directive.name.node == name)
+}
+
+fn string_argument<'a>(directive: &'a ConstDirective, name: &str) -> Option<&'a str> {
+    match directive.get_argument(name).map(|value| &value.node) {
+        Some(Value::String(value)) => Some(value),
+        _ => None,
+    }
+}
+
+/// Parses the `fields` argument of a `@key` or `@requires` directive.
+fn field_set(directive: &ConstDirective, type_name: &str) -> Result<SelectionSet, SchemaError> {
+    let error = || {
+        SchemaError(format!(
+            r#"Invalid field set of the directive "@{}" of the type "{}"."#,
+            directive.name.node, type_name
+        ))
+    };
+    let fields = string_argument(directive, "fields").ok_or_else(error)?;
+    match parser::parse_query(format!("{{ {} }}", fields))
+        .map_err(|_| error())?
+        .operations
+    {
+        DocumentOperations::Single(operation) => Ok(operation.node.selection_set.node),
+        DocumentOperations::Multiple(_) => Err(error()),
+    }
+}
+
+fn fields(definition: &TypeDefinition) -> &[Positioned<FieldDefinition>] {
+    match &definition.kind {
+        TypeKind::Object(ty) => &ty.fields,
+        TypeKind::Interface(ty) => &ty.fields,
+        _ => &[],
+    }
+}
+
+fn strip_federation(definition: &mut TypeDefinition) {
+    let is_federation = |directive: &Positioned<ConstDirective>| {
+        FEDERATION_DIRECTIVES.contains(&directive.node.name.node.as_str())
+    };
+    let strip_fields = |fields: &mut Vec<Positioned<FieldDefinition>>| {
+        fields.retain(|field| {
+            directives_named(&field.node.directives, "inaccessible")
+                .next()
+                .is_none()
+        });
+        for field in fields {
+            field
+                .node
+                .directives
+                .retain(|directive| !is_federation(directive));
+        }
+    };
+
+    definition.extend = false;
+    definition
+        .directives
+        .retain(|directive| !is_federation(directive));
+    match &mut definition.kind {
+        TypeKind::Object(ty) => strip_fields(&mut ty.fields),
+        TypeKind::Interface(ty) => strip_fields(&mut ty.fields),
+        _ => {}
+    }
+}
+
+fn merge_definition(
+    existing: &mut TypeDefinition,
+    definition: TypeDefinition,
+) -> Result<(), SchemaError> {
+    let type_name = definition.name.node.as_str();
+    let merge_fields = |existing: &mut Vec<Positioned<FieldDefinition>>,
+                        fields: Vec<Positioned<FieldDefinition>>| {
+        for field in fields {
+            match existing
+                .iter()
+                .find(|existing| existing.node.name.node == field.node.name.node)
+            {
+                Some(existing) if existing.node.ty.node != field.node.ty.node => {
+                    return Err(SchemaError(format!(
+                        r#"The field "{}.{}" has different types in the subgraphs."#,
+                        type_name, field.node.name.node
+                    )));
+                }
+                Some(_) => {}
+                None => existing.push(field),
+            }
+        }
+        Ok(())
+    };
+    fn merge_names<T: PartialEq>(existing: &mut Vec<Positioned<T>>, names: Vec<Positioned<T>>) {
+        for name in names {
+            if !existing.iter().any(|existing| existing.node == name.node) {
+                existing.push(name);
+            }
+        }
+    }
+
+    match (&mut existing.kind, definition.kind) {
+        (TypeKind::Scalar, TypeKind::Scalar) => {}
+        (TypeKind::Object(existing), TypeKind::Object(ty)) => {
+            merge_names(&mut existing.implements, ty.implements);
+            merge_fields(&mut existing.fields, ty.fields)?;
+        }
+        (TypeKind::Interface(existing), TypeKind::Interface(ty)) => {
+            merge_names(&mut existing.implements, ty.implements);
+            merge_fields(&mut existing.fields, ty.fields)?;
+        }
+        (TypeKind::Union(existing), TypeKind::Union(ty)) => {
+            merge_names(&mut existing.members, ty.members);
+        }
+        (TypeKind::Enum(existing), TypeKind::Enum(ty)) => {
+            for value in ty.values {
+                if !existing
+                    .values
+                    .iter()
+                    .any(|existing| existing.node.value.node == value.node.value.node)
+                {
+                    existing.values.push(value);
+                }
+            }
+        }
+        (TypeKind::InputObject(existing), TypeKind::InputObject(ty)) => {
+            for field in ty.fields {
+                if !existing
+                    .fields
+                    .iter()
+                    .any(|existing| existing.node.name.node == field.node.name.node)
+                {
+                    existing.fields.push(field);
+                }
+            }
+        }
+        _ => {
+            return Err(SchemaError(format!(
+                r#"The type "{}" has different kinds in the subgraphs."#,
+                type_name
+            )))
+        }
+    }
+    if existing.description.is_none() {
+        existing.description = definition.description;
+    }
+    Ok(())
+}
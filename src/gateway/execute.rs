@@ -0,0 +1,339 @@
+use std::sync::Mutex;
+
+use futures_util::{future::BoxFuture, FutureExt};
+use indexmap::IndexMap;
+
+use crate::{
+    gateway::{
+        plan::{FetchNode, FlattenNode, PlanNode, QueryPlan, SelectedField, KEY_PREFIX},
+        Fetcher, Supergraph,
+    },
+    registry::{MetaType, MetaTypeName},
+    Name, PathSegment, Request, Response, ServerError, Value, Variables,
+};
+
+struct Execution<'a> {
+    fetcher: &'a dyn Fetcher,
+    variables: &'a Variables,
+    data: Mutex<Value>,
+    errors: Mutex<Vec<ServerError>>,
+}
+
+/// Executes a query plan, and builds the response from the merged results
+/// of the subgraphs.
+pub(crate) async fn execute(
+    fetcher: &dyn Fetcher,
+    supergraph: &Supergraph,
+    plan: &QueryPlan,
+    variables: &Variables,
+) -> Response {
+    let execution = Execution {
+        fetcher,
+        variables,
+        data: Mutex::new(Value::Object(Default::default())),
+        errors: Mutex::new(Vec::new()),
+    };
+    execution.execute(&plan.node).await;
+
+    let data = execution.data.into_inner().unwrap();
+    let fields = plan.fields.iter().collect::<Vec<_>>();
+    let data = Completion { supergraph }
+        .complete(&plan.root_type, &data, &fields)
+        .unwrap_or_default();
+    let mut response = Response::new(data);
+    response.errors = execution.errors.into_inner().unwrap();
+    response
+}
+
+impl<'a> Execution<'a> {
+    fn execute<'b>(&'b self, node: &'b PlanNode) -> BoxFuture<'b, ()> {
+        async move {
+            match node {
+                PlanNode::Sequence(nodes) => {
+                    for node in nodes {
+                        self.execute(node).await;
+                    }
+                }
+                PlanNode::Parallel(nodes) => {
+                    futures_util::future::join_all(nodes.iter().map(|node| self.execute(node)))
+                        .await;
+                }
+                PlanNode::Fetch(fetch) => self.fetch(fetch).await,
+                PlanNode::Flatten(flatten) => self.flatten(flatten).await,
+            }
+        }
+        .boxed()
+    }
+
+    fn request(&self, fetch: &FetchNode, mut variables: Variables) -> Request {
+        for name in &fetch.variables {
+            if let Some(value) = self.variables.get(name) {
+                variables.insert(name.clone(), value.clone());
+            }
+        }
+        Request::new(fetch.operation.clone()).variables(variables)
+    }
+
+    async fn fetch(&self, fetch: &FetchNode) {
+        let response = self
+            .fetcher
+            .fetch(&fetch.subgraph, self.request(fetch, Variables::default()))
+            .await;
+        merge(&mut self.data.lock().unwrap(), response.data);
+        self.errors
+            .lock()
+            .unwrap()
+            .extend(response.errors.into_iter().map(|err| ServerError {
+                locations: Vec::new(),
+                ..err
+            }));
+    }
+
+    async fn flatten(&self, flatten: &FlattenNode) {
+        let (paths, representations): (Vec<_>, Vec<_>) = {
+            let data = self.data.lock().unwrap();
+            let mut entities = Vec::new();
+            collect_entities(&data, &flatten.path, &mut Vec::new(), &mut entities);
+            entities
+                .into_iter()
+                .filter(|(_, entity)| {
+                    matches!(entity.get("__typename"), Some(Value::String(type_name)) if *type_name == flatten.type_name)
+                })
+                .map(|(path, entity)| {
+                    let mut representation = IndexMap::new();
+                    representation.insert(
+                        Name::new("__typename"),
+                        Value::String(flatten.type_name.clone()),
+                    );
+                    for name in &flatten.requires {
+                        if let Some(value) = entity.get(format!("{}{}", KEY_PREFIX, name).as_str())
+                        {
+                            representation.insert(name.clone(), value.clone());
+                        }
+                    }
+                    (path, Value::Object(representation))
+                })
+                .unzip()
+        };
+        if representations.is_empty() {
+            return;
+        }
+
+        let mut variables = Variables::default();
+        variables.insert(Name::new("representations"), Value::List(representations));
+        let response = self
+            .fetcher
+            .fetch(
+                &flatten.fetch.subgraph,
+                self.request(&flatten.fetch, variables),
+            )
+            .await;
+
+        if let Value::Object(mut data) = response.data {
+            if let Some(Value::List(entities)) = data.remove("_entities") {
+                let mut data = self.data.lock().unwrap();
+                for (path, entity) in paths.iter().zip(entities) {
+                    if let Some(target) = get_mut(&mut data, path) {
+                        merge(target, entity);
+                    }
+                }
+            }
+        }
+
+        // The errors of the entities are moved to the path of the entities in
+        // the response. The subgraphs built with this crate don't include the
+        // index of the entity in the paths, which is only known if there is a
+        // single representation.
+        self.errors
+            .lock()
+            .unwrap()
+            .extend(response.errors.into_iter().map(|err| {
+                let path = match err.path.as_slice() {
+                    [PathSegment::Field(field), PathSegment::Index(idx), rest @ ..]
+                        if field == "_entities" && *idx < paths.len() =>
+                    {
+                        paths[*idx].iter().chain(rest).cloned().collect()
+                    }
+                    [PathSegment::Field(field), rest @ ..]
+                        if field == "_entities" && paths.len() == 1 =>
+                    {
+                        paths[0].iter().chain(rest).cloned().collect()
+                    }
+                    _ => Vec::new(),
+                };
+                ServerError {
+                    locations: Vec::new(),
+                    path,
+                    ..err
+                }
+            }));
+    }
+}
+
+/// Collects the objects at a path of the response.
+fn collect_entities<'a>(
+    value: &'a Value,
+    path: &[String],
+    current: &mut Vec<PathSegment>,
+    entities: &mut Vec<(Vec<PathSegment>, &'a IndexMap<Name, Value>)>,
+) {
+    match (path.split_first(), value) {
+        (None, Value::Object(obj)) => entities.push((current.clone(), obj)),
+        (Some((segment, rest)), Value::List(items)) if segment == "@" => {
+            for (idx, item) in items.iter().enumerate() {
+                current.push(PathSegment::Index(idx));
+                collect_entities(item, rest, current, entities);
+                current.pop();
+            }
+        }
+        (Some((segment, rest)), Value::Object(obj)) => {
+            if let Some(value) = obj.get(segment.as_str()) {
+                current.push(PathSegment::Field(segment.clone()));
+                collect_entities(value, rest, current, entities);
+                current.pop();
+            }
+        }
+        _ => {}
+    }
+}
+
+fn get_mut<'a>(value: &'a mut Value, path: &[PathSegment]) -> Option<&'a mut Value> {
+    match (path.split_first(), value) {
+        (None, value) => Some(value),
+        (Some((PathSegment::Index(idx), rest)), Value::List(items)) => {
+            get_mut(items.get_mut(*idx)?, rest)
+        }
+        (Some((PathSegment::Field(field), rest)), Value::Object(obj)) => {
+            get_mut(obj.get_mut(field.as_str())?, rest)
+        }
+        _ => None,
+    }
+}
+
+/// Merges a result of a subgraph into the response, without replacing the
+/// existing values with `null`.
+fn merge(target: &mut Value, value: Value) {
+    match (target, value) {
+        (Value::Object(target), Value::Object(obj)) => {
+            for (name, value) in obj {
+                match target.get_mut(&name) {
+                    Some(target) => merge(target, value),
+                    None => {
+                        target.insert(name, value);
+                    }
+                }
+            }
+        }
+        (Value::List(target), Value::List(items)) if target.len() == items.len() => {
+            for (target, item) in target.iter_mut().zip(items) {
+                merge(target, item);
+            }
+        }
+        (_, Value::Null) => {}
+        (target, value) => *target = value,
+    }
+}
+
+struct Completion<'a> {
+    supergraph: &'a Supergraph,
+}
+
+impl<'a> Completion<'a> {
+    /// Builds the value of the selected fields from the merged results,
+    /// returns `None` if a non-null value is `null`.
+    fn complete(&self, ty: &str, value: &Value, fields: &[&SelectedField]) -> Option<Value> {
+        match MetaTypeName::create(ty) {
+            MetaTypeName::NonNull(ty) => match self.complete(ty, value, fields) {
+                Some(Value::Null) | None => None,
+                value => value,
+            },
+            MetaTypeName::List(ty) => match value {
+                Value::List(items) => Some(
+                    items
+                        .iter()
+                        .map(|item| self.complete(ty, item, fields))
+                        .collect::<Option<Vec<_>>>()
+                        .map(Value::List)
+                        .unwrap_or_default(),
+                ),
+                _ => Some(Value::Null),
+            },
+            MetaTypeName::Named(type_name) => match value {
+                Value::Object(obj)
+                    if self
+                        .supergraph
+                        .registry
+                        .types
+                        .get(type_name)
+                        .map(MetaType::is_composite)
+                        .unwrap_or_default() =>
+                {
+                    Some(
+                        self.complete_object(type_name, obj, fields)
+                            .unwrap_or_default(),
+                    )
+                }
+                value => Some(value.clone()),
+            },
+        }
+    }
+
+    fn complete_object(
+        &self,
+        type_name: &str,
+        obj: &IndexMap<Name, Value>,
+        fields: &[&SelectedField],
+    ) -> Option<Value> {
+        let registry = &self.supergraph.registry;
+        let mut meta_type = registry.types.get(type_name)?;
+        if meta_type.is_abstract() {
+            if let Some(Value::String(type_name)) = obj.get("__typename") {
+                meta_type = registry.types.get(type_name.as_str())?;
+            }
+        }
+        let type_name = meta_type.name();
+
+        let mut grouped: IndexMap<&Name, Vec<&SelectedField>> = IndexMap::new();
+        for field in fields {
+            let applies = match &field.type_condition {
+                Some(type_condition) => {
+                    type_condition == type_name
+                        || registry
+                            .types
+                            .get(type_condition.as_str())
+                            .map(|ty| ty.is_possible_type(type_name))
+                            .unwrap_or_default()
+                }
+                None => true,
+            };
+            if applies {
+                grouped.entry(&field.response_key).or_default().push(field);
+            }
+        }
+
+        let mut output = IndexMap::new();
+        for (response_key, fields) in grouped {
+            if fields[0].name == "__typename" {
+                output.insert(response_key.clone(), Value::String(type_name.to_string()));
+                continue;
+            }
+            let meta_field = match meta_type {
+                MetaType::Object { .. } | MetaType::Interface { .. } => {
+                    meta_type.field_by_name(&fields[0].name)?
+                }
+                _ => return None,
+            };
+            let selections = fields
+                .iter()
+                .flat_map(|field| &field.selections)
+                .collect::<Vec<_>>();
+            let value = self.complete(
+                &meta_field.ty,
+                obj.get(response_key).unwrap_or(&Value::Null),
+                &selections,
+            )?;
+            output.insert(response_key.clone(), value);
+        }
+        Some(Value::Object(output))
+    }
+}
@@ -0,0 +1,216 @@
+//! Apollo Federation gateway.
+//!
+//! A [`Gateway`] executes the operations on a [`Supergraph`] composed from
+//! the schemas of several subgraphs, for example services built with
+//! `Schema::build(..).enable_federation()`:
+//!
+//! 1. The operation is validated against the supergraph.
+//! 2. A [`QueryPlan`] fetches the root fields from the subgraphs defining
+//!    them, and the other fields of the entities with the `_entities` field of
+//!    the subgraphs defining them, from the representations built with their
+//!    `@key` and `@requires` fields.
+//! 3. The requests are sent by a [`Fetcher`], and the results are merged into
+//!    the response.
+//!
+//! Introspection and subscriptions are not supported by the gateway.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use async_graphql::{
+//!     gateway::{Fetcher, Gateway, Supergraph},
+//!     *,
+//! };
+//!
+//! #[derive(SimpleObject)]
+//! struct User {
+//!     id: ID,
+//!     name: String,
+//! }
+//!
+//! struct AccountsQuery;
+//!
+//! #[Object(name = "Query")]
+//! impl AccountsQuery {
+//!     async fn me(&self) -> User {
+//!         User { id: "1".into(), name: "Alice".to_string() }
+//!     }
+//!
+//!     #[graphql(entity)]
+//!     async fn find_user_by_id(&self, id: ID) -> User {
+//!         User { id, name: "Alice".to_string() }
+//!     }
+//! }
+//!
+//! struct Review {
+//!     body: String,
+//! }
+//!
+//! #[Object]
+//! impl Review {
+//!     async fn body(&self) -> &str {
+//!         &self.body
+//!     }
+//! }
+//!
+//! struct UserReviews {
+//!     id: ID,
+//! }
+//!
+//! #[Object(extends, name = "User")]
+//! impl UserReviews {
+//!     #[graphql(external)]
+//!     async fn id(&self) -> &ID {
+//!         &self.id
+//!     }
+//!
+//!     async fn reviews(&self) -> Vec<Review> {
+//!         vec![Review { body: format!("Review of user {}", *self.id) }]
+//!     }
+//! }
+//!
+//! struct ReviewsQuery;
+//!
+//! #[Object(name = "Query")]
+//! impl ReviewsQuery {
+//!     #[graphql(entity)]
+//!     async fn find_user_by_id(&self, id: ID) -> UserReviews {
+//!         UserReviews { id }
+//!     }
+//! }
+//!
+//! struct Subgraphs {
+//!     accounts: Schema<AccountsQuery, EmptyMutation, EmptySubscription>,
+//!     reviews: Schema<ReviewsQuery, EmptyMutation, EmptySubscription>,
+//! }
+//!
+//! #[async_trait::async_trait]
+//! impl Fetcher for Subgraphs {
+//!     async fn fetch(&self, subgraph: &str, request: Request) -> Response {
+//!         match subgraph {
+//!             "accounts" => self.accounts.execute(request).await,
+//!             _ => self.reviews.execute(request).await,
+//!         }
+//!     }
+//! }
+//!
+//! # tokio::runtime::Runtime::new().unwrap().block_on(async move {
+//! let subgraphs = Subgraphs {
+//!     accounts: Schema::build(AccountsQuery, EmptyMutation, EmptySubscription)
+//!         .enable_federation()
+//!         .finish(),
+//!     reviews: Schema::build(ReviewsQuery, EmptyMutation, EmptySubscription)
+//!         .enable_federation()
+//!         .finish(),
+//! };
+//! let options = SDLExportOptions::new().federation();
+//! let supergraph = Supergraph::compose([
+//!     ("accounts", subgraphs.accounts.sdl_with_options(options.clone())),
+//!     ("reviews", subgraphs.reviews.sdl_with_options(options)),
+//! ])
+//! .unwrap();
+//!
+//! let gateway = Gateway::new(supergraph, subgraphs);
+//! assert_eq!(
+//!     gateway
+//!         .execute("{ me { name reviews { body } } }")
+//!         .await
+//!         .into_result()
+//!         .unwrap()
+//!         .data,
+//!     value!({
+//!         "me": {
+//!             "name": "Alice",
+//!             "reviews": [{ "body": "Review of user 1" }],
+//!         },
+//!     })
+//! );
+//! # });
+//! ```
+
+mod compose;
+mod execute;
+mod plan;
+
+pub use compose::Supergraph;
+pub use plan::{FetchNode, FlattenNode, PlanNode, QueryPlan};
+
+use crate::{
+    parser,
+    schema::{remove_skipped_selection, take_operation},
+    Request, Response, ServerError, Variables,
+};
+
+/// Sends the requests of a gateway to the subgraphs.
+#[async_trait::async_trait]
+pub trait Fetcher: Send + Sync + 'static {
+    /// Send a request to a subgraph.
+    async fn fetch(&self, subgraph: &str, request: Request) -> Response;
+}
+
+/// A federation gateway, executing the operations on a supergraph.
+pub struct Gateway {
+    supergraph: Supergraph,
+    fetcher: Box<dyn Fetcher>,
+}
+
+impl Gateway {
+    /// Create a gateway sending the requests to the subgraphs with a
+    /// fetcher.
+    pub fn new(supergraph: Supergraph, fetcher: impl Fetcher) -> Self {
+        Self {
+            supergraph,
+            fetcher: Box::new(fetcher),
+        }
+    }
+
+    /// Returns the supergraph.
+    pub fn supergraph(&self) -> &Supergraph {
+        &self.supergraph
+    }
+
+    /// Returns the query plan of a request, without executing it.
+    pub fn plan(&self, request: impl Into<Request>) -> Result<QueryPlan, Vec<ServerError>> {
+        self.prepare(&request.into()).map(|(plan, _)| plan)
+    }
+
+    /// Execute a request.
+    pub async fn execute(&self, request: impl Into<Request>) -> Response {
+        let request = request.into();
+        match self.prepare(&request) {
+            Ok((plan, variables)) => {
+                execute::execute(&*self.fetcher, &self.supergraph, &plan, &variables).await
+            }
+            Err(errors) => Response::from_request_errors(errors),
+        }
+    }
+
+    fn prepare(&self, request: &Request) -> Result<(QueryPlan, Variables), Vec<ServerError>> {
+        let mut document = parser::parse_query(&request.query).map_err(|err| vec![err.into()])?;
+        self.supergraph
+            .registry
+            .validate(&document, Some(&request.variables))?;
+        let (_, mut operation) =
+            take_operation(document.operations, request.operation_name.as_deref())
+                .map_err(|err| vec![err])?;
+
+        let mut variables = request.variables.clone();
+        for definition in &operation.node.variable_definitions {
+            let definition = &definition.node;
+            if !variables.contains_key(&definition.name.node) {
+                if let Some(default_value) = &definition.default_value {
+                    variables.insert(definition.name.node.clone(), default_value.node.clone());
+                }
+            }
+        }
+
+        for fragment in document.fragments.values_mut() {
+            remove_skipped_selection(&mut fragment.node.selection_set.node, &variables);
+        }
+        remove_skipped_selection(&mut operation.node.selection_set.node, &variables);
+
+        let plan = plan::plan(&self.supergraph, &operation.node, &document.fragments)
+            .map_err(|err| vec![err])?;
+        Ok((plan, variables))
+    }
+}
@@ -0,0 +1,692 @@
+use std::{collections::HashMap, fmt::Write};
+
+use async_graphql_value::Value;
+use indexmap::{IndexMap, IndexSet};
+
+use crate::{
+    gateway::compose::{Subgraph, Supergraph},
+    parser::types::{
+        FragmentDefinition, OperationDefinition, OperationType, Selection, SelectionSet,
+        VariableDefinition,
+    },
+    registry::{MetaType, MetaTypeName},
+    Name, Positioned, ServerError,
+};
+
+/// The prefix of the aliases of the fields fetched to build the
+/// representations of the entities.
+pub(crate) const KEY_PREFIX: &str = "_key_";
+
+/// A plan executing an operation with the subgraphs.
+#[derive(Debug, Clone)]
+pub struct QueryPlan {
+    /// The root node of the plan.
+    pub node: PlanNode,
+    pub(crate) root_type: String,
+    pub(crate) fields: Vec<SelectedField>,
+}
+
+/// A node of a query plan.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PlanNode {
+    /// Nodes executed one after the other.
+    Sequence(Vec<PlanNode>),
+    /// Nodes executed concurrently.
+    Parallel(Vec<PlanNode>),
+    /// Fetches root fields from a subgraph.
+    Fetch(FetchNode),
+    /// Fetches fields of the entities at a path of the response from a
+    /// subgraph.
+    Flatten(FlattenNode),
+}
+
+/// A request sent to a subgraph.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FetchNode {
+    /// The name of the subgraph.
+    pub subgraph: String,
+    /// The operation sent to the subgraph.
+    pub operation: String,
+    /// The variables of the request used by the operation.
+    pub variables: Vec<Name>,
+}
+
+/// A request fetching the fields of entities with the `_entities` field of a
+/// subgraph, whose results are merged into the entities.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FlattenNode {
+    /// The path of the entities in the response, `@` stands for the items of
+    /// a list.
+    pub path: Vec<String>,
+    /// The type of the entities.
+    pub type_name: String,
+    /// The fields of the representations of the entities.
+    pub requires: Vec<Name>,
+    /// The request sent to the subgraph, with the representations in the
+    /// `representations` variable.
+    pub fetch: FetchNode,
+}
+
+/// A field of the operation, with the fragments inlined.
+#[derive(Debug, Clone)]
+pub(crate) struct SelectedField {
+    /// The type the field is selected on, if it is selected in a fragment.
+    pub(crate) type_condition: Option<Name>,
+    pub(crate) response_key: Name,
+    pub(crate) name: Name,
+    arguments: Vec<(Positioned<Name>, Positioned<Value>)>,
+    pub(crate) selections: Vec<SelectedField>,
+}
+
+/// A fetch being planned.
+struct Fetch<'a> {
+    subgraph: usize,
+    variables: IndexSet<Name>,
+    dependents: IndexMap<(Vec<String>, usize, &'a str), Dependent<'a>>,
+}
+
+/// The fields of entities fetched from another subgraph.
+struct Dependent<'a> {
+    path: Vec<String>,
+    subgraph: usize,
+    type_name: &'a str,
+    requires: IndexSet<Name>,
+    fields: Vec<SelectedField>,
+    /// The fetch depends on fields fetched by the other dependent fetches.
+    deferred: bool,
+}
+
+struct Planner<'a> {
+    supergraph: &'a Supergraph,
+    variable_definitions: &'a [Positioned<VariableDefinition>],
+}
+
+/// Plans the execution of an operation, whose skipped selections have been
+/// removed.
+pub(crate) fn plan(
+    supergraph: &Supergraph,
+    operation: &OperationDefinition,
+    fragments: &HashMap<Name, Positioned<FragmentDefinition>>,
+) -> Result<QueryPlan, ServerError> {
+    let registry = &supergraph.registry;
+    let root_type = match operation.ty {
+        OperationType::Query => registry.query_type.as_str(),
+        OperationType::Mutation => registry
+            .mutation_type
+            .as_deref()
+            .ok_or_else(|| ServerError::new("Schema is not configured for mutations.", None))?,
+        OperationType::Subscription => {
+            return Err(ServerError::new(
+                "Subscriptions are not supported by the gateway.",
+                None,
+            ))
+        }
+    };
+
+    let mut fields = Vec::new();
+    collect_fields(
+        supergraph,
+        fragments,
+        &operation.selection_set.node,
+        None,
+        &mut fields,
+    );
+
+    let planner = Planner {
+        supergraph,
+        variable_definitions: &operation.variable_definitions,
+    };
+    let mut groups: Vec<(usize, Vec<SelectedField>)> = Vec::new();
+    for field in &fields {
+        if field.name == "__typename" {
+            continue;
+        }
+        let subgraph = supergraph
+            .subgraphs
+            .iter()
+            .position(|subgraph| subgraph.resolves(root_type, &field.name))
+            .ok_or_else(|| cannot_plan(root_type, &field.name))?;
+        // The root fields of a mutation are executed serially, so only the
+        // consecutive fields of a subgraph are fetched together.
+        let group = match operation.ty {
+            OperationType::Mutation => groups.last_mut().filter(|group| group.0 == subgraph),
+            _ => groups.iter_mut().find(|group| group.0 == subgraph),
+        };
+        match group {
+            Some(group) => group.1.push(field.clone()),
+            None => groups.push((subgraph, vec![field.clone()])),
+        }
+    }
+
+    let mut nodes = groups
+        .into_iter()
+        .map(|(subgraph, fields)| planner.plan_fetch(subgraph, operation.ty, &fields, None))
+        .collect::<Result<Vec<_>, _>>()?;
+    let node = match (operation.ty, nodes.len()) {
+        (_, 1) => nodes.remove(0),
+        (OperationType::Mutation, _) => PlanNode::Sequence(nodes),
+        _ => PlanNode::Parallel(nodes),
+    };
+
+    Ok(QueryPlan {
+        node,
+        root_type: root_type.to_string(),
+        fields,
+    })
+}
+
+fn cannot_plan(type_name: &str, field_name: &str) -> ServerError {
+    ServerError::new(
+        format!(
+            r#"Cannot plan the field "{}.{}", no subgraph can resolve it."#,
+            type_name, field_name
+        ),
+        None,
+    )
+}
+
+fn collect_fields(
+    supergraph: &Supergraph,
+    fragments: &HashMap<Name, Positioned<FragmentDefinition>>,
+    selection_set: &SelectionSet,
+    type_condition: Option<&Name>,
+    fields: &mut Vec<SelectedField>,
+) {
+    // The nested type conditions are narrowed to the object types.
+    fn narrow<'a>(
+        supergraph: &Supergraph,
+        outer: Option<&'a Name>,
+        inner: &'a Name,
+    ) -> Option<&'a Name> {
+        let is_object = |name: &Name| {
+            matches!(
+                supergraph.registry.types.get(name.as_str()),
+                Some(MetaType::Object { .. })
+            )
+        };
+        match outer {
+            Some(outer) if !is_object(inner) && is_object(outer) => Some(outer),
+            _ => Some(inner),
+        }
+    }
+
+    for selection in &selection_set.items {
+        match &selection.node {
+            Selection::Field(field) => {
+                let field = &field.node;
+                let mut selections = Vec::new();
+                collect_fields(
+                    supergraph,
+                    fragments,
+                    &field.selection_set.node,
+                    None,
+                    &mut selections,
+                );
+                fields.push(SelectedField {
+                    type_condition: type_condition.cloned(),
+                    response_key: field.response_key().node.clone(),
+                    name: field.name.node.clone(),
+                    arguments: field.arguments.clone(),
+                    selections,
+                });
+            }
+            Selection::FragmentSpread(fragment_spread) => {
+                if let Some(fragment) = fragments.get(&fragment_spread.node.fragment_name.node) {
+                    collect_fields(
+                        supergraph,
+                        fragments,
+                        &fragment.node.selection_set.node,
+                        narrow(
+                            supergraph,
+                            type_condition,
+                            &fragment.node.type_condition.node.on.node,
+                        ),
+                        fields,
+                    );
+                }
+            }
+            Selection::InlineFragment(inline_fragment) => {
+                let inline_fragment = &inline_fragment.node;
+                collect_fields(
+                    supergraph,
+                    fragments,
+                    &inline_fragment.selection_set.node,
+                    match &inline_fragment.type_condition {
+                        Some(inner) => narrow(supergraph, type_condition, &inner.node.on.node),
+                        None => type_condition,
+                    },
+                    fields,
+                );
+            }
+        }
+    }
+}
+
+impl<'a> Planner<'a> {
+    fn subgraph(&self, index: usize) -> &'a Subgraph {
+        &self.supergraph.subgraphs[index]
+    }
+
+    /// The concrete types of a type which are also possible types of the
+    /// parent type.
+    fn concrete_types(&self, type_name: &str, parent_type: &str) -> Vec<&'a str> {
+        let registry = &self.supergraph.registry;
+        let possible_types = |type_name: &str| -> Vec<&'a str> {
+            match registry.types.get(type_name) {
+                Some(ty @ MetaType::Object { .. }) => vec![ty.name()],
+                Some(ty) => ty
+                    .possible_types()
+                    .map(|types| types.iter().map(String::as_str).collect())
+                    .unwrap_or_default(),
+                None => Vec::new(),
+            }
+        };
+        let parent_types = possible_types(parent_type);
+        possible_types(type_name)
+            .into_iter()
+            .filter(|ty| parent_types.contains(ty))
+            .collect()
+    }
+
+    /// Finds a subgraph resolving a field of an entity, whose key fields are
+    /// provided by the current subgraph, as well as the required fields if
+    /// `provides_requires` is `true`.
+    fn find_entity_fetch(
+        &self,
+        current: usize,
+        type_name: &str,
+        field_name: &str,
+        provides_requires: bool,
+    ) -> Option<(usize, &'a SelectionSet, Option<&'a SelectionSet>)> {
+        let current_subgraph = self.subgraph(current);
+        self.supergraph
+            .subgraphs
+            .iter()
+            .enumerate()
+            .filter(|(index, subgraph)| {
+                *index != current && subgraph.resolves(type_name, field_name)
+            })
+            .find_map(|(index, subgraph)| {
+                let ty = subgraph.types.get(type_name)?;
+                let requires = ty
+                    .fields
+                    .get(field_name)
+                    .and_then(|field| field.requires.as_ref());
+                if let Some(requires) = requires {
+                    if provides_requires && !current_subgraph.provides(type_name, requires) {
+                        return None;
+                    }
+                }
+                let key = ty
+                    .keys
+                    .iter()
+                    .find(|key| current_subgraph.provides(type_name, key))?;
+                Some((index, key, requires))
+            })
+    }
+
+    fn plan_fetch(
+        &self,
+        subgraph: usize,
+        operation_type: OperationType,
+        fields: &[SelectedField],
+        flatten: Option<Dependent<'a>>,
+    ) -> Result<PlanNode, ServerError> {
+        let mut fetch = Fetch {
+            subgraph,
+            variables: IndexSet::new(),
+            dependents: IndexMap::new(),
+        };
+        let mut selection_set = String::new();
+        match &flatten {
+            Some(dependent) => {
+                self.write_selection_set(
+                    &mut fetch,
+                    dependent.type_name,
+                    fields,
+                    &dependent.path,
+                    &mut selection_set,
+                )?;
+                selection_set = format!(
+                    "{{ _entities(representations: $representations) {{ ... on {} {} }} }}",
+                    dependent.type_name, selection_set
+                );
+            }
+            None => {
+                let root_type = match operation_type {
+                    OperationType::Mutation => self.supergraph.registry.mutation_type.as_deref(),
+                    _ => Some(self.supergraph.registry.query_type.as_str()),
+                }
+                .unwrap_or_default();
+                self.write_selection_set(&mut fetch, root_type, fields, &[], &mut selection_set)?;
+            }
+        }
+
+        let mut variable_definitions = Vec::new();
+        if flatten.is_some() {
+            variable_definitions.push("$representations: [_Any!]!".to_string());
+        }
+        for definition in self.variable_definitions {
+            let definition = &definition.node;
+            if fetch.variables.contains(&definition.name.node) {
+                let mut output = format!("${}: {}", definition.name.node, definition.var_type.node);
+                if let Some(default_value) = &definition.default_value {
+                    let _ = write!(output, " = {}", default_value.node);
+                }
+                variable_definitions.push(output);
+            }
+        }
+        let mut operation = operation_type.to_string();
+        if !variable_definitions.is_empty() {
+            let _ = write!(operation, "({})", variable_definitions.join(", "));
+        }
+        operation.push(' ');
+        operation.push_str(&selection_set);
+
+        let fetch_node = FetchNode {
+            subgraph: self.subgraph(subgraph).name.clone(),
+            operation,
+            variables: fetch.variables.into_iter().collect(),
+        };
+        let node = match flatten {
+            Some(dependent) => PlanNode::Flatten(FlattenNode {
+                path: dependent.path,
+                type_name: dependent.type_name.to_string(),
+                requires: dependent.requires.into_iter().collect(),
+                fetch: fetch_node,
+            }),
+            None => PlanNode::Fetch(fetch_node),
+        };
+
+        let (deferred, dependents): (Vec<_>, Vec<_>) = fetch
+            .dependents
+            .into_values()
+            .partition(|dependent| dependent.deferred);
+        let mut nodes = vec![node];
+        for dependents in [dependents, deferred] {
+            let mut dependents = dependents
+                .into_iter()
+                .map(|mut dependent| {
+                    let fields = std::mem::take(&mut dependent.fields);
+                    self.plan_fetch(
+                        dependent.subgraph,
+                        OperationType::Query,
+                        &fields,
+                        Some(dependent),
+                    )
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            match dependents.len() {
+                0 => {}
+                1 => match dependents.remove(0) {
+                    PlanNode::Sequence(sequence) => nodes.extend(sequence),
+                    node => nodes.push(node),
+                },
+                _ => nodes.push(PlanNode::Parallel(dependents)),
+            }
+        }
+        Ok(match nodes.len() {
+            1 => nodes.remove(0),
+            _ => PlanNode::Sequence(nodes),
+        })
+    }
+
+    /// Writes the selection set of the fields resolved by the subgraph of a
+    /// fetch, and adds the other fields to the dependent fetches.
+    fn write_selection_set(
+        &self,
+        fetch: &mut Fetch<'a>,
+        parent_type: &str,
+        fields: &[SelectedField],
+        path: &[String],
+        output: &mut String,
+    ) -> Result<(), ServerError> {
+        let registry = &self.supergraph.registry;
+        let subgraph = self.subgraph(fetch.subgraph);
+
+        // The fields of the representations of the entities, by type.
+        let mut keys: IndexMap<&'a str, IndexSet<String>> = IndexMap::new();
+
+        output.push_str("{ __typename");
+        for field in fields {
+            if field.name == "__typename" {
+                continue;
+            }
+            let owner = field.type_condition.as_deref().unwrap_or(parent_type);
+
+            if subgraph.resolves(owner, &field.name) {
+                let meta_field = registry
+                    .types
+                    .get(owner)
+                    .and_then(|ty| ty.field_by_name(&field.name))
+                    .ok_or_else(|| cannot_plan(owner, &field.name))?;
+                if owner != parent_type {
+                    let _ = write!(output, " ... on {} {{", owner);
+                }
+                output.push(' ');
+                if field.response_key != field.name {
+                    let _ = write!(output, "{}: ", field.response_key);
+                }
+                output.push_str(&field.name);
+                if !field.arguments.is_empty() {
+                    output.push('(');
+                    for (idx, (name, value)) in field.arguments.iter().enumerate() {
+                        if idx > 0 {
+                            output.push_str(", ");
+                        }
+                        collect_variables(&value.node, &mut fetch.variables);
+                        let _ = write!(output, "{}: {}", name.node, value.node);
+                    }
+                    output.push(')');
+                }
+                if !field.selections.is_empty() {
+                    let mut field_path = path.to_vec();
+                    field_path.push(field.response_key.to_string());
+                    let mut ty = MetaTypeName::create(&meta_field.ty);
+                    loop {
+                        match ty {
+                            MetaTypeName::NonNull(inner) => ty = MetaTypeName::create(inner),
+                            MetaTypeName::List(inner) => {
+                                field_path.push("@".to_string());
+                                ty = MetaTypeName::create(inner);
+                            }
+                            MetaTypeName::Named(_) => break,
+                        }
+                    }
+                    output.push(' ');
+                    self.write_selection_set(
+                        fetch,
+                        MetaTypeName::concrete_typename(&meta_field.ty),
+                        &field.selections,
+                        &field_path,
+                        output,
+                    )?;
+                }
+                if owner != parent_type {
+                    output.push_str(" }");
+                }
+                continue;
+            }
+
+            let concrete_types = self.concrete_types(owner, parent_type);
+            if concrete_types.is_empty() {
+                return Err(cannot_plan(owner, &field.name));
+            }
+            for type_name in concrete_types {
+                let (index, key, requires) = self
+                    .find_entity_fetch(fetch.subgraph, type_name, &field.name, true)
+                    .or_else(|| {
+                        self.find_entity_fetch(fetch.subgraph, type_name, &field.name, false)
+                    })
+                    .ok_or_else(|| cannot_plan(type_name, &field.name))?;
+                let keys = keys.entry(type_name).or_default();
+                let mut field_sets = vec![key];
+                let mut fetched_requires = None;
+                if let Some(requires) = requires {
+                    if subgraph.provides(type_name, requires) {
+                        field_sets.push(requires);
+                    } else {
+                        // The required fields are fetched from other subgraphs
+                        // before the field.
+                        for required in field_set_fields(requires, true) {
+                            let (index, key, _) = self
+                                .find_entity_fetch(fetch.subgraph, type_name, &required.name, true)
+                                .filter(|(_, _, requires)| requires.is_none())
+                                .ok_or_else(|| cannot_plan(type_name, &required.name))?;
+                            self.add_dependent(
+                                fetch,
+                                keys,
+                                path,
+                                type_name,
+                                index,
+                                &[key],
+                                required,
+                                false,
+                            );
+                        }
+                        fetched_requires = Some(requires);
+                    }
+                }
+                self.add_dependent(
+                    fetch,
+                    keys,
+                    path,
+                    type_name,
+                    index,
+                    &field_sets,
+                    SelectedField {
+                        type_condition: None,
+                        ..field.clone()
+                    },
+                    fetched_requires.is_some(),
+                );
+                if let Some(requires) = fetched_requires {
+                    if let Some(dependent) =
+                        fetch.dependents.get_mut(&(path.to_vec(), index, type_name))
+                    {
+                        dependent.requires.extend(field_set_names(requires));
+                    }
+                }
+            }
+        }
+
+        for (type_name, keys) in keys {
+            if type_name != parent_type {
+                let _ = write!(output, " ... on {} {{", type_name);
+            }
+            for key in keys {
+                output.push(' ');
+                output.push_str(&key);
+            }
+            if type_name != parent_type {
+                output.push_str(" }");
+            }
+        }
+        output.push_str(" }");
+        Ok(())
+    }
+}
+
+impl<'a> Planner<'a> {
+    /// Adds a field to the fetch of the entities of a type at a path, and
+    /// the fields of their representations to the keys to fetch.
+    #[allow(clippy::too_many_arguments)]
+    fn add_dependent(
+        &self,
+        fetch: &mut Fetch<'a>,
+        keys: &mut IndexSet<String>,
+        path: &[String],
+        type_name: &'a str,
+        subgraph: usize,
+        field_sets: &[&SelectionSet],
+        field: SelectedField,
+        deferred: bool,
+    ) {
+        let dependent = fetch
+            .dependents
+            .entry((path.to_vec(), subgraph, type_name))
+            .or_insert_with(|| Dependent {
+                path: path.to_vec(),
+                subgraph,
+                type_name,
+                requires: IndexSet::new(),
+                fields: Vec::new(),
+                deferred: false,
+            });
+        for field_set in field_sets {
+            for selection in &field_set.items {
+                if let Selection::Field(key_field) = &selection.node {
+                    let mut output = format!("{}{}: ", KEY_PREFIX, key_field.node.name.node);
+                    write_field_set_field(&key_field.node, &mut output);
+                    keys.insert(output);
+                }
+            }
+            dependent.requires.extend(field_set_names(field_set));
+        }
+        dependent.fields.push(field);
+        dependent.deferred |= deferred;
+    }
+}
+
+fn field_set_names(field_set: &SelectionSet) -> impl Iterator<Item = Name> + '_ {
+    field_set
+        .items
+        .iter()
+        .filter_map(|selection| match &selection.node {
+            Selection::Field(field) => Some(field.node.name.node.clone()),
+            _ => None,
+        })
+}
+
+/// Converts a field set to selected fields, the top-level fields are aliased
+/// like the fields of the representations.
+fn field_set_fields(field_set: &SelectionSet, top_level: bool) -> Vec<SelectedField> {
+    field_set
+        .items
+        .iter()
+        .filter_map(|selection| match &selection.node {
+            Selection::Field(field) => Some(SelectedField {
+                type_condition: None,
+                response_key: if top_level {
+                    Name::new(format!("{}{}", KEY_PREFIX, field.node.name.node))
+                } else {
+                    field.node.name.node.clone()
+                },
+                name: field.node.name.node.clone(),
+                arguments: Vec::new(),
+                selections: field_set_fields(&field.node.selection_set.node, false),
+            }),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Writes a field of a `@key` or `@requires` field set.
+fn write_field_set_field(field: &crate::parser::types::Field, output: &mut String) {
+    output.push_str(&field.name.node);
+    if !field.selection_set.node.items.is_empty() {
+        output.push_str(" {");
+        for selection in &field.selection_set.node.items {
+            if let Selection::Field(field) = &selection.node {
+                output.push(' ');
+                write_field_set_field(&field.node, output);
+            }
+        }
+        output.push_str(" }");
+    }
+}
+
+fn collect_variables(value: &Value, variables: &mut IndexSet<Name>) {
+    match value {
+        Value::Variable(name) => {
+            variables.insert(name.clone());
+        }
+        Value::List(items) => items
+            .iter()
+            .for_each(|item| collect_variables(item, variables)),
+        Value::Object(obj) => obj
+            .values()
+            .for_each(|item| collect_variables(item, variables)),
+        _ => {}
+    }
+}
@@ -0,0 +1,107 @@
+use mime::Mime;
+
+/// The `application/graphql-response+json` media type of the GraphQL over
+/// HTTP specification.
+pub const GRAPHQL_RESPONSE_CONTENT_TYPE: &str = "application/graphql-response+json";
+
+/// The content type of a GraphQL response sent over HTTP.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ResponseContentType {
+    /// `application/json`, the response is always sent with the `200 OK`
+    /// status code.
+    Json,
+
+    /// `application/graphql-response+json`, requests failing before execution
+    /// (such as parse or validation errors) get a `4xx` status code.
+    GraphQLResponseJson,
+}
+
+impl ResponseContentType {
+    /// Negotiates the content type from the `Accept` header of a request.
+    ///
+    /// `application/graphql-response+json` is used when the client prefers it
+    /// over `application/json`, otherwise, including when the header is
+    /// missing, `application/json` is used for compatibility with legacy
+    /// clients.
+    pub fn from_accept(accept: Option<&str>) -> Self {
+        let accept = match accept {
+            Some(accept) => accept,
+            None => return ResponseContentType::Json,
+        };
+
+        let mut graphql_response_q = 0.0;
+        let mut json_q = 0.0;
+        for ty in accept
+            .split(',')
+            .filter_map(|ty| ty.trim().parse::<Mime>().ok())
+        {
+            let q = ty
+                .get_param("q")
+                .and_then(|q| q.as_str().parse::<f32>().ok())
+                .unwrap_or(1.0);
+            if ty.type_() != mime::APPLICATION {
+                continue;
+            }
+            if ty.subtype() == "graphql-response" && ty.suffix() == Some(mime::JSON) {
+                graphql_response_q = f32::max(graphql_response_q, q);
+            } else if ty.subtype() == mime::JSON {
+                json_q = f32::max(json_q, q);
+            }
+        }
+
+        if graphql_response_q > 0.0 && graphql_response_q >= json_q {
+            ResponseContentType::GraphQLResponseJson
+        } else {
+            ResponseContentType::Json
+        }
+    }
+
+    /// Returns the value of the `Content-Type` header.
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            ResponseContentType::Json => "application/json",
+            ResponseContentType::GraphQLResponseJson => GRAPHQL_RESPONSE_CONTENT_TYPE,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_accept() {
+        assert_eq!(
+            ResponseContentType::from_accept(None),
+            ResponseContentType::Json
+        );
+        assert_eq!(
+            ResponseContentType::from_accept(Some("*/*")),
+            ResponseContentType::Json
+        );
+        assert_eq!(
+            ResponseContentType::from_accept(Some("application/json")),
+            ResponseContentType::Json
+        );
+        assert_eq!(
+            ResponseContentType::from_accept(Some("application/graphql-response+json")),
+            ResponseContentType::GraphQLResponseJson
+        );
+        assert_eq!(
+            ResponseContentType::from_accept(Some(
+                "application/graphql-response+json, application/json;q=0.9"
+            )),
+            ResponseContentType::GraphQLResponseJson
+        );
+        assert_eq!(
+            ResponseContentType::from_accept(Some(
+                "application/graphql-response+json;q=0.5, application/json"
+            )),
+            ResponseContentType::Json
+        );
+        assert_eq!(
+            ResponseContentType::from_accept(Some("application/graphql-response+json;q=0")),
+            ResponseContentType::Json
+        );
+    }
+}
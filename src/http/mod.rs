@@ -1,20 +1,102 @@
 //! A helper module that supports HTTP
 
+mod content_type;
 mod graphiql_source;
 mod multipart;
+mod multipart_mixed;
 mod playground_source;
+mod sse;
 mod websocket;
 
+use std::collections::HashMap;
+
+pub use content_type::{ResponseContentType, GRAPHQL_RESPONSE_CONTENT_TYPE};
 use futures_util::io::{AsyncRead, AsyncReadExt};
 pub use graphiql_source::graphiql_source;
 use mime;
 pub use multipart::MultipartOptions;
+pub use multipart_mixed::{
+    accepts_multipart_mixed, create_multipart_mixed_stream, MULTIPART_MIXED_CONTENT_TYPE,
+};
 pub use playground_source::{playground_source, GraphQLPlaygroundConfig};
+pub use sse::{
+    accepts_event_stream, create_sse_stream, SseConnections, SseError, SseEventStream,
+    SSE_CONTENT_TYPE, SSE_TOKEN_HEADER,
+};
 pub use websocket::{
-    ClientMessage, Protocols as WebSocketProtocols, WebSocket, WsMessage, ALL_WEBSOCKET_PROTOCOLS,
+    ClientMessage, Protocols as WebSocketProtocols, WebSocket, WsCloseHandle, WsMessage,
+    WsOverflowPolicy, ALL_WEBSOCKET_PROTOCOLS,
+};
+
+use serde::Deserialize;
+
+use crate::{
+    parser::{
+        parse_query,
+        types::{DocumentOperations, ExecutableDocument, OperationType},
+    },
+    BatchRequest, ParseRequestError, Request, Value, Variables,
 };
 
-use crate::{BatchRequest, ParseRequestError, Request};
+/// Parse a GraphQL request from the query string of a `GET` request.
+///
+/// The `query`, `operationName`, `variables` and `extensions` parameters are
+/// read, the last two being JSON encoded. Only queries may be sent with `GET`,
+/// so a request selecting a mutation is rejected with
+/// [`ParseRequestError::MutationInGetRequest`].
+pub fn parse_query_string(input: &str) -> Result<Request, ParseRequestError> {
+    #[derive(Deserialize)]
+    struct RequestQuery {
+        #[serde(default)]
+        query: String,
+        #[serde(rename = "operationName")]
+        operation_name: Option<String>,
+        variables: Option<String>,
+        extensions: Option<String>,
+    }
+
+    let request_query: RequestQuery = serde_urlencoded::from_str(input)
+        .map_err(|err| ParseRequestError::InvalidRequest(Box::new(err)))?;
+
+    let mut request = Request::new(request_query.query);
+    if let Some(operation_name) = request_query.operation_name {
+        request = request.operation_name(operation_name);
+    }
+    if let Some(variables) = request_query.variables.filter(|s| !s.is_empty()) {
+        let variables = serde_json::from_str::<Variables>(&variables)
+            .map_err(|err| ParseRequestError::InvalidRequest(Box::new(err)))?;
+        request = request.variables(variables);
+    }
+    if let Some(extensions) = request_query.extensions.filter(|s| !s.is_empty()) {
+        request.extensions = serde_json::from_str::<HashMap<String, Value>>(&extensions)
+            .map_err(|err| ParseRequestError::InvalidRequest(Box::new(err)))?;
+    }
+
+    // Syntax errors are reported by the executor as a GraphQL response, so only
+    // a document that parses is checked here.
+    if let Ok(document) = parse_query(&request.query) {
+        if is_mutation(&document, request.operation_name.as_deref()) {
+            return Err(ParseRequestError::MutationInGetRequest);
+        }
+        request.parsed_query = Some(document);
+    }
+
+    Ok(request)
+}
+
+fn is_mutation(document: &ExecutableDocument, operation_name: Option<&str>) -> bool {
+    let operation = match (&document.operations, operation_name) {
+        (DocumentOperations::Single(operation), _) => Some(operation),
+        (DocumentOperations::Multiple(operations), Some(operation_name)) => {
+            operations.get(operation_name)
+        }
+        (DocumentOperations::Multiple(operations), None) if operations.len() == 1 => {
+            operations.values().next()
+        }
+        (DocumentOperations::Multiple(_), None) => None,
+    };
+    matches!(operation, Some(operation) if operation.node.ty == OperationType::Mutation)
+}
 
 /// Receive a GraphQL request from a content type and body.
 pub async fn receive_body(
@@ -114,3 +196,54 @@ pub async fn receive_batch_cbor(body: impl AsyncRead) -> Result<BatchRequest, Pa
     serde_cbor::from_slice::<BatchRequest>(&data)
         .map_err(|e| ParseRequestError::InvalidRequest(Box::new(e)))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value;
+
+    #[test]
+    fn test_parse_query_string() {
+        let request = parse_query_string(
+            "query=query%20Q(%24a%3A%20Int!)%20%7B%20add(a%3A%20%24a%2C%20b%3A%202)%20%7D&operationName=Q&variables=%7B%22a%22%3A1%7D&extensions=%7B%22persistedQuery%22%3A%7B%22version%22%3A1%7D%7D",
+        )
+        .unwrap();
+        assert_eq!(request.query, "query Q($a: Int!) { add(a: $a, b: 2) }");
+        assert_eq!(request.operation_name.as_deref(), Some("Q"));
+        assert_eq!(request.variables.into_value(), value!({ "a": 1 }));
+        assert_eq!(
+            request.extensions.get("persistedQuery"),
+            Some(&value!({ "version": 1 }))
+        );
+        assert!(request.parsed_query.is_some());
+
+        let request = parse_query_string("query=%7B%20a%20%7D&variables=").unwrap();
+        assert_eq!(request.query, "{ a }");
+        assert!(request.variables.is_empty());
+
+        assert!(matches!(
+            parse_query_string("query=%7B%20a%20%7D&variables=%7B"),
+            Err(ParseRequestError::InvalidRequest(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_query_string_rejects_mutations() {
+        assert!(matches!(
+            parse_query_string("query=mutation%20%7B%20a%20%7D"),
+            Err(ParseRequestError::MutationInGetRequest)
+        ));
+
+        // The operation selected by `operationName` decides.
+        let query = "query=query%20A%20%7B%20a%20%7D%20mutation%20B%20%7B%20b%20%7D";
+        assert!(parse_query_string(&format!("{}&operationName=A", query)).is_ok());
+        assert!(matches!(
+            parse_query_string(&format!("{}&operationName=B", query)),
+            Err(ParseRequestError::MutationInGetRequest)
+        ));
+
+        // Syntax errors are left to the executor.
+        let request = parse_query_string("query=mutation%20%7B").unwrap();
+        assert!(request.parsed_query.is_none());
+    }
+}
@@ -0,0 +1,82 @@
+use bytes::Bytes;
+use futures_util::stream::{BoxStream, Stream, StreamExt};
+use mime::Mime;
+
+use crate::Response;
+
+/// The content type of an incremental delivery response encoded with
+/// [`create_multipart_mixed_stream`].
+pub const MULTIPART_MIXED_CONTENT_TYPE: &str =
+    r#"multipart/mixed; boundary="-"; deferSpec=20220824"#;
+
+/// Returns `true` if the `Accept` header of a request allows a
+/// `multipart/mixed` response.
+pub fn accepts_multipart_mixed(accept: &str) -> bool {
+    accept
+        .split(',')
+        .filter_map(|ty| ty.trim().parse::<Mime>().ok())
+        .any(|ty| ty.type_() == mime::MULTIPART && ty.subtype() == "mixed")
+}
+
+/// Encodes the responses of
+/// [`Schema::execute_stream`](crate::Schema::execute_stream) as a
+/// `multipart/mixed` body, one JSON part per response.
+///
+/// This is used to deliver the subsequent payloads of queries using `@defer`
+/// or `@stream`, the body should be sent with the
+/// [`MULTIPART_MIXED_CONTENT_TYPE`] content type.
+pub fn create_multipart_mixed_stream<'a>(
+    stream: impl Stream<Item = Response> + Send + 'a,
+) -> BoxStream<'a, Bytes> {
+    let stream = stream.map(|resp| {
+        let mut data =
+            Vec::from(&b"\r\n---\r\nContent-Type: application/json; charset=utf-8\r\n\r\n"[..]);
+        serde_json::to_writer(&mut data, &resp).unwrap();
+        Bytes::from(data)
+    });
+    stream
+        .chain(futures_util::stream::once(async {
+            Bytes::from_static(b"\r\n-----\r\n")
+        }))
+        .boxed()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+
+    #[tokio::test]
+    async fn test_multipart_mixed_stream() {
+        let mut first = Response::new(value!({"a": 1}));
+        first.has_next = Some(true);
+        let mut second = Response::default();
+        second.incremental = vec![IncrementalPayload {
+            data: Some(value!({"b": 2})),
+            path: vec![],
+            ..Default::default()
+        }];
+        second.has_next = Some(false);
+
+        let body = create_multipart_mixed_stream(futures_util::stream::iter(vec![first, second]))
+            .collect::<Vec<_>>()
+            .await
+            .concat();
+        assert_eq!(
+            String::from_utf8(body).unwrap(),
+            "\r\n---\r\nContent-Type: application/json; charset=utf-8\r\n\r\n\
+             {\"data\":{\"a\":1},\"hasNext\":true}\
+             \r\n---\r\nContent-Type: application/json; charset=utf-8\r\n\r\n\
+             {\"incremental\":[{\"data\":{\"b\":2},\"path\":[]}],\"hasNext\":false}\
+             \r\n-----\r\n"
+        );
+    }
+
+    #[test]
+    fn test_accepts_multipart_mixed() {
+        assert!(accepts_multipart_mixed(
+            r#"multipart/mixed; deferSpec=20220824, application/json"#
+        ));
+        assert!(!accepts_multipart_mixed("application/json"));
+    }
+}
@@ -0,0 +1,528 @@
+//! Server-Sent Events transport for subscription
+
+use std::{
+    collections::{HashMap, HashSet},
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll, Waker},
+    time::{Duration, Instant},
+};
+
+use bytes::Bytes;
+use futures_util::stream::{BoxStream, Stream, StreamExt};
+use http::StatusCode;
+use mime::Mime;
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::{ObjectType, Request, Response, Schema, SubscriptionType, Value};
+
+/// The content type of an event stream.
+pub const SSE_CONTENT_TYPE: &str = "text/event-stream";
+
+/// The header carrying the token of an event stream in the "single
+/// connection" mode. The token can also be sent with the `token` query
+/// parameter.
+pub const SSE_TOKEN_HEADER: &str = "x-graphql-event-stream-token";
+
+/// Returns `true` if the `Accept` header of a request allows a
+/// `text/event-stream` response.
+pub fn accepts_event_stream(accept: &str) -> bool {
+    accept
+        .split(',')
+        .filter_map(|ty| ty.trim().parse::<Mime>().ok())
+        .any(|ty| ty.type_() == mime::TEXT && ty.subtype() == mime::EVENT_STREAM)
+}
+
+fn create_event(event: &str, data: &impl Serialize) -> Bytes {
+    let mut buf = format!("event: {}\ndata: ", event).into_bytes();
+    serde_json::to_writer(&mut buf, data).unwrap();
+    buf.extend_from_slice(b"\n\n");
+    Bytes::from(buf)
+}
+
+/// Encodes the responses of
+/// [`Schema::execute_stream`](crate::Schema::execute_stream) as an event
+/// stream, for the "distinct connections" mode of the
+/// [graphql-sse](https://github.com/enisdenjo/graphql-sse/blob/master/PROTOCOL.md)
+/// protocol.
+///
+/// Every response is sent as a `next` event, followed by a `complete` event
+/// when the stream ends. The body should be sent with the
+/// [`SSE_CONTENT_TYPE`] content type.
+pub fn create_sse_stream<'a>(
+    stream: impl Stream<Item = Response> + Send + 'a,
+) -> BoxStream<'a, Bytes> {
+    stream
+        .map(|resp| create_event("next", &resp))
+        .chain(futures_util::stream::once(async {
+            Bytes::from_static(b"event: complete\ndata:\n\n")
+        }))
+        .boxed()
+}
+
+/// An error of the "single connection" mode of the graphql-sse protocol.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum SseError {
+    /// No event stream was reserved with the token.
+    #[error("Event stream not found")]
+    StreamNotFound,
+
+    /// The event stream of the token is already established.
+    #[error("Event stream already established")]
+    StreamAlreadyEstablished,
+
+    /// The request has no `operationId` extension.
+    #[error("Missing operation id")]
+    MissingOperationId,
+
+    /// An operation with the same id is already running on the event stream.
+    #[error("Operation with id {0} already exists")]
+    OperationExists(String),
+
+    /// Too many event streams are reserved and not established yet.
+    #[error("Too many reserved event streams")]
+    TooManyReservations,
+
+    /// Too many operations are waiting for the event stream to be
+    /// established.
+    #[error("Too many pending operations")]
+    TooManyPendingOperations,
+}
+
+impl SseError {
+    /// Returns the HTTP status code of the error.
+    pub fn status_code(&self) -> StatusCode {
+        match self {
+            SseError::StreamNotFound => StatusCode::NOT_FOUND,
+            SseError::StreamAlreadyEstablished | SseError::OperationExists(_) => {
+                StatusCode::CONFLICT
+            }
+            SseError::MissingOperationId => StatusCode::BAD_REQUEST,
+            SseError::TooManyReservations | SseError::TooManyPendingOperations => {
+                StatusCode::TOO_MANY_REQUESTS
+            }
+        }
+    }
+}
+
+struct Connection {
+    reserved_at: Instant,
+    established: bool,
+    operations: HashSet<String>,
+    // The requests are only executed once the event stream is established.
+    started: Vec<(String, Request)>,
+    stopped: Vec<String>,
+    waker: Option<Waker>,
+}
+
+impl Connection {
+    fn new() -> Self {
+        Self {
+            reserved_at: Instant::now(),
+            established: false,
+            operations: HashSet::new(),
+            started: Vec::new(),
+            stopped: Vec::new(),
+            waker: None,
+        }
+    }
+
+    fn wake(&mut self) {
+        if let Some(waker) = self.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+type Connections = Arc<Mutex<HashMap<String, Connection>>>;
+
+/// The event streams of the "single connection" mode of the
+/// [graphql-sse](https://github.com/enisdenjo/graphql-sse/blob/master/PROTOCOL.md)
+/// protocol.
+///
+/// A client reserves an event stream with a `PUT` request, establishes it
+/// with a request accepting `text/event-stream`, and then executes operations
+/// with `POST` requests whose results are sent on the event stream. The
+/// operations are executed while the event stream is polled, and stopped when
+/// it is dropped.
+///
+/// The event streams which are not established within the
+/// [reservation timeout](Self::reservation_timeout) are released, and the
+/// number of [reserved event streams](Self::max_reservations) and of the
+/// [operations executed before they are established](Self::max_pending_operations)
+/// is limited.
+pub struct SseConnections<Query, Mutation, Subscription> {
+    schema: Schema<Query, Mutation, Subscription>,
+    connections: Connections,
+    reservation_timeout: Duration,
+    max_reservations: usize,
+    max_pending_operations: usize,
+}
+
+impl<Query, Mutation, Subscription> Clone for SseConnections<Query, Mutation, Subscription> {
+    fn clone(&self) -> Self {
+        Self {
+            schema: self.schema.clone(),
+            connections: self.connections.clone(),
+            reservation_timeout: self.reservation_timeout,
+            max_reservations: self.max_reservations,
+            max_pending_operations: self.max_pending_operations,
+        }
+    }
+}
+
+impl<Query, Mutation, Subscription> SseConnections<Query, Mutation, Subscription>
+where
+    Query: ObjectType + 'static,
+    Mutation: ObjectType + 'static,
+    Subscription: SubscriptionType + 'static,
+{
+    /// Create the event streams of the schema.
+    pub fn new(schema: Schema<Query, Mutation, Subscription>) -> Self {
+        Self {
+            schema,
+            connections: Default::default(),
+            reservation_timeout: Duration::from_secs(30),
+            max_reservations: 1024,
+            max_pending_operations: 16,
+        }
+    }
+
+    /// Specify the time a reserved event stream has to be established, the
+    /// default is 30 seconds.
+    #[must_use]
+    pub fn reservation_timeout(self, timeout: Duration) -> Self {
+        Self {
+            reservation_timeout: timeout,
+            ..self
+        }
+    }
+
+    /// Specify the maximum number of event streams reserved and not
+    /// established yet, the default is `1024`.
+    #[must_use]
+    pub fn max_reservations(self, max_reservations: usize) -> Self {
+        Self {
+            max_reservations,
+            ..self
+        }
+    }
+
+    /// Specify the maximum number of operations executed on an event stream
+    /// before it is established, the default is `16`.
+    #[must_use]
+    pub fn max_pending_operations(self, max_pending_operations: usize) -> Self {
+        Self {
+            max_pending_operations,
+            ..self
+        }
+    }
+
+    /// Locks the connections, releasing the expired reservations.
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, Connection>> {
+        let mut connections = self.connections.lock().unwrap();
+        let timeout = self.reservation_timeout;
+        connections.retain(|_, connection| {
+            connection.established || connection.reserved_at.elapsed() < timeout
+        });
+        connections
+    }
+
+    /// Returns the schema.
+    pub fn schema(&self) -> &Schema<Query, Mutation, Subscription> {
+        &self.schema
+    }
+
+    /// Reserves an event stream, returning its token.
+    pub fn reserve(&self) -> Result<String, SseError> {
+        let mut connections = self.lock();
+        let reservations = connections
+            .values()
+            .filter(|connection| !connection.established)
+            .count();
+        if reservations >= self.max_reservations {
+            return Err(SseError::TooManyReservations);
+        }
+        loop {
+            let token = generate_token();
+            if !connections.contains_key(&token) {
+                connections.insert(token.clone(), Connection::new());
+                return Ok(token);
+            }
+        }
+    }
+
+    /// Establishes the event stream reserved with the token.
+    ///
+    /// The body should be sent with the [`SSE_CONTENT_TYPE`] content type.
+    pub fn connect(&self, token: &str) -> Result<SseEventStream, SseError> {
+        let mut connections = self.lock();
+        let connection = connections.get_mut(token).ok_or(SseError::StreamNotFound)?;
+        if connection.established {
+            return Err(SseError::StreamAlreadyEstablished);
+        }
+        connection.established = true;
+        Ok(SseEventStream {
+            token: token.to_string(),
+            connections: self.connections.clone(),
+            execute: {
+                let schema = self.schema.clone();
+                Box::new(move |request| schema.execute_stream(request).boxed())
+            },
+            streams: HashMap::new(),
+        })
+    }
+
+    /// Executes a request on the event stream of the token.
+    ///
+    /// The request must have an `operationId` extension identifying the
+    /// operation on the event stream. The request is executed once the event
+    /// stream is established.
+    pub fn execute(&self, token: &str, request: Request) -> Result<(), SseError> {
+        let id = match request.extensions.get("operationId") {
+            Some(Value::String(id)) => id.clone(),
+            _ => return Err(SseError::MissingOperationId),
+        };
+
+        let mut connections = self.lock();
+        let connection = connections.get_mut(token).ok_or(SseError::StreamNotFound)?;
+        if connection.operations.contains(&id) {
+            return Err(SseError::OperationExists(id));
+        }
+        if !connection.established && connection.started.len() >= self.max_pending_operations {
+            return Err(SseError::TooManyPendingOperations);
+        }
+        connection.operations.insert(id.clone());
+        connection.started.push((id, request));
+        connection.wake();
+        Ok(())
+    }
+
+    /// Stops an operation running on the event stream of the token.
+    pub fn stop(&self, token: &str, operation_id: &str) -> Result<(), SseError> {
+        let mut connections = self.lock();
+        let connection = connections.get_mut(token).ok_or(SseError::StreamNotFound)?;
+        if connection.operations.remove(operation_id) {
+            connection.stopped.push(operation_id.to_string());
+            connection.wake();
+        }
+        Ok(())
+    }
+}
+
+/// Generates a token of 128 random bits, read from the random number
+/// generator of the operating system.
+fn generate_token() -> String {
+    let mut bytes = [0u8; 16];
+    getrandom::getrandom(&mut bytes).expect("failed to generate a random token");
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+#[derive(Serialize)]
+struct NextMessage<'a> {
+    id: &'a str,
+    payload: Response,
+}
+
+#[derive(Serialize)]
+struct CompleteMessage<'a> {
+    id: &'a str,
+}
+
+/// An event stream established with [`SseConnections::connect`].
+///
+/// Every result of an operation is sent as a `next` event, and a `complete`
+/// event is sent when the operation ends. Dropping the stream releases its
+/// token and stops all its operations.
+pub struct SseEventStream {
+    token: String,
+    connections: Connections,
+    execute: Box<dyn Fn(Request) -> BoxStream<'static, Response> + Send + Sync>,
+    streams: HashMap<String, BoxStream<'static, Response>>,
+}
+
+impl Stream for SseEventStream {
+    type Item = Bytes;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        {
+            let mut connections = this.connections.lock().unwrap();
+            let connection = match connections.get_mut(&this.token) {
+                Some(connection) => connection,
+                None => return Poll::Ready(None),
+            };
+            for id in connection.stopped.drain(..) {
+                this.streams.remove(&id);
+            }
+            for (id, request) in connection.started.drain(..) {
+                this.streams.insert(id, (this.execute)(request));
+            }
+            connection.waker = Some(cx.waker().clone());
+        }
+
+        for (id, stream) in &mut this.streams {
+            match stream.poll_next_unpin(cx) {
+                Poll::Ready(Some(payload)) => {
+                    return Poll::Ready(Some(create_event("next", &NextMessage { id, payload })));
+                }
+                Poll::Ready(None) => {
+                    let id = id.clone();
+                    this.streams.remove(&id);
+                    if let Some(connection) = this.connections.lock().unwrap().get_mut(&this.token)
+                    {
+                        connection.operations.remove(&id);
+                    }
+                    return Poll::Ready(Some(create_event(
+                        "complete",
+                        &CompleteMessage { id: &id },
+                    )));
+                }
+                Poll::Pending => {}
+            }
+        }
+
+        Poll::Pending
+    }
+}
+
+impl Drop for SseEventStream {
+    fn drop(&mut self) {
+        if let Ok(mut connections) = self.connections.lock() {
+            connections.remove(&self.token);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures_util::stream;
+
+    use super::*;
+    use crate::*;
+
+    struct Query;
+
+    #[Object(internal)]
+    impl Query {
+        async fn value(&self) -> i32 {
+            10
+        }
+    }
+
+    struct Subscription;
+
+    #[Subscription(internal)]
+    impl Subscription {
+        async fn values(&self) -> impl futures_util::Stream<Item = i32> {
+            stream::iter(vec![1, 2])
+        }
+    }
+
+    #[test]
+    fn test_accepts_event_stream() {
+        assert!(accepts_event_stream("text/event-stream"));
+        assert!(accepts_event_stream(
+            "application/json, text/event-stream;q=0.9"
+        ));
+        assert!(!accepts_event_stream("application/json"));
+    }
+
+    #[tokio::test]
+    async fn test_sse_stream() {
+        let schema = Schema::new(Query, EmptyMutation, Subscription);
+        let body = create_sse_stream(schema.execute_stream("subscription { values }"))
+            .collect::<Vec<_>>()
+            .await
+            .concat();
+        assert_eq!(
+            String::from_utf8(body).unwrap(),
+            "event: next\ndata: {\"data\":{\"values\":1}}\n\n\
+             event: next\ndata: {\"data\":{\"values\":2}}\n\n\
+             event: complete\ndata:\n\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_sse_connections() {
+        let connections = SseConnections::new(Schema::new(Query, EmptyMutation, Subscription));
+        let token = connections.reserve().unwrap();
+
+        assert_eq!(
+            connections.connect("unknown").err(),
+            Some(SseError::StreamNotFound)
+        );
+        assert_eq!(
+            connections.execute(&token, Request::new("{ value }")),
+            Err(SseError::MissingOperationId)
+        );
+
+        let mut request = Request::new("{ value }");
+        request
+            .extensions
+            .insert("operationId".to_string(), Value::from("1"));
+        connections.execute(&token, request).unwrap();
+
+        let mut stream = connections.connect(&token).unwrap();
+        assert_eq!(
+            connections.connect(&token).err(),
+            Some(SseError::StreamAlreadyEstablished)
+        );
+        assert_eq!(
+            stream.next().await.unwrap(),
+            "event: next\ndata: {\"id\":\"1\",\"payload\":{\"data\":{\"value\":10}}}\n\n"
+        );
+        assert_eq!(
+            stream.next().await.unwrap(),
+            "event: complete\ndata: {\"id\":\"1\"}\n\n"
+        );
+
+        drop(stream);
+        assert_eq!(
+            connections.connect(&token).err(),
+            Some(SseError::StreamNotFound)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_sse_connections_limits() {
+        let schema = Schema::new(Query, EmptyMutation, Subscription);
+        let request = |id: &str| {
+            let mut request = Request::new("{ value }");
+            request
+                .extensions
+                .insert("operationId".to_string(), Value::from(id));
+            request
+        };
+
+        let connections = SseConnections::new(schema.clone())
+            .max_reservations(1)
+            .max_pending_operations(1);
+        let token = connections.reserve().unwrap();
+        assert_eq!(connections.reserve(), Err(SseError::TooManyReservations));
+        connections.execute(&token, request("1")).unwrap();
+        assert_eq!(
+            connections.execute(&token, request("2")),
+            Err(SseError::TooManyPendingOperations)
+        );
+
+        // The established event streams are not limited.
+        let _stream = connections.connect(&token).unwrap();
+        connections.execute(&token, request("2")).unwrap();
+        connections.reserve().unwrap();
+
+        // The reservations expire when they are not established in time.
+        let connections = SseConnections::new(schema).reservation_timeout(Duration::ZERO);
+        let token = connections.reserve().unwrap();
+        assert_eq!(
+            connections.execute(&token, request("1")),
+            Err(SseError::StreamNotFound)
+        );
+        assert_eq!(
+            connections.connect(&token).err(),
+            Some(SseError::StreamNotFound)
+        );
+    }
+}
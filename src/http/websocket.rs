@@ -1,22 +1,29 @@
 //! WebSocket transport for subscription
 
 use std::{
-    collections::HashMap,
+    collections::{HashMap, VecDeque},
     future::Future,
     pin::Pin,
-    sync::Arc,
+    sync::{Arc, Mutex},
     task::{Context, Poll},
+    time::Duration,
 };
 
+use futures_timer::Delay;
 use futures_util::{
     future::{BoxFuture, Ready},
     stream::Stream,
+    task::AtomicWaker,
     FutureExt, StreamExt,
 };
+use indexmap::IndexMap;
 use pin_project_lite::pin_project;
 use serde::{Deserialize, Serialize};
 
-use crate::{Data, Error, ObjectType, Request, Response, Result, Schema, SubscriptionType};
+use crate::{
+    Data, Error, ObjectType, Request, Response, Result, Schema, ServerError, SubscriptionType,
+    Value,
+};
 
 /// All known protocols based on WebSocket.
 pub const ALL_WEBSOCKET_PROTOCOLS: [&str; 2] = ["graphql-transport-ws", "graphql-ws"];
@@ -63,6 +70,56 @@ impl WsMessage {
     }
 }
 
+/// A handle to close a [WebSocket] from the application code, for example
+/// when the token of the user is revoked.
+///
+/// The handle of a connection is returned by [WebSocket::close_handle], and
+/// is also in the context data of the operations of the connection.
+#[derive(Clone)]
+pub struct WsCloseHandle(Arc<CloseState>);
+
+#[derive(Default)]
+struct CloseState {
+    frame: Mutex<Option<(u16, String)>>,
+    waker: AtomicWaker,
+}
+
+impl WsCloseHandle {
+    /// Closes the connection with a close code and a reason.
+    ///
+    /// Only the first call takes effect.
+    pub fn close(&self, code: u16, reason: impl Into<String>) {
+        let mut frame = self.0.frame.lock().unwrap();
+        if frame.is_none() {
+            *frame = Some((code, reason.into()));
+            self.0.waker.wake();
+        }
+    }
+
+    fn poll_close(&self, cx: &mut Context<'_>) -> Option<(u16, String)> {
+        self.0.waker.register(cx.waker());
+        self.0.frame.lock().unwrap().clone()
+    }
+}
+
+/// What happens when an event of an operation is received while its buffer
+/// is full, see [WebSocket::subscription_buffer].
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum WsOverflowPolicy {
+    /// The oldest event of the buffer is dropped.
+    DropOldest,
+    /// The new event is dropped.
+    DropNewest,
+    /// The connection is closed with the `1008` code.
+    Close,
+}
+
+struct Operation {
+    stream: Pin<Box<dyn Stream<Item = Response> + Send>>,
+    buffer: VecDeque<Response>,
+    finished: bool,
+}
+
 pin_project! {
     /// A GraphQL connection over websocket.
     ///
@@ -72,14 +129,28 @@ pin_project! {
     /// - [graphql-ws](https://github.com/enisdenjo/graphql-ws/blob/master/PROTOCOL.md)
     pub struct WebSocket<S, Query, Mutation, Subscription, OnInit> {
         on_connection_init: Option<OnInit>,
+        on_operation: Option<OnOperationFn>,
         init_fut: Option<BoxFuture<'static, Result<Data>>>,
         connection_data: Option<Data>,
         data: Option<Arc<Data>>,
         schema: Schema<Query, Mutation, Subscription>,
-        streams: HashMap<String, Pin<Box<dyn Stream<Item = Response> + Send>>>,
+        streams: IndexMap<String, Operation>,
+        // The index of the operation whose events are sent first.
+        next_operation: usize,
         #[pin]
         stream: S,
         protocol: Protocols,
+        keepalive_interval: Option<Duration>,
+        keepalive_timer: Option<Delay>,
+        connection_init_timeout: Option<Duration>,
+        connection_init_timer: Option<Delay>,
+        idle_timeout: Option<Duration>,
+        idle_timer: Option<Delay>,
+        close_handle: WsCloseHandle,
+        closed: bool,
+        max_operations: Option<usize>,
+        subscription_buffer: Option<(usize, WsOverflowPolicy)>,
+        on_event_dropped: Option<OnEventDroppedFn>,
     }
 }
 
@@ -88,6 +159,11 @@ type MessageMapStream<S> =
 
 type DefaultOnConnInitType = fn(serde_json::Value) -> Ready<Result<Data>>;
 
+type OnOperationFn =
+    Box<dyn Fn(HashMap<String, Value>) -> BoxFuture<'static, Result<Data>> + Send + Sync>;
+
+type OnEventDroppedFn = Box<dyn Fn(&str) + Send + Sync>;
+
 fn default_on_connection_init(_: serde_json::Value) -> Ready<Result<Data>> {
     futures_util::future::ready(Ok(Data::default()))
 }
@@ -105,13 +181,26 @@ where
     ) -> Self {
         WebSocket {
             on_connection_init: Some(default_on_connection_init),
+            on_operation: None,
             init_fut: None,
             connection_data: None,
             data: None,
             schema,
-            streams: HashMap::new(),
+            streams: IndexMap::new(),
+            next_operation: 0,
             stream,
             protocol,
+            keepalive_interval: None,
+            keepalive_timer: None,
+            connection_init_timeout: None,
+            connection_init_timer: None,
+            idle_timeout: None,
+            idle_timer: None,
+            close_handle: WsCloseHandle(Default::default()),
+            closed: false,
+            max_operations: None,
+            subscription_buffer: None,
+            on_event_dropped: None,
         }
     }
 }
@@ -166,15 +255,119 @@ where
     {
         WebSocket {
             on_connection_init: Some(callback),
+            on_operation: self.on_operation,
             init_fut: self.init_fut,
             connection_data: self.connection_data,
             data: self.data,
             schema: self.schema,
             streams: self.streams,
+            next_operation: self.next_operation,
             stream: self.stream,
             protocol: self.protocol,
+            keepalive_interval: self.keepalive_interval,
+            keepalive_timer: self.keepalive_timer,
+            connection_init_timeout: self.connection_init_timeout,
+            connection_init_timer: self.connection_init_timer,
+            idle_timeout: self.idle_timeout,
+            idle_timer: self.idle_timer,
+            close_handle: self.close_handle,
+            closed: self.closed,
+            max_operations: self.max_operations,
+            subscription_buffer: self.subscription_buffer,
+            on_event_dropped: self.on_event_dropped,
         }
     }
+
+    /// Specify an operation callback function.
+    ///
+    /// This function if present, will be called with the `extensions` of the
+    /// request of each operation, the queries and mutations as well as the
+    /// subscriptions. The returned data is added to the data of the request,
+    /// and the operation fails with the error returned by the function.
+    #[must_use]
+    pub fn on_operation<F, R>(mut self, callback: F) -> Self
+    where
+        F: Fn(HashMap<String, Value>) -> R + Send + Sync + 'static,
+        R: Future<Output = Result<Data>> + Send + 'static,
+    {
+        self.on_operation = Some(Box::new(move |extensions| callback(extensions).boxed()));
+        self
+    }
+
+    /// Specify the interval of the keep-alive messages sent to the client
+    /// once the connection is acknowledged, `ka` messages with the
+    /// subscriptions-transport-ws protocol and `ping` messages with the
+    /// graphql-ws protocol.
+    ///
+    /// No keep-alive messages are sent by default.
+    #[must_use]
+    pub fn keepalive_interval(mut self, interval: Duration) -> Self {
+        self.keepalive_interval = Some(interval);
+        self
+    }
+
+    /// Specify the time the client has to send the `connection_init` message,
+    /// the connection is closed with the `4408` code after it.
+    ///
+    /// There is no timeout by default.
+    #[must_use]
+    pub fn connection_init_timeout(mut self, timeout: Duration) -> Self {
+        self.connection_init_timeout = Some(timeout);
+        self
+    }
+
+    /// Specify the time after which the connection is closed with the `3008`
+    /// code, when the client sends no messages.
+    ///
+    /// With the graphql-ws protocol, the `pong` messages of the client
+    /// answering the [keep-alive](Self::keepalive_interval) messages keep the
+    /// connection alive.
+    ///
+    /// There is no timeout by default.
+    #[must_use]
+    pub fn idle_timeout(mut self, timeout: Duration) -> Self {
+        self.idle_timeout = Some(timeout);
+        self
+    }
+
+    /// Specify the maximum number of concurrent operations of the connection,
+    /// the operations above the limit fail with an error.
+    ///
+    /// There is no limit by default.
+    #[must_use]
+    pub fn max_operations(mut self, max_operations: usize) -> Self {
+        self.max_operations = Some(max_operations);
+        self
+    }
+
+    /// Specify the maximum number of events buffered for each operation, and
+    /// what happens when an event is received while the buffer is full.
+    ///
+    /// The events of the operations are buffered as soon as they are
+    /// received, until they are sent to the client. By default, they are not
+    /// received before the previous event of the operation is sent.
+    #[must_use]
+    pub fn subscription_buffer(mut self, capacity: usize, policy: WsOverflowPolicy) -> Self {
+        self.subscription_buffer = Some((capacity.max(1), policy));
+        self
+    }
+
+    /// Specify a callback function called with the id of the operation each
+    /// time one of its events is dropped by the
+    /// [buffer](Self::subscription_buffer) of the operation.
+    #[must_use]
+    pub fn on_event_dropped<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&str) + Send + Sync + 'static,
+    {
+        self.on_event_dropped = Some(Box::new(callback));
+        self
+    }
+
+    /// Returns the handle to close this connection.
+    pub fn close_handle(&self) -> WsCloseHandle {
+        self.close_handle.clone()
+    }
 }
 
 impl<S, Query, Mutation, Subscription, OnInit, InitFut> Stream
@@ -189,9 +382,45 @@ where
 {
     type Item = WsMessage;
 
-    fn poll_next(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        if self.closed {
+            return Poll::Ready(None);
+        }
+        let res = self.as_mut().poll_message(cx);
+        // The connection ends once it is closed.
+        if let Poll::Ready(Some(WsMessage::Close(_, _))) = &res {
+            *self.project().closed = true;
+        }
+        res
+    }
+}
+
+impl<S, Query, Mutation, Subscription, OnInit, InitFut>
+    WebSocket<S, Query, Mutation, Subscription, OnInit>
+where
+    S: Stream<Item = serde_json::Result<ClientMessage>>,
+    Query: ObjectType + 'static,
+    Mutation: ObjectType + 'static,
+    Subscription: SubscriptionType + 'static,
+    OnInit: FnOnce(serde_json::Value) -> InitFut + Send + 'static,
+    InitFut: Future<Output = Result<Data>> + Send + 'static,
+{
+    fn poll_message(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<WsMessage>> {
         let mut this = self.project();
 
+        if let Some((code, reason)) = this.close_handle.poll_close(cx) {
+            return Poll::Ready(Some(WsMessage::Close(code, reason)));
+        }
+
+        // The timeouts start when the connection is first polled, rather than
+        // when it is built.
+        if let Some(timeout) = this.connection_init_timeout.take() {
+            *this.connection_init_timer = Some(Delay::new(timeout));
+        }
+        if let (None, Some(timeout)) = (&*this.idle_timer, *this.idle_timeout) {
+            *this.idle_timer = Some(Delay::new(timeout));
+        }
+
         if this.init_fut.is_none() {
             while let Poll::Ready(message) = Pin::new(&mut this.stream).poll_next(cx) {
                 let message = match message {
@@ -199,6 +428,10 @@ where
                     None => return Poll::Ready(None),
                 };
 
+                if let (Some(timer), Some(timeout)) = (&mut *this.idle_timer, *this.idle_timeout) {
+                    timer.reset(timeout);
+                }
+
                 let message: ClientMessage = match message {
                     Ok(message) => message,
                     Err(err) => return Poll::Ready(Some(WsMessage::Close(1002, err.to_string()))),
@@ -206,6 +439,7 @@ where
 
                 match message {
                     ClientMessage::ConnectionInit { payload } => {
+                        *this.connection_init_timer = None;
                         if let Some(on_connection_init) = this.on_connection_init.take() {
                             *this.init_fut = Some(Box::pin(async move {
                                 on_connection_init(payload.unwrap_or_default()).await
@@ -236,22 +470,58 @@ where
                         id,
                         payload: request,
                     } => {
-                        if let Some(data) = this.data.clone() {
-                            this.streams.insert(
-                                id,
-                                Box::pin(
-                                    this.schema.execute_stream_with_session_data(request, data),
-                                ),
-                            );
-                        } else {
+                        let data = match (this.data.clone(), *this.protocol) {
+                            (Some(data), _) => data,
+                            (None, Protocols::SubscriptionsTransportWS) => {
+                                return Poll::Ready(Some(WsMessage::Close(
+                                    1011,
+                                    "The handshake is not completed.".to_string(),
+                                )));
+                            }
+                            (None, Protocols::GraphQLWS) => {
+                                return Poll::Ready(Some(WsMessage::Close(
+                                    4401,
+                                    "Unauthorized".to_string(),
+                                )));
+                            }
+                        };
+                        if *this.protocol == Protocols::GraphQLWS && this.streams.contains_key(&id)
+                        {
                             return Poll::Ready(Some(WsMessage::Close(
-                                1011,
-                                "The handshake is not completed.".to_string(),
+                                4409,
+                                format!("Subscriber for {} already exists", id),
                             )));
                         }
+                        let stream = match *this.max_operations {
+                            Some(max_operations) if this.streams.len() >= max_operations => {
+                                Box::pin(futures_util::stream::once(futures_util::future::ready(
+                                    Response::from_request_errors(vec![ServerError::new(
+                                        "Too many operations.",
+                                        None,
+                                    )]),
+                                )))
+                            }
+                            _ => execute_operation(
+                                this.schema.clone(),
+                                this.on_operation.as_ref(),
+                                request,
+                                data,
+                            ),
+                        };
+                        this.streams.insert(
+                            id,
+                            Operation {
+                                stream,
+                                buffer: VecDeque::new(),
+                                finished: false,
+                            },
+                        );
                     }
                     ClientMessage::Stop { id } => {
-                        if this.streams.remove(&id).is_some() {
+                        if let Some((idx, _, _)) = this.streams.shift_remove_full(&id) {
+                            if idx < *this.next_operation {
+                                *this.next_operation -= 1;
+                            }
                             return Poll::Ready(Some(WsMessage::Text(
                                 serde_json::to_string(&ServerMessage::Complete { id: &id })
                                     .unwrap(),
@@ -282,7 +552,9 @@ where
                     Ok(data) => {
                         let mut ctx_data = this.connection_data.take().unwrap_or_default();
                         ctx_data.merge(data);
+                        ctx_data.insert(this.close_handle.clone());
                         *this.data = Some(Arc::new(ctx_data));
+                        *this.keepalive_timer = this.keepalive_interval.map(Delay::new);
                         Poll::Ready(Some(WsMessage::Text(
                             serde_json::to_string(&ServerMessage::ConnectionAck).unwrap(),
                         )))
@@ -302,21 +574,118 @@ where
             }
         }
 
-        for (id, stream) in &mut *this.streams {
-            match Pin::new(stream).poll_next(cx) {
-                Poll::Ready(Some(payload)) => {
+        if let Some(timer) = this.connection_init_timer {
+            if timer.poll_unpin(cx).is_ready() {
+                return Poll::Ready(Some(WsMessage::Close(
+                    4408,
+                    "Connection initialisation timeout".to_string(),
+                )));
+            }
+        }
+
+        if let Some(timer) = this.idle_timer {
+            if timer.poll_unpin(cx).is_ready() {
+                return Poll::Ready(Some(WsMessage::Close(3008, "Idle timeout".to_string())));
+            }
+        }
+
+        if let (Some(timer), Some(interval)) =
+            (&mut *this.keepalive_timer, *this.keepalive_interval)
+        {
+            if timer.poll_unpin(cx).is_ready() {
+                timer.reset(interval);
+                let message = match this.protocol {
+                    Protocols::SubscriptionsTransportWS => ServerMessage::KeepAlive,
+                    Protocols::GraphQLWS => ServerMessage::Ping { payload: None },
+                };
+                return Poll::Ready(Some(WsMessage::Text(
+                    serde_json::to_string(&message).unwrap(),
+                )));
+            }
+        }
+
+        // Receive the events of the operations, only one at a time when they
+        // are not buffered. At most `capacity` events of a buffered operation
+        // are received in each poll, so that an operation which is always
+        // ready doesn't starve the connection.
+        for (id, operation) in &mut *this.streams {
+            let mut received = 0;
+            while !operation.finished
+                && (operation.buffer.is_empty() || this.subscription_buffer.is_some())
+            {
+                if let Some((capacity, _)) = *this.subscription_buffer {
+                    if received == capacity {
+                        cx.waker().wake_by_ref();
+                        break;
+                    }
+                }
+                received += 1;
+                match operation.stream.poll_next_unpin(cx) {
+                    Poll::Ready(Some(resp)) => match *this.subscription_buffer {
+                        Some((capacity, policy)) if operation.buffer.len() >= capacity => {
+                            match policy {
+                                WsOverflowPolicy::DropOldest => {
+                                    operation.buffer.pop_front();
+                                    operation.buffer.push_back(resp);
+                                }
+                                WsOverflowPolicy::DropNewest => {}
+                                WsOverflowPolicy::Close => {
+                                    return Poll::Ready(Some(WsMessage::Close(
+                                        1008,
+                                        format!("The buffer of the operation {} is full", id),
+                                    )));
+                                }
+                            }
+                            if let Some(on_event_dropped) = this.on_event_dropped {
+                                on_event_dropped(id);
+                            }
+                        }
+                        _ => operation.buffer.push_back(resp),
+                    },
+                    Poll::Ready(None) => operation.finished = true,
+                    Poll::Pending => break,
+                }
+            }
+        }
+
+        // Send the events of the operations in turn, starting after the
+        // operation whose event was sent last.
+        let len = this.streams.len();
+        for offset in 0..len {
+            let idx = (*this.next_operation + offset) % len;
+            let (id, operation) = this.streams.get_index_mut(idx).unwrap();
+            match operation.buffer.pop_front() {
+                Some(payload)
+                    if *this.protocol == Protocols::GraphQLWS && payload.is_request_error() =>
+                {
+                    // The operation failed before it was executed, there is no
+                    // `complete` message.
+                    let id = id.clone();
+                    this.streams.shift_remove_index(idx);
+                    *this.next_operation = idx;
+                    return Poll::Ready(Some(WsMessage::Text(
+                        serde_json::to_string(&ServerMessage::Error {
+                            id: &id,
+                            payload: payload.errors,
+                        })
+                        .unwrap(),
+                    )));
+                }
+                Some(payload) => {
+                    *this.next_operation = idx + 1;
                     return Poll::Ready(Some(WsMessage::Text(
                         serde_json::to_string(&this.protocol.next_message(id, payload)).unwrap(),
                     )));
                 }
-                Poll::Ready(None) => {
+                None if operation.finished => {
                     let id = id.clone();
-                    this.streams.remove(&id);
+                    this.streams.shift_remove_index(idx);
+                    *this.next_operation = idx;
                     return Poll::Ready(Some(WsMessage::Text(
                         serde_json::to_string(&ServerMessage::Complete { id: &id }).unwrap(),
                     )));
                 }
-                Poll::Pending => {}
+                None => {}
             }
         }
 
@@ -324,6 +693,43 @@ where
     }
 }
 
+fn execute_operation<Query, Mutation, Subscription>(
+    schema: Schema<Query, Mutation, Subscription>,
+    on_operation: Option<&OnOperationFn>,
+    mut request: Request,
+    data: Arc<Data>,
+) -> Pin<Box<dyn Stream<Item = Response> + Send>>
+where
+    Query: ObjectType + 'static,
+    Mutation: ObjectType + 'static,
+    Subscription: SubscriptionType + 'static,
+{
+    let operation_fut = match on_operation {
+        Some(on_operation) => on_operation(request.extensions.clone()),
+        None => return Box::pin(schema.execute_stream_with_session_data(request, data)),
+    };
+    Box::pin(async_stream::stream! {
+        match operation_fut.await {
+            Ok(operation_data) => {
+                request.data.merge(operation_data);
+                let mut stream = schema.execute_stream_with_session_data(request, data);
+                while let Some(resp) = stream.next().await {
+                    yield resp;
+                }
+            }
+            Err(err) => {
+                yield Response::from_request_errors(vec![ServerError {
+                    message: err.message,
+                    source: err.source,
+                    locations: Vec::new(),
+                    path: Vec::new(),
+                    extensions: err.extensions,
+                }]);
+            }
+        }
+    })
+}
+
 /// Specification of which GraphQL Over WebSockets protocol is being utilized
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 pub enum Protocols {
@@ -439,11 +845,14 @@ enum ServerMessage<'a> {
         id: &'a str,
         payload: Response,
     },
-    // Not used by this library, as it's not necessary to send
-    // Error {
-    //     id: &'a str,
-    //     payload: serde_json::Value,
-    // },
+    /// graphql-ws protocol errors of an operation failing before it is
+    /// executed, such as parse or validation errors.
+    ///
+    /// https://github.com/enisdenjo/graphql-ws/blob/master/PROTOCOL.md#error
+    Error {
+        id: &'a str,
+        payload: Vec<ServerError>,
+    },
     Complete {
         id: &'a str,
     },
@@ -454,7 +863,14 @@ enum ServerMessage<'a> {
         #[serde(skip_serializing_if = "Option::is_none")]
         payload: Option<serde_json::Value>,
     },
-    // Not used by this library
-    // #[serde(rename = "ka")]
-    // KeepAlive
+    /// The keep-alive message of the graphql-ws protocol.
+    ///
+    /// https://github.com/enisdenjo/graphql-ws/blob/master/PROTOCOL.md#ping
+    Ping {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        payload: Option<serde_json::Value>,
+    },
+    /// The keep-alive message of the subscriptions-transport-ws protocol.
+    #[serde(rename = "ka")]
+    KeepAlive,
 }
@@ -0,0 +1,538 @@
+//! Support for incremental delivery with `@defer` and `@stream`.
+//!
+//! Deferred fragments and streamed items are resolved in the same execution
+//! as the initial payload, from the parent values resolved for it. Each of
+//! them is a delivery, sent as an incremental payload once it is resolved and
+//! the payload it is nested in has been sent.
+//!
+//! An object or a list whose deferred fragments or streamed items are still
+//! being resolved publishes its value as soon as the rest of it is resolved,
+//! so that its parent, and eventually the initial payload, does not wait for
+//! them.
+
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    future::Future,
+    pin::Pin,
+    sync::Mutex,
+    task::{Context, Poll},
+};
+
+use futures_util::{
+    future::{self, BoxFuture},
+    stream::{FuturesUnordered, StreamExt},
+    FutureExt,
+};
+
+use crate::{
+    context::{QueryPathNode, QueryPathSegment},
+    parser::types::{Directive, Field, FragmentDefinition, Selection, SelectionSet},
+    ContextBase, ContextSelectionSet, IncrementalPayload, Name, PathSegment, Positioned, QueryEnv,
+    ServerError, ServerResult, Value,
+};
+
+/// The id of the initial payload.
+const INITIAL: usize = 0;
+
+/// A value to resolve, with the path where it is published.
+pub(crate) type Child<'a> = (Vec<PathSegment>, BoxFuture<'a, ServerResult<Value>>);
+
+struct Delivery {
+    /// The delivery this one is nested in, which is sent first.
+    parent: usize,
+    payload: Option<IncrementalPayload>,
+    errors: Vec<ServerError>,
+    finished: bool,
+}
+
+#[derive(Default)]
+struct Deliveries {
+    next_id: usize,
+    /// The deliveries being polled, innermost last.
+    current: Vec<usize>,
+    pending: BTreeMap<usize, Delivery>,
+    sent: HashSet<usize>,
+}
+
+impl Deliveries {
+    fn is_sendable(&self, delivery: &Delivery) -> bool {
+        delivery.payload.is_some()
+            && (delivery.parent == INITIAL || self.sent.contains(&delivery.parent))
+    }
+
+    /// Removes the deliveries that were cancelled, or are nested in one.
+    fn prune(&mut self) {
+        self.pending
+            .retain(|_, delivery| !delivery.finished || delivery.payload.is_some());
+        loop {
+            let orphans = self
+                .pending
+                .iter()
+                .filter(|(_, delivery)| {
+                    delivery.parent != INITIAL
+                        && !self.sent.contains(&delivery.parent)
+                        && !self.pending.contains_key(&delivery.parent)
+                })
+                .map(|(id, _)| *id)
+                .collect::<Vec<_>>();
+            if orphans.is_empty() {
+                break;
+            }
+            for id in orphans {
+                self.pending.remove(&id);
+            }
+        }
+    }
+}
+
+/// The state of the incremental delivery of an operation.
+#[derive(Default)]
+pub(crate) struct Incremental {
+    /// Values published by the objects and lists whose deferred fragments or
+    /// streamed items are still being resolved.
+    published: Mutex<HashMap<Vec<PathSegment>, Value>>,
+    deliveries: Mutex<Deliveries>,
+}
+
+impl Incremental {
+    pub(crate) fn publish(&self, path: Vec<PathSegment>, value: Value) {
+        self.published.lock().unwrap().insert(path, value);
+    }
+
+    pub(crate) fn take_published(&self, path: &[PathSegment]) -> Option<Value> {
+        self.published.lock().unwrap().remove(path)
+    }
+
+    /// Registers a delivery nested in `parent`, or in the delivery being
+    /// polled.
+    fn begin(&self, parent: Option<usize>) -> usize {
+        let mut deliveries = self.deliveries.lock().unwrap();
+        deliveries.next_id += 1;
+        let id = deliveries.next_id;
+        let parent = parent
+            .or_else(|| deliveries.current.last().copied())
+            .unwrap_or(INITIAL);
+        deliveries.pending.insert(
+            id,
+            Delivery {
+                parent,
+                payload: None,
+                errors: Vec::new(),
+                finished: false,
+            },
+        );
+        id
+    }
+
+    fn complete(&self, id: usize, payload: IncrementalPayload) {
+        if let Some(delivery) = self.deliveries.lock().unwrap().pending.get_mut(&id) {
+            if delivery.payload.is_none() {
+                delivery.payload = Some(payload);
+            }
+        }
+    }
+
+    /// Returns `true` if some payloads can be sent.
+    pub(crate) fn has_payloads(&self) -> bool {
+        let deliveries = self.deliveries.lock().unwrap();
+        deliveries
+            .pending
+            .values()
+            .any(|delivery| deliveries.is_sendable(delivery))
+    }
+
+    /// Takes the payloads whose parent has already been sent.
+    pub(crate) fn take_payloads(&self) -> Vec<IncrementalPayload> {
+        let mut deliveries = self.deliveries.lock().unwrap();
+        deliveries.prune();
+        let ids = deliveries
+            .pending
+            .iter()
+            .filter(|(_, delivery)| deliveries.is_sendable(delivery))
+            .map(|(id, _)| *id)
+            .collect::<Vec<_>>();
+
+        let mut payloads = Vec::with_capacity(ids.len());
+        for id in ids {
+            if let Some(mut delivery) = deliveries.pending.remove(&id) {
+                let mut payload = delivery.payload.take().unwrap_or_default();
+                payload.errors.append(&mut delivery.errors);
+                payloads.push(payload);
+                deliveries.sent.insert(id);
+            }
+        }
+        payloads
+    }
+
+    /// Returns `true` if some deliveries have not been sent yet.
+    pub(crate) fn has_next(&self) -> bool {
+        let mut deliveries = self.deliveries.lock().unwrap();
+        deliveries.prune();
+        !deliveries.pending.is_empty()
+    }
+}
+
+pub(crate) fn path_segments(path_node: Option<&QueryPathNode<'_>>) -> Vec<PathSegment> {
+    let mut path = Vec::new();
+    if let Some(path_node) = path_node {
+        path_node.for_each(|segment| {
+            path.push(match segment {
+                QueryPathSegment::Name(name) => PathSegment::Field((*name).to_string()),
+                QueryPathSegment::Index(idx) => PathSegment::Index(*idx),
+            })
+        });
+    }
+    path
+}
+
+fn find_directive<'a>(
+    directives: &'a [Positioned<Directive>],
+    name: &str,
+) -> Option<&'a Positioned<Directive>> {
+    directives
+        .iter()
+        .find(|directive| directive.node.name.node == name)
+}
+
+/// Returns `true` if a `@defer` or `@stream` directive is used in the
+/// selection set or in the fragments it spreads.
+pub(crate) fn uses_incremental_delivery<'a>(
+    selection_set: &'a SelectionSet,
+    fragments: &'a HashMap<Name, Positioned<FragmentDefinition>>,
+    visited: &mut HashSet<&'a Name>,
+) -> bool {
+    selection_set
+        .items
+        .iter()
+        .any(|selection| match &selection.node {
+            Selection::Field(field) => {
+                find_directive(&field.node.directives, "stream").is_some()
+                    || uses_incremental_delivery(&field.node.selection_set.node, fragments, visited)
+            }
+            Selection::FragmentSpread(spread) => {
+                find_directive(&spread.node.directives, "defer").is_some()
+                    || (visited.insert(&spread.node.fragment_name.node)
+                        && fragments
+                            .get(&spread.node.fragment_name.node)
+                            .map(|fragment| {
+                                uses_incremental_delivery(
+                                    &fragment.node.selection_set.node,
+                                    fragments,
+                                    visited,
+                                )
+                            })
+                            .unwrap_or_default())
+            }
+            Selection::InlineFragment(fragment) => {
+                find_directive(&fragment.node.directives, "defer").is_some()
+                    || uses_incremental_delivery(
+                        &fragment.node.selection_set.node,
+                        fragments,
+                        visited,
+                    )
+            }
+        })
+}
+
+/// Returns the label of the fragment if it has an enabled `@defer`
+/// directive, or `None` if it must be resolved now.
+pub(crate) fn defer_label(
+    ctx: &ContextSelectionSet<'_>,
+    directives: &[Positioned<Directive>],
+) -> ServerResult<Option<Option<String>>> {
+    if ctx.query_env.incremental.is_none() {
+        return Ok(None);
+    }
+    let directive = match find_directive(directives, "defer") {
+        Some(directive) => directive,
+        None => return Ok(None),
+    };
+
+    let ctx_directive = ContextBase {
+        path_node: ctx.path_node,
+        item: directive,
+        schema_env: ctx.schema_env,
+        query_env: ctx.query_env,
+    };
+    let (_, enabled) = ctx_directive.param_value::<bool>("if", Some(|| true))?;
+    if !enabled {
+        return Ok(None);
+    }
+    let (_, label) = ctx_directive.param_value::<Option<String>>("label", None)?;
+    Ok(Some(label))
+}
+
+/// Returns the number of items to resolve in the initial payload if the field
+/// has an enabled `@stream` directive.
+///
+/// Only the outermost list of the field is streamed, the lists nested in it
+/// are resolved at the path of one of its items.
+pub(crate) fn stream_initial_count(
+    ctx: &ContextSelectionSet<'_>,
+    field: &Positioned<Field>,
+) -> ServerResult<Option<(usize, Option<String>)>> {
+    if ctx.query_env.incremental.is_none() {
+        return Ok(None);
+    }
+    if !matches!(
+        ctx.path_node,
+        Some(QueryPathNode {
+            segment: QueryPathSegment::Name(_),
+            ..
+        })
+    ) {
+        return Ok(None);
+    }
+    let directive = match find_directive(&field.node.directives, "stream") {
+        Some(directive) => directive,
+        None => return Ok(None),
+    };
+
+    let ctx_directive = ContextBase {
+        path_node: ctx.path_node,
+        item: directive,
+        schema_env: ctx.schema_env,
+        query_env: ctx.query_env,
+    };
+    let (_, enabled) = ctx_directive.param_value::<bool>("if", Some(|| true))?;
+    if !enabled {
+        return Ok(None);
+    }
+    let (_, initial_count) = ctx_directive.param_value::<usize>("initialCount", Some(|| 0))?;
+    let (_, label) = ctx_directive.param_value::<Option<String>>("label", None)?;
+    Ok(Some((initial_count, label)))
+}
+
+/// Resolves the values of an object or a list.
+///
+/// A child may publish its value at its path while its deferred fragments
+/// or streamed items are still being resolved. Once the value of every child
+/// is known, they are combined with `build`, and if some children or `tails`
+/// are still running, the result is passed to `publish` before they are
+/// polled to completion.
+pub(crate) async fn join<'a, T>(
+    incremental: &Incremental,
+    children: Vec<Child<'a>>,
+    tails: Vec<BoxFuture<'a, ()>>,
+    build: impl FnOnce(Vec<Value>) -> T,
+    publish: impl FnOnce(&T),
+) -> ServerResult<T> {
+    let mut children = children
+        .into_iter()
+        .map(|(path, fut)| (path, Some(fut), None))
+        .collect::<Vec<_>>();
+    let mut tails = tails.into_iter().collect::<FuturesUnordered<_>>();
+    let mut build = Some(build);
+    let mut publish = Some(publish);
+    let mut result = None;
+
+    future::poll_fn(|cx| {
+        if result.is_none() {
+            for (path, fut, value) in &mut children {
+                let mut child = match fut.take() {
+                    Some(child) => child,
+                    None => continue,
+                };
+                match child.as_mut().poll(cx) {
+                    Poll::Ready(Ok(resolved)) => {
+                        incremental.take_published(path);
+                        *value = Some(resolved);
+                    }
+                    Poll::Ready(Err(err)) => return Poll::Ready(Err(err)),
+                    Poll::Pending => match incremental.take_published(path) {
+                        Some(published) => {
+                            *value = Some(published);
+                            tails.push(child.map(|_| ()).boxed());
+                        }
+                        None => *fut = Some(child),
+                    },
+                }
+            }
+            if children.iter().all(|(_, _, value)| value.is_some()) {
+                let values = children
+                    .drain(..)
+                    .map(|(_, _, value)| value.unwrap_or_default())
+                    .collect();
+                result = build.take().map(|build| build(values));
+            }
+        }
+
+        while let Poll::Ready(Some(())) = tails.poll_next_unpin(cx) {}
+
+        match result.take() {
+            Some(value) if tails.is_empty() => Poll::Ready(Ok(value)),
+            Some(value) => {
+                if let Some(publish) = publish.take() {
+                    publish(&value);
+                }
+                result = Some(value);
+                Poll::Pending
+            }
+            None => Poll::Pending,
+        }
+    })
+    .await
+}
+
+#[derive(Clone, Copy)]
+enum DeliveryKind {
+    Defer,
+    Stream,
+}
+
+struct Target {
+    kind: DeliveryKind,
+    path: Vec<PathSegment>,
+    label: Option<String>,
+}
+
+impl Target {
+    fn payload(&self, res: ServerResult<Value>) -> IncrementalPayload {
+        let (value, errors) = match res {
+            Ok(value) => (Some(value), Vec::new()),
+            Err(err) => (None, vec![err]),
+        };
+        match self.kind {
+            DeliveryKind::Defer => IncrementalPayload {
+                data: Some(value.unwrap_or_default()),
+                items: None,
+                path: self.path.clone(),
+                label: self.label.clone(),
+                errors,
+            },
+            DeliveryKind::Stream => IncrementalPayload {
+                data: None,
+                items: value.map(|value| vec![value]),
+                path: self.path.clone(),
+                label: self.label.clone(),
+                errors,
+            },
+        }
+    }
+}
+
+/// Polls a delivery, and collects the errors added while polling it.
+struct Deliver<'a> {
+    query_env: &'a QueryEnv,
+    id: usize,
+    fut: BoxFuture<'a, ()>,
+    finished: bool,
+}
+
+impl<'a> Deliver<'a> {
+    fn incremental(&self) -> &'a Incremental {
+        self.query_env
+            .incremental
+            .as_ref()
+            .expect("incremental delivery is enabled")
+    }
+}
+
+impl Future for Deliver<'_> {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+        let incremental = this.incremental();
+        incremental.deliveries.lock().unwrap().current.push(this.id);
+        let start = this.query_env.errors.lock().unwrap().len();
+
+        let res = this.fut.as_mut().poll(cx);
+
+        let errors = {
+            let mut errors = this.query_env.errors.lock().unwrap();
+            let start = start.min(errors.len());
+            errors.split_off(start)
+        };
+        let mut deliveries = incremental.deliveries.lock().unwrap();
+        deliveries.current.pop();
+        if let Some(delivery) = deliveries.pending.get_mut(&this.id) {
+            delivery.errors.extend(errors);
+            delivery.finished = res.is_ready();
+        }
+        this.finished = res.is_ready();
+        res
+    }
+}
+
+impl Drop for Deliver<'_> {
+    fn drop(&mut self) {
+        if !self.finished {
+            let mut deliveries = self.incremental().deliveries.lock().unwrap();
+            if let Some(delivery) = deliveries.pending.get_mut(&self.id) {
+                delivery.finished = true;
+            }
+        }
+    }
+}
+
+/// Creates a delivery resolving the children with [`join`], which is sent
+/// as soon as their values are known.
+fn deliver<'a>(
+    query_env: &'a QueryEnv,
+    parent: Option<usize>,
+    target: Target,
+    children: Vec<Child<'a>>,
+    tails: Vec<BoxFuture<'a, ()>>,
+    build: impl FnOnce(Vec<Value>) -> Value + Send + 'a,
+) -> (usize, BoxFuture<'a, ()>) {
+    let incremental = query_env
+        .incremental
+        .as_ref()
+        .expect("incremental delivery is enabled");
+    let id = incremental.begin(parent);
+    let fut = async move {
+        let res = join(incremental, children, tails, build, |value| {
+            incremental.complete(id, target.payload(Ok(value.clone())))
+        })
+        .await;
+        incremental.complete(id, target.payload(res));
+    };
+    let deliver = Deliver {
+        query_env,
+        id,
+        fut: fut.boxed(),
+        finished: false,
+    };
+    (id, deliver.boxed())
+}
+
+/// Creates the delivery of a deferred fragment, resolving its fields from
+/// the parent value.
+pub(crate) fn defer<'a>(
+    ctx: &ContextSelectionSet<'a>,
+    label: Option<String>,
+    children: Vec<Child<'a>>,
+    tails: Vec<BoxFuture<'a, ()>>,
+    build: impl FnOnce(Vec<Value>) -> Value + Send + 'a,
+) -> BoxFuture<'a, ()> {
+    let target = Target {
+        kind: DeliveryKind::Defer,
+        path: path_segments(ctx.path_node.as_ref()),
+        label,
+    };
+    deliver(ctx.query_env, None, target, children, tails, build).1
+}
+
+/// Creates the delivery of a streamed item, sent after the item `previous`
+/// if it is not the first streamed item of the list.
+pub(crate) fn stream_item<'a>(
+    query_env: &'a QueryEnv,
+    previous: Option<usize>,
+    label: Option<String>,
+    item: Child<'a>,
+) -> (usize, BoxFuture<'a, ()>) {
+    let target = Target {
+        kind: DeliveryKind::Stream,
+        path: item.0.clone(),
+        label,
+    };
+    deliver(
+        query_env,
+        previous,
+        target,
+        vec![item],
+        Vec::new(),
+        |values| values.into_iter().next().unwrap_or_default(),
+    )
+}
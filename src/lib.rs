@@ -64,7 +64,8 @@
 //!   extension](extensions/struct.ApolloTracing.html).
 //! - `apollo_persisted_queries`: Enable the [Apollo persisted queries
 //!   extension](extensions/apollo_persisted_queries/struct.
-//!   ApolloPersistedQueries.html).
+//!   ApolloPersistedQueries.html) and the [trusted documents
+//!   extension](extensions/apollo_persisted_queries/struct.TrustedDocuments.html).
 //! - `log`: Enable the [logger extension](extensions/struct.Logger.html).
 //! - `tracing`: Enable the [tracing extension](extensions/struct.Tracing.html).
 //! - `opentelemetry`: Enable the [OpenTelemetry
@@ -88,7 +89,8 @@
 //! - `hashbrown`: Integrate with the [`hashbrown` crate](https://github.com/rust-lang/hashbrown).
 //! - `time`: Integrate with the [`time` crate](https://github.com/time-rs/time).
 //! - `tokio-sync` Integrate with the [`tokio::sync::RwLock`](https://docs.rs/tokio/1.18.1/tokio/sync/struct.RwLock.html)
-//!   and [`tokio::sync::Mutex`](https://docs.rs/tokio/1.18.1/tokio/sync/struct.Mutex.html).
+//!   and [`tokio::sync::Mutex`](https://docs.rs/tokio/1.18.1/tokio/sync/struct.Mutex.html),
+//!   and enable the [in-memory pub/sub broker](pubsub/struct.MemoryPubSub.html).
 //! - `fast_chemail`: Integrate with the [`fast_chemail` crate](https://crates.io/crates/fast_chemail).
 //!
 //! ## Integrations
@@ -179,12 +181,15 @@
 #![forbid(unsafe_code)]
 #![cfg_attr(docsrs, feature(doc_cfg))]
 
+mod auth;
 mod base;
 mod custom_directive;
 mod error;
 mod guard;
+mod incremental;
 mod look_ahead;
 mod model;
+mod redaction;
 mod request;
 mod response;
 mod schema;
@@ -195,8 +200,11 @@ pub mod context;
 #[cfg(feature = "dataloader")]
 #[cfg_attr(docsrs, doc(cfg(feature = "dataloader")))]
 pub mod dataloader;
+pub mod dynamic;
 pub mod extensions;
+pub mod gateway;
 pub mod http;
+pub mod pubsub;
 pub mod resolver_utils;
 pub mod types;
 #[doc(hidden)]
@@ -214,6 +222,7 @@ pub use async_graphql_value::{
 pub use async_stream;
 #[doc(hidden)]
 pub use async_trait;
+pub use auth::{AuthMode, AuthPolicy, AuthRequest};
 pub use base::{
     ComplexObject, Description, InputObjectType, InputType, InterfaceType, ObjectType,
     OneofObjectType, OutputType, TypeName, UnionType,
@@ -235,11 +244,14 @@ pub use indexmap;
 pub use look_ahead::Lookahead;
 #[doc(no_inline)]
 pub use parser::{Pos, Positioned};
-pub use registry::{CacheControl, SDLExportOptions};
+pub use redaction::{Redaction, RedactionPolicy, Redactor};
+pub use registry::{
+    CacheControl, SDLExportOptions, SchemaChange, SchemaChangeKind, SchemaChangeLevel, SchemaDiff,
+};
 pub use request::{BatchRequest, Request};
 #[doc(no_inline)]
 pub use resolver_utils::{ContainerType, EnumType, ScalarType};
-pub use response::{BatchResponse, Response};
+pub use response::{BatchResponse, IncrementalPayload, Response};
 pub use schema::{IntrospectionMode, Schema, SchemaBuilder, SchemaEnv};
 #[doc(hidden)]
 pub use static_assertions;
@@ -1,6 +1,7 @@
 use std::collections::HashSet;
 
 use crate::{
+    auth,
     model::{__EnumValue, __Field, __InputValue, __TypeKind},
     registry,
     registry::is_visible,
@@ -120,21 +121,22 @@ impl<'a> __Type<'a> {
         #[graphql(default = false)] include_deprecated: bool,
     ) -> Option<Vec<__Field<'a>>> {
         if let TypeDetail::Named(ty) = &self.detail {
-            ty.fields().map(|fields| {
-                fields
-                    .values()
-                    .filter(|field| is_visible(ctx, &field.visible))
-                    .filter(|field| {
-                        (include_deprecated || !field.deprecation.is_deprecated())
-                            && !field.name.starts_with("__")
-                    })
-                    .map(|field| __Field {
+            let fields = ty.fields()?.values().filter(|field| {
+                is_visible(ctx, &field.visible)
+                    && (include_deprecated || !field.deprecation.is_deprecated())
+                    && !field.name.starts_with("__")
+            });
+            let mut visible_fields = Vec::new();
+            for field in fields {
+                if auth::is_visible(ctx, ty.name(), field).await {
+                    visible_fields.push(__Field {
                         registry: self.registry,
                         visible_types: self.visible_types,
                         field,
-                    })
-                    .collect()
-            })
+                    });
+                }
+            }
+            Some(visible_fields)
         } else {
             None
         }
@@ -0,0 +1,270 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, Weak},
+};
+
+use futures_util::stream::{self, BoxStream, StreamExt};
+use tokio::sync::broadcast::{self, error::RecvError};
+
+use super::PubSub;
+use crate::Result;
+
+/// An in-memory [PubSub] broker.
+///
+/// Each topic is a [broadcast](tokio::sync::broadcast) channel, which is
+/// created by the first subscriber and removed when the last one is dropped.
+///
+/// A subscriber that falls behind the capacity of the channel misses the
+/// oldest payloads.
+///
+/// The clones of the broker share the same topics.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::{pubsub::*, *};
+/// use futures_util::stream::{Stream, StreamExt};
+///
+/// struct Query;
+///
+/// #[Object]
+/// impl Query {
+///     async fn value(&self) -> i32 {
+///         0
+///     }
+/// }
+///
+/// struct Mutation;
+///
+/// #[Object]
+/// impl Mutation {
+///     async fn send(&self, ctx: &Context<'_>, value: i32) -> Result<bool> {
+///         let pubsub = ctx.data_unchecked::<MemoryPubSub<i32>>();
+///         pubsub.publish("values", value).await?;
+///         Ok(true)
+///     }
+/// }
+///
+/// struct Subscription;
+///
+/// #[Subscription]
+/// impl Subscription {
+///     async fn values(&self, ctx: &Context<'_>) -> impl Stream<Item = i32> {
+///         ctx.data_unchecked::<MemoryPubSub<i32>>().subscribe("values")
+///     }
+///
+///     async fn even_values(&self, ctx: &Context<'_>) -> impl Stream<Item = i32> {
+///         ctx.data_unchecked::<MemoryPubSub<i32>>()
+///             .subscribe_filter("values", |value| value % 2 == 0)
+///     }
+/// }
+///
+/// let schema = Schema::build(Query, Mutation, Subscription)
+///     .data(MemoryPubSub::<i32>::new())
+///     .finish();
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async move {
+/// let stream = schema.execute_stream("subscription { evenValues }");
+/// let publish = async {
+///     for value in 1..=4 {
+///         schema.execute(format!("mutation {{ send(value: {}) }}", value)).await;
+///     }
+/// };
+/// let (responses, _) =
+///     futures_util::future::join(stream.take(2).collect::<Vec<_>>(), publish).await;
+/// assert_eq!(
+///     responses.into_iter().map(|resp| resp.data).collect::<Vec<_>>(),
+///     vec![value!({ "evenValues": 2 }), value!({ "evenValues": 4 })]
+/// );
+/// # });
+/// ```
+pub struct MemoryPubSub<T> {
+    topics: Arc<Mutex<Topics<T>>>,
+    capacity: usize,
+}
+
+type Topics<T> = HashMap<String, broadcast::Sender<T>>;
+
+/// Removes the topic of a subscriber when it is the last one, it must be
+/// dropped after the receiver of the subscriber.
+struct Subscriber<T> {
+    topics: Weak<Mutex<Topics<T>>>,
+    topic: String,
+}
+
+impl<T> Drop for Subscriber<T> {
+    fn drop(&mut self) {
+        if let Some(topics) = self.topics.upgrade() {
+            let mut topics = topics.lock().unwrap();
+            if let Some(sender) = topics.get(&self.topic) {
+                if sender.receiver_count() == 0 {
+                    topics.remove(&self.topic);
+                }
+            }
+        }
+    }
+}
+
+impl<T> Clone for MemoryPubSub<T> {
+    fn clone(&self) -> Self {
+        Self {
+            topics: self.topics.clone(),
+            capacity: self.capacity,
+        }
+    }
+}
+
+impl<T: Clone + Send + 'static> Default for MemoryPubSub<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone + Send + 'static> MemoryPubSub<T> {
+    /// Create an in-memory broker that buffers up to 1024 payloads for each
+    /// topic.
+    pub fn new() -> Self {
+        Self::with_capacity(1024)
+    }
+
+    /// Create an in-memory broker that buffers up to `capacity` payloads for
+    /// each topic.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is 0.
+    pub fn with_capacity(capacity: usize) -> Self {
+        assert!(capacity > 0, "capacity is empty");
+        Self {
+            topics: Default::default(),
+            capacity,
+        }
+    }
+
+    /// Returns the number of topics with subscribers.
+    pub fn topic_count(&self) -> usize {
+        self.topics.lock().unwrap().len()
+    }
+
+    /// Returns the number of subscribers of a topic.
+    pub fn subscriber_count(&self, topic: &str) -> usize {
+        self.topics
+            .lock()
+            .unwrap()
+            .get(topic)
+            .map(broadcast::Sender::receiver_count)
+            .unwrap_or_default()
+    }
+}
+
+#[async_trait::async_trait]
+impl<T: Clone + Send + 'static> PubSub<T> for MemoryPubSub<T> {
+    async fn publish(&self, topic: &str, payload: T) -> Result<()> {
+        if let Some(sender) = self.topics.lock().unwrap().get(topic) {
+            sender.send(payload).ok();
+        }
+        Ok(())
+    }
+
+    fn subscribe(&self, topic: &str) -> BoxStream<'static, T> {
+        let receiver = self
+            .topics
+            .lock()
+            .unwrap()
+            .entry(topic.to_string())
+            .or_insert_with(|| broadcast::channel(self.capacity).0)
+            .subscribe();
+
+        let subscriber = Subscriber {
+            topics: Arc::downgrade(&self.topics),
+            topic: topic.to_string(),
+        };
+
+        // The receiver is dropped before the subscriber.
+        stream::unfold(
+            (receiver, subscriber),
+            |(mut receiver, subscriber)| async move {
+                loop {
+                    match receiver.recv().await {
+                        Ok(payload) => return Some((payload, (receiver, subscriber))),
+                        Err(RecvError::Lagged(_)) => continue,
+                        Err(RecvError::Closed) => return None,
+                    }
+                }
+            },
+        )
+        .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_memory_pubsub() {
+        let pubsub = MemoryPubSub::<i32>::new();
+        let mut a = pubsub.subscribe("a");
+        let mut b = pubsub.subscribe("b");
+        let mut even = pubsub.subscribe_filter("a", |value| value % 2 == 0);
+        assert_eq!(pubsub.subscriber_count("a"), 2);
+
+        for value in 1..=4 {
+            pubsub.publish("a", value).await.unwrap();
+        }
+        pubsub.publish("b", 10).await.unwrap();
+        pubsub.publish("c", 20).await.unwrap();
+
+        assert_eq!(
+            a.by_ref().take(4).collect::<Vec<_>>().await,
+            vec![1, 2, 3, 4]
+        );
+        assert_eq!(even.by_ref().take(2).collect::<Vec<_>>().await, vec![2, 4]);
+        assert_eq!(b.next().await, Some(10));
+
+        drop(pubsub);
+        assert_eq!(a.next().await, None);
+        assert_eq!(b.next().await, None);
+    }
+
+    #[tokio::test]
+    async fn test_memory_pubsub_remove_topic() {
+        let pubsub = MemoryPubSub::<i32>::new();
+        let a = pubsub.subscribe("a");
+        let b = pubsub.subscribe("a");
+        let c = pubsub.subscribe("c");
+        assert_eq!(pubsub.topic_count(), 2);
+
+        drop(a);
+        assert_eq!(pubsub.topic_count(), 2);
+        drop(b);
+        assert_eq!(pubsub.topic_count(), 1);
+        assert_eq!(pubsub.subscriber_count("a"), 0);
+        drop(c);
+        assert_eq!(pubsub.topic_count(), 0);
+
+        let mut stream = pubsub.subscribe("a");
+        pubsub.publish("a", 2).await.unwrap();
+        assert_eq!(stream.next().await, Some(2));
+    }
+
+    #[tokio::test]
+    async fn test_memory_pubsub_lagged() {
+        let pubsub = MemoryPubSub::<i32>::with_capacity(2);
+        let stream = pubsub.subscribe("a");
+        for value in 1..=4 {
+            pubsub.publish("a", value).await.unwrap();
+        }
+        drop(pubsub);
+        assert_eq!(stream.collect::<Vec<_>>().await, vec![3, 4]);
+    }
+
+    #[tokio::test]
+    async fn test_dyn_pubsub() {
+        let pubsub: Arc<dyn PubSub<i32>> = Arc::new(MemoryPubSub::<i32>::new());
+        let mut stream = pubsub.subscribe_filter("a", |value| *value > 1);
+        pubsub.publish("a", 1).await.unwrap();
+        pubsub.publish("a", 2).await.unwrap();
+        assert_eq!(stream.next().await, Some(2));
+    }
+}
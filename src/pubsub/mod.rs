@@ -0,0 +1,72 @@
+//! Publish/subscribe brokers for subscriptions.
+//!
+//! A [PubSub] broker delivers the payloads published to a topic to all the
+//! streams subscribed to it, so that the mutations can feed the streams
+//! returned by the resolvers of the subscription fields.
+//!
+//! [MemoryPubSub] (requires the `tokio-sync` feature) is an in-memory broker
+//! for a single process. An external broker, such as Redis or NATS, is plugged
+//! in by implementing [PubSub] for an adapter.
+
+#[cfg(feature = "tokio-sync")]
+mod memory;
+
+use std::sync::Arc;
+
+use futures_util::{
+    future,
+    stream::{BoxStream, StreamExt},
+};
+#[cfg(feature = "tokio-sync")]
+#[cfg_attr(docsrs, doc(cfg(feature = "tokio-sync")))]
+pub use memory::MemoryPubSub;
+
+use crate::Result;
+
+/// A topic-based publish/subscribe broker.
+///
+/// Implement this trait for an adapter to deliver the payloads through an
+/// external broker.
+#[async_trait::async_trait]
+pub trait PubSub<T>: Send + Sync + 'static
+where
+    T: Send + 'static,
+{
+    /// Publish a payload to all the subscribers of a topic.
+    ///
+    /// A payload published to a topic without subscribers is discarded.
+    async fn publish(&self, topic: &str, payload: T) -> Result<()>;
+
+    /// Subscribe to a topic.
+    ///
+    /// The stream yields the payloads published after this call, and ends
+    /// when the broker is dropped.
+    fn subscribe(&self, topic: &str) -> BoxStream<'static, T>;
+
+    /// Subscribe to the payloads of a topic for which the predicate returns
+    /// `true`.
+    fn subscribe_filter<F>(&self, topic: &str, predicate: F) -> BoxStream<'static, T>
+    where
+        Self: Sized,
+        F: Fn(&T) -> bool + Send + 'static,
+    {
+        self.subscribe(topic)
+            .filter(move |payload| future::ready(predicate(payload)))
+            .boxed()
+    }
+}
+
+#[async_trait::async_trait]
+impl<T, P> PubSub<T> for Arc<P>
+where
+    T: Send + 'static,
+    P: PubSub<T> + ?Sized,
+{
+    async fn publish(&self, topic: &str, payload: T) -> Result<()> {
+        P::publish(self, topic, payload).await
+    }
+
+    fn subscribe(&self, topic: &str) -> BoxStream<'static, T> {
+        P::subscribe(self, topic)
+    }
+}
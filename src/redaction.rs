@@ -0,0 +1,262 @@
+//! Field redaction
+
+use std::future::Future;
+
+use crate::{
+    context::QueryPathSegment,
+    parser::types::Field,
+    registry::{MetaField, MetaTypeName},
+    Context, ContextSelectionSet, Name, PathSegment, Positioned, ServerError, ServerResult, Value,
+};
+
+/// How a value of a redacted field is replaced.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Redaction {
+    /// Keep the value.
+    Keep,
+    /// Replace the value with a masked value.
+    Mask(Value),
+    /// Replace the value with `null`.
+    Null,
+}
+
+/// Redaction policy
+///
+/// The policy decides how the values of the fields annotated with
+/// `#[graphql(redact = "...")]` are redacted, from their category and the
+/// context, for example the role of the caller.
+pub trait RedactionPolicy: Send + Sync + 'static {
+    /// Returns how a resolved value of a category is redacted.
+    ///
+    /// The policy is called with each item of the lists, and never with
+    /// `null`.
+    fn redact(&self, ctx: &Context<'_>, category: &str, value: &Value) -> Redaction;
+}
+
+impl<T> RedactionPolicy for T
+where
+    T: Fn(&Context<'_>, &str, &Value) -> Redaction + Send + Sync + 'static,
+{
+    fn redact(&self, ctx: &Context<'_>, category: &str, value: &Value) -> Redaction {
+        self(ctx, category, value)
+    }
+}
+
+/// Redacts the values of the fields annotated with
+/// `#[graphql(redact = "...")]` after they are resolved, when it is in the
+/// data of the schema or of the request.
+///
+/// The paths of the redacted fields are listed in the `redacted` extension
+/// of the response.
+///
+/// A value of a non-null type replaced with `null` is an error of the field.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+///
+/// #[derive(SimpleObject)]
+/// struct User {
+///     name: String,
+///     #[graphql(redact = "email")]
+///     email: String,
+/// }
+///
+/// struct Query;
+///
+/// #[Object]
+/// impl Query {
+///     async fn users(&self) -> Vec<User> {
+///         vec![User { name: "Alice".to_string(), email: "alice@example.com".to_string() }]
+///     }
+/// }
+///
+/// let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+/// let redactor = Redactor::new(|_: &Context<'_>, _: &str, _: &Value| {
+///     Redaction::Mask(Value::from("***"))
+/// });
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async move {
+/// let response = schema
+///     .execute(Request::new("{ users { name email } }").data(redactor))
+///     .await;
+/// assert_eq!(
+///     response.data,
+///     value!({ "users": [{ "name": "Alice", "email": "***" }] })
+/// );
+/// assert_eq!(
+///     response.extensions["redacted"],
+///     value!([["users", 0, "email"]])
+/// );
+/// # });
+/// ```
+pub struct Redactor(Box<dyn RedactionPolicy>);
+
+impl Redactor {
+    /// Create a redactor with a policy.
+    pub fn new(policy: impl RedactionPolicy) -> Self {
+        Self(Box::new(policy))
+    }
+}
+
+/// Returns the field of the registry if it is redacted, and there is a
+/// redactor in the context data.
+pub(crate) fn redacted_field<'a>(
+    ctx: &ContextSelectionSet<'a>,
+    type_name: &str,
+    field: &Field,
+) -> Option<&'a MetaField> {
+    ctx.data_opt::<Redactor>()?;
+    ctx.schema_env
+        .registry
+        .types
+        .get(type_name)?
+        .field_by_name(&field.name.node)
+        .filter(|meta_field| meta_field.redact.is_some())
+}
+
+/// Redacts the value of a field once it is resolved.
+pub(crate) async fn redact_field<'a>(
+    ctx: ContextSelectionSet<'a>,
+    field: &'a Positioned<Field>,
+    meta_field: &'a MetaField,
+    resolve_fut: impl Future<Output = ServerResult<(Name, Value)>>,
+) -> ServerResult<(Name, Value)> {
+    let (name, value) = resolve_fut.await?;
+    let ctx_field = ctx.with_field(field);
+    let redactor = ctx_field.data_unchecked::<Redactor>();
+    let category = meta_field.redact.as_deref().unwrap_or_default();
+
+    let mut redacted = false;
+    let value = redact_value(
+        &ctx_field,
+        redactor,
+        meta_field,
+        category,
+        &meta_field.ty,
+        value,
+        &mut Vec::new(),
+        &mut redacted,
+    );
+    if redacted {
+        let mut path = Vec::new();
+        if let Some(path_node) = &ctx_field.path_node {
+            path_node.for_each(|segment| {
+                path.push(match segment {
+                    QueryPathSegment::Name(name) => Value::String(name.to_string()),
+                    QueryPathSegment::Index(idx) => Value::Number((*idx).into()),
+                })
+            });
+        }
+        ctx_field
+            .query_env
+            .redacted
+            .lock()
+            .unwrap()
+            .push(Value::List(path));
+    }
+
+    value.map(|value| (name, value))
+}
+
+/// Returns an error at the path of the value if a value of a non-null type is
+/// redacted to `null`, the error is added to the response by the closest
+/// nullable list or field, which becomes `null`.
+#[allow(clippy::too_many_arguments)]
+fn redact_value(
+    ctx: &Context<'_>,
+    redactor: &Redactor,
+    meta_field: &MetaField,
+    category: &str,
+    ty: &str,
+    value: Value,
+    indices: &mut Vec<usize>,
+    redacted: &mut bool,
+) -> ServerResult<Value> {
+    match MetaTypeName::create(ty) {
+        MetaTypeName::NonNull(ty) => {
+            match redact_nullable(
+                ctx, redactor, meta_field, category, ty, value, indices, redacted,
+            )? {
+                Value::Null => Err(null_error(ctx, meta_field, indices)),
+                value => Ok(value),
+            }
+        }
+        _ => match redact_nullable(
+            ctx, redactor, meta_field, category, ty, value, indices, redacted,
+        ) {
+            Ok(value) => Ok(value),
+            Err(err) => {
+                ctx.add_error(err);
+                Ok(Value::Null)
+            }
+        },
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn redact_nullable(
+    ctx: &Context<'_>,
+    redactor: &Redactor,
+    meta_field: &MetaField,
+    category: &str,
+    ty: &str,
+    value: Value,
+    indices: &mut Vec<usize>,
+    redacted: &mut bool,
+) -> ServerResult<Value> {
+    match MetaTypeName::create(ty) {
+        MetaTypeName::List(ty) => match value {
+            Value::List(items) => {
+                let mut values = Vec::with_capacity(items.len());
+                for (idx, item) in items.into_iter().enumerate() {
+                    indices.push(idx);
+                    let value = redact_value(
+                        ctx, redactor, meta_field, category, ty, item, indices, redacted,
+                    );
+                    indices.pop();
+                    values.push(value?);
+                }
+                Ok(Value::List(values))
+            }
+            value => Ok(value),
+        },
+        MetaTypeName::Named(_) => match value {
+            Value::Null => Ok(Value::Null),
+            value => match redactor.0.redact(ctx, category, &value) {
+                Redaction::Keep => Ok(value),
+                Redaction::Mask(masked) => {
+                    *redacted = true;
+                    Ok(masked)
+                }
+                Redaction::Null => {
+                    *redacted = true;
+                    Ok(Value::Null)
+                }
+            },
+        },
+        MetaTypeName::NonNull(_) => redact_value(
+            ctx, redactor, meta_field, category, ty, value, indices, redacted,
+        ),
+    }
+}
+
+/// The error of a value of a non-null type redacted to `null`.
+fn null_error(ctx: &Context<'_>, meta_field: &MetaField, indices: &[usize]) -> ServerError {
+    let message = if indices.is_empty() {
+        format!(
+            r#"The non-null field "{}" is redacted to null."#,
+            meta_field.name
+        )
+    } else {
+        format!(
+            r#"The non-null item of the field "{}" is redacted to null."#,
+            meta_field.name
+        )
+    };
+    let mut err = ctx.set_error_path(ServerError::new(message, Some(ctx.item.pos)));
+    err.path
+        .extend(indices.iter().map(|idx| PathSegment::Index(*idx)));
+    err
+}
@@ -1,9 +1,20 @@
 use std::fmt::Write;
 
-use crate::registry::{Deprecation, MetaField, MetaInputValue, MetaType, Registry};
+use crate::registry::{Deprecation, MetaField, MetaInputValue, MetaListSize, MetaType, Registry};
 
 const SYSTEM_SCALARS: &[&str] = &["Int", "Float", "String", "Boolean", "ID"];
 const FEDERATION_SCALARS: &[&str] = &["Any"];
+const FEDERATION_V2_URL: &str = "https://specs.apollo.dev/federation/v2.0";
+const FEDERATION_V2_IMPORTS: &[&str] = &[
+    "@key",
+    "@external",
+    "@requires",
+    "@provides",
+    "@shareable",
+    "@inaccessible",
+    "@override",
+    "@tag",
+];
 
 /// Options for SDL export
 #[derive(Debug, Copy, Clone, Default)]
@@ -86,6 +97,41 @@ impl Registry {
             sdl.write_str("directive @oneOf on INPUT_OBJECT\n\n").ok();
         }
 
+        let fields = || {
+            self.types
+                .values()
+                .filter_map(MetaType::fields)
+                .flat_map(|fields| fields.values())
+        };
+        if fields().any(|field| field.cost.is_some()) {
+            sdl.write_str("directive @cost(weight: Int!) on FIELD_DEFINITION\n\n")
+                .ok();
+        }
+        if fields().any(|field| field.list_size.is_some()) {
+            sdl.write_str(
+                "directive @listSize(assumedSize: Int, slicingArguments: [String!], sizedFields: [String!]) on FIELD_DEFINITION\n\n",
+            )
+            .ok();
+        }
+        if fields().any(|field| field.auth.is_some()) {
+            sdl.write_str("directive @auth(requires: [String!]!) on FIELD_DEFINITION\n\n")
+                .ok();
+        }
+
+        if options.federation && self.uses_federation_v2() {
+            writeln!(
+                sdl,
+                "extend schema @link(url: \"{}\", import: [{}])\n",
+                FEDERATION_V2_URL,
+                FEDERATION_V2_IMPORTS
+                    .iter()
+                    .map(|name| format!("\"{}\"", name))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+            .ok();
+        }
+
         for ty in self.types.values() {
             if ty.name().starts_with("__") {
                 continue;
@@ -152,6 +198,9 @@ impl Registry {
                         sdl.push_str(", ");
                     }
                     sdl.push_str(&export_input_value(arg));
+                    if options.federation {
+                        write_value_directives(sdl, arg.inaccessible, &arg.tags);
+                    }
                 }
                 write!(sdl, "): {}", field.ty).ok();
             } else {
@@ -160,6 +209,25 @@ impl Registry {
 
             write_deprecated(sdl, &field.deprecation);
 
+            if let Some(weight) = field.cost {
+                write!(sdl, " @cost(weight: {})", weight).ok();
+            }
+            if let Some(list_size) = &field.list_size {
+                write_list_size(sdl, list_size);
+            }
+            if let Some(requires) = &field.auth {
+                write!(
+                    sdl,
+                    " @auth(requires: [{}])",
+                    requires
+                        .iter()
+                        .map(|requirement| format!("\"{}\"", requirement))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )
+                .ok();
+            }
+
             if options.federation {
                 if field.external {
                     write!(sdl, " @external").ok();
@@ -170,6 +238,13 @@ impl Registry {
                 if let Some(provides) = field.provides {
                     write!(sdl, " @provides(fields: \"{}\")", provides).ok();
                 }
+                if field.shareable {
+                    write!(sdl, " @shareable").ok();
+                }
+                write_value_directives(sdl, field.inaccessible, &field.tags);
+                if let Some(from) = field.override_from {
+                    write!(sdl, " @override(from: \"{}\")", escape_string(from)).ok();
+                }
             }
 
             writeln!(sdl).ok();
@@ -179,7 +254,11 @@ impl Registry {
     fn export_type(&self, ty: &MetaType, sdl: &mut String, options: &SDLExportOptions) {
         match ty {
             MetaType::Scalar {
-                name, description, ..
+                name,
+                description,
+                inaccessible,
+                tags,
+                ..
             } => {
                 let mut export_scalar = !SYSTEM_SCALARS.contains(&name.as_str());
                 if options.federation && FEDERATION_SCALARS.contains(&name.as_str()) {
@@ -189,7 +268,11 @@ impl Registry {
                     if let Some(description) = description {
                         export_description(sdl, options, true, description);
                     }
-                    writeln!(sdl, "scalar {}", name).ok();
+                    write!(sdl, "scalar {}", name).ok();
+                    if options.federation {
+                        write_value_directives(sdl, *inaccessible, tags);
+                    }
+                    writeln!(sdl).ok();
                 }
             }
             MetaType::Object {
@@ -198,6 +281,9 @@ impl Registry {
                 extends,
                 keys,
                 description,
+                shareable,
+                inaccessible,
+                tags,
                 ..
             } => {
                 if Some(name.as_str()) == self.subscription_type.as_deref()
@@ -241,6 +327,10 @@ impl Registry {
                             write!(sdl, "@key(fields: \"{}\") ", key).ok();
                         }
                     }
+                    if *shareable {
+                        write!(sdl, "@shareable ").ok();
+                    }
+                    write_type_directives(sdl, *inaccessible, tags);
                 }
 
                 writeln!(sdl, "{{").ok();
@@ -253,6 +343,8 @@ impl Registry {
                 extends,
                 keys,
                 description,
+                inaccessible,
+                tags,
                 ..
             } => {
                 if let Some(description) = description {
@@ -270,6 +362,7 @@ impl Registry {
                             write!(sdl, "@key(fields: \"{}\") ", key).ok();
                         }
                     }
+                    write_type_directives(sdl, *inaccessible, tags);
                 }
                 self.write_implements(sdl, name);
 
@@ -281,6 +374,8 @@ impl Registry {
                 name,
                 enum_values,
                 description,
+                inaccessible,
+                tags,
                 ..
             } => {
                 if let Some(description) = description {
@@ -288,6 +383,9 @@ impl Registry {
                 }
 
                 write!(sdl, "enum {} ", name).ok();
+                if options.federation {
+                    write_type_directives(sdl, *inaccessible, tags);
+                }
                 writeln!(sdl, "{{").ok();
 
                 let mut values = enum_values.values().collect::<Vec<_>>();
@@ -298,6 +396,9 @@ impl Registry {
                 for value in values {
                     write!(sdl, "\t{}", value.name).ok();
                     write_deprecated(sdl, &value.deprecation);
+                    if options.federation {
+                        write_value_directives(sdl, value.inaccessible, &value.tags);
+                    }
                     writeln!(sdl).ok();
                 }
 
@@ -308,6 +409,8 @@ impl Registry {
                 input_fields,
                 description,
                 oneof,
+                inaccessible,
+                tags,
                 ..
             } => {
                 if let Some(description) = description {
@@ -319,6 +422,9 @@ impl Registry {
                 if *oneof {
                     write!(sdl, "@oneof ").ok();
                 }
+                if options.federation {
+                    write_type_directives(sdl, *inaccessible, tags);
+                }
                 writeln!(sdl, "{{").ok();
 
                 let mut fields = input_fields.values().collect::<Vec<_>>();
@@ -330,7 +436,11 @@ impl Registry {
                     if let Some(description) = field.description {
                         export_description(sdl, options, false, description);
                     }
-                    writeln!(sdl, "\t{}", export_input_value(&field)).ok();
+                    write!(sdl, "\t{}", export_input_value(&field)).ok();
+                    if options.federation {
+                        write_value_directives(sdl, field.inaccessible, &field.tags);
+                    }
+                    writeln!(sdl).ok();
                 }
 
                 writeln!(sdl, "}}").ok();
@@ -339,13 +449,19 @@ impl Registry {
                 name,
                 possible_types,
                 description,
+                inaccessible,
+                tags,
                 ..
             } => {
                 if let Some(description) = description {
                     export_description(sdl, options, true, description);
                 }
 
-                write!(sdl, "union {} =", name).ok();
+                write!(sdl, "union {}", name).ok();
+                if options.federation {
+                    write_value_directives(sdl, *inaccessible, tags);
+                }
+                write!(sdl, " =").ok();
                 for (idx, ty) in possible_types.iter().enumerate() {
                     if idx == 0 {
                         write!(sdl, " {}", ty).ok();
@@ -358,6 +474,69 @@ impl Registry {
         }
     }
 
+    /// Returns `true` if any type uses a directive introduced by Federation 2,
+    /// in which case the subgraph schema must link the Federation 2 spec.
+    fn uses_federation_v2(&self) -> bool {
+        fn field_uses_v2(field: &MetaField) -> bool {
+            field.shareable
+                || field.inaccessible
+                || !field.tags.is_empty()
+                || field.override_from.is_some()
+                || field.args.values().any(input_value_uses_v2)
+        }
+
+        fn input_value_uses_v2(input_value: &MetaInputValue) -> bool {
+            input_value.inaccessible || !input_value.tags.is_empty()
+        }
+
+        self.types.values().any(|ty| match ty {
+            MetaType::Scalar {
+                inaccessible, tags, ..
+            }
+            | MetaType::Union {
+                inaccessible, tags, ..
+            } => *inaccessible || !tags.is_empty(),
+            MetaType::Object {
+                fields,
+                shareable,
+                inaccessible,
+                tags,
+                ..
+            } => {
+                *shareable
+                    || *inaccessible
+                    || !tags.is_empty()
+                    || fields.values().any(field_uses_v2)
+            }
+            MetaType::Interface {
+                fields,
+                inaccessible,
+                tags,
+                ..
+            } => *inaccessible || !tags.is_empty() || fields.values().any(field_uses_v2),
+            MetaType::Enum {
+                enum_values,
+                inaccessible,
+                tags,
+                ..
+            } => {
+                *inaccessible
+                    || !tags.is_empty()
+                    || enum_values
+                        .values()
+                        .any(|value| value.inaccessible || !value.tags.is_empty())
+            }
+            MetaType::InputObject {
+                input_fields,
+                inaccessible,
+                tags,
+                ..
+            } => {
+                *inaccessible || !tags.is_empty() || input_fields.values().any(input_value_uses_v2)
+            }
+        })
+    }
+
     fn write_implements(&self, sdl: &mut String, name: &str) {
         if let Some(implements) = self.implements.get(name) {
             if !implements.is_empty() {
@@ -405,6 +584,53 @@ fn export_input_value(input_value: &MetaInputValue) -> String {
     }
 }
 
+fn write_type_directives(sdl: &mut String, inaccessible: bool, tags: &[String]) {
+    if inaccessible {
+        write!(sdl, "@inaccessible ").ok();
+    }
+    for tag in tags {
+        write!(sdl, "@tag(name: \"{}\") ", escape_string(tag)).ok();
+    }
+}
+
+fn write_list_size(sdl: &mut String, list_size: &MetaListSize) {
+    let write_names = |names: &[String]| {
+        names
+            .iter()
+            .map(|name| format!("\"{}\"", name))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let mut args = Vec::new();
+    if let Some(assumed_size) = list_size.assumed_size {
+        args.push(format!("assumedSize: {}", assumed_size));
+    }
+    if !list_size.slicing_arguments.is_empty() {
+        args.push(format!(
+            "slicingArguments: [{}]",
+            write_names(&list_size.slicing_arguments)
+        ));
+    }
+    if !list_size.sized_fields.is_empty() {
+        args.push(format!(
+            "sizedFields: [{}]",
+            write_names(&list_size.sized_fields)
+        ));
+    }
+    if !args.is_empty() {
+        write!(sdl, " @listSize({})", args.join(", ")).ok();
+    }
+}
+
+fn write_value_directives(sdl: &mut String, inaccessible: bool, tags: &[String]) {
+    if inaccessible {
+        write!(sdl, " @inaccessible").ok();
+    }
+    for tag in tags {
+        write!(sdl, " @tag(name: \"{}\")", escape_string(tag)).ok();
+    }
+}
+
 fn write_deprecated(sdl: &mut String, deprecation: &Deprecation) {
     if let Deprecation::Deprecated { reason } = deprecation {
         let _ = match reason {
@@ -0,0 +1,580 @@
+use indexmap::IndexMap;
+
+use crate::{
+    dynamic::{intern_str, SchemaError},
+    parser::types::{
+        ConstDirective, DirectiveLocation, ExecutableDocument, FieldDefinition,
+        InputValueDefinition, ServiceDocument, TypeKind, TypeSystemDefinition,
+    },
+    registry::{
+        __DirectiveLocation, Deprecation, MetaDirective, MetaEnumValue, MetaField, MetaInputValue,
+        MetaType, MetaTypeName, Registry,
+    },
+    schema::register_builtin_types,
+    validation::{check_rules, ValidationMode, ValidationResult},
+    Name, Positioned, ServerError, Value, Variables,
+};
+
+fn description(description: &Option<Positioned<String>>) -> Option<&'static str> {
+    description
+        .as_ref()
+        .map(|description| intern_str(&description.node))
+}
+
+fn directive<'a>(
+    directives: &'a [Positioned<ConstDirective>],
+    name: &str,
+) -> Option<&'a ConstDirective> {
+    directives
+        .iter()
+        .map(|directive| &directive.node)
+        .find(|directive| directive.name.node == name)
+}
+
+fn deprecation(directives: &[Positioned<ConstDirective>]) -> Deprecation {
+    match directive(directives, "deprecated") {
+        Some(directive) => Deprecation::Deprecated {
+            reason: match directive.get_argument("reason").map(|reason| &reason.node) {
+                Some(Value::String(reason)) => Some(intern_str(reason)),
+                _ => None,
+            },
+        },
+        None => Deprecation::NoDeprecated,
+    }
+}
+
+fn input_values(values: &[Positioned<InputValueDefinition>]) -> IndexMap<String, MetaInputValue> {
+    values
+        .iter()
+        .map(|value| {
+            let value = &value.node;
+            (
+                value.name.node.to_string(),
+                MetaInputValue {
+                    name: intern_str(&value.name.node),
+                    description: description(&value.description),
+                    ty: value.ty.node.to_string(),
+                    default_value: value
+                        .default_value
+                        .as_ref()
+                        .map(|value| value.node.to_string()),
+                    visible: None,
+                    inaccessible: false,
+                    tags: Vec::new(),
+                    is_secret: false,
+                },
+            )
+        })
+        .collect()
+}
+
+fn fields(fields: &[Positioned<FieldDefinition>]) -> IndexMap<String, MetaField> {
+    fields
+        .iter()
+        .map(|field| {
+            let field = &field.node;
+            (
+                field.name.node.to_string(),
+                MetaField {
+                    name: field.name.node.to_string(),
+                    description: description(&field.description),
+                    args: input_values(&field.arguments),
+                    ty: field.ty.node.to_string(),
+                    deprecation: deprecation(&field.directives),
+                    cache_control: Default::default(),
+                    external: false,
+                    requires: None,
+                    provides: None,
+                    shareable: false,
+                    inaccessible: false,
+                    tags: Vec::new(),
+                    override_from: None,
+                    cost: None,
+                    list_size: None,
+                    auth: None,
+                    redact: None,
+                    visible: None,
+                    compute_complexity: None,
+                },
+            )
+        })
+        .collect()
+}
+
+fn directive_location(location: DirectiveLocation) -> __DirectiveLocation {
+    match location {
+        DirectiveLocation::Query => __DirectiveLocation::QUERY,
+        DirectiveLocation::Mutation => __DirectiveLocation::MUTATION,
+        DirectiveLocation::Subscription => __DirectiveLocation::SUBSCRIPTION,
+        DirectiveLocation::Field => __DirectiveLocation::FIELD,
+        DirectiveLocation::FragmentDefinition => __DirectiveLocation::FRAGMENT_DEFINITION,
+        DirectiveLocation::FragmentSpread => __DirectiveLocation::FRAGMENT_SPREAD,
+        DirectiveLocation::InlineFragment => __DirectiveLocation::INLINE_FRAGMENT,
+        DirectiveLocation::Schema => __DirectiveLocation::SCHEMA,
+        DirectiveLocation::Scalar => __DirectiveLocation::SCALAR,
+        DirectiveLocation::Object => __DirectiveLocation::OBJECT,
+        DirectiveLocation::FieldDefinition => __DirectiveLocation::FIELD_DEFINITION,
+        DirectiveLocation::ArgumentDefinition => __DirectiveLocation::ARGUMENT_DEFINITION,
+        DirectiveLocation::Interface => __DirectiveLocation::INTERFACE,
+        DirectiveLocation::Union => __DirectiveLocation::UNION,
+        DirectiveLocation::Enum => __DirectiveLocation::ENUM,
+        DirectiveLocation::EnumValue => __DirectiveLocation::ENUM_VALUE,
+        DirectiveLocation::InputObject => __DirectiveLocation::INPUT_OBJECT,
+        DirectiveLocation::InputFieldDefinition => __DirectiveLocation::INPUT_FIELD_DEFINITION,
+        DirectiveLocation::VariableDefinition => __DirectiveLocation::VARIABLE_DEFINITION,
+    }
+}
+
+impl Registry {
+    /// Create a registry from the definitions of a schema written in SDL, for
+    /// example to validate the queries sent to a remote service.
+    ///
+    /// The registry describes the schema but cannot execute queries. Since it
+    /// holds static strings, the names and descriptions of the schema are
+    /// leaked, once for each distinct string.
+    pub fn from_service_document(doc: &ServiceDocument) -> Result<Registry, SchemaError> {
+        let mut registry = Registry::default();
+        register_builtin_types(&mut registry);
+
+        let mut roots = None;
+        let mut implements = Vec::new();
+
+        for definition in &doc.definitions {
+            let definition = match definition {
+                TypeSystemDefinition::Schema(schema) => {
+                    let schema = &schema.node;
+                    let name = |name: &Option<Positioned<Name>>| {
+                        name.as_ref().map(|name| name.node.to_string())
+                    };
+                    roots = Some((
+                        name(&schema.query),
+                        name(&schema.mutation),
+                        name(&schema.subscription),
+                    ));
+                    continue;
+                }
+                TypeSystemDefinition::Directive(directive) => {
+                    let directive = &directive.node;
+                    if !registry
+                        .directives
+                        .contains_key(directive.name.node.as_str())
+                    {
+                        registry.add_directive(MetaDirective {
+                            name: intern_str(&directive.name.node),
+                            description: description(&directive.description),
+                            locations: directive
+                                .locations
+                                .iter()
+                                .map(|location| directive_location(location.node))
+                                .collect(),
+                            args: input_values(&directive.arguments),
+                            is_repeatable: false,
+                            visible: None,
+                        });
+                    }
+                    continue;
+                }
+                TypeSystemDefinition::Type(definition) => &definition.node,
+            };
+
+            let name = definition.name.node.as_str();
+            let description = description(&definition.description);
+
+            let interfaces = match &definition.kind {
+                TypeKind::Object(ty) => ty.implements.as_slice(),
+                TypeKind::Interface(ty) => ty.implements.as_slice(),
+                _ => &[],
+            };
+            for interface in interfaces {
+                implements.push((name.to_string(), interface.node.to_string()));
+            }
+
+            if definition.extend {
+                let existing = match registry.types.get_mut(name) {
+                    Some(existing) => existing,
+                    None => {
+                        return Err(SchemaError(format!(
+                            r#"Cannot extend the unknown type "{}"."#,
+                            name
+                        )))
+                    }
+                };
+                match (existing, &definition.kind) {
+                    (
+                        MetaType::Object {
+                            fields: existing, ..
+                        },
+                        TypeKind::Object(ty),
+                    ) => existing.extend(fields(&ty.fields)),
+                    (
+                        MetaType::Interface {
+                            fields: existing, ..
+                        },
+                        TypeKind::Interface(ty),
+                    ) => existing.extend(fields(&ty.fields)),
+                    (MetaType::Union { possible_types, .. }, TypeKind::Union(ty)) => {
+                        possible_types.extend(ty.members.iter().map(|name| name.node.to_string()))
+                    }
+                    (MetaType::Enum { enum_values, .. }, TypeKind::Enum(ty)) => {
+                        for value in &ty.values {
+                            let name = intern_str(&value.node.value.node);
+                            enum_values.insert(
+                                name,
+                                MetaEnumValue {
+                                    name,
+                                    description: self::description(&value.node.description),
+                                    deprecation: deprecation(&value.node.directives),
+                                    visible: None,
+                                    inaccessible: false,
+                                    tags: Vec::new(),
+                                },
+                            );
+                        }
+                    }
+                    (MetaType::InputObject { input_fields, .. }, TypeKind::InputObject(ty)) => {
+                        input_fields.extend(input_values(&ty.fields))
+                    }
+                    (MetaType::Scalar { .. }, TypeKind::Scalar) => {}
+                    _ => {
+                        return Err(SchemaError(format!(
+                            r#"The extension of "{}" has a different kind."#,
+                            name
+                        )))
+                    }
+                }
+                continue;
+            }
+
+            if registry.types.contains_key(name) {
+                if matches!(definition.kind, TypeKind::Scalar) {
+                    // Built-in scalars may be declared in the SDL.
+                    continue;
+                }
+                return Err(SchemaError(format!(
+                    r#"Type "{}" is already defined."#,
+                    name
+                )));
+            }
+
+            let ty = match &definition.kind {
+                TypeKind::Scalar => MetaType::Scalar {
+                    name: name.to_string(),
+                    description,
+                    is_valid: |_| true,
+                    visible: None,
+                    inaccessible: false,
+                    tags: Vec::new(),
+                    specified_by_url: directive(&definition.directives, "specifiedBy")
+                        .and_then(|directive| directive.get_argument("url"))
+                        .and_then(|url| match &url.node {
+                            Value::String(url) => Some(intern_str(url)),
+                            _ => None,
+                        }),
+                },
+                TypeKind::Object(ty) => MetaType::Object {
+                    name: name.to_string(),
+                    description,
+                    fields: fields(&ty.fields),
+                    cache_control: Default::default(),
+                    extends: false,
+                    keys: None,
+                    visible: None,
+                    shareable: false,
+                    inaccessible: false,
+                    tags: Vec::new(),
+                    is_subscription: false,
+                    rust_typename: intern_str(name),
+                },
+                TypeKind::Interface(ty) => MetaType::Interface {
+                    name: name.to_string(),
+                    description,
+                    fields: fields(&ty.fields),
+                    possible_types: Default::default(),
+                    extends: false,
+                    keys: None,
+                    visible: None,
+                    inaccessible: false,
+                    tags: Vec::new(),
+                    rust_typename: intern_str(name),
+                },
+                TypeKind::Union(ty) => MetaType::Union {
+                    name: name.to_string(),
+                    description,
+                    possible_types: ty
+                        .members
+                        .iter()
+                        .map(|name| name.node.to_string())
+                        .collect(),
+                    visible: None,
+                    inaccessible: false,
+                    tags: Vec::new(),
+                    rust_typename: intern_str(name),
+                },
+                TypeKind::Enum(ty) => MetaType::Enum {
+                    name: name.to_string(),
+                    description,
+                    enum_values: ty
+                        .values
+                        .iter()
+                        .map(|value| {
+                            let name = intern_str(&value.node.value.node);
+                            (
+                                name,
+                                MetaEnumValue {
+                                    name,
+                                    description: self::description(&value.node.description),
+                                    deprecation: deprecation(&value.node.directives),
+                                    visible: None,
+                                    inaccessible: false,
+                                    tags: Vec::new(),
+                                },
+                            )
+                        })
+                        .collect(),
+                    visible: None,
+                    inaccessible: false,
+                    tags: Vec::new(),
+                    rust_typename: intern_str(name),
+                },
+                TypeKind::InputObject(ty) => MetaType::InputObject {
+                    name: name.to_string(),
+                    description,
+                    input_fields: input_values(&ty.fields),
+                    visible: None,
+                    inaccessible: false,
+                    tags: Vec::new(),
+                    rust_typename: intern_str(name),
+                    oneof: directive(&definition.directives, "oneOf").is_some(),
+                },
+            };
+            registry.types.insert(name.to_string(), ty);
+        }
+
+        let (query_type, mutation_type, subscription_type) = roots.unwrap_or_else(|| {
+            let root = |name: &str| {
+                Some(name.to_string()).filter(|name| registry.types.contains_key(name))
+            };
+            (root("Query"), root("Mutation"), root("Subscription"))
+        });
+        registry.query_type =
+            query_type.ok_or_else(|| SchemaError("The schema has no query type.".to_string()))?;
+        registry.mutation_type = mutation_type;
+        registry.subscription_type = subscription_type;
+        if let Some(subscription_type) = &registry.subscription_type {
+            if let Some(MetaType::Object {
+                is_subscription, ..
+            }) = registry.types.get_mut(subscription_type)
+            {
+                *is_subscription = true;
+            }
+        }
+
+        for (name, interface) in implements {
+            match registry.types.get_mut(&interface) {
+                Some(MetaType::Interface { possible_types, .. }) => {
+                    possible_types.insert(name.clone());
+                }
+                _ => {
+                    return Err(SchemaError(format!(
+                        r#"Type "{}" implements "{}" which is not an interface."#,
+                        name, interface
+                    )))
+                }
+            }
+            registry.add_implements(&name, &interface);
+        }
+
+        check_types(&registry)?;
+        Ok(registry)
+    }
+
+    /// Validate an executable document against this schema with all the
+    /// validation rules.
+    pub fn validate(
+        &self,
+        doc: &ExecutableDocument,
+        variables: Option<&Variables>,
+    ) -> Result<ValidationResult, Vec<ServerError>> {
+        check_rules(self, doc, variables, ValidationMode::Strict)
+    }
+}
+
+fn check_types(registry: &Registry) -> Result<(), SchemaError> {
+    let check = |ty: &str, location: &dyn Fn() -> String, input: bool| match registry
+        .concrete_type_by_name(ty)
+    {
+        Some(meta_type) if !input || meta_type.is_input() => Ok(()),
+        Some(MetaType::InputObject { .. }) if !input => Err(SchemaError(format!(
+            r#"Type "{}" of {} must be an output type."#,
+            ty,
+            location()
+        ))),
+        Some(MetaType::InputObject { .. }) => Ok(()),
+        Some(_) => Err(SchemaError(format!(
+            r#"Type "{}" of {} must be an input type."#,
+            ty,
+            location()
+        ))),
+        None => Err(SchemaError(format!(
+            r#"Unknown type "{}" of {}."#,
+            MetaTypeName::concrete_typename(ty),
+            location()
+        ))),
+    };
+
+    for root in [
+        Some(&registry.query_type),
+        registry.mutation_type.as_ref(),
+        registry.subscription_type.as_ref(),
+    ]
+    .into_iter()
+    .flatten()
+    {
+        if !matches!(registry.types.get(root), Some(MetaType::Object { .. })) {
+            return Err(SchemaError(format!(
+                r#"Root type "{}" must be an object."#,
+                root
+            )));
+        }
+    }
+
+    for ty in registry.types.values() {
+        match ty {
+            MetaType::Object { name, fields, .. } | MetaType::Interface { name, fields, .. } => {
+                for field in fields.values() {
+                    let location = || format!(r#"field "{}.{}""#, name, field.name);
+                    check(&field.ty, &location, false)?;
+                    for arg in field.args.values() {
+                        check(
+                            &arg.ty,
+                            &|| format!(r#"argument "{}" of {}"#, arg.name, location()),
+                            true,
+                        )?;
+                    }
+                }
+            }
+            MetaType::Union {
+                name,
+                possible_types,
+                ..
+            } => {
+                for member in possible_types {
+                    if !matches!(registry.types.get(member), Some(MetaType::Object { .. })) {
+                        return Err(SchemaError(format!(
+                            r#"Member "{}" of union "{}" must be an object."#,
+                            member, name
+                        )));
+                    }
+                }
+            }
+            MetaType::InputObject {
+                name, input_fields, ..
+            } => {
+                for field in input_fields.values() {
+                    check(
+                        &field.ty,
+                        &|| format!(r#"field "{}.{}""#, name, field.name),
+                        true,
+                    )?;
+                }
+            }
+            MetaType::Scalar { .. } | MetaType::Enum { .. } => {}
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::{parse_query, parse_schema};
+
+    #[test]
+    fn from_service_document() {
+        let doc = parse_schema(
+            r#"
+            type Query {
+                node(id: ID!): Node
+                search(text: String!, status: Status = OPEN): [SearchResult!]!
+            }
+
+            interface Node { id: ID! }
+            type User implements Node { id: ID! name: String }
+            type Todo implements Node { id: ID! status: Status! }
+            extend type Todo { title: String! }
+            union SearchResult = User | Todo
+            enum Status { OPEN DONE }
+            "#,
+        )
+        .unwrap();
+        let registry = Registry::from_service_document(&doc).unwrap();
+        assert_eq!(registry.query_type, "Query");
+        assert!(registry.mutation_type.is_none());
+        assert!(registry.types["Node"].is_possible_type("Todo"));
+        assert!(registry.types["Todo"].field_by_name("title").is_some());
+
+        let query = parse_query(
+            r#"{
+                node(id: "1") { id ... on Todo { title status } }
+                search(text: "a") { __typename ... on User { name } }
+            }"#,
+        )
+        .unwrap();
+        assert!(registry.validate(&query, None).is_ok());
+
+        let query = parse_query(r#"{ node(id: 1) { title } search { __typename } }"#).unwrap();
+        assert_eq!(
+            registry
+                .validate(&query, None)
+                .unwrap_err()
+                .into_iter()
+                .map(|err| err.message)
+                .collect::<Vec<_>>(),
+            vec![
+                r#"Unknown field "title" on type "Node"."#.to_string(),
+                r#"Field "search" argument "text" of type "Query" is required but not provided"#
+                    .to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn from_service_document_interns_strings() {
+        let doc = parse_schema(r#""The root." type Query { a: Int }"#).unwrap();
+        let a = Registry::from_service_document(&doc).unwrap();
+        let b = Registry::from_service_document(&doc).unwrap();
+        let description = |registry: &Registry| match &registry.types["Query"] {
+            MetaType::Object { description, .. } => description.unwrap(),
+            _ => unreachable!(),
+        };
+        assert!(std::ptr::eq(description(&a), description(&b)));
+        assert!(std::ptr::eq(
+            a.types["Query"].rust_typename().unwrap(),
+            b.types["Query"].rust_typename().unwrap()
+        ));
+    }
+
+    #[test]
+    fn from_service_document_errors() {
+        let check = |sdl: &str, err: &str| {
+            let doc = parse_schema(sdl).unwrap();
+            assert_eq!(
+                Registry::from_service_document(&doc).err(),
+                Some(SchemaError(err.to_string()))
+            );
+        };
+        check(
+            "type Query { a: Unknown }",
+            r#"Unknown type "Unknown" of field "Query.a"."#,
+        );
+        check(
+            "type Query { a(i: Query): Int }",
+            r#"Type "Query" of argument "i" of field "Query.a" must be an input type."#,
+        );
+        check("type Mutation { a: Int }", "The schema has no query type.");
+        check(
+            "type Query { a: Int } extend type B { a: Int }",
+            r#"Cannot extend the unknown type "B"."#,
+        );
+    }
+}
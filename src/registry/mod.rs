@@ -1,5 +1,7 @@
 mod cache_control;
 mod export_sdl;
+mod import_sdl;
+mod schema_diff;
 mod stringify_exec_doc;
 
 use std::{
@@ -10,6 +12,7 @@ use std::{
 pub use cache_control::CacheControl;
 pub use export_sdl::SDLExportOptions;
 use indexmap::{map::IndexMap, set::IndexSet};
+pub use schema_diff::{SchemaChange, SchemaChangeKind, SchemaChangeLevel, SchemaDiff};
 
 pub use crate::model::__DirectiveLocation;
 use crate::{
@@ -111,6 +114,8 @@ pub struct MetaInputValue {
     pub ty: String,
     pub default_value: Option<String>,
     pub visible: Option<MetaVisibleFn>,
+    pub inaccessible: bool,
+    pub tags: Vec<String>,
     pub is_secret: bool,
 }
 
@@ -165,16 +170,43 @@ pub struct MetaField {
     pub external: bool,
     pub requires: Option<&'static str>,
     pub provides: Option<&'static str>,
+    pub shareable: bool,
+    pub inaccessible: bool,
+    pub tags: Vec<String>,
+    pub override_from: Option<&'static str>,
+    pub cost: Option<usize>,
+    pub list_size: Option<MetaListSize>,
+    /// The requirements of the `@auth` directive of the field, checked by the
+    /// authorization policy of the schema.
+    pub auth: Option<Vec<String>>,
+    /// The category of the values of the field, redacted by the
+    /// [`Redactor`](crate::Redactor) of the context data.
+    pub redact: Option<String>,
     pub visible: Option<MetaVisibleFn>,
     pub compute_complexity: Option<ComplexityType>,
 }
 
+/// The `@listSize` directive of a field, describing the size of the list it
+/// returns for the cost analysis.
+#[derive(Debug, Clone, Default)]
+pub struct MetaListSize {
+    /// The size of the list when no slicing argument is provided.
+    pub assumed_size: Option<usize>,
+    /// The arguments setting the size of the list, such as `first`.
+    pub slicing_arguments: Vec<String>,
+    /// The child fields the size applies to, such as the `edges` of a
+    /// connection.
+    pub sized_fields: Vec<String>,
+}
+
 #[derive(Clone)]
 pub struct MetaEnumValue {
     pub name: &'static str,
     pub description: Option<&'static str>,
     pub deprecation: Deprecation,
     pub visible: Option<MetaVisibleFn>,
+    pub inaccessible: bool,
+    pub tags: Vec<String>,
 }
 
 type MetaVisibleFn = fn(&Context<'_>) -> bool;
@@ -209,6 +241,8 @@ pub enum MetaType {
         description: Option<&'static str>,
         is_valid: fn(value: &Value) -> bool,
         visible: Option<MetaVisibleFn>,
+        inaccessible: bool,
+        tags: Vec<String>,
         specified_by_url: Option<&'static str>,
     },
     Object {
@@ -219,6 +253,9 @@ pub enum MetaType {
         extends: bool,
         keys: Option<Vec<String>>,
         visible: Option<MetaVisibleFn>,
+        shareable: bool,
+        inaccessible: bool,
+        tags: Vec<String>,
         is_subscription: bool,
         rust_typename: &'static str,
     },
@@ -230,6 +267,8 @@ pub enum MetaType {
         extends: bool,
         keys: Option<Vec<String>>,
         visible: Option<MetaVisibleFn>,
+        inaccessible: bool,
+        tags: Vec<String>,
         rust_typename: &'static str,
     },
     Union {
@@ -237,6 +276,8 @@ pub enum MetaType {
         description: Option<&'static str>,
         possible_types: IndexSet<String>,
         visible: Option<MetaVisibleFn>,
+        inaccessible: bool,
+        tags: Vec<String>,
         rust_typename: &'static str,
     },
     Enum {
@@ -244,6 +285,8 @@ pub enum MetaType {
         description: Option<&'static str>,
         enum_values: IndexMap<&'static str, MetaEnumValue>,
         visible: Option<MetaVisibleFn>,
+        inaccessible: bool,
+        tags: Vec<String>,
         rust_typename: &'static str,
     },
     InputObject {
@@ -251,6 +294,8 @@ pub enum MetaType {
         description: Option<&'static str>,
         input_fields: IndexMap<String, MetaInputValue>,
         visible: Option<MetaVisibleFn>,
+        inaccessible: bool,
+        tags: Vec<String>,
         rust_typename: &'static str,
         oneof: bool,
     },
@@ -494,6 +539,9 @@ impl Registry {
                         extends: false,
                         keys: None,
                         visible: None,
+                        shareable: false,
+                        inaccessible: false,
+                        tags: Default::default(),
                         is_subscription: false,
                         rust_typename: "__fake_type__",
                     },
@@ -620,6 +668,14 @@ impl Registry {
                     requires: None,
                     provides: None,
                     visible: None,
+                    shareable: false,
+                    inaccessible: false,
+                    tags: Default::default(),
+                    override_from: None,
+                    cost: None,
+                    list_size: None,
+                    auth: None,
+                    redact: None,
                     compute_complexity: None,
                 },
             );
@@ -633,6 +689,8 @@ impl Registry {
                     description: None,
                     possible_types,
                     visible: None,
+                    inaccessible: false,
+                    tags: Default::default(),
                     rust_typename: "async_graphql::federation::Entity",
                 },
             );
@@ -653,6 +711,8 @@ impl Registry {
                                     ty: "[_Any!]!".to_string(),
                                     default_value: None,
                                     visible: None,
+                                    inaccessible: false,
+                                    tags: Default::default(),
                                     is_secret: false,
                                 },
                             );
@@ -665,6 +725,14 @@ impl Registry {
                         requires: None,
                         provides: None,
                         visible: None,
+                        shareable: false,
+                        inaccessible: false,
+                        tags: Default::default(),
+                        override_from: None,
+                        cost: None,
+                        list_size: None,
+                        auth: None,
+                        redact: None,
                         compute_complexity: None,
                     },
                 );
@@ -695,6 +763,14 @@ impl Registry {
                             requires: None,
                             provides: None,
                             visible: None,
+                            shareable: false,
+                            inaccessible: false,
+                            tags: Default::default(),
+                            override_from: None,
+                            cost: None,
+                            list_size: None,
+                            auth: None,
+                            redact: None,
                             compute_complexity: None,
                         },
                     );
@@ -704,6 +780,9 @@ impl Registry {
                 extends: false,
                 keys: None,
                 visible: None,
+                shareable: false,
+                inaccessible: false,
+                tags: Default::default(),
                 is_subscription: false,
                 rust_typename: "async_graphql::federation::Service",
             },
@@ -0,0 +1,875 @@
+use std::fmt::{self, Display, Formatter};
+
+use indexmap::{IndexMap, IndexSet};
+use serde::Serialize;
+
+use crate::{
+    parser::{
+        self,
+        types::{
+            ConstDirective, FieldDefinition, InputValueDefinition, TypeKind, TypeSystemDefinition,
+        },
+    },
+    registry::{
+        is_system_type, MetaField, MetaInputValue, MetaType, MetaTypeId, MetaTypeName, Registry,
+    },
+    Positioned,
+};
+
+/// The severity of a schema change.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum SchemaChangeLevel {
+    /// The change breaks existing clients, such as a removed field.
+    Breaking,
+    /// The change does not break existing queries, but may change the behavior
+    /// of existing clients, such as a new enum value.
+    Dangerous,
+    /// The change is backward compatible.
+    Safe,
+}
+
+impl Display for SchemaChangeLevel {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            SchemaChangeLevel::Breaking => "BREAKING",
+            SchemaChangeLevel::Dangerous => "DANGEROUS",
+            SchemaChangeLevel::Safe => "SAFE",
+        })
+    }
+}
+
+/// The kind of a schema change.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[allow(missing_docs)]
+pub enum SchemaChangeKind {
+    TypeAdded,
+    TypeRemoved,
+    TypeKindChanged,
+    FieldAdded,
+    FieldRemoved,
+    FieldTypeChanged,
+    FieldDeprecated,
+    RequiredArgumentAdded,
+    OptionalArgumentAdded,
+    ArgumentRemoved,
+    ArgumentTypeChanged,
+    ArgumentDefaultValueChanged,
+    RequiredInputFieldAdded,
+    OptionalInputFieldAdded,
+    InputFieldRemoved,
+    InputFieldTypeChanged,
+    InputFieldDefaultValueChanged,
+    EnumValueAdded,
+    EnumValueRemoved,
+    EnumValueDeprecated,
+    UnionMemberAdded,
+    UnionMemberRemoved,
+    InterfaceImplementationAdded,
+    InterfaceImplementationRemoved,
+}
+
+/// A change between two schemas.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub struct SchemaChange {
+    /// The severity of the change.
+    pub level: SchemaChangeLevel,
+    /// The kind of the change.
+    pub kind: SchemaChangeKind,
+    /// The path of the changed element, such as `Query.user.id` for the `id`
+    /// argument of the `user` field of the `Query` type.
+    pub path: String,
+    /// A human readable description of the change.
+    pub message: String,
+}
+
+impl Display for SchemaChange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.level, self.message)
+    }
+}
+
+/// The changes between two schemas.
+///
+/// The report is serializable, so it can be written as JSON and checked in
+/// CI:
+///
+/// ```
+/// use async_graphql::*;
+///
+/// struct Query;
+///
+/// #[Object]
+/// impl Query {
+///     async fn value(&self) -> Option<i32> {
+///         None
+///     }
+/// }
+///
+/// let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+/// let diff = schema
+///     .diff_sdl("type Query { value: Int! name: String! }")
+///     .unwrap();
+/// assert!(diff.has_breaking_changes());
+/// assert_eq!(
+///     serde_json::to_value(&diff).unwrap(),
+///     serde_json::json!({
+///         "changes": [
+///             {
+///                 "level": "BREAKING",
+///                 "kind": "FIELD_TYPE_CHANGED",
+///                 "path": "Query.value",
+///                 "message": "Field `Query.value` changed type from `Int!` to `Int`.",
+///             },
+///             {
+///                 "level": "BREAKING",
+///                 "kind": "FIELD_REMOVED",
+///                 "path": "Query.name",
+///                 "message": "Field `Query.name` was removed.",
+///             },
+///         ]
+///     })
+/// );
+/// ```
+#[derive(Debug, Clone, Default, Eq, PartialEq, Serialize)]
+pub struct SchemaDiff {
+    changes: Vec<SchemaChange>,
+}
+
+impl SchemaDiff {
+    /// Compare two schemas written in SDL.
+    pub fn from_sdl(old: &str, new: &str) -> Result<Self, parser::Error> {
+        Ok(diff(&Model::from_sdl(old)?, &Model::from_sdl(new)?))
+    }
+
+    /// Returns all the changes.
+    pub fn changes(&self) -> &[SchemaChange] {
+        &self.changes
+    }
+
+    /// Returns `true` if the schemas are identical.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Returns `true` if some changes break the existing clients.
+    pub fn has_breaking_changes(&self) -> bool {
+        self.breaking_changes().next().is_some()
+    }
+
+    /// Returns the changes breaking the existing clients.
+    pub fn breaking_changes(&self) -> impl Iterator<Item = &SchemaChange> {
+        self.changes_by_level(SchemaChangeLevel::Breaking)
+    }
+
+    /// Returns the changes which may change the behavior of the existing
+    /// clients.
+    pub fn dangerous_changes(&self) -> impl Iterator<Item = &SchemaChange> {
+        self.changes_by_level(SchemaChangeLevel::Dangerous)
+    }
+
+    fn changes_by_level(&self, level: SchemaChangeLevel) -> impl Iterator<Item = &SchemaChange> {
+        self.changes
+            .iter()
+            .filter(move |change| change.level == level)
+    }
+}
+
+impl Display for SchemaDiff {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for change in &self.changes {
+            writeln!(f, "{}", change)?;
+        }
+        Ok(())
+    }
+}
+
+impl Registry {
+    /// Compare this schema with a `new` version of it.
+    pub fn diff(&self, new: &Registry) -> SchemaDiff {
+        diff(&Model::from_registry(self), &Model::from_registry(new))
+    }
+
+    /// Compare a previous version of this schema written in SDL with this
+    /// schema.
+    pub fn diff_sdl(&self, old: &str) -> Result<SchemaDiff, parser::Error> {
+        Ok(diff(&Model::from_sdl(old)?, &Model::from_registry(self)))
+    }
+}
+
+struct InputValue {
+    ty: String,
+    default_value: Option<String>,
+}
+
+impl InputValue {
+    fn is_required(&self) -> bool {
+        MetaTypeName::create(&self.ty).is_non_null() && self.default_value.is_none()
+    }
+}
+
+struct Field {
+    ty: String,
+    args: IndexMap<String, InputValue>,
+    deprecated: bool,
+}
+
+#[derive(Default)]
+struct Type {
+    fields: IndexMap<String, Field>,
+    input_fields: IndexMap<String, InputValue>,
+    enum_values: IndexMap<String, bool>,
+    possible_types: IndexSet<String>,
+    implements: IndexSet<String>,
+}
+
+/// The parts of a schema which are compared, built either from a registry or
+/// from SDL.
+#[derive(Default)]
+struct Model {
+    types: IndexMap<String, (MetaTypeId, Type)>,
+}
+
+impl Model {
+    fn from_registry(registry: &Registry) -> Self {
+        fn input_values<'a>(
+            values: impl Iterator<Item = &'a MetaInputValue>,
+        ) -> IndexMap<String, InputValue> {
+            values
+                .map(|value| {
+                    (
+                        value.name.to_string(),
+                        InputValue {
+                            ty: value.ty.clone(),
+                            default_value: value.default_value.clone(),
+                        },
+                    )
+                })
+                .collect()
+        }
+
+        fn fields<'a>(fields: impl Iterator<Item = &'a MetaField>) -> IndexMap<String, Field> {
+            fields
+                .filter(|field| !field.name.starts_with("__"))
+                .map(|field| {
+                    (
+                        field.name.clone(),
+                        Field {
+                            ty: field.ty.clone(),
+                            args: input_values(field.args.values()),
+                            deprecated: field.deprecation.is_deprecated(),
+                        },
+                    )
+                })
+                .collect()
+        }
+
+        let mut model = Model::default();
+        for ty in registry.types.values() {
+            if is_system_type(ty.name()) {
+                continue;
+            }
+
+            let mut model_ty = Type {
+                implements: registry
+                    .implements
+                    .get(ty.name())
+                    .map(|implements| implements.iter().cloned().collect())
+                    .unwrap_or_default(),
+                ..Type::default()
+            };
+            match ty {
+                MetaType::Scalar { .. } => {}
+                MetaType::Object {
+                    fields: meta_fields,
+                    ..
+                }
+                | MetaType::Interface {
+                    fields: meta_fields,
+                    ..
+                } => model_ty.fields = fields(meta_fields.values()),
+                MetaType::Union { possible_types, .. } => {
+                    model_ty.possible_types = possible_types.clone()
+                }
+                MetaType::Enum { enum_values, .. } => {
+                    model_ty.enum_values = enum_values
+                        .values()
+                        .map(|value| (value.name.to_string(), value.deprecation.is_deprecated()))
+                        .collect()
+                }
+                MetaType::InputObject { input_fields, .. } => {
+                    model_ty.input_fields = input_values(input_fields.values())
+                }
+            }
+            model
+                .types
+                .insert(ty.name().to_string(), (ty.type_id(), model_ty));
+        }
+        model
+    }
+
+    fn from_sdl(sdl: &str) -> Result<Self, parser::Error> {
+        fn is_deprecated(directives: &[Positioned<ConstDirective>]) -> bool {
+            directives
+                .iter()
+                .any(|directive| directive.node.name.node == "deprecated")
+        }
+
+        fn input_values(
+            values: &[Positioned<InputValueDefinition>],
+        ) -> IndexMap<String, InputValue> {
+            values
+                .iter()
+                .map(|value| {
+                    (
+                        value.node.name.node.to_string(),
+                        InputValue {
+                            ty: value.node.ty.node.to_string(),
+                            default_value: value
+                                .node
+                                .default_value
+                                .as_ref()
+                                .map(|value| value.node.to_string()),
+                        },
+                    )
+                })
+                .collect()
+        }
+
+        fn fields(fields: &[Positioned<FieldDefinition>]) -> IndexMap<String, Field> {
+            fields
+                .iter()
+                .filter(|field| !field.node.name.node.starts_with("__"))
+                .map(|field| {
+                    (
+                        field.node.name.node.to_string(),
+                        Field {
+                            ty: field.node.ty.node.to_string(),
+                            args: input_values(&field.node.arguments),
+                            deprecated: is_deprecated(&field.node.directives),
+                        },
+                    )
+                })
+                .collect()
+        }
+
+        let doc = parser::parse_schema(sdl)?;
+        let mut model = Model::default();
+        for definition in &doc.definitions {
+            let definition = match definition {
+                TypeSystemDefinition::Type(definition) => &definition.node,
+                _ => continue,
+            };
+            let name = definition.name.node.as_str();
+            if is_system_type(name) {
+                continue;
+            }
+
+            // Type extensions are merged into their base type.
+            let type_id = match &definition.kind {
+                TypeKind::Scalar => MetaTypeId::Scalar,
+                TypeKind::Object(_) => MetaTypeId::Object,
+                TypeKind::Interface(_) => MetaTypeId::Interface,
+                TypeKind::Union(_) => MetaTypeId::Union,
+                TypeKind::Enum(_) => MetaTypeId::Enum,
+                TypeKind::InputObject(_) => MetaTypeId::InputObject,
+            };
+            let (_, ty) = model
+                .types
+                .entry(name.to_string())
+                .or_insert_with(|| (type_id, Type::default()));
+            match &definition.kind {
+                TypeKind::Scalar => {}
+                TypeKind::Object(object) => {
+                    ty.implements
+                        .extend(object.implements.iter().map(|name| name.node.to_string()));
+                    ty.fields.extend(fields(&object.fields));
+                }
+                TypeKind::Interface(interface) => {
+                    ty.implements.extend(
+                        interface
+                            .implements
+                            .iter()
+                            .map(|name| name.node.to_string()),
+                    );
+                    ty.fields.extend(fields(&interface.fields));
+                }
+                TypeKind::Union(union) => ty
+                    .possible_types
+                    .extend(union.members.iter().map(|name| name.node.to_string())),
+                TypeKind::Enum(enum_type) => {
+                    ty.enum_values.extend(enum_type.values.iter().map(|value| {
+                        (
+                            value.node.value.node.to_string(),
+                            is_deprecated(&value.node.directives),
+                        )
+                    }))
+                }
+                TypeKind::InputObject(input_object) => {
+                    ty.input_fields.extend(input_values(&input_object.fields))
+                }
+            }
+        }
+        Ok(model)
+    }
+}
+
+/// Returns `true` if the values of the `old` output type are also valid values
+/// of the `new` type, for example when a nullable field becomes non-null.
+fn is_safe_output_change(old: &str, new: &str) -> bool {
+    match (MetaTypeName::create(old), MetaTypeName::create(new)) {
+        (MetaTypeName::List(old), MetaTypeName::List(new)) => is_safe_output_change(old, new),
+        (MetaTypeName::NonNull(old), MetaTypeName::NonNull(new)) => is_safe_output_change(old, new),
+        (MetaTypeName::List(_), MetaTypeName::NonNull(new))
+        | (MetaTypeName::Named(_), MetaTypeName::NonNull(new)) => is_safe_output_change(old, new),
+        (MetaTypeName::Named(old), MetaTypeName::Named(new)) => old == new,
+        _ => false,
+    }
+}
+
+/// Returns `true` if the values accepted by the `old` input type are also
+/// accepted by the `new` type, for example when a non-null argument becomes
+/// nullable.
+fn is_safe_input_change(old: &str, new: &str) -> bool {
+    match (MetaTypeName::create(old), MetaTypeName::create(new)) {
+        (MetaTypeName::List(old), MetaTypeName::List(new)) => is_safe_input_change(old, new),
+        (MetaTypeName::NonNull(old), MetaTypeName::NonNull(new)) => is_safe_input_change(old, new),
+        (MetaTypeName::NonNull(old), _) => is_safe_input_change(old, new),
+        (MetaTypeName::Named(old), MetaTypeName::Named(new)) => old == new,
+        _ => false,
+    }
+}
+
+fn type_kind_name(type_id: MetaTypeId) -> &'static str {
+    match type_id {
+        MetaTypeId::Scalar => "scalar",
+        MetaTypeId::Object => "object",
+        MetaTypeId::Interface => "interface",
+        MetaTypeId::Union => "union",
+        MetaTypeId::Enum => "enum",
+        MetaTypeId::InputObject => "input object",
+    }
+}
+
+#[derive(Default)]
+struct Changes(Vec<SchemaChange>);
+
+impl Changes {
+    fn push(
+        &mut self,
+        level: SchemaChangeLevel,
+        kind: SchemaChangeKind,
+        path: String,
+        message: String,
+    ) {
+        self.0.push(SchemaChange {
+            level,
+            kind,
+            path,
+            message,
+        });
+    }
+
+    fn input_values(
+        &mut self,
+        path: &str,
+        is_argument: bool,
+        old: &IndexMap<String, InputValue>,
+        new: &IndexMap<String, InputValue>,
+    ) {
+        use SchemaChangeKind::*;
+        use SchemaChangeLevel::*;
+
+        let (element, removed, required_added, optional_added, type_changed, default_changed) =
+            if is_argument {
+                (
+                    "Argument",
+                    ArgumentRemoved,
+                    RequiredArgumentAdded,
+                    OptionalArgumentAdded,
+                    ArgumentTypeChanged,
+                    ArgumentDefaultValueChanged,
+                )
+            } else {
+                (
+                    "Input field",
+                    InputFieldRemoved,
+                    RequiredInputFieldAdded,
+                    OptionalInputFieldAdded,
+                    InputFieldTypeChanged,
+                    InputFieldDefaultValueChanged,
+                )
+            };
+
+        for (name, old_value) in old {
+            let path = format!("{}.{}", path, name);
+            let new_value = match new.get(name) {
+                Some(new_value) => new_value,
+                None => {
+                    let message = format!("{} `{}` was removed.", element, path);
+                    self.push(Breaking, removed, path, message);
+                    continue;
+                }
+            };
+
+            if old_value.ty != new_value.ty {
+                let level = if is_safe_input_change(&old_value.ty, &new_value.ty) {
+                    Safe
+                } else {
+                    Breaking
+                };
+                let message = format!(
+                    "{} `{}` changed type from `{}` to `{}`.",
+                    element, path, old_value.ty, new_value.ty
+                );
+                self.push(level, type_changed, path.clone(), message);
+            }
+
+            if old_value.default_value != new_value.default_value {
+                let message = format!(
+                    "{} `{}` changed default value from `{}` to `{}`.",
+                    element,
+                    path,
+                    old_value.default_value.as_deref().unwrap_or("null"),
+                    new_value.default_value.as_deref().unwrap_or("null"),
+                );
+                self.push(Dangerous, default_changed, path, message);
+            }
+        }
+
+        for (name, new_value) in new {
+            if old.contains_key(name) {
+                continue;
+            }
+            let path = format!("{}.{}", path, name);
+            if new_value.is_required() {
+                let message = format!("Required {} `{}` was added.", element.to_lowercase(), path);
+                self.push(Breaking, required_added, path, message);
+            } else {
+                let message = format!("Optional {} `{}` was added.", element.to_lowercase(), path);
+                self.push(Dangerous, optional_added, path, message);
+            }
+        }
+    }
+
+    fn fields(
+        &mut self,
+        type_name: &str,
+        old: &IndexMap<String, Field>,
+        new: &IndexMap<String, Field>,
+    ) {
+        use SchemaChangeKind::*;
+        use SchemaChangeLevel::*;
+
+        for (name, old_field) in old {
+            let path = format!("{}.{}", type_name, name);
+            let new_field = match new.get(name) {
+                Some(new_field) => new_field,
+                None => {
+                    let message = format!("Field `{}` was removed.", path);
+                    self.push(Breaking, FieldRemoved, path, message);
+                    continue;
+                }
+            };
+
+            if old_field.ty != new_field.ty {
+                let level = if is_safe_output_change(&old_field.ty, &new_field.ty) {
+                    Safe
+                } else {
+                    Breaking
+                };
+                let message = format!(
+                    "Field `{}` changed type from `{}` to `{}`.",
+                    path, old_field.ty, new_field.ty
+                );
+                self.push(level, FieldTypeChanged, path.clone(), message);
+            }
+
+            if !old_field.deprecated && new_field.deprecated {
+                let message = format!("Field `{}` was deprecated.", path);
+                self.push(Safe, FieldDeprecated, path.clone(), message);
+            }
+
+            self.input_values(&path, true, &old_field.args, &new_field.args);
+        }
+
+        for name in new.keys() {
+            if !old.contains_key(name) {
+                let path = format!("{}.{}", type_name, name);
+                let message = format!("Field `{}` was added.", path);
+                self.push(Safe, FieldAdded, path, message);
+            }
+        }
+    }
+
+    fn names(
+        &mut self,
+        type_name: &str,
+        old: &IndexSet<String>,
+        new: &IndexSet<String>,
+        (removed, removed_message): (SchemaChangeKind, &str),
+        (added, added_message): (SchemaChangeKind, &str),
+    ) {
+        for name in old.difference(new) {
+            let message = format!("`{}` {} `{}`.", name, removed_message, type_name);
+            self.push(
+                SchemaChangeLevel::Breaking,
+                removed,
+                type_name.to_string(),
+                message,
+            );
+        }
+        for name in new.difference(old) {
+            let message = format!("`{}` {} `{}`.", name, added_message, type_name);
+            self.push(
+                SchemaChangeLevel::Dangerous,
+                added,
+                type_name.to_string(),
+                message,
+            );
+        }
+    }
+}
+
+fn diff(old: &Model, new: &Model) -> SchemaDiff {
+    use SchemaChangeKind::*;
+    use SchemaChangeLevel::*;
+
+    let mut changes = Changes::default();
+
+    for (name, (old_type_id, old_ty)) in &old.types {
+        let (new_type_id, new_ty) = match new.types.get(name) {
+            Some(new_ty) => new_ty,
+            None => {
+                let message = format!("Type `{}` was removed.", name);
+                changes.push(Breaking, TypeRemoved, name.clone(), message);
+                continue;
+            }
+        };
+
+        if old_type_id != new_type_id {
+            let message = format!(
+                "Type `{}` changed from {} to {}.",
+                name,
+                type_kind_name(*old_type_id),
+                type_kind_name(*new_type_id)
+            );
+            changes.push(Breaking, TypeKindChanged, name.clone(), message);
+            continue;
+        }
+
+        match old_type_id {
+            MetaTypeId::Scalar => {}
+            MetaTypeId::Object | MetaTypeId::Interface => {
+                changes.names(
+                    name,
+                    &old_ty.implements,
+                    &new_ty.implements,
+                    (
+                        InterfaceImplementationRemoved,
+                        "is no longer implemented by",
+                    ),
+                    (InterfaceImplementationAdded, "is now implemented by"),
+                );
+                changes.fields(name, &old_ty.fields, &new_ty.fields);
+            }
+            MetaTypeId::Union => changes.names(
+                name,
+                &old_ty.possible_types,
+                &new_ty.possible_types,
+                (UnionMemberRemoved, "was removed from the union"),
+                (UnionMemberAdded, "was added to the union"),
+            ),
+            MetaTypeId::Enum => {
+                for (value, old_deprecated) in &old_ty.enum_values {
+                    let path = format!("{}.{}", name, value);
+                    match new_ty.enum_values.get(value) {
+                        Some(new_deprecated) => {
+                            if !old_deprecated && *new_deprecated {
+                                let message = format!("Enum value `{}` was deprecated.", path);
+                                changes.push(Safe, EnumValueDeprecated, path, message);
+                            }
+                        }
+                        None => {
+                            let message = format!("Enum value `{}` was removed.", path);
+                            changes.push(Breaking, EnumValueRemoved, path, message);
+                        }
+                    }
+                }
+                for value in new_ty.enum_values.keys() {
+                    if !old_ty.enum_values.contains_key(value) {
+                        let path = format!("{}.{}", name, value);
+                        let message = format!("Enum value `{}` was added.", path);
+                        changes.push(Dangerous, EnumValueAdded, path, message);
+                    }
+                }
+            }
+            MetaTypeId::InputObject => {
+                changes.input_values(name, false, &old_ty.input_fields, &new_ty.input_fields)
+            }
+        }
+    }
+
+    for (name, (type_id, _)) in &new.types {
+        if !old.types.contains_key(name) {
+            let message = format!("Type `{}` ({}) was added.", name, type_kind_name(*type_id));
+            changes.push(Safe, TypeAdded, name.clone(), message);
+        }
+    }
+
+    SchemaDiff { changes: changes.0 }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(old: &str, new: &str, expected: &[(SchemaChangeLevel, SchemaChangeKind, &str)]) {
+        let diff = SchemaDiff::from_sdl(old, new).unwrap();
+        let changes = diff
+            .changes()
+            .iter()
+            .map(|change| (change.level, change.kind, change.path.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(changes, expected);
+    }
+
+    #[test]
+    fn identical() {
+        let sdl = r#"
+            type Query { a(x: Int = 1): [String!] b: E }
+            enum E { A B }
+        "#;
+        assert!(SchemaDiff::from_sdl(sdl, sdl).unwrap().is_empty());
+    }
+
+    #[test]
+    fn types() {
+        use SchemaChangeKind::*;
+        use SchemaChangeLevel::*;
+
+        check(
+            "type Query { a: Int } type A { a: Int } scalar B",
+            "type Query { a: Int } input B { a: Int } enum C { A }",
+            &[
+                (Breaking, TypeRemoved, "A"),
+                (Breaking, TypeKindChanged, "B"),
+                (Safe, TypeAdded, "C"),
+            ],
+        );
+    }
+
+    #[test]
+    fn fields() {
+        use SchemaChangeKind::*;
+        use SchemaChangeLevel::*;
+
+        check(
+            r#"
+            type Query {
+                a: Int
+                b: Int!
+                c: [Int]
+                d: [Int!]!
+                e: Int
+                f: Int
+            }
+            "#,
+            r#"
+            type Query {
+                a: Int!
+                b: Int
+                c: [Int!]
+                d: [Int]!
+                f: String @deprecated
+                g: Int
+            }
+            "#,
+            &[
+                (Safe, FieldTypeChanged, "Query.a"),
+                (Breaking, FieldTypeChanged, "Query.b"),
+                (Safe, FieldTypeChanged, "Query.c"),
+                (Breaking, FieldTypeChanged, "Query.d"),
+                (Breaking, FieldRemoved, "Query.e"),
+                (Breaking, FieldTypeChanged, "Query.f"),
+                (Safe, FieldDeprecated, "Query.f"),
+                (Safe, FieldAdded, "Query.g"),
+            ],
+        );
+    }
+
+    #[test]
+    fn arguments() {
+        use SchemaChangeKind::*;
+        use SchemaChangeLevel::*;
+
+        check(
+            "type Query { a(x: Int, y: Int!, z: [Int!], w: Int = 1, v: Int): Int }",
+            "type Query { a(x: Int!, y: Int, z: [Int], w: Int = 2, r: Int!, o: Int, d: Int! = 1): Int }",
+            &[
+                (Breaking, ArgumentTypeChanged, "Query.a.x"),
+                (Safe, ArgumentTypeChanged, "Query.a.y"),
+                (Safe, ArgumentTypeChanged, "Query.a.z"),
+                (Dangerous, ArgumentDefaultValueChanged, "Query.a.w"),
+                (Breaking, ArgumentRemoved, "Query.a.v"),
+                (Breaking, RequiredArgumentAdded, "Query.a.r"),
+                (Dangerous, OptionalArgumentAdded, "Query.a.o"),
+                (Dangerous, OptionalArgumentAdded, "Query.a.d"),
+            ],
+        );
+    }
+
+    #[test]
+    fn input_objects() {
+        use SchemaChangeKind::*;
+        use SchemaChangeLevel::*;
+
+        check(
+            "type Query { a(i: I): Int } input I { a: Int b: Int }",
+            "type Query { a(i: I): Int } input I { a: String c: Int! d: Int }",
+            &[
+                (Breaking, InputFieldTypeChanged, "I.a"),
+                (Breaking, InputFieldRemoved, "I.b"),
+                (Breaking, RequiredInputFieldAdded, "I.c"),
+                (Dangerous, OptionalInputFieldAdded, "I.d"),
+            ],
+        );
+    }
+
+    #[test]
+    fn enums_unions_and_interfaces() {
+        use SchemaChangeKind::*;
+        use SchemaChangeLevel::*;
+
+        check(
+            r#"
+            type Query { a: E b: U }
+            enum E { A B }
+            union U = X | Y
+            interface N { id: ID! }
+            type X implements N { id: ID! }
+            type Y { id: ID! }
+            "#,
+            r#"
+            type Query { a: E b: U }
+            enum E { A @deprecated C }
+            union U = X | Z
+            interface N { id: ID! }
+            type X { id: ID! }
+            type Y implements N { id: ID! }
+            type Z { id: ID! }
+            "#,
+            &[
+                (Safe, EnumValueDeprecated, "E.A"),
+                (Breaking, EnumValueRemoved, "E.B"),
+                (Dangerous, EnumValueAdded, "E.C"),
+                (Breaking, UnionMemberRemoved, "U"),
+                (Dangerous, UnionMemberAdded, "U"),
+                (Breaking, InterfaceImplementationRemoved, "X"),
+                (Dangerous, InterfaceImplementationAdded, "Y"),
+                (Safe, TypeAdded, "Z"),
+            ],
+        );
+    }
+}
@@ -1,15 +1,36 @@
-use std::fmt::{Error, Result as FmtResult, Write};
+use std::{
+    collections::HashMap,
+    fmt::{Error, Result as FmtResult, Write},
+};
 
-use async_graphql_value::ConstValue;
+use async_graphql_value::{ConstValue, Value};
+use indexmap::IndexSet;
 
 use crate::{
     parser::types::{
         ExecutableDocument, FragmentDefinition, OperationType, Selection, SelectionSet,
+        VariableDefinition,
     },
     registry::{MetaInputValue, MetaType, MetaTypeName, Registry},
-    Variables,
+    Name, Positioned, Variables,
 };
 
+/// An operation forwarded to a remote service.
+pub(crate) struct RemoteOperation {
+    /// The document of the operation.
+    pub(crate) query: String,
+    /// The names of the variables used by the operation.
+    pub(crate) variables: IndexSet<Name>,
+}
+
+struct RemoteStringifier<'a> {
+    registry: &'a Registry,
+    fragments: &'a HashMap<Name, Positioned<FragmentDefinition>>,
+    /// The local and remote names of a renamed type.
+    renamed_type: Option<(&'a str, &'a str)>,
+    variables: IndexSet<Name>,
+}
+
 impl Registry {
     pub(crate) fn stringify_exec_doc(
         &self,
@@ -218,6 +239,184 @@ impl Registry {
     }
 }
 
+impl Registry {
+    /// Stringify selections forwarded to a remote service as an operation,
+    /// declaring the variables they use.
+    ///
+    /// The fragments are inlined, and `__typename` is selected on the
+    /// abstract types to resolve the concrete type of the values. The
+    /// directives are not forwarded, the skipped selections have already been
+    /// removed.
+    pub(crate) fn stringify_remote_operation(
+        &self,
+        ty: OperationType,
+        parent_type: &str,
+        selections: &[Positioned<Selection>],
+        variable_definitions: &[Positioned<VariableDefinition>],
+        fragments: &HashMap<Name, Positioned<FragmentDefinition>>,
+        renamed_type: Option<(&str, &str)>,
+    ) -> Result<RemoteOperation, Error> {
+        let mut stringifier = RemoteStringifier {
+            registry: self,
+            fragments,
+            renamed_type,
+            variables: IndexSet::new(),
+        };
+        let mut selection_set = String::new();
+        stringifier.stringify_selections(
+            &mut selection_set,
+            selections,
+            self.types.get(parent_type),
+        )?;
+
+        let mut output = ty.to_string();
+        let variable_definitions = variable_definitions
+            .iter()
+            .map(|definition| &definition.node)
+            .filter(|definition| stringifier.variables.contains(&definition.name.node))
+            .collect::<Vec<_>>();
+        if !variable_definitions.is_empty() {
+            output.push('(');
+            for (idx, variable_definition) in variable_definitions.into_iter().enumerate() {
+                if idx > 0 {
+                    output.push_str(", ");
+                }
+                write!(
+                    output,
+                    "${}: {}",
+                    variable_definition.name.node, variable_definition.var_type.node
+                )?;
+                if let Some(default_value) = &variable_definition.default_value {
+                    write!(output, " = {}", default_value.node)?;
+                }
+            }
+            output.push(')');
+        }
+        output.push(' ');
+        output.push_str(&selection_set);
+
+        Ok(RemoteOperation {
+            query: output,
+            variables: stringifier.variables,
+        })
+    }
+}
+
+impl<'a> RemoteStringifier<'a> {
+    fn stringify_selections(
+        &mut self,
+        output: &mut String,
+        selections: &'a [Positioned<Selection>],
+        parent_type: Option<&'a MetaType>,
+    ) -> FmtResult {
+        output.push_str("{ ");
+        if parent_type.map(MetaType::is_abstract).unwrap_or_default() {
+            output.push_str("__typename ");
+        }
+        self.stringify_items(output, selections, parent_type)?;
+        output.push('}');
+        Ok(())
+    }
+
+    fn stringify_items(
+        &mut self,
+        output: &mut String,
+        selections: &'a [Positioned<Selection>],
+        parent_type: Option<&'a MetaType>,
+    ) -> FmtResult {
+        for selection in selections {
+            match &selection.node {
+                Selection::Field(field) => {
+                    if let Some(alias) = &field.node.alias {
+                        write!(output, "{}: ", alias.node)?;
+                    }
+                    write!(output, "{}", field.node.name.node)?;
+                    if !field.node.arguments.is_empty() {
+                        output.push('(');
+                        for (idx, (name, value)) in field.node.arguments.iter().enumerate() {
+                            if idx > 0 {
+                                output.push_str(", ");
+                            }
+                            self.collect_variables(&value.node);
+                            write!(output, "{}: {}", name.node, value.node)?;
+                        }
+                        output.push(')');
+                    }
+                    output.push(' ');
+                    if !field.node.selection_set.node.items.is_empty() {
+                        let field_type = parent_type
+                            .and_then(|ty| ty.field_by_name(field.node.name.node.as_str()))
+                            .and_then(|field| self.registry.concrete_type_by_name(&field.ty));
+                        self.stringify_selections(
+                            output,
+                            &field.node.selection_set.node.items,
+                            field_type,
+                        )?;
+                        output.push(' ');
+                    }
+                }
+                Selection::FragmentSpread(fragment_spread) => {
+                    if let Some(fragment) =
+                        self.fragments.get(&fragment_spread.node.fragment_name.node)
+                    {
+                        self.stringify_inline_fragment(
+                            output,
+                            Some(fragment.node.type_condition.node.on.node.as_str()),
+                            &fragment.node.selection_set.node,
+                        )?;
+                    }
+                }
+                Selection::InlineFragment(inline_fragment) => {
+                    match &inline_fragment.node.type_condition {
+                        Some(type_condition) => self.stringify_inline_fragment(
+                            output,
+                            Some(type_condition.node.on.node.as_str()),
+                            &inline_fragment.node.selection_set.node,
+                        )?,
+                        None => self.stringify_items(
+                            output,
+                            &inline_fragment.node.selection_set.node.items,
+                            parent_type,
+                        )?,
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn stringify_inline_fragment(
+        &mut self,
+        output: &mut String,
+        type_condition: Option<&str>,
+        selection_set: &'a SelectionSet,
+    ) -> FmtResult {
+        let parent_type = type_condition.and_then(|name| self.registry.types.get(name));
+        output.push_str("... ");
+        if let Some(name) = type_condition {
+            let name = match self.renamed_type {
+                Some((local, remote)) if local == name => remote,
+                _ => name,
+            };
+            write!(output, "on {} ", name)?;
+        }
+        self.stringify_selections(output, &selection_set.items, parent_type)?;
+        output.push(' ');
+        Ok(())
+    }
+
+    fn collect_variables(&mut self, value: &Value) {
+        match value {
+            Value::Variable(name) => {
+                self.variables.insert(name.clone());
+            }
+            Value::List(items) => items.iter().for_each(|item| self.collect_variables(item)),
+            Value::Object(obj) => obj.values().for_each(|item| self.collect_variables(item)),
+            _ => {}
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,11 +1,12 @@
 use std::{future::Future, pin::Pin, sync::Arc};
 
-use futures_util::FutureExt;
+use futures_util::{future::BoxFuture, FutureExt};
 use indexmap::IndexMap;
 
 use crate::{
-    extensions::ResolveInfo, parser::types::Selection, Context, ContextBase, ContextSelectionSet,
-    Error, Name, OutputType, ServerError, ServerResult, Value,
+    auth, extensions::ResolveInfo, incremental, parser::types::Selection, redaction, Context,
+    ContextBase, ContextSelectionSet, Error, Name, OutputType, PathSegment, ServerError,
+    ServerResult, Value,
 };
 
 /// Represents a GraphQL container object.
@@ -116,7 +117,7 @@ pub async fn resolve_container_serial<'a, T: ContainerType + ?Sized>(
     resolve_container_inner(ctx, root, false).await
 }
 
-fn insert_value(target: &mut IndexMap<Name, Value>, name: Name, value: Value) {
+pub(crate) fn insert_value(target: &mut IndexMap<Name, Value>, name: Name, value: Value) {
     if let Some(prev_value) = target.get_mut(&name) {
         if let Value::Object(target_map) = prev_value {
             if let Value::Object(obj) = value {
@@ -147,14 +148,23 @@ async fn resolve_container_inner<'a, T: ContainerType + ?Sized>(
     root: &'a T,
     parallel: bool,
 ) -> ServerResult<Value> {
-    let mut fields = Fields(Vec::new());
+    let mut fields = Fields::default();
     fields.add_set(ctx, root)?;
 
+    if let Some(incremental) = &ctx.query_env.incremental {
+        let path = incremental::path_segments(ctx.path_node.as_ref());
+        let (children, build) = fields.children(&path);
+        return incremental::join(incremental, children, fields.deferred, build, |value| {
+            incremental.publish(path.clone(), value.clone())
+        })
+        .await;
+    }
+
     let res = if parallel {
-        futures_util::future::try_join_all(fields.0).await?
+        futures_util::future::try_join_all(fields.futures).await?
     } else {
-        let mut results = Vec::with_capacity(fields.0.len());
-        for field in fields.0 {
+        let mut results = Vec::with_capacity(fields.futures.len());
+        for field in fields.futures {
             results.push(field.await?);
         }
         results
@@ -170,9 +180,53 @@ async fn resolve_container_inner<'a, T: ContainerType + ?Sized>(
 type BoxFieldFuture<'a> = Pin<Box<dyn Future<Output = ServerResult<(Name, Value)>> + 'a + Send>>;
 
 /// A set of fields on an container that are being selected.
-pub struct Fields<'a>(Vec<BoxFieldFuture<'a>>);
+#[derive(Default)]
+pub struct Fields<'a> {
+    futures: Vec<BoxFieldFuture<'a>>,
+    /// The response keys of the fields, only with incremental delivery.
+    keys: Vec<Name>,
+    /// The deferred fragments, only with incremental delivery.
+    deferred: Vec<BoxFuture<'a, ()>>,
+}
 
 impl<'a> Fields<'a> {
+    fn push(&mut self, ctx: &ContextSelectionSet<'a>, key: &Name, fut: BoxFieldFuture<'a>) {
+        if ctx.query_env.incremental.is_some() {
+            self.keys.push(key.clone());
+        }
+        self.futures.push(fut);
+    }
+
+    /// Returns the fields to resolve with incremental delivery, and the
+    /// function building the object from their values.
+    fn children(
+        &mut self,
+        path: &[PathSegment],
+    ) -> (
+        Vec<incremental::Child<'a>>,
+        impl FnOnce(Vec<Value>) -> Value + Send + 'a,
+    ) {
+        let children = self
+            .keys
+            .iter()
+            .zip(std::mem::take(&mut self.futures))
+            .map(|(key, fut)| {
+                let mut path = path.to_vec();
+                path.push(PathSegment::Field(key.to_string()));
+                (path, fut.map(|res| res.map(|(_, value)| value)).boxed())
+            })
+            .collect();
+        let keys = std::mem::take(&mut self.keys);
+        let build = move |values: Vec<Value>| {
+            let mut map = IndexMap::new();
+            for (key, value) in keys.into_iter().zip(values) {
+                insert_value(&mut map, key, value);
+            }
+            Value::Object(map)
+        };
+        (children, build)
+    }
+
     /// Add another set of fields to this set of fields using the given
     /// container.
     pub fn add_set<T: ContainerType + ?Sized>(
@@ -189,9 +243,11 @@ impl<'a> Fields<'a> {
                         let field_name = ctx_field.item.node.response_key().node.clone();
                         let typename = root.introspection_type_name().into_owned();
 
-                        self.0.push(Box::pin(async move {
-                            Ok((field_name, Value::String(typename)))
-                        }));
+                        self.push(
+                            ctx,
+                            &field_name.clone(),
+                            Box::pin(async move { Ok((field_name, Value::String(typename))) }),
+                        );
                         continue;
                     }
 
@@ -202,6 +258,18 @@ impl<'a> Fields<'a> {
                             let field_name = ctx_field.item.node.response_key().node.clone();
                             let extensions = &ctx.query_env.extensions;
 
+                            if ctx.query_env.auth_denied.get().is_some() {
+                                if let Some((err, nullable)) =
+                                    auth::denied_field(&ctx_field, &T::type_name())
+                                {
+                                    if !nullable {
+                                        return Err(err);
+                                    }
+                                    ctx_field.add_error(err);
+                                    return Ok((field_name, Value::Null));
+                                }
+                            }
+
                             if extensions.is_empty() && field.node.directives.is_empty() {
                                 Ok((
                                     field_name,
@@ -292,7 +360,20 @@ impl<'a> Fields<'a> {
                         }
                     });
 
-                    self.0.push(resolve_fut);
+                    let key = &field.node.response_key().node;
+                    match redaction::redacted_field(ctx, &T::type_name(), &field.node) {
+                        Some(meta_field) => self.push(
+                            ctx,
+                            key,
+                            Box::pin(redaction::redact_field(
+                                ctx.clone(),
+                                field,
+                                meta_field,
+                                resolve_fut,
+                            )),
+                        ),
+                        None => self.push(ctx, key, resolve_fut),
+                    }
                 }
                 selection => {
                     let (type_condition, selection_set) = match selection {
@@ -336,9 +417,35 @@ impl<'a> Fields<'a> {
                                 .get(&*introspection_type_name)
                                 .map_or(false, |interfaces| interfaces.contains(condition))
                     });
+                    let applies_interface = !applies_concrete_object
+                        && type_condition.map_or(true, |condition| T::type_name() == condition);
+                    if applies_concrete_object || applies_interface {
+                        if let Some(label) = incremental::defer_label(ctx, selection.directives())?
+                        {
+                            // The fragment is delivered in a subsequent payload.
+                            let ctx_fragment = ctx.with_selection_set(selection_set);
+                            let mut fields = Fields::default();
+                            if applies_concrete_object {
+                                root.collect_all_fields(&ctx_fragment, &mut fields)?;
+                            } else {
+                                fields.add_set(&ctx_fragment, root)?;
+                            }
+                            let path = incremental::path_segments(ctx.path_node.as_ref());
+                            let (children, build) = fields.children(&path);
+                            self.deferred.push(incremental::defer(
+                                &ctx_fragment,
+                                label,
+                                children,
+                                fields.deferred,
+                                build,
+                            ));
+                            continue;
+                        }
+                    }
+
                     if applies_concrete_object {
                         root.collect_all_fields(&ctx.with_selection_set(selection_set), self)?;
-                    } else if type_condition.map_or(true, |condition| T::type_name() == condition) {
+                    } else if applies_interface {
                         // The fragment applies to an interface type.
                         self.add_set(&ctx.with_selection_set(selection_set), root)?;
                     }
@@ -1,61 +1,91 @@
+use futures_util::FutureExt;
+
 use crate::{
-    extensions::ResolveInfo, parser::types::Field, ContextSelectionSet, OutputType, Positioned,
-    ServerResult, Value,
+    extensions::ResolveInfo, incremental, parser::types::Field, ContextSelectionSet, OutputType,
+    PathSegment, Positioned, ServerResult, Value,
 };
 
 /// Resolve an list by executing each of the items concurrently.
+///
+/// If the field has a `@stream` directive, only the first `initialCount`
+/// items are resolved in the initial payload, each of the remaining ones is
+/// delivered in a subsequent payload once it is resolved.
 pub async fn resolve_list<'a, T: OutputType + 'a>(
     ctx: &ContextSelectionSet<'a>,
     field: &Positioned<Field>,
     iter: impl IntoIterator<Item = T>,
     len: Option<usize>,
 ) -> ServerResult<Value> {
-    let extensions = &ctx.query_env.extensions;
-    if !extensions.is_empty() {
-        let mut futures = len.map(Vec::with_capacity).unwrap_or_default();
-        for (idx, item) in iter.into_iter().enumerate() {
-            futures.push({
-                let ctx = ctx.clone();
-                async move {
-                    let ctx_idx = ctx.with_index(idx);
-                    let extensions = &ctx.query_env.extensions;
+    if let Some(incremental) = &ctx.query_env.incremental {
+        let items = iter.into_iter().collect::<Vec<_>>();
+        let (initial_count, label) = match incremental::stream_initial_count(ctx, field)? {
+            Some((initial_count, label)) => (initial_count, label),
+            None => (items.len(), None),
+        };
+        let path = incremental::path_segments(ctx.path_node.as_ref());
 
-                    let resolve_info = ResolveInfo {
-                        path_node: ctx_idx.path_node.as_ref().unwrap(),
-                        parent_type: &Vec::<T>::type_name(),
-                        return_type: &T::qualified_type_name(),
-                        name: field.node.name.node.as_str(),
-                        alias: field.node.alias.as_ref().map(|alias| alias.node.as_str()),
-                    };
-                    let resolve_fut = async {
-                        OutputType::resolve(&item, &ctx_idx, field)
-                            .await
-                            .map(Option::Some)
-                            .map_err(|err| ctx_idx.set_error_path(err))
-                    };
-                    futures_util::pin_mut!(resolve_fut);
-                    extensions
-                        .resolve(resolve_info, &mut resolve_fut)
-                        .await
-                        .map(|value| value.expect("You definitely encountered a bug!"))
-                }
-            });
+        let mut children = Vec::with_capacity(initial_count.min(items.len()));
+        let mut streamed = Vec::new();
+        let mut previous = None;
+        for (idx, item) in items.iter().enumerate() {
+            let mut item_path = path.clone();
+            item_path.push(PathSegment::Index(idx));
+            let child = (item_path, resolve_item(ctx, field, idx, item).boxed());
+            if idx < initial_count {
+                children.push(child);
+            } else {
+                let (id, fut) =
+                    incremental::stream_item(ctx.query_env, previous, label.clone(), child);
+                previous = Some(id);
+                streamed.push(fut);
+            }
         }
-        Ok(Value::List(
-            futures_util::future::try_join_all(futures).await?,
-        ))
-    } else {
-        let mut futures = len.map(Vec::with_capacity).unwrap_or_default();
-        for (idx, item) in iter.into_iter().enumerate() {
-            let ctx_idx = ctx.with_index(idx);
-            futures.push(async move {
-                OutputType::resolve(&item, &ctx_idx, field)
-                    .await
-                    .map_err(|err| ctx_idx.set_error_path(err))
-            });
-        }
-        Ok(Value::List(
-            futures_util::future::try_join_all(futures).await?,
-        ))
+
+        return incremental::join(incremental, children, streamed, Value::List, |value| {
+            incremental.publish(path.clone(), value.clone())
+        })
+        .await;
+    }
+
+    let mut futures = len.map(Vec::with_capacity).unwrap_or_default();
+    for (idx, item) in iter.into_iter().enumerate() {
+        futures.push(async move { resolve_item(ctx, field, idx, &item).await });
+    }
+    Ok(Value::List(
+        futures_util::future::try_join_all(futures).await?,
+    ))
+}
+
+async fn resolve_item<'a, T: OutputType + 'a>(
+    ctx: &ContextSelectionSet<'a>,
+    field: &Positioned<Field>,
+    idx: usize,
+    item: &T,
+) -> ServerResult<Value> {
+    let ctx_idx = ctx.with_index(idx);
+    let extensions = &ctx.query_env.extensions;
+    if extensions.is_empty() {
+        return OutputType::resolve(item, &ctx_idx, field)
+            .await
+            .map_err(|err| ctx_idx.set_error_path(err));
     }
+
+    let resolve_info = ResolveInfo {
+        path_node: ctx_idx.path_node.as_ref().unwrap(),
+        parent_type: &Vec::<T>::type_name(),
+        return_type: &T::qualified_type_name(),
+        name: field.node.name.node.as_str(),
+        alias: field.node.alias.as_ref().map(|alias| alias.node.as_str()),
+    };
+    let resolve_fut = async {
+        OutputType::resolve(item, &ctx_idx, field)
+            .await
+            .map(Option::Some)
+            .map_err(|err| ctx_idx.set_error_path(err))
+    };
+    futures_util::pin_mut!(resolve_fut);
+    extensions
+        .resolve(resolve_info, &mut resolve_fut)
+        .await
+        .map(|value| value.expect("You definitely encountered a bug!"))
 }
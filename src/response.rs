@@ -2,15 +2,15 @@ use std::collections::BTreeMap;
 
 use http::{
     header::{HeaderMap, HeaderName},
-    HeaderValue,
+    HeaderValue, StatusCode,
 };
-use serde::{Deserialize, Serialize};
+use serde::{ser::SerializeMap, Deserialize, Serialize, Serializer};
 
-use crate::{CacheControl, Result, ServerError, Value};
+use crate::{http::ResponseContentType, CacheControl, PathSegment, Result, ServerError, Value};
 
 /// Query response
 #[non_exhaustive]
-#[derive(Debug, Default, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Default, Deserialize)]
 pub struct Response {
     /// Data of query result
     #[serde(default)]
@@ -31,6 +31,80 @@ pub struct Response {
     /// HTTP headers
     #[serde(skip)]
     pub http_headers: HeaderMap,
+
+    /// Payloads of deferred fragments and streamed lists
+    #[serde(default)]
+    pub incremental: Vec<IncrementalPayload>,
+
+    /// Whether more payloads follow this one, only set when using incremental
+    /// delivery with `@defer` or `@stream`
+    #[serde(rename = "hasNext", default)]
+    pub has_next: Option<bool>,
+
+    /// Whether the request failed before execution began
+    #[serde(skip)]
+    pub(crate) request_error: bool,
+}
+
+// `request_error` is only used to choose the status code, two responses with
+// the same content are equal.
+impl PartialEq for Response {
+    fn eq(&self, other: &Self) -> bool {
+        self.data == other.data
+            && self.extensions == other.extensions
+            && self.cache_control == other.cache_control
+            && self.errors == other.errors
+            && self.http_headers == other.http_headers
+            && self.incremental == other.incremental
+            && self.has_next == other.has_next
+    }
+}
+
+impl Serialize for Response {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        // Subsequent payloads of an incremental response have no data.
+        if self.has_next.is_none() || self.data != Value::Null {
+            map.serialize_entry("data", &self.data)?;
+        }
+        if !self.extensions.is_empty() {
+            map.serialize_entry("extensions", &self.extensions)?;
+        }
+        if !self.errors.is_empty() {
+            map.serialize_entry("errors", &self.errors)?;
+        }
+        if !self.incremental.is_empty() {
+            map.serialize_entry("incremental", &self.incremental)?;
+        }
+        if let Some(has_next) = self.has_next {
+            map.serialize_entry("hasNext", &has_next)?;
+        }
+        map.end()
+    }
+}
+
+/// A payload of an incremental response, delivering a deferred fragment or
+/// the remaining items of a streamed list.
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct IncrementalPayload {
+    /// Data of the deferred fragment
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub data: Option<Value>,
+
+    /// Items of the streamed list
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub items: Option<Vec<Value>>,
+
+    /// Path of the object or the first item this payload belongs to
+    pub path: Vec<PathSegment>,
+
+    /// Label of the `@defer` or `@stream` directive
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub label: Option<String>,
+
+    /// Errors
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub errors: Vec<ServerError>,
 }
 
 impl Response {
@@ -52,6 +126,17 @@ impl Response {
         }
     }
 
+    /// Create a response from the errors of a request that failed before
+    /// execution began, such as parse or validation errors.
+    #[must_use]
+    pub(crate) fn from_request_errors(errors: Vec<ServerError>) -> Self {
+        Self {
+            errors,
+            request_error: true,
+            ..Default::default()
+        }
+    }
+
     /// Set the extension result of the response.
     #[must_use]
     pub fn extension(mut self, name: impl Into<String>, value: Value) -> Self {
@@ -89,6 +174,13 @@ impl Response {
         !self.is_ok()
     }
 
+    /// Returns `true` if the request failed before execution began, for
+    /// example because the query could not be parsed or validated.
+    #[inline]
+    pub fn is_request_error(&self) -> bool {
+        self.request_error
+    }
+
     /// Extract the error from the response. Only if the `error` field is empty
     /// will this return `Ok`.
     #[inline]
@@ -132,6 +224,25 @@ impl BatchResponse {
         }
     }
 
+    /// Returns the HTTP status code to send the response with.
+    ///
+    /// With [`ResponseContentType::GraphQLResponseJson`] a request failing
+    /// before execution gets `400 Bad Request`, a batch only does if all its
+    /// requests failed. Responses sent as `application/json` always use `200
+    /// OK`.
+    pub fn status_code(&self, content_type: ResponseContentType) -> StatusCode {
+        let request_error = match self {
+            BatchResponse::Single(resp) => resp.is_request_error(),
+            BatchResponse::Batch(resp) => {
+                !resp.is_empty() && resp.iter().all(Response::is_request_error)
+            }
+        };
+        match content_type {
+            ResponseContentType::GraphQLResponseJson if request_error => StatusCode::BAD_REQUEST,
+            _ => StatusCode::OK,
+        }
+    }
+
     /// Returns HTTP headers map.
     pub fn http_headers(&self) -> HeaderMap {
         match self {
@@ -192,4 +303,40 @@ mod tests {
             r#"[{"data":true},{"data":"1"}]"#
         );
     }
+
+    #[test]
+    fn test_batch_response_status_code() {
+        let request_error = || Response::from_request_errors(vec![ServerError::new("err", None)]);
+
+        let resp = BatchResponse::Single(request_error());
+        assert_eq!(resp.status_code(ResponseContentType::Json), StatusCode::OK);
+        assert_eq!(
+            resp.status_code(ResponseContentType::GraphQLResponseJson),
+            StatusCode::BAD_REQUEST
+        );
+
+        let resp =
+            BatchResponse::Single(Response::from_errors(vec![ServerError::new("err", None)]));
+        assert_eq!(
+            resp.status_code(ResponseContentType::GraphQLResponseJson),
+            StatusCode::OK
+        );
+
+        let resp = BatchResponse::Batch(vec![request_error(), Response::new(Value::Null)]);
+        assert_eq!(
+            resp.status_code(ResponseContentType::GraphQLResponseJson),
+            StatusCode::OK
+        );
+
+        let resp = BatchResponse::Batch(vec![request_error(), request_error()]);
+        assert_eq!(
+            resp.status_code(ResponseContentType::GraphQLResponseJson),
+            StatusCode::BAD_REQUEST
+        );
+
+        assert_eq!(
+            request_error(),
+            Response::from_errors(vec![ServerError::new("err", None)])
+        );
+    }
 }
@@ -3,29 +3,39 @@ use std::{
     collections::{HashMap, HashSet},
     ops::Deref,
     sync::Arc,
+    task::Poll,
 };
 
-use futures_util::stream::{self, Stream, StreamExt};
+use futures_util::{
+    future,
+    stream::{self, Stream, StreamExt},
+    FutureExt,
+};
 use indexmap::map::IndexMap;
 
 use crate::{
+    auth::{self, AuthMode, AuthPolicy},
     context::{Data, QueryEnvInner},
     custom_directive::CustomDirectiveFactory,
     extensions::{ExtensionFactory, Extensions},
+    incremental,
     model::__DirectiveLocation,
     parser::{
-        parse_query,
-        types::{Directive, DocumentOperations, OperationType, Selection, SelectionSet},
+        self, parse_query,
+        types::{
+            Directive, DocumentOperations, OperationDefinition, OperationType, Selection,
+            SelectionSet,
+        },
         Positioned,
     },
-    registry::{MetaDirective, MetaInputValue, Registry, SDLExportOptions},
+    registry::{MetaDirective, MetaInputValue, Registry, SDLExportOptions, SchemaDiff},
     resolver_utils::{resolve_container, resolve_container_serial},
     subscription::collect_subscription_streams,
     types::QueryRoot,
     validation::{check_rules, ValidationMode},
     BatchRequest, BatchResponse, CacheControl, ContextBase, EmptyMutation, EmptySubscription,
     InputType, ObjectType, OutputType, QueryEnv, Request, Response, ServerError, SubscriptionType,
-    Variables, ID,
+    Value, Variables, ID,
 };
 
 /// Introspection mode
@@ -55,8 +65,12 @@ pub struct SchemaBuilder<Query, Mutation, Subscription> {
     data: Data,
     complexity: Option<usize>,
     depth: Option<usize>,
+    cost: Option<usize>,
     extensions: Vec<Box<dyn ExtensionFactory>>,
     custom_directives: HashMap<&'static str, Box<dyn CustomDirectiveFactory>>,
+    auth_policy: Option<Box<dyn AuthPolicy>>,
+    auth_mode: AuthMode,
+    hide_denied_fields: bool,
 }
 
 impl<Query, Mutation, Subscription> SchemaBuilder<Query, Mutation, Subscription> {
@@ -110,6 +124,40 @@ impl<Query, Mutation, Subscription> SchemaBuilder<Query, Mutation, Subscription>
         self
     }
 
+    /// Set the maximum cost a query can have, as computed from the `@cost` and
+    /// `@listSize` directives of the fields. By default, there is no limit.
+    #[must_use]
+    pub fn limit_cost(mut self, cost: usize) -> Self {
+        self.cost = Some(cost);
+        self
+    }
+
+    /// Set the policy checking the fields annotated with the `@auth`
+    /// directive, before an operation is executed.
+    ///
+    /// Reference: <https://docs.rs/async-graphql/latest/async_graphql/trait.AuthPolicy.html>
+    #[must_use]
+    pub fn auth_policy(mut self, policy: impl AuthPolicy) -> Self {
+        self.auth_policy = Some(Box::new(policy));
+        self
+    }
+
+    /// Set what happens to an operation selecting fields denied by the
+    /// authorization policy, the operation is rejected by default.
+    #[must_use]
+    pub fn auth_mode(mut self, mode: AuthMode) -> Self {
+        self.auth_mode = mode;
+        self
+    }
+
+    /// Hide the fields denied by the authorization policy from the
+    /// introspection.
+    #[must_use]
+    pub fn hide_denied_fields(mut self) -> Self {
+        self.hide_denied_fields = true;
+        self
+    }
+
     /// Add an extension to the schema.
     ///
     /// # Examples
@@ -219,11 +267,15 @@ impl<Query, Mutation, Subscription> SchemaBuilder<Query, Mutation, Subscription>
             subscription: self.subscription,
             complexity: self.complexity,
             depth: self.depth,
+            cost: self.cost,
             extensions: self.extensions,
             env: SchemaEnv(Arc::new(SchemaEnvInner {
                 registry: self.registry,
                 data: self.data,
                 custom_directives: self.custom_directives,
+                auth_policy: self.auth_policy,
+                auth_mode: self.auth_mode,
+                hide_denied_fields: self.hide_denied_fields,
             })),
         }))
     }
@@ -234,11 +286,14 @@ pub struct SchemaEnvInner {
     pub registry: Registry,
     pub data: Data,
     pub custom_directives: HashMap<&'static str, Box<dyn CustomDirectiveFactory>>,
+    pub auth_policy: Option<Box<dyn AuthPolicy>>,
+    pub auth_mode: AuthMode,
+    pub hide_denied_fields: bool,
 }
 
 #[doc(hidden)]
 #[derive(Clone)]
-pub struct SchemaEnv(Arc<SchemaEnvInner>);
+pub struct SchemaEnv(pub(crate) Arc<SchemaEnvInner>);
 
 impl Deref for SchemaEnv {
     type Target = SchemaEnvInner;
@@ -256,6 +311,7 @@ pub struct SchemaInner<Query, Mutation, Subscription> {
     pub(crate) subscription: Subscription,
     pub(crate) complexity: Option<usize>,
     pub(crate) depth: Option<usize>,
+    pub(crate) cost: Option<usize>,
     pub(crate) extensions: Vec<Box<dyn ExtensionFactory>>,
     pub(crate) env: SchemaEnv,
 }
@@ -339,8 +395,12 @@ where
             data: Default::default(),
             complexity: None,
             depth: None,
+            cost: None,
             extensions: Default::default(),
             custom_directives: Default::default(),
+            auth_policy: None,
+            auth_mode: Default::default(),
+            hide_denied_fields: false,
         }
     }
 
@@ -366,60 +426,7 @@ where
             ignore_name_conflicts,
         };
 
-        registry.add_directive(MetaDirective {
-            name: "include",
-            description: Some("Directs the executor to include this field or fragment only when the `if` argument is true."),
-            locations: vec![
-                __DirectiveLocation::FIELD,
-                __DirectiveLocation::FRAGMENT_SPREAD,
-                __DirectiveLocation::INLINE_FRAGMENT
-            ],
-            args: {
-                let mut args = IndexMap::new();
-                args.insert("if".to_string(), MetaInputValue {
-                    name: "if",
-                    description: Some("Included when true."),
-                    ty: "Boolean!".to_string(),
-                    default_value: None,
-                    visible: None,
-                    is_secret: false,
-                });
-                args
-            },
-            is_repeatable: false,
-            visible: None,
-        });
-
-        registry.add_directive(MetaDirective {
-            name: "skip",
-            description: Some("Directs the executor to skip this field or fragment when the `if` argument is true."),
-            locations: vec![
-                __DirectiveLocation::FIELD,
-                __DirectiveLocation::FRAGMENT_SPREAD,
-                __DirectiveLocation::INLINE_FRAGMENT
-            ],
-            args: {
-                let mut args = IndexMap::new();
-                args.insert("if".to_string(), MetaInputValue {
-                    name: "if",
-                    description: Some("Skipped when true."),
-                    ty: "Boolean!".to_string(),
-                    default_value: None,
-                    visible: None,
-                    is_secret: false,
-                });
-                args
-            },
-            is_repeatable: false,
-            visible: None,
-        });
-
-        // register scalars
-        <bool as InputType>::create_type_info(&mut registry);
-        <i32 as InputType>::create_type_info(&mut registry);
-        <f32 as InputType>::create_type_info(&mut registry);
-        <String as InputType>::create_type_info(&mut registry);
-        <ID as InputType>::create_type_info(&mut registry);
+        register_builtin_types(&mut registry);
 
         QueryRoot::<Query>::create_type_info(&mut registry);
         if !Mutation::is_empty() {
@@ -458,6 +465,12 @@ where
         self.0.env.registry.export_sdl(options)
     }
 
+    /// Compare a previous version of this schema written in SDL, such as the
+    /// schema deployed in production, with this schema.
+    pub fn diff_sdl(&self, old_sdl: &str) -> Result<SchemaDiff, parser::Error> {
+        self.0.env.registry.diff_sdl(old_sdl)
+    }
+
     /// Get all names in this schema
     ///
     /// Maybe you want to serialize a custom binary protocol. In order to
@@ -476,110 +489,6 @@ where
         )
     }
 
-    async fn prepare_request(
-        &self,
-        mut extensions: Extensions,
-        request: Request,
-        session_data: Arc<Data>,
-    ) -> Result<(QueryEnv, CacheControl), Vec<ServerError>> {
-        let mut request = request;
-        let query_data = Arc::new(std::mem::take(&mut request.data));
-        extensions.attach_query_data(query_data.clone());
-
-        let mut request = extensions.prepare_request(request).await?;
-        let mut document = {
-            let query = &request.query;
-            let parsed_doc = request.parsed_query.take();
-            let fut_parse = async move {
-                match parsed_doc {
-                    Some(parsed_doc) => Ok(parsed_doc),
-                    None => parse_query(query).map_err(Into::into),
-                }
-            };
-            futures_util::pin_mut!(fut_parse);
-            extensions
-                .parse_query(query, &request.variables, &mut fut_parse)
-                .await?
-        };
-
-        // check rules
-        let validation_result = {
-            let validation_fut = async {
-                check_rules(
-                    &self.env.registry,
-                    &document,
-                    Some(&request.variables),
-                    self.validation_mode,
-                )
-            };
-            futures_util::pin_mut!(validation_fut);
-            extensions.validation(&mut validation_fut).await?
-        };
-
-        // check limit
-        if let Some(limit_complexity) = self.complexity {
-            if validation_result.complexity > limit_complexity {
-                return Err(vec![ServerError::new("Query is too complex.", None)]);
-            }
-        }
-
-        if let Some(limit_depth) = self.depth {
-            if validation_result.depth > limit_depth {
-                return Err(vec![ServerError::new("Query is nested too deep.", None)]);
-            }
-        }
-
-        let operation = if let Some(operation_name) = &request.operation_name {
-            match document.operations {
-                DocumentOperations::Single(_) => None,
-                DocumentOperations::Multiple(mut operations) => operations
-                    .remove(operation_name.as_str())
-                    .map(|operation| (Some(operation_name.clone()), operation)),
-            }
-            .ok_or_else(|| {
-                ServerError::new(
-                    format!(r#"Unknown operation named "{}""#, operation_name),
-                    None,
-                )
-            })
-        } else {
-            match document.operations {
-                DocumentOperations::Single(operation) => Ok((None, operation)),
-                DocumentOperations::Multiple(map) if map.len() == 1 => {
-                    let (operation_name, operation) = map.into_iter().next().unwrap();
-                    Ok((Some(operation_name.to_string()), operation))
-                }
-                DocumentOperations::Multiple(_) => Err(ServerError::new(
-                    "Operation name required in request.",
-                    None,
-                )),
-            }
-        };
-
-        let (operation_name, mut operation) = operation.map_err(|err| vec![err])?;
-
-        // remove skipped fields
-        for fragment in document.fragments.values_mut() {
-            remove_skipped_selection(&mut fragment.node.selection_set.node, &request.variables);
-        }
-        remove_skipped_selection(&mut operation.node.selection_set.node, &request.variables);
-
-        let env = QueryEnvInner {
-            extensions,
-            variables: request.variables,
-            operation_name,
-            operation,
-            fragments: document.fragments,
-            uploads: request.uploads,
-            session_data,
-            ctx_data: query_data,
-            http_headers: Default::default(),
-            introspection_mode: request.introspection_mode,
-            errors: Default::default(),
-        };
-        Ok((QueryEnv::new(env), validation_result.cache_control))
-    }
-
     async fn execute_once(&self, env: QueryEnv) -> Response {
         // execute
         let ctx = ContextBase {
@@ -614,9 +523,100 @@ where
 
         resp.errors
             .extend(std::mem::take(&mut *env.errors.lock().unwrap()));
+        take_redacted(&env, &mut resp);
         resp
     }
 
+    /// Executes a query with incremental delivery, yielding the initial
+    /// payload and then the payloads of the deferred fragments and streamed
+    /// items as they are resolved.
+    fn execute_incremental(
+        &self,
+        env: QueryEnv,
+        cache_control: CacheControl,
+    ) -> impl Stream<Item = Response> + Send {
+        let schema = self.clone();
+        async_stream::stream! {
+            let ctx = ContextBase {
+                path_node: None,
+                item: &env.operation.node.selection_set,
+                schema_env: &schema.env,
+                query_env: &env,
+            };
+            let incremental = env
+                .incremental
+                .as_ref()
+                .expect("incremental delivery is enabled");
+            let mut root = resolve_container(&ctx, &schema.query).boxed();
+            let mut done = false;
+
+            let res = future::poll_fn(|cx| match root.poll_unpin(cx) {
+                Poll::Ready(res) => {
+                    done = true;
+                    incremental.take_published(&[]);
+                    Poll::Ready(res)
+                }
+                Poll::Pending => match incremental.take_published(&[]) {
+                    Some(value) => Poll::Ready(Ok(value)),
+                    None => Poll::Pending,
+                },
+            })
+            .await;
+
+            let mut resp = match res {
+                Ok(value) => Response::new(value),
+                Err(err) => Response::from_errors(vec![err]),
+            }
+            .cache_control(cache_control)
+            .http_headers(std::mem::take(&mut *env.http_headers.lock().unwrap()));
+            resp.errors
+                .extend(std::mem::take(&mut *env.errors.lock().unwrap()));
+            take_redacted(&env, &mut resp);
+            if resp.data == Value::Null || !incremental.has_next() {
+                yield resp;
+                return;
+            }
+            resp.has_next = Some(true);
+            yield resp;
+
+            loop {
+                let payloads = incremental.take_payloads();
+                if !payloads.is_empty() {
+                    let has_next = incremental.has_next();
+                    let mut resp = Response {
+                        incremental: payloads,
+                        has_next: Some(has_next),
+                        ..Default::default()
+                    };
+                    take_redacted(&env, &mut resp);
+                    yield resp;
+                    if !has_next {
+                        return;
+                    }
+                    continue;
+                }
+                if done {
+                    // The remaining deliveries were cancelled.
+                    yield Response {
+                        has_next: Some(false),
+                        ..Default::default()
+                    };
+                    return;
+                }
+
+                future::poll_fn(|cx| match root.poll_unpin(cx) {
+                    Poll::Ready(_) => {
+                        done = true;
+                        Poll::Ready(())
+                    }
+                    Poll::Pending if incremental.has_payloads() => Poll::Ready(()),
+                    Poll::Pending => Poll::Pending,
+                })
+                .await;
+            }
+        }
+    }
+
     /// Execute a GraphQL query.
     pub async fn execute(&self, request: impl Into<Request>) -> Response {
         let request = request.into();
@@ -624,11 +624,23 @@ where
         let request_fut = {
             let extensions = extensions.clone();
             async move {
-                match self
-                    .prepare_request(extensions, request, Default::default())
-                    .await
+                match prepare_request(
+                    extensions,
+                    request,
+                    Default::default(),
+                    &self.env.registry,
+                    self.validation_mode,
+                    self.complexity,
+                    self.depth,
+                    self.cost,
+                    false,
+                )
+                .await
                 {
                     Ok((env, cache_control)) => {
+                        if let Err(errors) = auth::authorize(&self.env, &env).await {
+                            return Response::from_request_errors(errors);
+                        }
                         let fut = async {
                             self.execute_once(env.clone())
                                 .await
@@ -639,7 +651,7 @@ where
                             .execute(env.operation_name.as_deref(), &mut fut)
                             .await
                     }
-                    Err(errors) => Response::from_errors(errors),
+                    Err(errors) => Response::from_request_errors(errors),
                 }
             }
         };
@@ -674,14 +686,36 @@ where
         let stream = futures_util::stream::StreamExt::boxed({
             let extensions = extensions.clone();
             async_stream::stream! {
-                let (env, cache_control) = match schema.prepare_request(extensions, request, session_data).await {
+                let (env, cache_control) = match prepare_request(
+                    extensions,
+                    request,
+                    session_data,
+                    &schema.env.registry,
+                    schema.validation_mode,
+                    schema.complexity,
+                    schema.depth,
+                    schema.cost,
+                    true,
+                ).await {
                     Ok(res) => res,
                     Err(errors) => {
-                        yield Response::from_errors(errors);
+                        yield Response::from_request_errors(errors);
                         return;
                     }
                 };
+                if let Err(errors) = auth::authorize(&schema.env, &env).await {
+                    yield Response::from_request_errors(errors);
+                    return;
+                }
 
+                if env.incremental.is_some() {
+                    let stream = schema.execute_incremental(env, cache_control);
+                    futures_util::pin_mut!(stream);
+                    while let Some(resp) = stream.next().await {
+                        yield resp;
+                    }
+                    return;
+                }
                 if env.operation.node.ty != OperationType::Subscription {
                     yield schema.execute_once(env).await.cache_control(cache_control);
                     return;
@@ -724,7 +758,301 @@ where
     }
 }
 
-fn remove_skipped_selection(selection_set: &mut SelectionSet, variables: &Variables) {
+/// Registers the directives and scalars every schema supports.
+pub(crate) fn register_builtin_types(registry: &mut Registry) {
+    registry.add_directive(MetaDirective {
+        name: "include",
+        description: Some("Directs the executor to include this field or fragment only when the `if` argument is true."),
+        locations: vec![
+            __DirectiveLocation::FIELD,
+            __DirectiveLocation::FRAGMENT_SPREAD,
+            __DirectiveLocation::INLINE_FRAGMENT
+        ],
+        args: {
+            let mut args = IndexMap::new();
+            args.insert("if".to_string(), MetaInputValue {
+                name: "if",
+                description: Some("Included when true."),
+                ty: "Boolean!".to_string(),
+                default_value: None,
+                visible: None,
+                inaccessible: false,
+                tags: Default::default(),
+                is_secret: false,
+            });
+            args
+        },
+        is_repeatable: false,
+        visible: None,
+    });
+
+    registry.add_directive(MetaDirective {
+        name: "skip",
+        description: Some(
+            "Directs the executor to skip this field or fragment when the `if` argument is true.",
+        ),
+        locations: vec![
+            __DirectiveLocation::FIELD,
+            __DirectiveLocation::FRAGMENT_SPREAD,
+            __DirectiveLocation::INLINE_FRAGMENT,
+        ],
+        args: {
+            let mut args = IndexMap::new();
+            args.insert(
+                "if".to_string(),
+                MetaInputValue {
+                    name: "if",
+                    description: Some("Skipped when true."),
+                    ty: "Boolean!".to_string(),
+                    default_value: None,
+                    visible: None,
+                    inaccessible: false,
+                    tags: Default::default(),
+                    is_secret: false,
+                },
+            );
+            args
+        },
+        is_repeatable: false,
+        visible: None,
+    });
+
+    registry.add_directive(MetaDirective {
+        name: "defer",
+        description: Some("Directs the executor to deliver this fragment in a subsequent payload when using incremental delivery."),
+        locations: vec![
+            __DirectiveLocation::FRAGMENT_SPREAD,
+            __DirectiveLocation::INLINE_FRAGMENT
+        ],
+        args: {
+            let mut args = IndexMap::new();
+            args.insert("if".to_string(), MetaInputValue {
+                name: "if",
+                description: Some("Deferred when true."),
+                ty: "Boolean!".to_string(),
+                default_value: Some("true".to_string()),
+                visible: None,
+                inaccessible: false,
+                tags: Default::default(),
+                is_secret: false,
+            });
+            args.insert("label".to_string(), MetaInputValue {
+                name: "label",
+                description: Some("Identifies the subsequent payload of this fragment."),
+                ty: "String".to_string(),
+                default_value: None,
+                visible: None,
+                inaccessible: false,
+                tags: Default::default(),
+                is_secret: false,
+            });
+            args
+        },
+        is_repeatable: false,
+        visible: None,
+    });
+
+    registry.add_directive(MetaDirective {
+        name: "stream",
+        description: Some("Directs the executor to deliver the items of this list field after the first `initialCount` ones in subsequent payloads when using incremental delivery."),
+        locations: vec![
+            __DirectiveLocation::FIELD
+        ],
+        args: {
+            let mut args = IndexMap::new();
+            args.insert("if".to_string(), MetaInputValue {
+                name: "if",
+                description: Some("Streamed when true."),
+                ty: "Boolean!".to_string(),
+                default_value: Some("true".to_string()),
+                visible: None,
+                inaccessible: false,
+                tags: Default::default(),
+                is_secret: false,
+            });
+            args.insert("label".to_string(), MetaInputValue {
+                name: "label",
+                description: Some("Identifies the subsequent payloads of this field."),
+                ty: "String".to_string(),
+                default_value: None,
+                visible: None,
+                inaccessible: false,
+                tags: Default::default(),
+                is_secret: false,
+            });
+            args.insert("initialCount".to_string(), MetaInputValue {
+                name: "initialCount",
+                description: Some("The number of items delivered in the initial payload."),
+                ty: "Int!".to_string(),
+                default_value: Some("0".to_string()),
+                visible: None,
+                inaccessible: false,
+                tags: Default::default(),
+                is_secret: false,
+            });
+            args
+        },
+        is_repeatable: false,
+        visible: None,
+    });
+
+    // register scalars
+    <bool as InputType>::create_type_info(registry);
+    <i32 as InputType>::create_type_info(registry);
+    <f32 as InputType>::create_type_info(registry);
+    <String as InputType>::create_type_info(registry);
+    <ID as InputType>::create_type_info(registry);
+}
+
+/// Lists the paths of the fields redacted so far in the `redacted` extension
+/// of the response.
+fn take_redacted(env: &QueryEnv, resp: &mut Response) {
+    let redacted = std::mem::take(&mut *env.redacted.lock().unwrap());
+    if !redacted.is_empty() {
+        resp.extensions
+            .insert("redacted".to_string(), Value::List(redacted));
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn prepare_request(
+    mut extensions: Extensions,
+    request: Request,
+    session_data: Arc<Data>,
+    registry: &Registry,
+    validation_mode: ValidationMode,
+    complexity: Option<usize>,
+    depth: Option<usize>,
+    cost: Option<usize>,
+    incremental: bool,
+) -> Result<(QueryEnv, CacheControl), Vec<ServerError>> {
+    let mut request = request;
+    let query_data = Arc::new(std::mem::take(&mut request.data));
+    extensions.attach_query_data(query_data.clone());
+
+    let mut request = extensions.prepare_request(request).await?;
+    let mut document = {
+        let query = &request.query;
+        let parsed_doc = request.parsed_query.take();
+        let fut_parse = async move {
+            match parsed_doc {
+                Some(parsed_doc) => Ok(parsed_doc),
+                None => parse_query(query).map_err(Into::into),
+            }
+        };
+        futures_util::pin_mut!(fut_parse);
+        extensions
+            .parse_query(query, &request.variables, &mut fut_parse)
+            .await?
+    };
+
+    // check rules
+    let validation_result = {
+        let validation_fut = async {
+            check_rules(
+                registry,
+                &document,
+                Some(&request.variables),
+                validation_mode,
+            )
+        };
+        futures_util::pin_mut!(validation_fut);
+        extensions.validation(&mut validation_fut).await?
+    };
+
+    // check limit
+    if let Some(limit_complexity) = complexity {
+        if validation_result.complexity > limit_complexity {
+            return Err(vec![ServerError::new("Query is too complex.", None)]);
+        }
+    }
+
+    if let Some(limit_depth) = depth {
+        if validation_result.depth > limit_depth {
+            return Err(vec![ServerError::new("Query is nested too deep.", None)]);
+        }
+    }
+
+    if let Some(limit_cost) = cost {
+        if validation_result.cost > limit_cost {
+            return Err(vec![ServerError::new("Query is too expensive.", None)]);
+        }
+    }
+
+    let operation = take_operation(document.operations, request.operation_name.as_deref());
+
+    let (operation_name, mut operation) = operation.map_err(|err| vec![err])?;
+
+    // remove skipped fields
+    for fragment in document.fragments.values_mut() {
+        remove_skipped_selection(&mut fragment.node.selection_set.node, &request.variables);
+    }
+    remove_skipped_selection(&mut operation.node.selection_set.node, &request.variables);
+
+    let incremental = incremental
+        && operation.node.ty == OperationType::Query
+        && incremental::uses_incremental_delivery(
+            &operation.node.selection_set.node,
+            &document.fragments,
+            &mut HashSet::new(),
+        );
+    let env = QueryEnvInner {
+        extensions,
+        variables: request.variables,
+        operation_name,
+        operation,
+        fragments: document.fragments,
+        uploads: request.uploads,
+        session_data,
+        ctx_data: query_data,
+        http_headers: Default::default(),
+        introspection_mode: request.introspection_mode,
+        errors: Default::default(),
+        auth_denied: Default::default(),
+        redacted: Default::default(),
+        incremental: if incremental {
+            Some(Default::default())
+        } else {
+            None
+        },
+    };
+    Ok((QueryEnv::new(env), validation_result.cache_control))
+}
+
+/// Takes the operation to execute from the operations of a document.
+pub(crate) fn take_operation(
+    operations: DocumentOperations,
+    operation_name: Option<&str>,
+) -> Result<(Option<String>, Positioned<OperationDefinition>), ServerError> {
+    if let Some(operation_name) = operation_name {
+        match operations {
+            DocumentOperations::Single(_) => None,
+            DocumentOperations::Multiple(mut operations) => operations
+                .remove(operation_name)
+                .map(|operation| (Some(operation_name.to_string()), operation)),
+        }
+        .ok_or_else(|| {
+            ServerError::new(
+                format!(r#"Unknown operation named "{}""#, operation_name),
+                None,
+            )
+        })
+    } else {
+        match operations {
+            DocumentOperations::Single(operation) => Ok((None, operation)),
+            DocumentOperations::Multiple(map) if map.len() == 1 => {
+                let (operation_name, operation) = map.into_iter().next().unwrap();
+                Ok((Some(operation_name.to_string()), operation))
+            }
+            DocumentOperations::Multiple(_) => Err(ServerError::new(
+                "Operation name required in request.",
+                None,
+            )),
+        }
+    }
+}
+
+pub(crate) fn remove_skipped_selection(selection_set: &mut SelectionSet, variables: &Variables) {
     fn is_skipped(directives: &[Positioned<Directive>], variables: &Variables) -> bool {
         for directive in directives {
             let include = match &*directive.node.name.node {
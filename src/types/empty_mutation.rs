@@ -56,6 +56,9 @@ impl OutputType for EmptyMutation {
             extends: false,
             keys: None,
             visible: None,
+            shareable: false,
+            inaccessible: false,
+            tags: Default::default(),
             is_subscription: false,
             rust_typename: std::any::type_name::<Self>(),
         })
@@ -25,6 +25,9 @@ impl SubscriptionType for EmptySubscription {
             extends: false,
             keys: None,
             visible: None,
+            shareable: false,
+            inaccessible: false,
+            tags: Default::default(),
             is_subscription: true,
             rust_typename: std::any::type_name::<Self>(),
         })
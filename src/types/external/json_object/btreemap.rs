@@ -29,6 +29,8 @@ where
             description: Some("A scalar that can represent any JSON Object value."),
             is_valid: |_| true,
             visible: None,
+            inaccessible: false,
+            tags: Default::default(),
             specified_by_url: None,
         })
     }
@@ -84,6 +86,8 @@ where
             description: Some("A scalar that can represent any JSON Object value."),
             is_valid: |_| true,
             visible: None,
+            inaccessible: false,
+            tags: Default::default(),
             specified_by_url: None,
         })
     }
@@ -54,6 +54,8 @@ impl<T: DeserializeOwned + Serialize + Send + Sync> InputType for Json<T> {
             description: Some("A scalar that can represent any JSON value."),
             is_valid: |_| true,
             visible: None,
+            inaccessible: false,
+            tags: Default::default(),
             specified_by_url: None,
         })
     }
@@ -83,6 +85,8 @@ impl<T: Serialize + Send + Sync> OutputType for Json<T> {
             description: Some("A scalar that can represent any JSON value."),
             is_valid: |_| true,
             visible: None,
+            inaccessible: false,
+            tags: Default::default(),
             specified_by_url: None,
         })
     }
@@ -110,6 +114,8 @@ impl InputType for serde_json::Value {
                 description: Some("A scalar that can represent any JSON value."),
                 is_valid: |_| true,
                 visible: None,
+                inaccessible: false,
+                tags: Default::default(),
                 specified_by_url: None,
             }
         })
@@ -141,6 +147,8 @@ impl OutputType for serde_json::Value {
                 description: Some("A scalar that can represent any JSON value."),
                 is_valid: |_| true,
                 visible: None,
+                inaccessible: false,
+                tags: Default::default(),
                 specified_by_url: None,
             }
         })
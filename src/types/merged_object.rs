@@ -79,6 +79,9 @@ where
                 extends: false,
                 keys: None,
                 visible: None,
+                shareable: false,
+                inaccessible: false,
+                tags: Default::default(),
                 is_subscription: false,
                 rust_typename: std::any::type_name::<Self>(),
             }
@@ -137,6 +140,9 @@ where
                 extends: false,
                 keys: None,
                 visible: None,
+                shareable: false,
+                inaccessible: false,
+                tags: Default::default(),
                 is_subscription: false,
                 rust_typename: std::any::type_name::<Self>(),
             }
@@ -170,6 +176,9 @@ impl SubscriptionType for MergedObjectTail {
             extends: false,
             keys: None,
             visible: None,
+            shareable: false,
+            inaccessible: false,
+            tags: Default::default(),
             is_subscription: false,
             rust_typename: std::any::type_name::<Self>(),
         })
@@ -23,7 +23,7 @@ pub use id::ID;
 pub use json::Json;
 pub use maybe_undefined::MaybeUndefined;
 pub use merged_object::{MergedObject, MergedObjectTail};
-pub(crate) use query_root::QueryRoot;
+pub(crate) use query_root::{register_introspection_fields, resolve_introspection_field, QueryRoot};
 #[cfg(feature = "string_number")]
 pub use string_number::StringNumber;
 pub use upload::{Upload, UploadValue};
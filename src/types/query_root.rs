@@ -26,40 +26,8 @@ pub(crate) struct QueryRoot<T> {
 #[async_trait::async_trait]
 impl<T: ObjectType> ContainerType for QueryRoot<T> {
     async fn resolve_field(&self, ctx: &Context<'_>) -> ServerResult<Option<Value>> {
-        if matches!(
-            ctx.schema_env.registry.introspection_mode,
-            IntrospectionMode::Enabled | IntrospectionMode::IntrospectionOnly
-        ) && matches!(
-            ctx.query_env.introspection_mode,
-            IntrospectionMode::Enabled | IntrospectionMode::IntrospectionOnly,
-        ) {
-            if ctx.item.node.name.node == "__schema" {
-                let ctx_obj = ctx.with_selection_set(&ctx.item.node.selection_set);
-                let visible_types = ctx.schema_env.registry.find_visible_types(ctx);
-                return OutputType::resolve(
-                    &__Schema::new(&ctx.schema_env.registry, &visible_types),
-                    &ctx_obj,
-                    ctx.item,
-                )
-                .await
-                .map(Some);
-            } else if ctx.item.node.name.node == "__type" {
-                let (_, type_name) = ctx.param_value::<String>("name", None)?;
-                let ctx_obj = ctx.with_selection_set(&ctx.item.node.selection_set);
-                let visible_types = ctx.schema_env.registry.find_visible_types(ctx);
-                return OutputType::resolve(
-                    &ctx.schema_env
-                        .registry
-                        .types
-                        .get(&type_name)
-                        .filter(|_| visible_types.contains(type_name.as_str()))
-                        .map(|ty| __Type::new_simple(&ctx.schema_env.registry, &visible_types, ty)),
-                    &ctx_obj,
-                    ctx.item,
-                )
-                .await
-                .map(Some);
-            }
+        if let Some(value) = resolve_introspection_field(ctx).await? {
+            return Ok(Some(value));
         }
 
         if ctx.schema_env.registry.introspection_mode == IntrospectionMode::IntrospectionOnly
@@ -111,64 +79,7 @@ impl<T: ObjectType> OutputType for QueryRoot<T> {
     fn create_type_info(registry: &mut registry::Registry) -> String {
         let root = T::create_type_info(registry);
 
-        if matches!(
-            registry.introspection_mode,
-            IntrospectionMode::Enabled | IntrospectionMode::IntrospectionOnly
-        ) {
-            let schema_type = __Schema::create_type_info(registry);
-            if let Some(registry::MetaType::Object { fields, .. }) =
-                registry.types.get_mut(T::type_name().as_ref())
-            {
-                fields.insert(
-                    "__schema".to_string(),
-                    registry::MetaField {
-                        name: "__schema".to_string(),
-                        description: Some("Access the current type schema of this server."),
-                        args: Default::default(),
-                        ty: schema_type,
-                        deprecation: Default::default(),
-                        cache_control: Default::default(),
-                        external: false,
-                        requires: None,
-                        provides: None,
-                        visible: None,
-                        compute_complexity: None,
-                    },
-                );
-
-                fields.insert(
-                    "__type".to_string(),
-                    registry::MetaField {
-                        name: "__type".to_string(),
-                        description: Some("Request the type information of a single type."),
-                        args: {
-                            let mut args = IndexMap::new();
-                            args.insert(
-                                "name".to_string(),
-                                registry::MetaInputValue {
-                                    name: "name",
-                                    description: None,
-                                    ty: "String!".to_string(),
-                                    default_value: None,
-                                    visible: None,
-                                    is_secret: false,
-                                },
-                            );
-                            args
-                        },
-                        ty: "__Type".to_string(),
-                        deprecation: Default::default(),
-                        cache_control: Default::default(),
-                        external: false,
-                        requires: None,
-                        provides: None,
-                        visible: None,
-                        compute_complexity: None,
-                    },
-                );
-            }
-        }
-
+        register_introspection_fields(registry, &T::type_name());
         root
     }
 
@@ -182,3 +93,124 @@ impl<T: ObjectType> OutputType for QueryRoot<T> {
 }
 
 impl<T: ObjectType> ObjectType for QueryRoot<T> {}
+
+/// Resolves the `__schema` and `__type` fields of the query root, returns
+/// `None` for any other field or if introspection is disabled.
+pub(crate) async fn resolve_introspection_field(ctx: &Context<'_>) -> ServerResult<Option<Value>> {
+    if matches!(
+        ctx.schema_env.registry.introspection_mode,
+        IntrospectionMode::Enabled | IntrospectionMode::IntrospectionOnly
+    ) && matches!(
+        ctx.query_env.introspection_mode,
+        IntrospectionMode::Enabled | IntrospectionMode::IntrospectionOnly,
+    ) {
+        if ctx.item.node.name.node == "__schema" {
+            let ctx_obj = ctx.with_selection_set(&ctx.item.node.selection_set);
+            let visible_types = ctx.schema_env.registry.find_visible_types(ctx);
+            return OutputType::resolve(
+                &__Schema::new(&ctx.schema_env.registry, &visible_types),
+                &ctx_obj,
+                ctx.item,
+            )
+            .await
+            .map(Some);
+        } else if ctx.item.node.name.node == "__type" {
+            let (_, type_name) = ctx.param_value::<String>("name", None)?;
+            let ctx_obj = ctx.with_selection_set(&ctx.item.node.selection_set);
+            let visible_types = ctx.schema_env.registry.find_visible_types(ctx);
+            return OutputType::resolve(
+                &ctx.schema_env
+                    .registry
+                    .types
+                    .get(&type_name)
+                    .filter(|_| visible_types.contains(type_name.as_str()))
+                    .map(|ty| __Type::new_simple(&ctx.schema_env.registry, &visible_types, ty)),
+                &ctx_obj,
+                ctx.item,
+            )
+            .await
+            .map(Some);
+        }
+    }
+
+    Ok(None)
+}
+
+/// Adds the `__schema` and `__type` fields to the query root type if
+/// introspection is enabled.
+pub(crate) fn register_introspection_fields(registry: &mut registry::Registry, query_type: &str) {
+    if matches!(
+        registry.introspection_mode,
+        IntrospectionMode::Enabled | IntrospectionMode::IntrospectionOnly
+    ) {
+        let schema_type = __Schema::create_type_info(registry);
+        if let Some(registry::MetaType::Object { fields, .. }) = registry.types.get_mut(query_type)
+        {
+            fields.insert(
+                "__schema".to_string(),
+                registry::MetaField {
+                    name: "__schema".to_string(),
+                    description: Some("Access the current type schema of this server."),
+                    args: Default::default(),
+                    ty: schema_type,
+                    deprecation: Default::default(),
+                    cache_control: Default::default(),
+                    external: false,
+                    requires: None,
+                    provides: None,
+                    visible: None,
+                    shareable: false,
+                    inaccessible: false,
+                    tags: Default::default(),
+                    override_from: None,
+                    cost: None,
+                    list_size: None,
+                    auth: None,
+                    redact: None,
+                    compute_complexity: None,
+                },
+            );
+
+            fields.insert(
+                "__type".to_string(),
+                registry::MetaField {
+                    name: "__type".to_string(),
+                    description: Some("Request the type information of a single type."),
+                    args: {
+                        let mut args = IndexMap::new();
+                        args.insert(
+                            "name".to_string(),
+                            registry::MetaInputValue {
+                                name: "name",
+                                description: None,
+                                ty: "String!".to_string(),
+                                default_value: None,
+                                visible: None,
+                                inaccessible: false,
+                                tags: Default::default(),
+                                is_secret: false,
+                            },
+                        );
+                        args
+                    },
+                    ty: "__Type".to_string(),
+                    deprecation: Default::default(),
+                    cache_control: Default::default(),
+                    external: false,
+                    requires: None,
+                    provides: None,
+                    visible: None,
+                    shareable: false,
+                    inaccessible: false,
+                    tags: Default::default(),
+                    override_from: None,
+                    cost: None,
+                    list_size: None,
+                    auth: None,
+                    redact: None,
+                    compute_complexity: None,
+                },
+            );
+        }
+    }
+}
@@ -26,6 +26,9 @@ pub struct ValidationResult {
 
     /// Query depth
     pub depth: usize,
+
+    /// Query cost
+    pub cost: usize,
 }
 
 /// Validation mode
@@ -49,6 +52,7 @@ pub fn check_rules(
     let mut cache_control = CacheControl::default();
     let mut complexity = 0;
     let mut depth = 0;
+    let mut cost = 0;
 
     match mode {
         ValidationMode::Strict => {
@@ -79,7 +83,8 @@ pub fn check_rules(
                     cache_control: &mut cache_control,
                 })
                 .with(visitors::ComplexityCalculate::new(&mut complexity))
-                .with(visitors::DepthCalculate::new(&mut depth));
+                .with(visitors::DepthCalculate::new(&mut depth))
+                .with(visitors::CostCalculate::new(&mut cost));
             visit(&mut visitor, &mut ctx, doc);
         }
         ValidationMode::Fast => {
@@ -90,7 +95,8 @@ pub fn check_rules(
                     cache_control: &mut cache_control,
                 })
                 .with(visitors::ComplexityCalculate::new(&mut complexity))
-                .with(visitors::DepthCalculate::new(&mut depth));
+                .with(visitors::DepthCalculate::new(&mut depth))
+                .with(visitors::CostCalculate::new(&mut cost));
             visit(&mut visitor, &mut ctx, doc);
         }
     }
@@ -103,5 +109,6 @@ pub fn check_rules(
         cache_control,
         complexity,
         depth,
+        cost,
     })
 }
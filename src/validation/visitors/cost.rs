@@ -0,0 +1,238 @@
+use async_graphql_parser::types::{ExecutableDocument, OperationDefinition, VariableDefinition};
+use async_graphql_value::Name;
+
+use crate::{
+    parser::types::Field,
+    registry::{MetaField, MetaListSize, MetaType, MetaTypeName},
+    validation::visitor::{VisitMode, Visitor, VisitorContext},
+    Positioned,
+};
+
+struct FieldCost<'a> {
+    cost: usize,
+    sized_fields: &'a [String],
+    size: usize,
+}
+
+/// Computes the cost of a query from the `@cost` and `@listSize` directives
+/// of the fields.
+///
+/// Fields returning a composite type weigh `1` and the other fields `0`,
+/// unless they have a `@cost` directive. The cost of a list is the cost of its
+/// items multiplied by the size of the list, read from the slicing arguments
+/// or the assumed size of the `@listSize` directive.
+pub struct CostCalculate<'ctx, 'a> {
+    pub cost: &'a mut usize,
+    cost_stack: Vec<FieldCost<'ctx>>,
+    variable_definition: Option<&'ctx [Positioned<VariableDefinition>]>,
+}
+
+impl<'ctx, 'a> CostCalculate<'ctx, 'a> {
+    pub fn new(cost: &'a mut usize) -> Self {
+        Self {
+            cost,
+            cost_stack: Default::default(),
+            variable_definition: None,
+        }
+    }
+
+    fn list_size(
+        &self,
+        ctx: &VisitorContext<'ctx>,
+        field: &Field,
+        list_size: &MetaListSize,
+    ) -> Option<usize> {
+        let variable_definition = self.variable_definition.unwrap_or_default();
+        list_size
+            .slicing_arguments
+            .iter()
+            .filter_map(|name| {
+                ctx.param_value::<Option<usize>>(variable_definition, field, name, None)
+                    .ok()
+                    .flatten()
+            })
+            .max()
+            .or(list_size.assumed_size)
+    }
+}
+
+fn meta_field<'ctx>(ctx: &VisitorContext<'ctx>, field: &Field) -> Option<&'ctx MetaField> {
+    ctx.parent_type()
+        .and_then(|ty| ty.field_by_name(field.name.node.as_str()))
+}
+
+impl<'ctx, 'a> Visitor<'ctx> for CostCalculate<'ctx, 'a> {
+    fn mode(&self) -> VisitMode {
+        VisitMode::Inline
+    }
+
+    fn enter_document(&mut self, _ctx: &mut VisitorContext<'ctx>, _doc: &'ctx ExecutableDocument) {
+        self.cost_stack.push(FieldCost {
+            cost: 0,
+            sized_fields: &[],
+            size: 1,
+        });
+    }
+
+    fn exit_document(&mut self, _ctx: &mut VisitorContext<'ctx>, _doc: &'ctx ExecutableDocument) {
+        *self.cost = self.cost_stack.pop().unwrap().cost;
+    }
+
+    fn enter_operation_definition(
+        &mut self,
+        _ctx: &mut VisitorContext<'ctx>,
+        _name: Option<&'ctx Name>,
+        operation_definition: &'ctx Positioned<OperationDefinition>,
+    ) {
+        self.variable_definition = Some(&operation_definition.node.variable_definitions);
+    }
+
+    fn enter_field(&mut self, ctx: &mut VisitorContext<'ctx>, field: &'ctx Positioned<Field>) {
+        let list_size = meta_field(ctx, &field.node).and_then(|field| field.list_size.as_ref());
+        let size = list_size
+            .and_then(|list_size| self.list_size(ctx, &field.node, list_size))
+            .unwrap_or(1);
+        self.cost_stack.push(FieldCost {
+            cost: 0,
+            sized_fields: list_size
+                .map(|list_size| list_size.sized_fields.as_slice())
+                .unwrap_or_default(),
+            size,
+        });
+    }
+
+    fn exit_field(&mut self, ctx: &mut VisitorContext<'ctx>, field: &'ctx Positioned<Field>) {
+        let FieldCost {
+            cost: children_cost,
+            sized_fields,
+            size,
+        } = self.cost_stack.pop().unwrap();
+
+        let mut cost = match meta_field(ctx, &field.node) {
+            Some(meta_field) => {
+                let weight = meta_field.cost.unwrap_or_else(|| {
+                    let is_composite = ctx
+                        .registry
+                        .types
+                        .get(MetaTypeName::concrete_typename(&meta_field.ty))
+                        .map(MetaType::is_composite)
+                        .unwrap_or_default();
+                    usize::from(is_composite)
+                });
+                let cost = weight.saturating_add(children_cost);
+                if sized_fields.is_empty() {
+                    cost.saturating_mul(size)
+                } else {
+                    cost
+                }
+            }
+            None => children_cost,
+        };
+
+        let parent = self.cost_stack.last_mut().unwrap();
+        if parent
+            .sized_fields
+            .iter()
+            .any(|name| name == field.node.name.node.as_str())
+        {
+            cost = cost.saturating_mul(parent.size);
+        }
+        parent.cost = parent.cost.saturating_add(cost);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        parser::parse_query,
+        validation::{visit, VisitorContext},
+        EmptyMutation, EmptySubscription, Object, Schema, SimpleObject, Variables,
+    };
+
+    #[derive(SimpleObject)]
+    #[graphql(internal)]
+    struct User {
+        id: i32,
+        #[graphql(cost = 5)]
+        score: i32,
+    }
+
+    #[derive(SimpleObject)]
+    #[graphql(internal)]
+    struct UserConnection {
+        edges: Vec<User>,
+        total: i32,
+    }
+
+    struct Query;
+
+    #[Object(internal)]
+    #[allow(unreachable_code)]
+    impl Query {
+        async fn value(&self) -> i32 {
+            todo!()
+        }
+
+        async fn user(&self) -> User {
+            todo!()
+        }
+
+        #[graphql(cost = 10)]
+        async fn expensive(&self) -> i32 {
+            todo!()
+        }
+
+        #[graphql(list_size(assumed_size = 10, slicing_argument = "first"))]
+        #[allow(unused_variables)]
+        async fn users(&self, first: Option<i32>) -> Vec<User> {
+            todo!()
+        }
+
+        #[graphql(list_size(slicing_argument = "first", sized_field = "edges"))]
+        #[allow(unused_variables)]
+        async fn connection(&self, first: i32) -> UserConnection {
+            todo!()
+        }
+    }
+
+    fn check_cost(query: &str, variables: Option<Variables>, expect_cost: usize) {
+        let registry =
+            Schema::<Query, EmptyMutation, EmptySubscription>::create_registry(Default::default());
+        let doc = parse_query(query).unwrap();
+        let mut ctx = VisitorContext::new(&registry, &doc, variables.as_ref());
+        let mut cost = 0;
+        let mut cost_calculate = CostCalculate::new(&mut cost);
+        visit(&mut cost_calculate, &mut ctx, &doc);
+        assert_eq!(cost, expect_cost);
+    }
+
+    #[test]
+    fn cost() {
+        check_cost("{ value }", None, 0);
+        check_cost("{ user { id } }", None, 1);
+        check_cost("{ user { id score } }", None, 6);
+        check_cost("{ expensive }", None, 10);
+        check_cost("{ users { id } }", None, 10);
+        check_cost("{ users { score } }", None, 60);
+        check_cost("{ users(first: 3) { score } }", None, 18);
+        check_cost(
+            "query($first: Int) { users(first: $first) { score } }",
+            Some(Variables::from_json(serde_json::json!({"first": 2}))),
+            12,
+        );
+        check_cost(
+            "{ connection(first: 4) { total edges { score } } }",
+            None,
+            25,
+        );
+        check_cost(
+            r#"
+            { user { ...UserFields } }
+            fragment UserFields on User { id score }
+            "#,
+            None,
+            6,
+        );
+    }
+}
@@ -1,7 +1,9 @@
 mod cache_control;
 mod complexity;
+mod cost;
 mod depth;
 
 pub use cache_control::CacheControlCalculate;
 pub use complexity::ComplexityCalculate;
+pub use cost::CostCalculate;
 pub use depth::DepthCalculate;
@@ -0,0 +1,274 @@
+use async_graphql::*;
+
+struct Roles(Vec<&'static str>);
+
+#[derive(SimpleObject)]
+struct User {
+    id: i32,
+    name: String,
+    #[graphql(auth(requires = "admin"))]
+    email: Option<String>,
+}
+
+struct Query;
+
+#[Object]
+impl Query {
+    async fn users(&self) -> Vec<User> {
+        (1..=2)
+            .map(|id| User {
+                id,
+                name: format!("User {}", id),
+                email: Some(format!("user{}@example.com", id)),
+            })
+            .collect()
+    }
+
+    async fn me(&self) -> User {
+        User {
+            id: 1,
+            name: "User 1".to_string(),
+            email: Some("user1@example.com".to_string()),
+        }
+    }
+
+    #[graphql(auth(requires = "owner", requires = "admin"))]
+    async fn secret(&self, id: i32) -> String {
+        format!("Secret {}", id)
+    }
+}
+
+struct Policy;
+
+#[async_trait::async_trait]
+impl AuthPolicy for Policy {
+    async fn check(&self, ctx: &Context<'_>, request: &AuthRequest<'_>) -> Result<()> {
+        let roles = ctx.data::<Roles>()?;
+        let allowed = request.requires.iter().any(|role| match role.as_str() {
+            // The owner of a secret can read it.
+            "owner" => request.arguments.get("id") == Some(&value!(1)),
+            role => roles.0.contains(&role),
+        });
+        match allowed {
+            true => Ok(()),
+            false => Err(format!(
+                "Cannot query \"{}.{}\".",
+                request.info.parent_type, request.info.name
+            )
+            .into()),
+        }
+    }
+}
+
+fn schema(mode: AuthMode) -> Schema<Query, EmptyMutation, EmptySubscription> {
+    Schema::build(Query, EmptyMutation, EmptySubscription)
+        .auth_policy(Policy)
+        .auth_mode(mode)
+        .hide_denied_fields()
+        .finish()
+}
+
+#[tokio::test]
+pub async fn test_auth_sdl() {
+    let sdl = schema(AuthMode::Reject).sdl();
+    assert!(sdl.contains("directive @auth(requires: [String!]!) on FIELD_DEFINITION"));
+    assert!(sdl.contains(r#"email: String @auth(requires: ["admin"])"#));
+    assert!(sdl.contains(r#"secret(id: Int!): String! @auth(requires: ["owner", "admin"])"#));
+}
+
+#[tokio::test]
+pub async fn test_auth_reject() {
+    let schema = schema(AuthMode::Reject);
+
+    let request = Request::new("{ users { name email } }").data(Roles(vec!["admin"]));
+    assert_eq!(
+        schema.execute(request).await.into_result().unwrap().data,
+        value!({
+            "users": [
+                { "name": "User 1", "email": "user1@example.com" },
+                { "name": "User 2", "email": "user2@example.com" },
+            ]
+        })
+    );
+
+    let request = Request::new("{ users { name email } secret(id: 2) }").data(Roles(vec![]));
+    let response = schema.execute(request).await;
+    assert_eq!(response.data, Value::Null);
+    assert_eq!(
+        response.errors,
+        vec![
+            ServerError {
+                message: r#"Cannot query "User.email"."#.to_string(),
+                source: None,
+                locations: vec![Pos {
+                    line: 1,
+                    column: 16
+                }],
+                path: vec![
+                    PathSegment::Field("users".to_string()),
+                    PathSegment::Field("email".to_string()),
+                ],
+                extensions: None,
+            },
+            ServerError {
+                message: r#"Cannot query "Query.secret"."#.to_string(),
+                source: None,
+                locations: vec![Pos {
+                    line: 1,
+                    column: 24
+                }],
+                path: vec![PathSegment::Field("secret".to_string())],
+                extensions: None,
+            },
+        ]
+    );
+
+    let request = Request::new("query($id: Int!) { secret(id: $id) }")
+        .variables(Variables::from_value(value!({ "id": 1 })))
+        .data(Roles(vec![]));
+    assert_eq!(
+        schema.execute(request).await.into_result().unwrap().data,
+        value!({ "secret": "Secret 1" })
+    );
+}
+
+#[tokio::test]
+pub async fn test_auth_argument_error() {
+    // The undefined variables are only detected during the execution with the
+    // fast validation, the field is denied instead of being checked without
+    // its arguments.
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .auth_policy(|_: &Context<'_>, _: &AuthRequest<'_>| -> Result<()> {
+            panic!("the policy must not be called")
+        })
+        .validation_mode(ValidationMode::Fast)
+        .finish();
+
+    let response = schema.execute("{ secret(id: $id) }").await;
+    assert_eq!(response.data, Value::Null);
+    assert_eq!(
+        response.errors,
+        vec![ServerError {
+            message: "Variable id is not defined.".to_string(),
+            source: None,
+            locations: vec![Pos {
+                line: 1,
+                column: 14
+            }],
+            path: vec![PathSegment::Field("secret".to_string())],
+            extensions: None,
+        }]
+    );
+}
+
+#[tokio::test]
+pub async fn test_auth_nullify() {
+    let schema = schema(AuthMode::Nullify);
+
+    let request = Request::new("{ users { name email } }").data(Roles(vec![]));
+    let response = schema.execute(request).await;
+    assert_eq!(
+        response.data,
+        value!({
+            "users": [
+                { "name": "User 1", "email": null },
+                { "name": "User 2", "email": null },
+            ]
+        })
+    );
+    assert_eq!(
+        response
+            .errors
+            .into_iter()
+            .map(|err| (err.message, err.path))
+            .collect::<Vec<_>>(),
+        (0..2)
+            .map(|idx| (
+                r#"Cannot query "User.email"."#.to_string(),
+                vec![
+                    PathSegment::Field("users".to_string()),
+                    PathSegment::Index(idx),
+                    PathSegment::Field("email".to_string()),
+                ]
+            ))
+            .collect::<Vec<_>>()
+    );
+
+    // A denied non-null field nulls its parent.
+    let request = Request::new("{ secret(id: 2) }").data(Roles(vec![]));
+    let response = schema.execute(request).await;
+    assert_eq!(response.data, Value::Null);
+    assert_eq!(
+        response.errors[0].message,
+        r#"Cannot query "Query.secret"."#
+    );
+}
+
+#[tokio::test]
+pub async fn test_auth_fragment_at_several_paths() {
+    // The email of the current user can be read without being an admin.
+    let policy = |_: &Context<'_>, request: &AuthRequest<'_>| -> Result<()> {
+        match request.info.path_node.to_string().as_str() {
+            "me.email" => Ok(()),
+            path => Err(format!("Cannot query \"{}\".", path).into()),
+        }
+    };
+    let query = r#"{ me { ...UserEmail } users { ...UserEmail } }
+
+        fragment UserEmail on User { email }"#;
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .auth_policy(policy)
+        .auth_mode(AuthMode::Nullify)
+        .finish();
+    let response = schema.execute(query).await;
+    assert_eq!(
+        response.data,
+        value!({
+            "me": { "email": "user1@example.com" },
+            "users": [{ "email": null }, { "email": null }],
+        })
+    );
+    assert_eq!(
+        response
+            .errors
+            .into_iter()
+            .map(|err| err.message)
+            .collect::<Vec<_>>(),
+        vec![r#"Cannot query "users.email"."#; 2]
+    );
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .auth_policy(policy)
+        .finish();
+    let response = schema.execute(query).await;
+    assert_eq!(response.data, Value::Null);
+    assert_eq!(
+        response
+            .errors
+            .into_iter()
+            .map(|err| err.message)
+            .collect::<Vec<_>>(),
+        vec![r#"Cannot query "users.email"."#]
+    );
+}
+
+#[tokio::test]
+pub async fn test_auth_introspection() {
+    let schema = schema(AuthMode::Reject);
+    let query = r#"{ __type(name: "User") { fields { name } } }"#;
+
+    let request = Request::new(query).data(Roles(vec![]));
+    assert_eq!(
+        schema.execute(request).await.into_result().unwrap().data,
+        value!({ "__type": { "fields": [{ "name": "id" }, { "name": "name" }] } })
+    );
+
+    let request = Request::new(query).data(Roles(vec!["admin"]));
+    assert_eq!(
+        schema.execute(request).await.into_result().unwrap().data,
+        value!({
+            "__type": { "fields": [{ "name": "id" }, { "name": "name" }, { "name": "email" }] }
+        })
+    );
+}
@@ -0,0 +1,86 @@
+use async_graphql::*;
+
+#[derive(SimpleObject)]
+struct User {
+    id: i32,
+    #[graphql(cost = 5)]
+    score: i32,
+}
+
+#[derive(SimpleObject)]
+struct UserConnection {
+    edges: Vec<User>,
+}
+
+fn users(count: i32) -> Vec<User> {
+    (0..count).map(|id| User { id, score: id * 10 }).collect()
+}
+
+struct Query;
+
+#[Object]
+impl Query {
+    #[graphql(list_size(assumed_size = 10, slicing_argument = "first"))]
+    async fn users(&self, first: Option<i32>) -> Vec<User> {
+        users(first.unwrap_or(10))
+    }
+
+    #[graphql(cost = 2, list_size(slicing_argument = "first", sized_field = "edges"))]
+    async fn connection(&self, first: i32) -> UserConnection {
+        UserConnection {
+            edges: users(first),
+        }
+    }
+}
+
+#[tokio::test]
+pub async fn test_cost_sdl() {
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let sdl = schema.sdl();
+    assert!(sdl.contains("directive @cost(weight: Int!) on FIELD_DEFINITION"));
+    assert!(sdl.contains(
+        "directive @listSize(assumedSize: Int, slicingArguments: [String!], sizedFields: [String!]) on FIELD_DEFINITION"
+    ));
+    assert!(sdl.contains("score: Int! @cost(weight: 5)"));
+    assert!(sdl.contains(
+        r#"users(first: Int): [User!]! @listSize(assumedSize: 10, slicingArguments: ["first"])"#
+    ));
+    assert!(sdl.contains(
+        r#"connection(first: Int!): UserConnection! @cost(weight: 2) @listSize(slicingArguments: ["first"], sizedFields: ["edges"])"#
+    ));
+}
+
+#[tokio::test]
+pub async fn test_limit_cost() {
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .limit_cost(30)
+        .finish();
+
+    assert_eq!(
+        schema
+            .execute("{ users(first: 2) { score } }")
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "users": [{ "score": 0 }, { "score": 10 }] })
+    );
+
+    assert_eq!(
+        schema
+            .execute("{ users { score } }")
+            .await
+            .into_result()
+            .unwrap_err(),
+        vec![ServerError::new("Query is too expensive.", None)]
+    );
+
+    assert_eq!(
+        schema
+            .execute("{ connection(first: 6) { edges { score } } }")
+            .await
+            .into_result()
+            .unwrap_err(),
+        vec![ServerError::new("Query is too expensive.", None)]
+    );
+}
@@ -0,0 +1,415 @@
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc, Mutex,
+};
+
+use async_graphql::*;
+use futures_channel::oneshot;
+use futures_util::stream::StreamExt;
+
+#[derive(SimpleObject)]
+struct Friend {
+    id: i32,
+    name: String,
+}
+
+struct User {
+    id: i32,
+}
+
+#[Object]
+impl User {
+    async fn id(&self) -> i32 {
+        self.id
+    }
+
+    async fn name(&self) -> String {
+        format!("user{}", self.id)
+    }
+
+    async fn friends(&self) -> Vec<Friend> {
+        (0..3)
+            .map(|id| Friend {
+                id,
+                name: format!("friend{}", id),
+            })
+            .collect()
+    }
+}
+
+struct Query;
+
+#[Object]
+impl Query {
+    async fn value(&self) -> i32 {
+        10
+    }
+
+    async fn users(&self) -> Vec<User> {
+        vec![User { id: 1 }, User { id: 2 }]
+    }
+
+    async fn numbers(&self) -> Vec<i32> {
+        vec![1, 2, 3, 4, 5]
+    }
+
+    async fn matrix(&self) -> Vec<Vec<i32>> {
+        vec![vec![1, 2, 3], vec![4, 5, 6]]
+    }
+}
+
+async fn execute_incremental(query: &str) -> Vec<serde_json::Value> {
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    schema
+        .execute_stream(query)
+        .map(|resp| serde_json::to_value(&resp).unwrap())
+        .collect()
+        .await
+}
+
+#[tokio::test]
+pub async fn test_defer_inline_fragment() {
+    assert_eq!(
+        execute_incremental(r#"{ value ... @defer(label: "users") { users { id } } }"#).await,
+        vec![
+            serde_json::json!({
+                "data": {"value": 10},
+                "hasNext": true,
+            }),
+            serde_json::json!({
+                "incremental": [{
+                    "data": {"users": [{"id": 1}, {"id": 2}]},
+                    "path": [],
+                    "label": "users",
+                }],
+                "hasNext": false,
+            }),
+        ]
+    );
+}
+
+#[tokio::test]
+pub async fn test_defer_fragment_spread_in_list() {
+    assert_eq!(
+        execute_incremental(
+            r#"{
+                users { id ...UserName @defer }
+            }
+
+            fragment UserName on User { name }"#
+        )
+        .await,
+        vec![
+            serde_json::json!({
+                "data": {"users": [{"id": 1}, {"id": 2}]},
+                "hasNext": true,
+            }),
+            serde_json::json!({
+                "incremental": [
+                    {
+                        "data": {"name": "user1"},
+                        "path": ["users", 0],
+                    },
+                    {
+                        "data": {"name": "user2"},
+                        "path": ["users", 1],
+                    }
+                ],
+                "hasNext": false,
+            }),
+        ]
+    );
+}
+
+#[tokio::test]
+pub async fn test_nested_defer() {
+    assert_eq!(
+        execute_incremental(
+            r#"{
+                value
+                ... @defer(label: "outer") {
+                    users {
+                        id
+                        ... @defer(label: "inner") { name }
+                    }
+                }
+            }"#
+        )
+        .await,
+        vec![
+            serde_json::json!({
+                "data": {"value": 10},
+                "hasNext": true,
+            }),
+            serde_json::json!({
+                "incremental": [{
+                    "data": {"users": [{"id": 1}, {"id": 2}]},
+                    "path": [],
+                    "label": "outer",
+                }],
+                "hasNext": true,
+            }),
+            serde_json::json!({
+                "incremental": [
+                    {
+                        "data": {"name": "user1"},
+                        "path": ["users", 0],
+                        "label": "inner",
+                    },
+                    {
+                        "data": {"name": "user2"},
+                        "path": ["users", 1],
+                        "label": "inner",
+                    }
+                ],
+                "hasNext": false,
+            }),
+        ]
+    );
+}
+
+#[tokio::test]
+pub async fn test_defer_disabled() {
+    assert_eq!(
+        execute_incremental(r#"{ value ... @defer(if: false) { numbers } }"#).await,
+        vec![serde_json::json!({
+            "data": {"value": 10, "numbers": [1, 2, 3, 4, 5]},
+        })]
+    );
+}
+
+#[tokio::test]
+pub async fn test_stream() {
+    assert_eq!(
+        execute_incremental(r#"{ numbers @stream(initialCount: 2, label: "numbers") }"#).await,
+        vec![
+            serde_json::json!({
+                "data": {"numbers": [1, 2]},
+                "hasNext": true,
+            }),
+            serde_json::json!({
+                "incremental": [{
+                    "items": [3],
+                    "path": ["numbers", 2],
+                    "label": "numbers",
+                }],
+                "hasNext": true,
+            }),
+            serde_json::json!({
+                "incremental": [{
+                    "items": [4],
+                    "path": ["numbers", 3],
+                    "label": "numbers",
+                }],
+                "hasNext": true,
+            }),
+            serde_json::json!({
+                "incremental": [{
+                    "items": [5],
+                    "path": ["numbers", 4],
+                    "label": "numbers",
+                }],
+                "hasNext": false,
+            }),
+        ]
+    );
+
+    assert_eq!(
+        execute_incremental(r#"{ numbers @stream(initialCount: 5) }"#).await,
+        vec![serde_json::json!({
+            "data": {"numbers": [1, 2, 3, 4, 5]},
+        })]
+    );
+}
+
+#[tokio::test]
+pub async fn test_stream_nested_list() {
+    assert_eq!(
+        execute_incremental(r#"{ matrix @stream(initialCount: 1) }"#).await,
+        vec![
+            serde_json::json!({
+                "data": {"matrix": [[1, 2, 3]]},
+                "hasNext": true,
+            }),
+            serde_json::json!({
+                "incremental": [{
+                    "items": [[4, 5, 6]],
+                    "path": ["matrix", 1],
+                }],
+                "hasNext": false,
+            }),
+        ]
+    );
+}
+
+#[tokio::test]
+pub async fn test_stream_nested_in_list() {
+    assert_eq!(
+        execute_incremental(r#"{ users { friends @stream(initialCount: 2) { id } } }"#).await,
+        vec![
+            serde_json::json!({
+                "data": {"users": [
+                    {"friends": [{"id": 0}, {"id": 1}]},
+                    {"friends": [{"id": 0}, {"id": 1}]},
+                ]},
+                "hasNext": true,
+            }),
+            serde_json::json!({
+                "incremental": [
+                    {
+                        "items": [{"id": 2}],
+                        "path": ["users", 0, "friends", 2],
+                    },
+                    {
+                        "items": [{"id": 2}],
+                        "path": ["users", 1, "friends", 2],
+                    }
+                ],
+                "hasNext": false,
+            }),
+        ]
+    );
+}
+
+#[tokio::test]
+pub async fn test_defer_resolves_parents_once() {
+    struct Item;
+
+    #[Object]
+    impl Item {
+        async fn a(&self) -> i32 {
+            1
+        }
+
+        async fn b(&self) -> Result<i32> {
+            Err("b failed".into())
+        }
+
+        async fn c(&self) -> Option<i32> {
+            Some(3)
+        }
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn item(&self, ctx: &Context<'_>) -> Item {
+            ctx.data_unchecked::<Arc<AtomicUsize>>()
+                .fetch_add(1, Ordering::SeqCst);
+            Item
+        }
+    }
+
+    let counter = Arc::new(AtomicUsize::new(0));
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .data(counter.clone())
+        .finish();
+    let responses = schema
+        .execute_stream(
+            r#"{
+                item {
+                    a
+                    ... @defer(label: "b") { b }
+                    ... @defer(label: "c") { c }
+                }
+            }"#,
+        )
+        .map(|resp| serde_json::to_value(&resp).unwrap())
+        .collect::<Vec<_>>()
+        .await;
+    assert_eq!(
+        responses,
+        vec![
+            serde_json::json!({
+                "data": {"item": {"a": 1}},
+                "hasNext": true,
+            }),
+            serde_json::json!({
+                "incremental": [
+                    {
+                        "data": null,
+                        "path": ["item"],
+                        "label": "b",
+                        "errors": [{
+                            "message": "b failed",
+                            "locations": [{"line": 4, "column": 46}],
+                            "path": ["item", "b"],
+                        }],
+                    },
+                    {
+                        "data": {"c": 3},
+                        "path": ["item"],
+                        "label": "c",
+                    },
+                ],
+                "hasNext": false,
+            }),
+        ]
+    );
+    assert_eq!(counter.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+pub async fn test_defer_does_not_wait_for_deferred_fields() {
+    type Receiver = Mutex<Option<oneshot::Receiver<i32>>>;
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn value(&self) -> i32 {
+            10
+        }
+
+        async fn slow(&self, ctx: &Context<'_>) -> i32 {
+            let receiver = ctx.data_unchecked::<Receiver>().lock().unwrap().take();
+            receiver.unwrap().await.unwrap()
+        }
+    }
+
+    let (sender, receiver) = oneshot::channel();
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .data(Mutex::new(Some(receiver)))
+        .finish();
+    let mut stream = schema
+        .execute_stream("{ value ... @defer { slow } }")
+        .map(|resp| serde_json::to_value(&resp).unwrap());
+
+    assert_eq!(
+        stream.next().await,
+        Some(serde_json::json!({
+            "data": {"value": 10},
+            "hasNext": true,
+        }))
+    );
+    sender.send(20).unwrap();
+    assert_eq!(
+        stream.next().await,
+        Some(serde_json::json!({
+            "incremental": [{
+                "data": {"slow": 20},
+                "path": [],
+            }],
+            "hasNext": false,
+        }))
+    );
+    assert_eq!(stream.next().await, None);
+}
+
+#[tokio::test]
+pub async fn test_defer_and_stream_without_incremental_delivery() {
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert_eq!(
+        schema
+            .execute(r#"{ numbers @stream(initialCount: 1) ... @defer { value } }"#)
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({
+            "numbers": [1, 2, 3, 4, 5],
+            "value": 10,
+        })
+    );
+}
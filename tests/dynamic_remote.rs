@@ -0,0 +1,273 @@
+use async_graphql::{
+    dynamic::*, value, EmptySubscription, Interface, PathSegment, Pos, ServerError, SimpleObject,
+    Variables,
+};
+
+#[derive(SimpleObject)]
+struct Book {
+    id: i32,
+    title: String,
+}
+
+#[derive(SimpleObject)]
+struct Magazine {
+    id: i32,
+    issue: i32,
+}
+
+#[derive(Interface)]
+#[graphql(field(name = "id", type = "&i32"))]
+enum Item {
+    Book(Book),
+    Magazine(Magazine),
+}
+
+struct RemoteQuery;
+
+#[async_graphql::Object]
+impl RemoteQuery {
+    async fn items(&self, limit: Option<usize>) -> Vec<Item> {
+        let items = vec![
+            Item::Book(Book {
+                id: 1,
+                title: "a".to_string(),
+            }),
+            Item::Magazine(Magazine { id: 2, issue: 10 }),
+        ];
+        items.into_iter().take(limit.unwrap_or(2)).collect()
+    }
+
+    async fn book(&self, id: i32) -> async_graphql::Result<Option<Book>> {
+        if id > 0 {
+            Ok(Some(Book {
+                id,
+                title: "a".to_string(),
+            }))
+        } else {
+            Err("invalid id".into())
+        }
+    }
+}
+
+struct RemoteMutation;
+
+#[async_graphql::Object]
+impl RemoteMutation {
+    async fn add_book(&self, title: String) -> Book {
+        Book { id: 3, title }
+    }
+}
+
+fn remote() -> RemoteSchema {
+    let schema = async_graphql::Schema::new(RemoteQuery, RemoteMutation, EmptySubscription);
+    RemoteSchema::new(&schema.sdl(), move |request| {
+        let schema = schema.clone();
+        async move { schema.execute(request).await }
+    })
+    .unwrap()
+}
+
+fn query() -> Object {
+    Object::new("Query").field(Field::new(
+        "version",
+        TypeRef::named_nn(TypeRef::INT),
+        |_| FieldFuture::new(async move { Ok(Some(FieldValue::value(1))) }),
+    ))
+}
+
+#[tokio::test]
+async fn test_merge_remote() {
+    let schema = Schema::build("Query", Some("Mutation"))
+        .register(query())
+        .register(Object::new("Mutation"))
+        .merge_remote(remote())
+        .finish()
+        .unwrap();
+
+    let query = r#"
+        query($limit: Int) {
+            version
+            items(limit: $limit) {
+                __typename
+                id
+                ... on Book { title }
+                ...MagazineFields
+            }
+            book(id: 1) { title }
+        }
+
+        fragment MagazineFields on Magazine { issue }
+    "#;
+    assert_eq!(
+        schema
+            .execute(
+                async_graphql::Request::new(query)
+                    .variables(Variables::from_value(value!({ "limit": 2 })))
+            )
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({
+            "version": 1,
+            "items": [
+                { "__typename": "Book", "id": 1, "title": "a" },
+                { "__typename": "Magazine", "id": 2, "issue": 10 },
+            ],
+            "book": { "title": "a" },
+        })
+    );
+
+    assert_eq!(
+        schema
+            .execute(r#"mutation { b: addBook(title: "b") { id title } }"#)
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "b": { "id": 3, "title": "b" } })
+    );
+}
+
+#[tokio::test]
+async fn test_mount_remote() {
+    let schema = Schema::build("Query", None)
+        .register(query())
+        .mount_remote("library", "Library", remote())
+        .finish()
+        .unwrap();
+
+    let query = r#"
+        {
+            version
+            library {
+                ...LibraryFields
+                first: items(limit: 1) { id }
+            }
+        }
+
+        fragment LibraryFields on Library { book(id: 2) { id } }
+    "#;
+    assert_eq!(
+        schema.execute(query).await.into_result().unwrap().data,
+        value!({
+            "version": 1,
+            "library": {
+                "book": { "id": 2 },
+                "first": [{ "id": 1 }],
+            },
+        })
+    );
+}
+
+#[tokio::test]
+async fn test_remote_errors() {
+    let shelf = RemoteSchema::new(
+        "type Query { shelf: Shelf! } type Shelf { book(id: Int!): Book } type Book { id: Int! }",
+        |request| async move {
+            assert_eq!(request.query, "query { shelf { book(id: 0) { id } } }");
+            let mut response = async_graphql::Response::new(value!({ "shelf": { "book": null } }));
+            response.errors = vec![ServerError {
+                message: "invalid id".to_string(),
+                source: None,
+                locations: vec![Pos {
+                    line: 1,
+                    column: 17,
+                }],
+                path: vec![
+                    PathSegment::Field("shelf".to_string()),
+                    PathSegment::Field("book".to_string()),
+                ],
+                extensions: None,
+            }];
+            response
+        },
+    )
+    .unwrap();
+    let schema = Schema::build("Query", None)
+        .register(query())
+        .mount_remote("library", "Library", shelf)
+        .finish()
+        .unwrap();
+
+    let response = schema
+        .execute("{ library { shelf { book(id: 0) { id } } } }")
+        .await;
+    assert_eq!(
+        response.data,
+        value!({ "library": { "shelf": { "book": null } } })
+    );
+    assert_eq!(
+        response.errors,
+        vec![ServerError {
+            message: "invalid id".to_string(),
+            source: None,
+            locations: vec![Pos { line: 1, column: 3 }],
+            path: vec![
+                PathSegment::Field("library".to_string()),
+                PathSegment::Field("shelf".to_string()),
+                PathSegment::Field("book".to_string()),
+            ],
+            extensions: None,
+        }]
+    );
+
+    let schema = Schema::build("Query", Some("Mutation"))
+        .register(query())
+        .register(Object::new("Mutation"))
+        .merge_remote(remote())
+        .finish()
+        .unwrap();
+    let response = schema.execute("{ version book(id: 0) { id } }").await;
+    assert_eq!(response.data, value!({ "version": 1, "book": null }));
+    assert_eq!(
+        response.errors,
+        vec![ServerError {
+            message: "invalid id".to_string(),
+            source: None,
+            locations: vec![Pos {
+                line: 1,
+                column: 11
+            }],
+            path: vec![PathSegment::Field("book".to_string())],
+            extensions: None,
+        }]
+    );
+}
+
+#[test]
+fn test_remote_schema_errors() {
+    assert_eq!(
+        Schema::build("Query", None)
+            .register(query())
+            .merge_remote(remote())
+            .finish()
+            .err(),
+        Some(SchemaError(
+            "The remote schema has mutations, but the schema has no mutation type.".to_string()
+        ))
+    );
+
+    assert_eq!(
+        Schema::build("Query", Some("Mutation"))
+            .register(
+                query().field(Field::new("book", TypeRef::named(TypeRef::INT), |_| {
+                    FieldFuture::new(async move { Ok(None::<FieldValue>) })
+                },))
+            )
+            .register(Object::new("Mutation"))
+            .merge_remote(remote())
+            .finish()
+            .err(),
+        Some(SchemaError(
+            r#"Field "Query.book" is already defined."#.to_string()
+        ))
+    );
+
+    assert!(
+        RemoteSchema::new("type Query { a: Unknown }", |_| async move {
+            async_graphql::Response::default()
+        })
+        .is_err()
+    );
+}
@@ -0,0 +1,460 @@
+use std::sync::{Arc, Mutex};
+
+use async_graphql::{
+    dynamic::*,
+    extensions::{Extension, ExtensionContext, ExtensionFactory, NextResolve, ResolveInfo},
+    value, Pos, ServerError, ServerResult, Value,
+};
+
+struct MyObjData {
+    a: i32,
+    b: String,
+}
+
+fn create_schema() -> Schema {
+    let my_obj = Object::new("MyObj")
+        .field(Field::new("a", TypeRef::named_nn(TypeRef::INT), |ctx| {
+            FieldFuture::new(async move {
+                let obj = ctx.parent_value.try_downcast_ref::<MyObjData>()?;
+                Ok(Some(FieldValue::value(obj.a)))
+            })
+        }))
+        .field(Field::new("b", TypeRef::named_nn(TypeRef::STRING), |ctx| {
+            FieldFuture::new(async move {
+                let obj = ctx.parent_value.try_downcast_ref::<MyObjData>()?;
+                Ok(Some(FieldValue::value(obj.b.clone())))
+            })
+        }));
+
+    let color = Enum::new("Color").items(["RED", "GREEN", "BLUE"]);
+
+    let filter = InputObject::new("Filter")
+        .field(InputValue::new("min", TypeRef::named_nn(TypeRef::INT)))
+        .field(InputValue::new("max", TypeRef::named_nn(TypeRef::INT)).default_value(100));
+
+    let query = Object::new("Query")
+        .field(
+            Field::new("add", TypeRef::named_nn(TypeRef::INT), |ctx| {
+                FieldFuture::new(async move {
+                    let a = ctx.args.try_get("a")?.i64()?;
+                    let b = ctx.args.try_get("b")?.i64()?;
+                    Ok(Some(FieldValue::value(a + b)))
+                })
+            })
+            .argument(InputValue::new("a", TypeRef::named_nn(TypeRef::INT)))
+            .argument(InputValue::new("b", TypeRef::named_nn(TypeRef::INT)).default_value(1)),
+        )
+        .field(Field::new("obj", TypeRef::named("MyObj"), |_| {
+            FieldFuture::from_value(Some(FieldValue::owned_any(MyObjData {
+                a: 10,
+                b: "abc".to_string(),
+            })))
+        }))
+        .field(Field::new(
+            "objs",
+            TypeRef::named_nn_list_nn("MyObj"),
+            |_| {
+                FieldFuture::from_value(Some(FieldValue::list((1..=2).map(|n| {
+                    FieldValue::owned_any(MyObjData {
+                        a: n,
+                        b: n.to_string(),
+                    })
+                }))))
+            },
+        ))
+        .field(
+            Field::new("color", TypeRef::named_nn("Color"), |ctx| {
+                FieldFuture::new(async move {
+                    let color = ctx.args.try_get("color")?.enum_name()?.to_string();
+                    Ok(Some(FieldValue::value(Value::Enum(
+                        async_graphql::Name::new(color),
+                    ))))
+                })
+            })
+            .argument(InputValue::new("color", TypeRef::named_nn("Color"))),
+        )
+        .field(
+            Field::new("range", TypeRef::named_nn_list_nn(TypeRef::INT), |ctx| {
+                FieldFuture::new(async move {
+                    let filter = ctx.args.try_get("filter")?.object()?;
+                    let min = filter.try_get("min")?.i64()?;
+                    let max = filter.try_get("max")?.i64()?;
+                    Ok(Some(FieldValue::value(vec![min, max])))
+                })
+            })
+            .argument(InputValue::new("filter", TypeRef::named_nn("Filter"))),
+        );
+
+    Schema::build(query.type_name(), None)
+        .register(my_obj)
+        .register(color)
+        .register(filter)
+        .register(query)
+        .finish()
+        .unwrap()
+}
+
+#[tokio::test]
+pub async fn test_dynamic_query() {
+    let schema = create_schema();
+
+    assert_eq!(
+        schema
+            .execute("{ add(a: 10, b: 20) addDefault: add(a: 10) }")
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "add": 30, "addDefault": 11 })
+    );
+
+    assert_eq!(
+        schema
+            .execute("{ obj { a b } objs { a ... on MyObj { b } } }")
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({
+            "obj": { "a": 10, "b": "abc" },
+            "objs": [{ "a": 1, "b": "1" }, { "a": 2, "b": "2" }],
+        })
+    );
+}
+
+#[tokio::test]
+pub async fn test_dynamic_input_types() {
+    let schema = create_schema();
+
+    assert_eq!(
+        schema
+            .execute("{ color(color: GREEN) range(filter: { min: 5 }) }")
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "color": "GREEN", "range": [5, 100] })
+    );
+
+    let resp = schema
+        .execute(
+            async_graphql::Request::new("query($c: Color!) { color(color: $c) }").variables(
+                async_graphql::Variables::from_json(serde_json::json!({ "c": "BLUE" })),
+            ),
+        )
+        .await;
+    assert_eq!(
+        resp.into_result().unwrap().data,
+        value!({ "color": "BLUE" })
+    );
+
+    assert!(schema.execute("{ color(color: YELLOW) }").await.is_err());
+}
+
+#[tokio::test]
+pub async fn test_dynamic_interface_and_union() {
+    let dog = Object::new("Dog")
+        .implement("Animal")
+        .field(Field::new(
+            "name",
+            TypeRef::named_nn(TypeRef::STRING),
+            |_| FieldFuture::from_value(Some(FieldValue::value("dog"))),
+        ))
+        .field(Field::new(
+            "barks",
+            TypeRef::named_nn(TypeRef::BOOLEAN),
+            |_| FieldFuture::from_value(Some(FieldValue::value(true))),
+        ));
+    let cat = Object::new("Cat")
+        .implement("Animal")
+        .field(Field::new(
+            "name",
+            TypeRef::named_nn(TypeRef::STRING),
+            |_| FieldFuture::from_value(Some(FieldValue::value("cat"))),
+        ))
+        .field(Field::new("lives", TypeRef::named_nn(TypeRef::INT), |_| {
+            FieldFuture::from_value(Some(FieldValue::value(9)))
+        }));
+    let animal = Interface::new("Animal").field(InterfaceField::new(
+        "name",
+        TypeRef::named_nn(TypeRef::STRING),
+    ));
+    let pet = Union::new("Pet").possible_type("Dog").possible_type("Cat");
+    let query = Object::new("Query")
+        .field(Field::new(
+            "animals",
+            TypeRef::named_nn_list_nn("Animal"),
+            |_| {
+                FieldFuture::from_value(Some(FieldValue::list([
+                    FieldValue::NULL.with_type("Dog"),
+                    FieldValue::NULL.with_type("Cat"),
+                ])))
+            },
+        ))
+        .field(Field::new("pet", TypeRef::named_nn("Pet"), |_| {
+            FieldFuture::from_value(Some(FieldValue::NULL.with_type("Cat")))
+        }));
+
+    let schema = Schema::build("Query", None)
+        .register(dog)
+        .register(cat)
+        .register(animal)
+        .register(pet)
+        .register(query)
+        .finish()
+        .unwrap();
+
+    assert_eq!(
+        schema
+            .execute(
+                r#"{
+                    animals { __typename name ... on Dog { barks } ... on Cat { lives } }
+                    pet { ... on Cat { name lives } }
+                }"#
+            )
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({
+            "animals": [
+                { "__typename": "Dog", "name": "dog", "barks": true },
+                { "__typename": "Cat", "name": "cat", "lives": 9 },
+            ],
+            "pet": { "name": "cat", "lives": 9 },
+        })
+    );
+}
+
+#[tokio::test]
+pub async fn test_dynamic_mutation() {
+    let query = Object::new("Query").field(Field::new(
+        "value",
+        TypeRef::named_nn(TypeRef::INT),
+        |ctx| {
+            FieldFuture::new(async move {
+                Ok(Some(FieldValue::value(
+                    *ctx.data::<Arc<Mutex<i32>>>()?.lock().unwrap(),
+                )))
+            })
+        },
+    ));
+    let mutation = Object::new("Mutation").field(
+        Field::new("add", TypeRef::named_nn(TypeRef::INT), |ctx| {
+            FieldFuture::new(async move {
+                let n = ctx.args.try_get("n")?.i64()? as i32;
+                let mut value = ctx.data::<Arc<Mutex<i32>>>()?.lock().unwrap();
+                *value += n;
+                Ok(Some(FieldValue::value(*value)))
+            })
+        })
+        .argument(InputValue::new("n", TypeRef::named_nn(TypeRef::INT))),
+    );
+
+    let schema = Schema::build("Query", Some("Mutation"))
+        .register(query)
+        .register(mutation)
+        .data(Arc::new(Mutex::new(0i32)))
+        .finish()
+        .unwrap();
+
+    assert_eq!(
+        schema
+            .execute("mutation { a: add(n: 1) b: add(n: 2) c: add(n: 3) }")
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "a": 1, "b": 3, "c": 6 })
+    );
+    assert_eq!(
+        schema
+            .execute("{ value }")
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "value": 6 })
+    );
+}
+
+#[tokio::test]
+pub async fn test_dynamic_errors() {
+    let query = Object::new("Query")
+        .field(Field::new("nullable", TypeRef::named(TypeRef::INT), |_| {
+            FieldFuture::new(async move { Err::<Option<FieldValue>, _>("nullable error".into()) })
+        }))
+        .field(Field::new("obj", TypeRef::named("Obj"), |_| {
+            FieldFuture::from_value(Some(FieldValue::owned_any(())))
+        }));
+    let obj = Object::new("Obj").field(Field::new(
+        "nonNull",
+        TypeRef::named_nn(TypeRef::INT),
+        |_| FieldFuture::new(async move { Err::<Option<FieldValue>, _>("non-null error".into()) }),
+    ));
+    let schema = Schema::build("Query", None)
+        .register(query)
+        .register(obj)
+        .finish()
+        .unwrap();
+
+    let resp = schema.execute("{ nullable obj { nonNull } }").await;
+    assert_eq!(resp.data, value!({ "nullable": null, "obj": null }));
+    assert_eq!(
+        resp.errors,
+        vec![
+            ServerError {
+                message: "nullable error".to_string(),
+                source: None,
+                locations: vec![Pos { line: 1, column: 3 }],
+                path: vec![async_graphql::PathSegment::Field("nullable".to_string())],
+                extensions: None,
+            },
+            ServerError {
+                message: "non-null error".to_string(),
+                source: None,
+                locations: vec![Pos {
+                    line: 1,
+                    column: 18
+                }],
+                path: vec![
+                    async_graphql::PathSegment::Field("obj".to_string()),
+                    async_graphql::PathSegment::Field("nonNull".to_string())
+                ],
+                extensions: None,
+            },
+        ]
+    );
+}
+
+#[tokio::test]
+pub async fn test_dynamic_sdl_and_introspection() {
+    let schema = create_schema();
+    let sdl = schema.sdl();
+    assert!(sdl.contains("add(a: Int!, b: Int! = 1): Int!"));
+    assert!(sdl.contains("enum Color {"));
+    assert!(sdl.contains("input Filter {"));
+
+    assert_eq!(
+        schema
+            .execute(r#"{ __type(name: "MyObj") { fields { name } } }"#)
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({
+            "__type": { "fields": [{ "name": "a" }, { "name": "b" }] }
+        })
+    );
+}
+
+#[tokio::test]
+pub async fn test_dynamic_extensions() {
+    struct MyExtensionImpl {
+        calls: Arc<Mutex<Vec<String>>>,
+    }
+
+    #[async_trait::async_trait]
+    impl Extension for MyExtensionImpl {
+        async fn resolve(
+            &self,
+            ctx: &ExtensionContext<'_>,
+            info: ResolveInfo<'_>,
+            next: NextResolve<'_>,
+        ) -> ServerResult<Option<Value>> {
+            self.calls.lock().unwrap().push(format!(
+                "{}.{}: {}",
+                info.parent_type, info.name, info.return_type
+            ));
+            next.run(ctx, info).await
+        }
+    }
+
+    struct MyExtension {
+        calls: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl ExtensionFactory for MyExtension {
+        fn create(&self) -> Arc<dyn Extension> {
+            Arc::new(MyExtensionImpl {
+                calls: self.calls.clone(),
+            })
+        }
+    }
+
+    let calls: Arc<Mutex<Vec<String>>> = Default::default();
+    let query =
+        Object::new("Query").field(Field::new("value", TypeRef::named_nn(TypeRef::INT), |_| {
+            FieldFuture::from_value(Some(FieldValue::value(10)))
+        }));
+    let schema = Schema::build("Query", None)
+        .register(query)
+        .extension(MyExtension {
+            calls: calls.clone(),
+        })
+        .finish()
+        .unwrap();
+
+    assert_eq!(
+        schema
+            .execute("{ value }")
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "value": 10 })
+    );
+    assert_eq!(
+        *calls.lock().unwrap(),
+        vec!["Query.value: Int!".to_string()]
+    );
+}
+
+#[test]
+pub fn test_dynamic_schema_errors() {
+    let query = || {
+        Object::new("Query").field(Field::new("obj", TypeRef::named("MyObj"), |_| {
+            FieldFuture::from_value(None)
+        }))
+    };
+
+    assert_eq!(
+        Schema::build("Query", None)
+            .register(query())
+            .finish()
+            .err(),
+        Some(SchemaError(
+            r#"Unknown type "MyObj" of field "Query.obj"."#.to_string()
+        ))
+    );
+
+    assert_eq!(
+        Schema::build("Query", None)
+            .register(query())
+            .register(InputObject::new("MyObj"))
+            .finish()
+            .err(),
+        Some(SchemaError(
+            r#"Type "MyObj" of field "Query.obj" must be an output type."#.to_string()
+        ))
+    );
+
+    assert_eq!(
+        Schema::build("Query", None)
+            .register(query())
+            .register(Scalar::new("Int"))
+            .finish()
+            .err(),
+        Some(SchemaError(r#"Type "Int" is already defined."#.to_string()))
+    );
+
+    assert_eq!(
+        Schema::build("Root", None)
+            .register(Scalar::new("Root"))
+            .finish()
+            .err(),
+        Some(SchemaError(
+            r#"Root type "Root" must be an object."#.to_string()
+        ))
+    );
+}
@@ -286,3 +286,110 @@ pub async fn test_entity_union() {
         })
     );
 }
+
+#[tokio::test]
+pub async fn test_federation_v2_directives() {
+    #[derive(SimpleObject)]
+    #[graphql(shareable)]
+    struct Product {
+        upc: String,
+        #[graphql(inaccessible, tag = "internal")]
+        weight: i32,
+        #[graphql(override_from = "inventory")]
+        in_stock: bool,
+    }
+
+    #[derive(SimpleObject)]
+    #[graphql(tag = "public", tag = "reviews")]
+    struct Review {
+        #[graphql(shareable)]
+        body: String,
+    }
+
+    #[derive(Enum, Copy, Clone, Eq, PartialEq)]
+    #[graphql(inaccessible)]
+    enum Status {
+        Active,
+        #[graphql(tag = "legacy")]
+        Discontinued,
+    }
+
+    #[derive(InputObject)]
+    struct ProductFilter {
+        #[graphql(inaccessible)]
+        status: Option<Status>,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        #[graphql(entity)]
+        async fn find_product_by_upc(&self, upc: String) -> Product {
+            Product {
+                upc,
+                weight: 10,
+                in_stock: true,
+            }
+        }
+
+        async fn reviews(
+            &self,
+            #[graphql(tag = "filtering")] _filter: Option<ProductFilter>,
+        ) -> Vec<Review> {
+            Vec::new()
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let sdl = schema.sdl_with_options(SDLExportOptions::new().federation());
+
+    assert!(sdl.starts_with(
+        "extend schema @link(url: \"https://specs.apollo.dev/federation/v2.0\", import: [\"@key\", \"@external\", \"@requires\", \"@provides\", \"@shareable\", \"@inaccessible\", \"@override\", \"@tag\"])\n"
+    ));
+    assert!(sdl.contains("type Product @key(fields: \"upc\") @shareable {"));
+    assert!(sdl.contains("\tweight: Int! @inaccessible @tag(name: \"internal\")\n"));
+    assert!(sdl.contains("\tinStock: Boolean! @override(from: \"inventory\")\n"));
+    assert!(sdl.contains("type Review @tag(name: \"public\") @tag(name: \"reviews\") {"));
+    assert!(sdl.contains("\tbody: String! @shareable\n"));
+    assert!(sdl.contains("enum Status @inaccessible {"));
+    assert!(sdl.contains("\tDISCONTINUED @tag(name: \"legacy\")\n"));
+    assert!(sdl.contains("\tstatus: Status @inaccessible\n"));
+    assert!(sdl.contains("\treviews(filter: ProductFilter @tag(name: \"filtering\")): [Review!]!"));
+
+    // The Federation 2 directives are only exported in federation mode.
+    let sdl = schema.sdl();
+    assert!(!sdl.contains("@link"));
+    assert!(!sdl.contains("@shareable"));
+    assert!(!sdl.contains("@inaccessible"));
+    assert!(!sdl.contains("@tag"));
+}
+
+#[tokio::test]
+pub async fn test_federation_v1_sdl_without_link() {
+    #[derive(SimpleObject)]
+    struct MyObj {
+        id: i32,
+        #[graphql(external)]
+        name: String,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        #[graphql(entity)]
+        async fn find_obj(&self, id: i32) -> MyObj {
+            MyObj {
+                id,
+                name: String::new(),
+            }
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let sdl = schema.sdl_with_options(SDLExportOptions::new().federation());
+    assert!(!sdl.contains("@link"));
+    assert!(sdl.contains("type MyObj @key(fields: \"id\") {"));
+    assert!(sdl.contains("\tname: String! @external\n"));
+}
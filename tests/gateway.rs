@@ -0,0 +1,483 @@
+use async_graphql::{
+    gateway::{FetchNode, Fetcher, FlattenNode, Gateway, PlanNode, Supergraph},
+    *,
+};
+
+mod accounts {
+    use async_graphql::*;
+
+    #[derive(SimpleObject)]
+    #[graphql(complex)]
+    pub struct User {
+        pub id: ID,
+        pub name: String,
+    }
+
+    #[ComplexObject]
+    impl User {
+        async fn email(&self) -> Result<String> {
+            match self.id.as_str() {
+                "2" => Err("No email".into()),
+                id => Ok(format!("{}@example.com", id)),
+            }
+        }
+    }
+
+    pub struct Query;
+
+    #[Object]
+    impl Query {
+        async fn me(&self) -> User {
+            User {
+                id: "1".into(),
+                name: "Alice".to_string(),
+            }
+        }
+
+        #[graphql(entity)]
+        async fn find_user_by_id(&self, id: ID) -> Result<User> {
+            match id.as_str() {
+                "1" => Ok(User {
+                    id,
+                    name: "Alice".to_string(),
+                }),
+                "2" | "3" => Ok(User {
+                    id,
+                    name: "Bob".to_string(),
+                }),
+                _ => Err("Unknown user".into()),
+            }
+        }
+    }
+
+    pub type Schema = async_graphql::Schema<Query, EmptyMutation, EmptySubscription>;
+}
+
+mod products {
+    use async_graphql::*;
+
+    #[derive(SimpleObject)]
+    pub struct Product {
+        pub upc: String,
+        pub name: String,
+        pub weight: i32,
+    }
+
+    fn product(upc: String) -> Product {
+        Product {
+            name: format!("Product {}", upc),
+            weight: upc.len() as i32 * 10,
+            upc,
+        }
+    }
+
+    pub struct Query;
+
+    #[Object]
+    impl Query {
+        async fn top_products(&self, #[graphql(default = 1)] first: usize) -> Vec<Product> {
+            ["1", "22", "333"]
+                .into_iter()
+                .take(first)
+                .map(|upc| product(upc.to_string()))
+                .collect()
+        }
+
+        #[graphql(entity)]
+        async fn find_product_by_upc(&self, upc: String) -> Product {
+            product(upc)
+        }
+    }
+
+    pub struct Mutation;
+
+    #[Object]
+    impl Mutation {
+        async fn create_product(&self, upc: String) -> Product {
+            product(upc)
+        }
+    }
+
+    pub type Schema = async_graphql::Schema<Query, Mutation, EmptySubscription>;
+}
+
+mod inventory {
+    use async_graphql::*;
+
+    pub struct Product {
+        upc: String,
+        weight: Option<i32>,
+    }
+
+    #[Object(extends)]
+    impl Product {
+        #[graphql(external)]
+        async fn upc(&self) -> &str {
+            &self.upc
+        }
+
+        #[graphql(external)]
+        async fn weight(&self) -> i32 {
+            self.weight.unwrap_or_default()
+        }
+
+        async fn in_stock(&self) -> bool {
+            self.upc != "22"
+        }
+
+        #[graphql(requires = "weight")]
+        async fn shipping_estimate(&self) -> Option<i32> {
+            self.weight.map(|weight| weight / 2)
+        }
+    }
+
+    pub struct Query;
+
+    #[Object]
+    impl Query {
+        #[graphql(entity)]
+        async fn find_product_by_upc(
+            &self,
+            #[graphql(key)] upc: String,
+            weight: Option<i32>,
+        ) -> Product {
+            Product { upc, weight }
+        }
+    }
+
+    pub type Schema = async_graphql::Schema<Query, EmptyMutation, EmptySubscription>;
+}
+
+mod reviews {
+    use async_graphql::*;
+
+    pub struct User {
+        id: ID,
+    }
+
+    #[Object(extends)]
+    impl User {
+        #[graphql(external)]
+        async fn id(&self) -> &ID {
+            &self.id
+        }
+
+        async fn reviews(&self) -> Vec<Review> {
+            vec![Review {
+                body: format!("Review by {}", *self.id),
+                author: self.id.clone(),
+                upc: self.id.repeat(2),
+            }]
+        }
+    }
+
+    pub struct Product {
+        upc: String,
+    }
+
+    #[Object(extends)]
+    impl Product {
+        #[graphql(external)]
+        async fn upc(&self) -> &str {
+            &self.upc
+        }
+
+        async fn reviews(&self) -> Vec<Review> {
+            vec![Review {
+                body: format!("Review of {}", self.upc),
+                author: ID::from(if self.upc == "333" { "3" } else { "2" }),
+                upc: self.upc.clone(),
+            }]
+        }
+    }
+
+    pub struct Review {
+        body: String,
+        author: ID,
+        upc: String,
+    }
+
+    #[Object]
+    impl Review {
+        async fn body(&self) -> &str {
+            &self.body
+        }
+
+        async fn author(&self) -> User {
+            User {
+                id: self.author.clone(),
+            }
+        }
+
+        async fn product(&self) -> Product {
+            Product {
+                upc: self.upc.clone(),
+            }
+        }
+    }
+
+    pub struct Query;
+
+    #[Object]
+    impl Query {
+        #[graphql(entity)]
+        async fn find_user_by_id(&self, id: ID) -> User {
+            User { id }
+        }
+
+        #[graphql(entity)]
+        async fn find_product_by_upc(&self, upc: String) -> Product {
+            Product { upc }
+        }
+    }
+
+    pub type Schema = async_graphql::Schema<Query, EmptyMutation, EmptySubscription>;
+}
+
+struct Subgraphs {
+    accounts: accounts::Schema,
+    products: products::Schema,
+    inventory: inventory::Schema,
+    reviews: reviews::Schema,
+}
+
+#[async_trait::async_trait]
+impl Fetcher for Subgraphs {
+    async fn fetch(&self, subgraph: &str, request: Request) -> Response {
+        match subgraph {
+            "accounts" => self.accounts.execute(request).await,
+            "products" => self.products.execute(request).await,
+            "inventory" => self.inventory.execute(request).await,
+            "reviews" => self.reviews.execute(request).await,
+            _ => unreachable!(),
+        }
+    }
+}
+
+fn gateway() -> Gateway {
+    let subgraphs = Subgraphs {
+        accounts: Schema::build(accounts::Query, EmptyMutation, EmptySubscription)
+            .enable_federation()
+            .finish(),
+        products: Schema::build(products::Query, products::Mutation, EmptySubscription)
+            .enable_federation()
+            .finish(),
+        inventory: Schema::build(inventory::Query, EmptyMutation, EmptySubscription)
+            .enable_federation()
+            .finish(),
+        reviews: Schema::build(reviews::Query, EmptyMutation, EmptySubscription)
+            .enable_federation()
+            .finish(),
+    };
+    let options = SDLExportOptions::new().federation();
+    let supergraph = Supergraph::compose([
+        ("accounts", subgraphs.accounts.sdl_with_options(options)),
+        ("products", subgraphs.products.sdl_with_options(options)),
+        ("inventory", subgraphs.inventory.sdl_with_options(options)),
+        ("reviews", subgraphs.reviews.sdl_with_options(options)),
+    ])
+    .unwrap();
+    Gateway::new(supergraph, subgraphs)
+}
+
+#[test]
+fn test_compose() {
+    let gateway = gateway();
+    let sdl = gateway.supergraph().sdl();
+    assert!(sdl.contains("type Product {"));
+    assert!(sdl.contains("\tshippingEstimate: Int\n"));
+    assert!(sdl.contains("\treviews: [Review!]!\n"));
+    assert!(!sdl.contains("@key"));
+    assert!(!sdl.contains("_entities"));
+    assert_eq!(
+        gateway.supergraph().subgraphs().collect::<Vec<_>>(),
+        vec!["accounts", "products", "inventory", "reviews"]
+    );
+
+    assert_eq!(
+        Supergraph::compose([
+            ("a", "type Query { a: Int } type A { a: Int }"),
+            ("b", "type Query { b: Int } enum A { A }"),
+        ])
+        .err()
+        .unwrap()
+        .0,
+        r#"The type "A" has different kinds in the subgraphs."#
+    );
+    assert_eq!(
+        Supergraph::compose([
+            ("a", "type Query { a: Int }"),
+            ("b", "type Query { a: String }"),
+        ])
+        .err()
+        .unwrap()
+        .0,
+        r#"The field "Query.a" has different types in the subgraphs."#
+    );
+}
+
+#[test]
+fn test_plan() {
+    let plan = gateway()
+        .plan("{ me { name reviews { body product { name shippingEstimate } } } }")
+        .unwrap();
+    let product_path = vec![
+        "me".to_string(),
+        "reviews".to_string(),
+        "@".to_string(),
+        "product".to_string(),
+    ];
+    assert_eq!(
+        plan.node,
+        PlanNode::Sequence(vec![
+            PlanNode::Fetch(FetchNode {
+                subgraph: "accounts".to_string(),
+                operation: "query { __typename me { __typename name _key_id: id } }".to_string(),
+                variables: vec![],
+            }),
+            PlanNode::Flatten(FlattenNode {
+                path: vec!["me".to_string()],
+                type_name: "User".to_string(),
+                requires: vec![Name::new("id")],
+                fetch: FetchNode {
+                    subgraph: "reviews".to_string(),
+                    operation: "query($representations: [_Any!]!) { _entities(representations: $representations) { ... on User { __typename reviews { __typename body product { __typename _key_upc: upc } } } } }".to_string(),
+                    variables: vec![],
+                },
+            }),
+            PlanNode::Flatten(FlattenNode {
+                path: product_path.clone(),
+                type_name: "Product".to_string(),
+                requires: vec![Name::new("upc")],
+                fetch: FetchNode {
+                    subgraph: "products".to_string(),
+                    operation: "query($representations: [_Any!]!) { _entities(representations: $representations) { ... on Product { __typename name _key_weight: weight } } }".to_string(),
+                    variables: vec![],
+                },
+            }),
+            PlanNode::Flatten(FlattenNode {
+                path: product_path,
+                type_name: "Product".to_string(),
+                requires: vec![Name::new("upc"), Name::new("weight")],
+                fetch: FetchNode {
+                    subgraph: "inventory".to_string(),
+                    operation: "query($representations: [_Any!]!) { _entities(representations: $representations) { ... on Product { __typename shippingEstimate } } }".to_string(),
+                    variables: vec![],
+                },
+            }),
+        ])
+    );
+}
+
+#[tokio::test]
+async fn test_execute() {
+    let gateway = gateway();
+    let query = r#"
+        query($first: Int) {
+            me {
+                __typename
+                name
+                reviews { ...ReviewFields }
+            }
+            products: topProducts(first: $first) {
+                name
+                inStock
+                shippingEstimate
+                reviews { author { name } }
+            }
+        }
+
+        fragment ReviewFields on Review {
+            body
+            product { upc name inStock }
+        }
+    "#;
+    assert_eq!(
+        gateway
+            .execute(Request::new(query).variables(Variables::from_value(value!({ "first": 2 }))))
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({
+            "me": {
+                "__typename": "User",
+                "name": "Alice",
+                "reviews": [{
+                    "body": "Review by 1",
+                    "product": { "upc": "11", "name": "Product 11", "inStock": true },
+                }],
+            },
+            "products": [
+                {
+                    "name": "Product 1",
+                    "inStock": true,
+                    "shippingEstimate": 5,
+                    "reviews": [{ "author": { "name": "Bob" } }],
+                },
+                {
+                    "name": "Product 22",
+                    "inStock": false,
+                    "shippingEstimate": 10,
+                    "reviews": [{ "author": { "name": "Bob" } }],
+                },
+            ],
+        })
+    );
+}
+
+#[tokio::test]
+async fn test_mutation() {
+    assert_eq!(
+        gateway()
+            .execute(r#"mutation { createProduct(upc: "4444") { name inStock } }"#)
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "createProduct": { "name": "Product 4444", "inStock": true } })
+    );
+}
+
+#[tokio::test]
+async fn test_errors() {
+    let gateway = gateway();
+
+    let response = gateway
+        .execute("{ topProducts { upc reviews { author { email } } } }")
+        .await;
+    assert_eq!(response.data, Value::Null);
+    assert_eq!(
+        response.errors,
+        vec![ServerError {
+            message: "No email".to_string(),
+            source: None,
+            locations: vec![],
+            path: vec![
+                PathSegment::Field("topProducts".to_string()),
+                PathSegment::Index(0),
+                PathSegment::Field("reviews".to_string()),
+                PathSegment::Index(0),
+                PathSegment::Field("author".to_string()),
+                PathSegment::Field("email".to_string()),
+            ],
+            extensions: None,
+        }]
+    );
+
+    let resp = gateway.execute("{ unknown }").await;
+    assert!(resp.is_request_error());
+    assert_eq!(
+        resp.errors[0].message,
+        r#"Unknown field "unknown" on type "Query"."#
+    );
+    assert!(gateway.execute("{").await.is_request_error());
+    assert_eq!(
+        gateway
+            .execute("{ __schema { queryType { name } } }")
+            .await
+            .errors[0]
+            .message,
+        r#"Unknown field "__schema" on type "Query"."#
+    );
+}
@@ -0,0 +1,246 @@
+use async_graphql::*;
+
+#[derive(Clone, Copy, PartialEq)]
+enum Role {
+    Admin,
+    Support,
+    Guest,
+}
+
+#[derive(SimpleObject)]
+struct Address {
+    city: String,
+    #[graphql(redact = "street")]
+    street: String,
+}
+
+#[derive(SimpleObject)]
+struct User {
+    name: String,
+    #[graphql(redact = "email")]
+    email: String,
+    #[graphql(redact = "phone")]
+    phone: Option<String>,
+    #[graphql(redact = "tag")]
+    tags: Vec<String>,
+    #[graphql(redact = "code")]
+    codes: Option<Vec<String>>,
+    #[graphql(redact = "code")]
+    required_codes: Vec<String>,
+    address: Address,
+}
+
+struct Query;
+
+fn user(name: &str) -> User {
+    User {
+        name: name.to_string(),
+        email: format!("{}@example.com", name),
+        phone: Some("555-0100".to_string()),
+        tags: vec!["vip".to_string(), "internal".to_string()],
+        codes: Some(vec!["A1".to_string(), "secret".to_string()]),
+        required_codes: vec!["A1".to_string(), "secret".to_string()],
+        address: Address {
+            city: "Paris".to_string(),
+            street: "1 Main Street".to_string(),
+        },
+    }
+}
+
+#[Object]
+impl Query {
+    async fn me(&self) -> User {
+        user("alice")
+    }
+
+    async fn users(&self) -> Vec<User> {
+        vec![user("alice"), user("bob")]
+    }
+}
+
+fn redactor() -> Redactor {
+    Redactor::new(|ctx: &Context<'_>, category: &str, value: &Value| {
+        let role = *ctx.data_unchecked::<Role>();
+        match (role, category, value) {
+            (Role::Admin, _, _) => Redaction::Keep,
+            (_, "email", Value::String(email)) => {
+                let domain = email.split('@').nth(1).unwrap_or_default();
+                Redaction::Mask(Value::String(format!("***@{}", domain)))
+            }
+            (Role::Support, "phone", _) => Redaction::Keep,
+            (_, "tag", Value::String(tag)) if tag != "internal" => Redaction::Keep,
+            (_, "tag", _) => Redaction::Mask(Value::String("***".to_string())),
+            (_, "code", Value::String(code)) if code != "secret" => Redaction::Keep,
+            (Role::Guest, "street", _) => Redaction::Null,
+            _ => Redaction::Null,
+        }
+    })
+}
+
+fn request(query: &str, role: Role) -> Request {
+    Request::new(query).data(role).data(redactor())
+}
+
+#[tokio::test]
+pub async fn test_redaction() {
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let query = "{ users { name email phone tags } }";
+
+    let response = schema.execute(request(query, Role::Admin)).await;
+    assert_eq!(
+        response.data,
+        value!({
+            "users": [
+                {
+                    "name": "alice",
+                    "email": "alice@example.com",
+                    "phone": "555-0100",
+                    "tags": ["vip", "internal"],
+                },
+                {
+                    "name": "bob",
+                    "email": "bob@example.com",
+                    "phone": "555-0100",
+                    "tags": ["vip", "internal"],
+                },
+            ]
+        })
+    );
+    assert!(!response.extensions.contains_key("redacted"));
+
+    let response = schema.execute(request(query, Role::Support)).await;
+    assert_eq!(
+        response.data,
+        value!({
+            "users": [
+                {
+                    "name": "alice",
+                    "email": "***@example.com",
+                    "phone": "555-0100",
+                    "tags": ["vip", "***"],
+                },
+                {
+                    "name": "bob",
+                    "email": "***@example.com",
+                    "phone": "555-0100",
+                    "tags": ["vip", "***"],
+                },
+            ]
+        })
+    );
+    assert_eq!(
+        response.extensions["redacted"],
+        value!([
+            ["users", 0, "email"],
+            ["users", 0, "tags"],
+            ["users", 1, "email"],
+            ["users", 1, "tags"],
+        ])
+    );
+
+    let response = schema
+        .execute(request("{ users { phone } }", Role::Guest))
+        .await;
+    assert_eq!(
+        response.data,
+        value!({ "users": [{ "phone": null }, { "phone": null }] })
+    );
+    assert!(response.errors.is_empty());
+}
+
+#[tokio::test]
+pub async fn test_redaction_nested() {
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+
+    let response = schema
+        .execute(request("{ me { address { city street } } }", Role::Guest))
+        .await;
+    assert_eq!(response.data, Value::Null);
+    assert_eq!(
+        response.errors,
+        vec![ServerError {
+            message: r#"The non-null field "street" is redacted to null."#.to_string(),
+            source: None,
+            locations: vec![Pos {
+                line: 1,
+                column: 23
+            }],
+            path: vec![
+                PathSegment::Field("me".to_string()),
+                PathSegment::Field("address".to_string()),
+                PathSegment::Field("street".to_string()),
+            ],
+            extensions: None,
+        }]
+    );
+
+    // Without a redactor, the values are kept.
+    let response = schema
+        .execute(Request::new("{ users { email address { street } } }"))
+        .await;
+    assert_eq!(
+        response.into_result().unwrap().data,
+        value!({
+            "users": [
+                { "email": "alice@example.com", "address": { "street": "1 Main Street" } },
+                { "email": "bob@example.com", "address": { "street": "1 Main Street" } },
+            ]
+        })
+    );
+}
+
+#[tokio::test]
+pub async fn test_redaction_non_null_items() {
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+
+    // The nullable list becomes `null`.
+    let response = schema
+        .execute(request("{ me { name codes } }", Role::Guest))
+        .await;
+    assert_eq!(
+        response.data,
+        value!({ "me": { "name": "alice", "codes": null } })
+    );
+    assert_eq!(
+        response.errors,
+        vec![ServerError {
+            message: r#"The non-null item of the field "codes" is redacted to null."#.to_string(),
+            source: None,
+            locations: vec![Pos {
+                line: 1,
+                column: 13
+            }],
+            path: vec![
+                PathSegment::Field("me".to_string()),
+                PathSegment::Field("codes".to_string()),
+                PathSegment::Index(1),
+            ],
+            extensions: None,
+        }]
+    );
+    assert_eq!(response.extensions["redacted"], value!([["me", "codes"]]));
+
+    // The null is propagated to the closest nullable parent.
+    let response = schema
+        .execute(request("{ me { name requiredCodes } }", Role::Guest))
+        .await;
+    assert_eq!(response.data, Value::Null);
+    assert_eq!(
+        response.errors,
+        vec![ServerError {
+            message: r#"The non-null item of the field "requiredCodes" is redacted to null."#
+                .to_string(),
+            source: None,
+            locations: vec![Pos {
+                line: 1,
+                column: 13
+            }],
+            path: vec![
+                PathSegment::Field("me".to_string()),
+                PathSegment::Field("requiredCodes".to_string()),
+                PathSegment::Index(1),
+            ],
+            extensions: None,
+        }]
+    );
+}
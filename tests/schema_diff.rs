@@ -0,0 +1,133 @@
+use async_graphql::*;
+
+mod v1 {
+    use async_graphql::*;
+
+    #[derive(Enum, Copy, Clone, Eq, PartialEq)]
+    pub enum Status {
+        Open,
+        Closed,
+    }
+
+    #[derive(InputObject)]
+    pub struct TodoFilter {
+        pub status: Option<Status>,
+    }
+
+    #[derive(SimpleObject)]
+    pub struct Todo {
+        pub id: ID,
+        pub title: Option<String>,
+        pub status: Status,
+    }
+
+    #[derive(Interface)]
+    #[graphql(field(name = "id", type = "&ID"))]
+    pub enum Node {
+        Todo(Todo),
+    }
+
+    pub struct Query;
+
+    #[Object]
+    impl Query {
+        async fn todos(
+            &self,
+            filter: Option<TodoFilter>,
+            #[graphql(default = 10)] first: i32,
+        ) -> Vec<Todo> {
+            let _ = (filter, first);
+            Vec::new()
+        }
+
+        async fn node(&self, id: ID) -> Option<Node> {
+            let _ = id;
+            None
+        }
+    }
+}
+
+mod v2 {
+    use async_graphql::*;
+
+    #[derive(Enum, Copy, Clone, Eq, PartialEq)]
+    pub enum Status {
+        Open,
+        Done,
+    }
+
+    #[derive(InputObject)]
+    pub struct TodoFilter {
+        pub status: Option<Status>,
+        pub owner: ID,
+    }
+
+    #[derive(SimpleObject)]
+    pub struct Todo {
+        pub id: ID,
+        pub title: String,
+        pub status: Option<Status>,
+    }
+
+    #[derive(Interface)]
+    #[graphql(field(name = "id", type = "&ID"))]
+    pub enum Node {
+        Todo(Todo),
+    }
+
+    pub struct Query;
+
+    #[Object]
+    impl Query {
+        async fn todos(
+            &self,
+            filter: Option<TodoFilter>,
+            #[graphql(default = 20)] first: i32,
+        ) -> Vec<Todo> {
+            let _ = (filter, first);
+            Vec::new()
+        }
+
+        async fn node(&self, id: ID) -> Option<Node> {
+            let _ = id;
+            None
+        }
+    }
+}
+
+#[test]
+pub fn test_schema_diff_same_schema() {
+    let schema = Schema::new(v1::Query, EmptyMutation, EmptySubscription);
+    assert!(schema.diff_sdl(&schema.sdl()).unwrap().is_empty());
+}
+
+#[test]
+pub fn test_schema_diff() {
+    let old = Schema::new(v1::Query, EmptyMutation, EmptySubscription).sdl();
+    let schema = Schema::new(v2::Query, EmptyMutation, EmptySubscription);
+    let diff = schema.diff_sdl(&old).unwrap();
+
+    assert!(diff.has_breaking_changes());
+    assert_eq!(
+        diff.to_string(),
+        "DANGEROUS: Argument `Query.todos.first` changed default value from `10` to `20`.
+BREAKING: Enum value `Status.CLOSED` was removed.
+DANGEROUS: Enum value `Status.DONE` was added.
+SAFE: Field `Todo.title` changed type from `String` to `String!`.
+BREAKING: Field `Todo.status` changed type from `Status!` to `Status`.
+BREAKING: Required input field `TodoFilter.owner` was added.
+"
+    );
+    assert_eq!(
+        serde_json::to_value(diff.breaking_changes().next().unwrap()).unwrap(),
+        serde_json::json!({
+            "level": "BREAKING",
+            "kind": "ENUM_VALUE_REMOVED",
+            "path": "Status.CLOSED",
+            "message": "Enum value `Status.CLOSED` was removed.",
+        })
+    );
+    assert!(Schema::new(v2::Query, EmptyMutation, EmptySubscription)
+        .diff_sdl("type Query {")
+        .is_err());
+}
@@ -514,7 +514,7 @@ pub async fn test_start_before_connection_init() {
 
     assert_eq!(
         stream.next().await.unwrap().unwrap_close(),
-        (1011, "The handshake is not completed.".to_string())
+        (4401, "Unauthorized".to_string())
     );
 }
 
@@ -727,3 +727,922 @@ pub async fn test_ping_pong() {
             .is_err()
     );
 }
+
+#[tokio::test]
+pub async fn test_keepalive() {
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn value(&self) -> i32 {
+            10
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let (mut tx, rx) = mpsc::unbounded();
+    let mut stream = http::WebSocket::new(schema, rx, WebSocketProtocols::GraphQLWS)
+        .keepalive_interval(Duration::from_millis(100))
+        .idle_timeout(Duration::from_millis(300));
+
+    tx.send(
+        serde_json::to_string(&value!({
+            "type": "connection_init",
+        }))
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&stream.next().await.unwrap().unwrap_text())
+            .unwrap(),
+        serde_json::json!({
+            "type": "connection_ack",
+        }),
+    );
+
+    // The pongs of the client keep the connection alive.
+    for _ in 0..5 {
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&stream.next().await.unwrap().unwrap_text())
+                .unwrap(),
+            serde_json::json!({
+                "type": "ping",
+            }),
+        );
+
+        tx.send(
+            serde_json::to_string(&value!({
+                "type": "pong",
+            }))
+            .unwrap(),
+        )
+        .await
+        .unwrap();
+    }
+
+    let mut pings = 0;
+    let close = loop {
+        match stream.next().await.unwrap() {
+            http::WsMessage::Text(_) => pings += 1,
+            http::WsMessage::Close(code, reason) => break (code, reason),
+        }
+    };
+    assert!(pings >= 2);
+    assert_eq!(close, (3008, "Idle timeout".to_string()));
+    assert!(stream.next().await.is_none());
+}
+
+#[tokio::test]
+pub async fn test_connection_init_timeout() {
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn value(&self) -> i32 {
+            10
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let (_tx, rx) = mpsc::unbounded::<String>();
+    let mut stream = http::WebSocket::new(schema.clone(), rx, WebSocketProtocols::GraphQLWS)
+        .connection_init_timeout(Duration::from_millis(100));
+
+    assert_eq!(
+        (4408, "Connection initialisation timeout".to_string()),
+        stream.next().await.unwrap().unwrap_close()
+    );
+    assert!(stream.next().await.is_none());
+
+    let (mut tx, rx) = mpsc::unbounded();
+    let mut stream = http::WebSocket::new(schema, rx, WebSocketProtocols::GraphQLWS)
+        .connection_init_timeout(Duration::from_millis(100));
+
+    // The timeout starts when the connection is first polled.
+    tokio::time::sleep(Duration::from_millis(150)).await;
+    assert!(
+        tokio::time::timeout(Duration::from_millis(20), stream.next())
+            .await
+            .is_err()
+    );
+
+    tx.send(
+        serde_json::to_string(&value!({
+            "type": "connection_init",
+        }))
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&stream.next().await.unwrap().unwrap_text())
+            .unwrap(),
+        serde_json::json!({
+            "type": "connection_ack",
+        }),
+    );
+    assert!(
+        tokio::time::timeout(Duration::from_millis(200), stream.next())
+            .await
+            .is_err()
+    );
+}
+
+#[tokio::test]
+pub async fn test_close_handle() {
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn value(&self) -> i32 {
+            10
+        }
+    }
+
+    struct Subscription;
+
+    #[Subscription]
+    impl Subscription {
+        async fn values(&self, ctx: &Context<'_>) -> impl Stream<Item = i32> {
+            let close_handle = ctx.data_unchecked::<http::WsCloseHandle>().clone();
+            futures_util::stream::iter(0..10).map(move |n| {
+                if n == 2 {
+                    close_handle.close(4403, "Token revoked");
+                }
+                n
+            })
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, Subscription);
+    let (mut tx, rx) = mpsc::unbounded();
+    let mut stream = http::WebSocket::new(schema, rx, WebSocketProtocols::GraphQLWS);
+
+    tx.send(
+        serde_json::to_string(&value!({
+            "type": "connection_init",
+        }))
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&stream.next().await.unwrap().unwrap_text())
+            .unwrap(),
+        serde_json::json!({
+            "type": "connection_ack",
+        }),
+    );
+
+    tx.send(
+        serde_json::to_string(&value!({
+            "type": "start",
+            "id": "1",
+            "payload": {
+                "query": "subscription { values }"
+            },
+        }))
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    for i in 0..3 {
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&stream.next().await.unwrap().unwrap_text())
+                .unwrap(),
+            serde_json::json!({
+                "type": "next",
+                "id": "1",
+                "payload": { "data": { "values": i } },
+            }),
+        );
+    }
+
+    assert_eq!(
+        (4403, "Token revoked".to_string()),
+        stream.next().await.unwrap().unwrap_close()
+    );
+    assert!(stream.next().await.is_none());
+
+    // The connection can be closed before it is initialized.
+    let (_tx, rx) = mpsc::unbounded::<String>();
+    let mut stream = http::WebSocket::new(
+        Schema::new(Query, EmptyMutation, EmptySubscription),
+        rx,
+        WebSocketProtocols::GraphQLWS,
+    );
+    let close_handle = stream.close_handle();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        close_handle.close(4400, "Bye");
+    });
+    assert_eq!(
+        (4400, "Bye".to_string()),
+        stream.next().await.unwrap().unwrap_close()
+    );
+}
+
+#[tokio::test]
+pub async fn test_operation_error() {
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn value(&self) -> i32 {
+            10
+        }
+    }
+
+    struct Subscription;
+
+    #[Subscription]
+    impl Subscription {
+        async fn values(&self) -> impl Stream<Item = i32> {
+            futures_util::stream::iter(0..10)
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, Subscription);
+    let (mut tx, rx) = mpsc::unbounded();
+    let mut stream = http::WebSocket::new(schema, rx, WebSocketProtocols::GraphQLWS);
+
+    tx.send(
+        serde_json::to_string(&value!({
+            "type": "connection_init",
+        }))
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&stream.next().await.unwrap().unwrap_text())
+            .unwrap(),
+        serde_json::json!({
+            "type": "connection_ack",
+        }),
+    );
+
+    tx.send(
+        serde_json::to_string(&value!({
+            "type": "subscribe",
+            "id": "1",
+            "payload": {
+                "query": "subscription { valuesAbc }"
+            },
+        }))
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&stream.next().await.unwrap().unwrap_text())
+            .unwrap(),
+        serde_json::json!({
+            "type": "error",
+            "id": "1",
+            "payload": [{
+                "message": "Unknown field \"valuesAbc\" on type \"Subscription\". Did you mean \"values\"?",
+                "locations": [{
+                    "line": 1,
+                    "column": 16
+                }],
+            }],
+        }),
+    );
+
+    // The id of the failed operation can be used again.
+    tx.send(
+        serde_json::to_string(&value!({
+            "type": "subscribe",
+            "id": "1",
+            "payload": {
+                "query": "{ value }"
+            },
+        }))
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&stream.next().await.unwrap().unwrap_text())
+            .unwrap(),
+        serde_json::json!({
+            "type": "next",
+            "id": "1",
+            "payload": { "data": { "value": 10 } },
+        }),
+    );
+}
+
+#[tokio::test]
+pub async fn test_duplicate_operation_id() {
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn value(&self) -> i32 {
+            10
+        }
+    }
+
+    struct Subscription;
+
+    #[Subscription]
+    impl Subscription {
+        async fn values(&self) -> impl Stream<Item = i32> {
+            futures_util::stream::pending()
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, Subscription);
+    let (mut tx, rx) = mpsc::unbounded();
+    let mut stream = http::WebSocket::new(schema, rx, WebSocketProtocols::GraphQLWS);
+
+    tx.send(
+        serde_json::to_string(&value!({
+            "type": "connection_init",
+        }))
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&stream.next().await.unwrap().unwrap_text())
+            .unwrap(),
+        serde_json::json!({
+            "type": "connection_ack",
+        }),
+    );
+
+    for _ in 0..2 {
+        tx.send(
+            serde_json::to_string(&value!({
+                "type": "subscribe",
+                "id": "1",
+                "payload": {
+                    "query": "subscription { values }"
+                },
+            }))
+            .unwrap(),
+        )
+        .await
+        .unwrap();
+    }
+
+    assert_eq!(
+        (4409, "Subscriber for 1 already exists".to_string()),
+        stream.next().await.unwrap().unwrap_close()
+    );
+    assert!(stream.next().await.is_none());
+}
+
+#[tokio::test]
+pub async fn test_mutation_over_websocket() {
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn value(&self, ctx: &Context<'_>) -> i32 {
+            *ctx.data_unchecked::<Mutex<i32>>().lock().unwrap()
+        }
+    }
+
+    struct Mutation;
+
+    #[Object]
+    impl Mutation {
+        async fn add(&self, ctx: &Context<'_>, n: i32) -> i32 {
+            let mut value = ctx.data_unchecked::<Mutex<i32>>().lock().unwrap();
+            *value += n;
+            *value
+        }
+    }
+
+    let schema = Schema::build(Query, Mutation, EmptySubscription)
+        .data(Mutex::new(10))
+        .finish();
+    let (mut tx, rx) = mpsc::unbounded();
+    let mut stream = http::WebSocket::new(schema, rx, WebSocketProtocols::GraphQLWS);
+
+    tx.send(
+        serde_json::to_string(&value!({
+            "type": "connection_init",
+        }))
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&stream.next().await.unwrap().unwrap_text())
+            .unwrap(),
+        serde_json::json!({
+            "type": "connection_ack",
+        }),
+    );
+
+    for (id, query, data) in [
+        ("1", "mutation { add(n: 5) }", value!({ "add": 15 })),
+        ("2", "{ value }", value!({ "value": 15 })),
+    ] {
+        tx.send(
+            serde_json::to_string(&value!({
+                "type": "subscribe",
+                "id": id,
+                "payload": {
+                    "query": query
+                },
+            }))
+            .unwrap(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&stream.next().await.unwrap().unwrap_text())
+                .unwrap(),
+            serde_json::json!({
+                "type": "next",
+                "id": id,
+                "payload": { "data": data },
+            }),
+        );
+
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&stream.next().await.unwrap().unwrap_text())
+                .unwrap(),
+            serde_json::json!({
+                "type": "complete",
+                "id": id,
+            }),
+        );
+    }
+}
+
+#[tokio::test]
+pub async fn test_on_operation() {
+    struct RequestId(String);
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn request_id(&self, ctx: &Context<'_>) -> String {
+            ctx.data_unchecked::<RequestId>().0.clone()
+        }
+    }
+
+    struct Subscription;
+
+    #[Subscription]
+    impl Subscription {
+        async fn request_ids(&self, ctx: &Context<'_>) -> impl Stream<Item = String> {
+            futures_util::stream::iter(vec![ctx.data_unchecked::<RequestId>().0.clone()])
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, Subscription);
+    let (mut tx, rx) = mpsc::unbounded();
+    let mut stream = http::WebSocket::new(schema, rx, WebSocketProtocols::GraphQLWS).on_operation(
+        |extensions| async move {
+            match extensions.get("requestId") {
+                Some(Value::String(request_id)) => {
+                    let mut data = Data::default();
+                    data.insert(RequestId(request_id.clone()));
+                    Ok(data)
+                }
+                _ => Err("Missing request id".into()),
+            }
+        },
+    );
+
+    tx.send(
+        serde_json::to_string(&value!({
+            "type": "connection_init",
+        }))
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&stream.next().await.unwrap().unwrap_text())
+            .unwrap(),
+        serde_json::json!({
+            "type": "connection_ack",
+        }),
+    );
+
+    for (id, query, data) in [
+        ("1", "{ requestId }", value!({ "requestId": "a" })),
+        (
+            "2",
+            "subscription { requestIds }",
+            value!({ "requestIds": "b" }),
+        ),
+    ] {
+        tx.send(
+            serde_json::to_string(&value!({
+                "type": "subscribe",
+                "id": id,
+                "payload": {
+                    "query": query,
+                    "extensions": { "requestId": if id == "1" { "a" } else { "b" } },
+                },
+            }))
+            .unwrap(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&stream.next().await.unwrap().unwrap_text())
+                .unwrap(),
+            serde_json::json!({
+                "type": "next",
+                "id": id,
+                "payload": { "data": data },
+            }),
+        );
+
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&stream.next().await.unwrap().unwrap_text())
+                .unwrap(),
+            serde_json::json!({
+                "type": "complete",
+                "id": id,
+            }),
+        );
+    }
+
+    tx.send(
+        serde_json::to_string(&value!({
+            "type": "subscribe",
+            "id": "3",
+            "payload": {
+                "query": "{ requestId }",
+            },
+        }))
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&stream.next().await.unwrap().unwrap_text())
+            .unwrap(),
+        serde_json::json!({
+            "type": "error",
+            "id": "3",
+            "payload": [{ "message": "Missing request id" }],
+        }),
+    );
+}
+
+#[tokio::test]
+pub async fn test_max_operations() {
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn value(&self) -> i32 {
+            10
+        }
+    }
+
+    struct Subscription;
+
+    #[Subscription]
+    impl Subscription {
+        async fn values(&self) -> impl Stream<Item = i32> {
+            futures_util::stream::pending()
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, Subscription);
+    let (mut tx, rx) = mpsc::unbounded();
+    let mut stream =
+        http::WebSocket::new(schema, rx, WebSocketProtocols::GraphQLWS).max_operations(1);
+
+    tx.send(
+        serde_json::to_string(&value!({
+            "type": "connection_init",
+        }))
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&stream.next().await.unwrap().unwrap_text())
+            .unwrap(),
+        serde_json::json!({
+            "type": "connection_ack",
+        }),
+    );
+
+    for id in ["1", "2"] {
+        tx.send(
+            serde_json::to_string(&value!({
+                "type": "subscribe",
+                "id": id,
+                "payload": {
+                    "query": "subscription { values }"
+                },
+            }))
+            .unwrap(),
+        )
+        .await
+        .unwrap();
+    }
+
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&stream.next().await.unwrap().unwrap_text())
+            .unwrap(),
+        serde_json::json!({
+            "type": "error",
+            "id": "2",
+            "payload": [{ "message": "Too many operations." }],
+        }),
+    );
+
+    tx.send(
+        serde_json::to_string(&value!({
+            "type": "complete",
+            "id": "1",
+        }))
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&stream.next().await.unwrap().unwrap_text())
+            .unwrap(),
+        serde_json::json!({
+            "type": "complete",
+            "id": "1",
+        }),
+    );
+
+    tx.send(
+        serde_json::to_string(&value!({
+            "type": "subscribe",
+            "id": "3",
+            "payload": {
+                "query": "{ value }"
+            },
+        }))
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&stream.next().await.unwrap().unwrap_text())
+            .unwrap(),
+        serde_json::json!({
+            "type": "next",
+            "id": "3",
+            "payload": { "data": { "value": 10 } },
+        }),
+    );
+}
+
+#[tokio::test]
+pub async fn test_subscription_buffer() {
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn value(&self) -> i32 {
+            10
+        }
+    }
+
+    struct Subscription;
+
+    #[Subscription]
+    impl Subscription {
+        async fn values(&self) -> impl Stream<Item = i32> {
+            futures_util::stream::iter(0..10)
+        }
+    }
+
+    // Up to 3 events are received each time an event is sent, the others are
+    // dropped according to the policy.
+    for (policy, values) in [
+        (http::WsOverflowPolicy::DropOldest, vec![0, 3, 6, 7, 8, 9]),
+        (http::WsOverflowPolicy::DropNewest, vec![0, 1, 2, 3, 6, 9]),
+        (http::WsOverflowPolicy::Close, vec![0]),
+    ] {
+        let schema = Schema::new(Query, EmptyMutation, Subscription);
+        let dropped = Arc::new(Mutex::new(Vec::new()));
+        let (mut tx, rx) = mpsc::unbounded();
+        let mut stream = http::WebSocket::new(schema, rx, WebSocketProtocols::GraphQLWS)
+            .subscription_buffer(3, policy)
+            .on_event_dropped({
+                let dropped = dropped.clone();
+                move |id| dropped.lock().unwrap().push(id.to_string())
+            });
+
+        tx.send(
+            serde_json::to_string(&value!({
+                "type": "connection_init",
+            }))
+            .unwrap(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&stream.next().await.unwrap().unwrap_text())
+                .unwrap(),
+            serde_json::json!({
+                "type": "connection_ack",
+            }),
+        );
+
+        tx.send(
+            serde_json::to_string(&value!({
+                "type": "subscribe",
+                "id": "1",
+                "payload": {
+                    "query": "subscription { values }"
+                },
+            }))
+            .unwrap(),
+        )
+        .await
+        .unwrap();
+
+        for i in values {
+            assert_eq!(
+                serde_json::from_str::<serde_json::Value>(
+                    &stream.next().await.unwrap().unwrap_text()
+                )
+                .unwrap(),
+                serde_json::json!({
+                    "type": "next",
+                    "id": "1",
+                    "payload": { "data": { "values": i } },
+                }),
+            );
+        }
+
+        if policy == http::WsOverflowPolicy::Close {
+            assert_eq!(
+                (1008, "The buffer of the operation 1 is full".to_string()),
+                stream.next().await.unwrap().unwrap_close()
+            );
+            assert!(dropped.lock().unwrap().is_empty());
+            continue;
+        }
+
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&stream.next().await.unwrap().unwrap_text())
+                .unwrap(),
+            serde_json::json!({
+                "type": "complete",
+                "id": "1",
+            }),
+        );
+        assert_eq!(*dropped.lock().unwrap(), vec!["1"; 4]);
+    }
+}
+
+#[tokio::test]
+pub async fn test_subscription_buffer_endless_stream() {
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn value(&self) -> i32 {
+            10
+        }
+    }
+
+    struct Subscription;
+
+    #[Subscription]
+    impl Subscription {
+        async fn values(&self) -> impl Stream<Item = i32> {
+            futures_util::stream::repeat(1)
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, Subscription);
+    let (mut tx, rx) = mpsc::unbounded();
+    let mut stream = http::WebSocket::new(schema, rx, WebSocketProtocols::GraphQLWS)
+        .subscription_buffer(3, http::WsOverflowPolicy::DropOldest);
+
+    tx.send(
+        serde_json::to_string(&value!({
+            "type": "connection_init",
+        }))
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+    stream.next().await.unwrap().unwrap_text();
+
+    tx.send(
+        serde_json::to_string(&value!({
+            "type": "subscribe",
+            "id": "1",
+            "payload": {
+                "query": "subscription { values }"
+            },
+        }))
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    // A stream which is always ready doesn't block the connection.
+    for _ in 0..5 {
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&stream.next().await.unwrap().unwrap_text())
+                .unwrap(),
+            serde_json::json!({
+                "type": "next",
+                "id": "1",
+                "payload": { "data": { "values": 1 } },
+            }),
+        );
+    }
+}
+
+#[tokio::test]
+pub async fn test_subscription_buffer_concurrent_operations() {
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn value(&self) -> i32 {
+            10
+        }
+    }
+
+    struct Subscription;
+
+    #[Subscription]
+    impl Subscription {
+        async fn values(&self, value: i32) -> impl Stream<Item = i32> {
+            futures_util::stream::repeat(value)
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, Subscription);
+    let (mut tx, rx) = mpsc::unbounded();
+    let mut stream = http::WebSocket::new(schema, rx, WebSocketProtocols::GraphQLWS)
+        .subscription_buffer(3, http::WsOverflowPolicy::DropOldest);
+
+    tx.send(
+        serde_json::to_string(&value!({
+            "type": "connection_init",
+        }))
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+    stream.next().await.unwrap().unwrap_text();
+
+    for id in ["1", "2"] {
+        tx.send(
+            serde_json::to_string(&value!({
+                "type": "subscribe",
+                "id": id,
+                "payload": {
+                    "query": format!("subscription {{ values(value: {}) }}", id)
+                },
+            }))
+            .unwrap(),
+        )
+        .await
+        .unwrap();
+    }
+
+    // The operations which are always ready send their events in turn.
+    let mut ids = Vec::new();
+    for _ in 0..6 {
+        let message: serde_json::Value =
+            serde_json::from_str(&stream.next().await.unwrap().unwrap_text()).unwrap();
+        assert_eq!(message["type"], "next");
+        assert_eq!(
+            message["payload"]["data"]["values"].to_string(),
+            message["id"].as_str().unwrap()
+        );
+        ids.push(message["id"].as_str().unwrap().to_string());
+    }
+    assert_eq!(ids, vec!["1", "2", "1", "2", "1", "2"]);
+}
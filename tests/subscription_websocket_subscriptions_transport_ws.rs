@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use async_graphql::{http::WebSocketProtocols, *};
 use futures_channel::mpsc;
 use futures_util::{
@@ -422,3 +424,45 @@ pub async fn test_start_before_connection_init() {
         (1011, "The handshake is not completed.".to_string())
     );
 }
+
+#[tokio::test]
+pub async fn test_keepalive() {
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn value(&self) -> i32 {
+            10
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let (mut tx, rx) = mpsc::unbounded();
+    let mut stream = http::WebSocket::new(schema, rx, WebSocketProtocols::SubscriptionsTransportWS)
+        .keepalive_interval(Duration::from_millis(50));
+
+    tx.send(
+        serde_json::to_string(&value!({
+            "type": "connection_init",
+        }))
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        Some(value!({
+            "type": "connection_ack",
+        })),
+        serde_json::from_str(&stream.next().await.unwrap().unwrap_text()).unwrap()
+    );
+
+    for _ in 0..3 {
+        assert_eq!(
+            Some(value!({
+                "type": "ka",
+            })),
+            serde_json::from_str(&stream.next().await.unwrap().unwrap_text()).unwrap()
+        );
+    }
+}